@@ -64,6 +64,7 @@ fn default_config() -> cbindgen::Config {
                 ("KeyEventArg".into(), "KeyEvent".into()),
                 ("PointerEventArg".into(), "PointerEvent".into()),
                 ("PointArg".into(), "Point".into()),
+                ("StringArg".into(), "SharedString".into()),
                 ("FloatArg".into(), "float".into()),
                 ("Coord".into(), "float".into()),
             ]
@@ -130,6 +131,9 @@ fn gen_corelib(
         "Rotate",
         "Opacity",
         "Layer",
+        "MenuEntryItem",
+        "MenuBarItem",
+        "ContextMenuArea",
     ];
 
     config.export.include = [
@@ -141,6 +145,8 @@ fn gen_corelib(
         "TextHorizontalAlignment",
         "TextVerticalAlignment",
         "TextOverflow",
+        "TextFormat",
+        "TextDecoration",
         "TextWrap",
         "ImageFit",
         "FillRule",
@@ -194,6 +200,7 @@ fn gen_corelib(
         "KeyEventArg",
         "PointerEventArg",
         "PointArg",
+        "StringArg",
         "Point",
         "slint_color_brighter",
         "slint_color_darker",
@@ -307,11 +314,15 @@ fn gen_corelib(
             "slint_windowrc_get_scale_factor",
             "slint_windowrc_set_scale_factor",
             "slint_windowrc_set_focus_item",
+            "slint_windowrc_register_menu_bar",
             "slint_windowrc_set_component",
             "slint_windowrc_show_popup",
             "slint_windowrc_set_rendering_notifier",
             "slint_windowrc_request_redraw",
             "slint_windowrc_on_close_requested",
+            "slint_windowrc_set_transient_parent",
+            "slint_windowrc_set_modal",
+            "slint_windowrc_is_modal",
             "slint_windowrc_position",
             "slint_windowrc_set_logical_position",
             "slint_windowrc_set_physical_position",
@@ -430,12 +441,17 @@ fn gen_corelib(
         "StandardListViewItem".to_owned(),
         "friend bool operator==(const StandardListViewItem&, const StandardListViewItem&) = default;".into(),
     );
+    config.export.body.insert(
+        "TableColumn".to_owned(),
+        "friend bool operator==(const TableColumn&, const TableColumn&) = default;".into(),
+    );
     config
         .export
         .body
         .insert("Flickable".to_owned(), "    inline Flickable(); inline ~Flickable();".into());
     config.export.pre_body.insert("FlickableDataBox".to_owned(), "struct FlickableData;".into());
     config.export.include.push("StandardListViewItem".into());
+    config.export.include.push("TableColumn".into());
     cbindgen::Builder::new()
         .with_config(config)
         .with_src(crate_dir.join("lib.rs"))
@@ -490,6 +506,7 @@ fn gen_backend_qt(
         "NativeLineEdit",
         "NativeScrollView",
         "NativeStandardListViewItem",
+        "NativeTableHeaderSection",
         "NativeComboBox",
         "NativeComboBoxPopup",
         "NativeTabWidget",
@@ -63,6 +63,7 @@ using cbindgen_private::KeyboardModifiers;
 using cbindgen_private::KeyEvent;
 using cbindgen_private::PointerEvent;
 using cbindgen_private::StandardListViewItem;
+using cbindgen_private::TableColumn;
 
 /// Internal function that checks that the API that must be called from the main
 /// thread is indeed called from the main thread, or abort the program otherwise
@@ -130,6 +131,12 @@ public:
         cbindgen_private::slint_windowrc_set_focus_item(&inner, &item_rc);
     }
 
+    void register_menu_bar(const ComponentRc &component_rc, uintptr_t item_index)
+    {
+        cbindgen_private::ItemRc item_rc { component_rc, item_index };
+        cbindgen_private::slint_windowrc_register_menu_bar(&inner, &item_rc);
+    }
+
     template<typename Component, typename ItemArray>
     void register_component(Component *c, ItemArray items) const
     {
@@ -212,6 +219,15 @@ public:
         cbindgen_private::slint_windowrc_set_physical_size(&inner, &size);
     }
 
+    void set_transient_parent(const WindowAdapterRc *parent) const
+    {
+        cbindgen_private::slint_windowrc_set_transient_parent(&inner,
+                                                              parent ? &parent->inner : nullptr);
+    }
+
+    void set_modal(bool modal) const { cbindgen_private::slint_windowrc_set_modal(&inner, modal); }
+    bool is_modal() const { return cbindgen_private::slint_windowrc_is_modal(&inner); }
+
     /// Registers a font by the specified path. The path must refer to an existing
     /// TrueType font.
     /// \returns an empty optional on success, otherwise an error string
@@ -453,6 +469,22 @@ public:
     /// a window frame (if present).
     void set_size(const slint::PhysicalSize &size) { inner.set_physical_size(size); }
 
+    /// Sets the window that this window is a transient child of, typically the main window of
+    /// an application for one of its dialogs. The windowing system keeps a transient window on
+    /// top of its parent. Pass nullptr to unset the parent. Call this function before showing
+    /// the window. The call is ignored if \a parent is this window, or is itself a transient
+    /// child of this window.
+    void set_transient_parent(const Window *parent)
+    {
+        inner.set_transient_parent(parent ? &parent->inner : nullptr);
+    }
+
+    /// Sets whether this window is modal. While a modal window is shown, its transient parent
+    /// doesn't receive any input and cannot be closed.
+    void set_modal(bool modal) { inner.set_modal(modal); }
+    /// Returns true if the window is modal.
+    bool is_modal() const { return inner.is_modal(); }
+
     /// \private
     private_api::WindowAdapterRc &window_handle() { return inner; }
     /// \private
@@ -28,7 +28,11 @@ pub fn use_modules() -> usize {
 
 #[no_mangle]
 pub unsafe extern "C" fn slint_run_event_loop() {
-    i_slint_backend_selector::with_platform(|b| b.run_event_loop());
+    // Don't unwind into the C++ code
+    if let Err(err) = i_slint_backend_selector::with_platform(|b| b.try_run_event_loop()) {
+        i_slint_core::debug_log!("{}", err);
+        std::process::abort();
+    }
 }
 
 /// Will execute the given functor in the main thread
@@ -146,6 +146,34 @@ require.extensions['.60'] = require.extensions['.slint'] =
                     enumerable: true,
                 })
             });
+            c.globals().forEach((g: string) => {
+                let global = {};
+                c.global_properties(g).forEach((x: string) => {
+                    Object.defineProperty(global, x.replace(/-/g, '_'), {
+                        get() { return comp.get_global_property(g, x); },
+                        set(newValue) { comp.set_global_property(g, x, newValue); },
+                        enumerable: true,
+                    })
+                });
+                c.global_callbacks(g).forEach((x: string) => {
+                    Object.defineProperty(global, x.replace(/-/g, '_'), {
+                        get() {
+                            let callback = function () { return comp.invoke_global_callback(g, x, [...arguments]); } as Callback;
+                            callback.setHandler = function (callback) { comp.connect_global_callback(g, x, callback) };
+                            return callback;
+                        },
+                        enumerable: true,
+                    })
+                });
+                let global_name = g.replace(/-/g, '_');
+                if (Object.prototype.hasOwnProperty.call(ret, global_name)) {
+                    throw new Error(`The global ${g} has the same name as a property or callback of the component`);
+                }
+                Object.defineProperty(ret, global_name, {
+                    value: global,
+                    enumerable: true,
+                })
+            });
             return ret;
         }
     }
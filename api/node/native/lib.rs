@@ -223,7 +223,14 @@ fn to_eval_value<'cx>(
                     .collect::<Result<_, _>>()?,
             ))
         }
-        Type::Enumeration(_) => todo!(),
+        Type::Enumeration(e) => {
+            let value = val.to_string(cx)?.value();
+            if !e.values.contains(&value) {
+                return cx
+                    .throw_error(format!("{:?} is not a value of the enum {}", value, e.name));
+            }
+            Ok(Value::EnumerationValue(e.name.clone(), value))
+        }
         Type::Invalid
         | Type::Void
         | Type::InferredProperty
@@ -287,6 +294,7 @@ fn to_js_value<'cx>(
             &format!("#{:02x}{:02x}{:02x}{:02x}", c.red(), c.green(), c.blue(), c.alpha()),
         )
         .as_value(cx),
+        Value::EnumerationValue(_, value) => JsString::new(cx, value.as_str()).as_value(cx),
         _ => todo!("converting {:?} to js has not been implemented", val),
     })
 }
@@ -332,6 +340,49 @@ fn to_js_value<'cx>(
             }
             Ok(array.as_value(&mut cx))
         }
+        method globals(mut cx) {
+            let this = cx.this();
+            let ct = cx.borrow(&this, |x| x.0.clone());
+            let ct = ct.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let array = JsArray::new(&mut cx, 0);
+            for (len, g) in ct.globals().enumerate() {
+                let global_name = JsString::new(&mut cx, g);
+                array.set(&mut cx, len as u32, global_name)?;
+            }
+            Ok(array.as_value(&mut cx))
+        }
+        method global_properties(mut cx) {
+            let global_name = cx.argument::<JsString>(0)?.value();
+            let this = cx.this();
+            let ct = cx.borrow(&this, |x| x.0.clone());
+            let ct = ct.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let properties = ct.global_properties_and_callbacks(&global_name)
+                .ok_or(())
+                .or_else(|()| cx.throw_error(format!("Global {} not found", global_name)))?
+                .filter(|(_, prop_type)| prop_type.is_property_type());
+            let array = JsArray::new(&mut cx, 0);
+            for (len, (p, _)) in properties.enumerate() {
+                let prop_name = JsString::new(&mut cx, p);
+                array.set(&mut cx, len as u32, prop_name)?;
+            }
+            Ok(array.as_value(&mut cx))
+        }
+        method global_callbacks(mut cx) {
+            let global_name = cx.argument::<JsString>(0)?.value();
+            let this = cx.this();
+            let ct = cx.borrow(&this, |x| x.0.clone());
+            let ct = ct.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let callbacks = ct.global_properties_and_callbacks(&global_name)
+                .ok_or(())
+                .or_else(|()| cx.throw_error(format!("Global {} not found", global_name)))?
+                .filter(|(_, prop_type)| matches!(prop_type, Type::Callback{..}));
+            let array = JsArray::new(&mut cx, 0);
+            for (len, (p, _)) in callbacks.enumerate() {
+                let prop_name = JsString::new(&mut cx, p);
+                array.set(&mut cx, len as u32, prop_name)?;
+            }
+            Ok(array.as_value(&mut cx))
+        }
     }
 
     class SlintComponent for WrappedComponentRc {
@@ -457,6 +508,109 @@ fn to_js_value<'cx>(
             }
         }
 
+        method get_global_property(mut cx) {
+            let global_name = cx.argument::<JsString>(0)?.value();
+            let prop_name = cx.argument::<JsString>(1)?.value();
+            let this = cx.this();
+            let persistent_context =
+                persistent_context::PersistentContext::from_object(&mut cx, this.downcast().unwrap())?;
+            let component = cx.borrow(&this, |x| x.0.as_ref().map(|c| c.clone_strong()));
+            let component = component.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let value = run_scoped(&mut cx,this.downcast().unwrap(), || {
+                component.get_global_property(global_name.as_str(), prop_name.as_str())
+                    .map_err(|_| "Cannot read property".to_string())
+            })?;
+            to_js_value(value, &mut cx, &persistent_context)
+        }
+        method set_global_property(mut cx) {
+            let global_name = cx.argument::<JsString>(0)?.value();
+            let prop_name = cx.argument::<JsString>(1)?.value();
+            let this = cx.this();
+            let component = cx.borrow(&this, |x| x.0.as_ref().map(|c| c.clone_strong()));
+            let component = component.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let ty = component.definition().global_properties_and_callbacks(&global_name)
+                .and_then(|mut props| props.find_map(|(name, proptype)| if name == prop_name { Some(proptype) } else { None }))
+                .ok_or(())
+                .or_else(|()| {
+                    cx.throw_error(format!("Property {} not found in the global {}", prop_name, global_name))
+                })?;
+
+            let persistent_context =
+                persistent_context::PersistentContext::from_object(&mut cx, this.downcast().unwrap())?;
+
+            let value = to_eval_value(cx.argument::<JsValue>(2)?, ty, &mut cx, &persistent_context)?;
+            run_scoped(&mut cx, this.downcast().unwrap(), || {
+                component.set_global_property(global_name.as_str(), prop_name.as_str(), value)
+                    .map_err(|_| "Cannot assign property".to_string())
+            })?;
+
+            Ok(JsUndefined::new().as_value(&mut cx))
+        }
+        method invoke_global_callback(mut cx) {
+            let global_name = cx.argument::<JsString>(0)?.value();
+            let callback_name = cx.argument::<JsString>(1)?.value();
+            let arguments = cx.argument::<JsArray>(2)?.to_vec(&mut cx)?;
+            let this = cx.this();
+            let component = cx.borrow(&this, |x| x.0.as_ref().map(|c| c.clone_strong()));
+            let component = component.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+            let ty = component.definition().global_properties_and_callbacks(&global_name)
+                .and_then(|mut props| props.find_map(|(name, proptype)| if name == callback_name { Some(proptype) } else { None }))
+                .ok_or(())
+                .or_else(|()| {
+                    cx.throw_error(format!("Callback {} not found in the global {}", callback_name, global_name))
+                })?;
+            let persistent_context =
+                persistent_context::PersistentContext::from_object(&mut cx, this.downcast().unwrap())?;
+            let args = if let Type::Callback {args, ..} = ty {
+                let count = args.len();
+                let args = arguments.into_iter()
+                    .zip(args.into_iter())
+                    .map(|(a, ty)| to_eval_value(a, ty, &mut cx, &persistent_context))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if args.len() != count {
+                    cx.throw_error(format!("{} expect {} arguments, but {} where provided", callback_name, count, args.len()))?;
+                }
+                args
+            } else {
+                cx.throw_error(format!("{} is not a callback", callback_name))?;
+                unreachable!()
+            };
+
+            let res = run_scoped(&mut cx,this.downcast().unwrap(), || {
+                component.invoke_global_callback(global_name.as_str(), callback_name.as_str(), args.as_slice())
+                    .map_err(|_| "Cannot emit callback".to_string())
+            })?;
+            to_js_value(res, &mut cx, &persistent_context)
+        }
+        method connect_global_callback(mut cx) {
+            let global_name = cx.argument::<JsString>(0)?.value();
+            let callback_name = cx.argument::<JsString>(1)?.value();
+            let handler = cx.argument::<JsFunction>(2)?;
+            let this = cx.this();
+            let persistent_context =
+                persistent_context::PersistentContext::from_object(&mut cx, this.downcast().unwrap())?;
+            let component = cx.borrow(&this, |x| x.0.as_ref().map(|c| c.clone_strong()));
+            let component = component.ok_or(()).or_else(|()| cx.throw_error("Invalid type"))?;
+
+            let ty = component.definition().global_properties_and_callbacks(&global_name)
+                .and_then(|mut props| props.find_map(|(name, proptype)| if name == callback_name { Some(proptype) } else { None }))
+                .ok_or(())
+                .or_else(|()| {
+                    cx.throw_error(format!("Callback {} not found in the global {}", callback_name, global_name))
+                })?;
+            if let Type::Callback {return_type, ..} = ty {
+                component.set_global_callback(
+                    global_name.as_str(),
+                    callback_name.as_str(),
+                    make_callback_handler(&mut cx, &persistent_context, handler, return_type)
+                ).or_else(|_| cx.throw_error("Cannot set callback"))?;
+                Ok(JsUndefined::new().as_value(&mut cx))
+            } else {
+                cx.throw_error(format!("{} is not a callback", callback_name))?;
+                unreachable!()
+            }
+        }
+
         method send_mouse_click(mut cx) {
             let x = cx.argument::<JsNumber>(0)?.value() as f32;
             let y = cx.argument::<JsNumber>(1)?.value() as f32;
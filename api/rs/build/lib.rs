@@ -101,6 +101,20 @@ pub fn with_include_paths(self, include_paths: Vec<std::path::PathBuf>) -> Self
         Self { config }
     }
 
+    /// Create a new configuration that sets the library paths used for looking up `.slint`
+    /// imports that start with `@name`. With the name `mycorp-ui` mapped to a directory,
+    /// `import { Button } from "@mycorp-ui/button.slint"` loads `button.slint` from that
+    /// directory.
+    #[must_use]
+    pub fn with_library_paths(
+        self,
+        library_paths: std::collections::HashMap<String, std::path::PathBuf>,
+    ) -> Self {
+        let mut config = self.config;
+        config.library_paths = library_paths;
+        Self { config }
+    }
+
     /// Create a new configuration that selects the style to be used for widgets.
     #[must_use]
     pub fn with_style(self, style: String) -> Self {
@@ -126,6 +140,21 @@ pub fn embed_resources(self, kind: EmbedResourcesKind) -> Self {
         };
         Self { config }
     }
+
+    /// Create a new configuration that compresses the images embedded with
+    /// [`EmbedResourcesKind::EmbedForSoftwareRenderer`].
+    ///
+    /// Each image is stored with a palette or run-length encoded if this makes it smaller,
+    /// and the software renderer decodes it line by line while drawing. Opaque images that are
+    /// run-length encoded are stored with 16 bits colors (RGB565).
+    ///
+    /// This can also be enabled with the `SLINT_COMPRESS_TEXTURES` environment variable.
+    #[must_use]
+    pub fn compress_textures(self, enable: bool) -> Self {
+        let mut config = self.config;
+        config.compress_textures = enable;
+        Self { config }
+    }
 }
 
 /// Error returned by the `compile` function
@@ -206,6 +206,10 @@ struct MyStruct {
 }
 ```
 
+With the `serde` feature, the generated structs also implement `serde::Serialize` and
+`serde::Deserialize`. The fields use the names of the .slint file (`kebab-case`), and missing
+fields are set to their default value when deserializing.
+
 ## Exported Global singletons
 
 When you export a [global singleton](docs::langref#global-singletons) from the main file,
@@ -247,7 +251,7 @@ struct MyStruct {
 };
 pub use i_slint_core::model::{
     FilterModel, MapModel, Model, ModelExt, ModelNotify, ModelPeer, ModelRc, ModelTracker,
-    StandardListViewItem, VecModel,
+    StandardListViewItem, TableColumn, VecModel,
 };
 pub use i_slint_core::sharedvector::SharedVector;
 pub use i_slint_core::timers::{Timer, TimerMode};
@@ -258,10 +262,21 @@ struct MyStruct {
 /// Enters the main event loop. This is necessary in order to receive
 /// events from the windowing system in order to render to the screen
 /// and react to user input.
+///
+/// # Panics
+///
+/// Panics if the backend cannot run the event loop, for example when the `linuxfb` backend
+/// cannot open the screen. Use [`try_run_event_loop()`] to handle that error.
 pub fn run_event_loop() {
     i_slint_backend_selector::with_platform(|b| b.run_event_loop())
 }
 
+/// Like [`run_event_loop()`], but returns an error if the backend cannot run the event loop,
+/// for example when the `linuxfb` backend cannot open the screen.
+pub fn try_run_event_loop() -> Result<(), platform::PlatformError> {
+    i_slint_backend_selector::with_platform(|b| b.try_run_event_loop())
+}
+
 /// Include the code generated with the slint-build crate from the build script. After calling `slint_build::compile`
 /// in your `build.rs` build script, the use of this macro includes the generated Rust code and makes the exported types
 /// available for you to instantiate.
@@ -285,6 +300,17 @@ pub mod platform {
     pub use i_slint_core::platform::*;
 }
 
+/// This module contains an API to inspect the tree of elements of a running component at run-time.
+///
+/// Use [`introspection::ElementHandle::root_of()`] with the window of a component to get the root
+/// element, and then find elements by the id they have in the .slint file.
+/// It is intended for tests and tooling.
+#[cfg(feature = "introspection")]
+pub mod introspection {
+    pub use i_slint_core::accessibility::AccessibleStringProperty;
+    pub use i_slint_core::introspection::*;
+}
+
 /// Helper type that helps checking that the generated code is generated for the right version
 #[doc(hidden)]
 #[allow(non_camel_case_types)]
@@ -143,6 +143,39 @@ pub fn create_window_adapter() -> alloc::rc::Rc<dyn i_slint_core::window::Window
     i_slint_backend_selector::with_platform(|b| b.create_window_adapter())
 }
 
+/// Wraps the structs generated for the structures declared in .slint files, to derive
+/// the serde traits on them when the `serde` feature is enabled.
+#[cfg(feature = "serde")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __slint_struct {
+    ($item:item) => {
+        #[derive(
+            $crate::private_unstable_api::re_exports::serde::Serialize,
+            $crate::private_unstable_api::re_exports::serde::Deserialize,
+        )]
+        #[serde(
+            crate = "slint::private_unstable_api::re_exports::serde",
+            default,
+            rename_all = "kebab-case"
+        )]
+        $item
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __slint_struct {
+    ($item:item) => {
+        $item
+    };
+}
+
+pub use crate::__slint_struct as declare_struct;
+
+pub use i_slint_core::__rtti_items as rtti_items;
+
 /// internal re_exports used by the macro generated
 pub mod re_exports {
     pub use alloc::boxed::Box;
@@ -157,8 +190,8 @@ pub mod re_exports {
     pub use i_slint_core::animations::{animation_tick, EasingCurve};
     pub use i_slint_core::callbacks::Callback;
     pub use i_slint_core::component::{
-        register_component, unregister_component, Component, ComponentRefPin, ComponentVTable,
-        ComponentWeak, IndexRange,
+        register_component, shared_globals, unregister_component, Component, ComponentRefPin,
+        ComponentVTable, ComponentWeak, IndexRange,
     };
     pub use i_slint_core::graphics::*;
     pub use i_slint_core::input::{
@@ -183,5 +216,7 @@ pub mod re_exports {
     pub use once_cell::race::OnceBox;
     pub use once_cell::unsync::OnceCell;
     pub use pin_weak::rc::PinWeak;
+    #[cfg(feature = "serde")]
+    pub use serde;
     pub use vtable::{self, *};
 }
@@ -20,7 +20,10 @@ function startClient(context: vscode.ExtensionContext) {
     const clientOptions: LanguageClientOptions = {
         documentSelector,
         synchronize: {},
-        initializationOptions: {}
+        initializationOptions: {
+            // Must be URIs that the `slint/load_file` request can read, since there is no file system
+            libraryPaths: vscode.workspace.getConfiguration('slint').get<{ [name: string]: string }>('libraryPaths') ?? {}
+        }
     };
 
     const serverMain = Uri.joinPath(context.extensionUri, 'out/browserServerMain.js');
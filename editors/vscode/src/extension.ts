@@ -110,7 +110,11 @@ function startClient(context: vscode.ExtensionContext) {
 
     console.log(`Starting LSP server from ${serverModule}`);
 
-    let args = vscode.workspace.getConfiguration('slint').get<[string]>('lsp-args');
+    let args = vscode.workspace.getConfiguration('slint').get<string[]>('lsp-args') ?? [];
+    let libraryPaths = vscode.workspace.getConfiguration('slint').get<{ [name: string]: string }>('libraryPaths') ?? {};
+    for (const [name, path] of Object.entries(libraryPaths)) {
+        args = args.concat(["-L", `${name}=${path}`]);
+    }
 
     let serverOptions: ServerOptions = {
         run: { command: serverModule, options: options, args: args },
@@ -234,6 +234,9 @@ pub fn vtable(_attr: TokenStream, item: TokenStream) -> TokenStream {
 
         let ident = field.ident.as_ref().unwrap();
         let mut some = None;
+        // The entry in the vtable constructor must be compiled out together with the field
+        let cfg_attrs =
+            field.attrs.iter().filter(|a| a.path.is_ident("cfg")).cloned().collect::<Vec<_>>();
 
         let func_ty = if let Type::BareFn(f) = &mut field.ty {
             Some(f)
@@ -543,7 +546,7 @@ unsafe fn dealloc(vtable: &Self::VTable, ptr: *mut u8, layout: vtable::Layout) {
                     .unwrap(),
                 });
 
-                vtable_ctor.push(quote!(#ident: {
+                vtable_ctor.push(quote!(#(#cfg_attrs)* #ident: {
                     #sig_extern {
                         // This is safe since the self must be a instance of our type
                         #[allow(unused)]
@@ -560,7 +563,7 @@ unsafe fn dealloc(vtable: &Self::VTable, ptr: *mut u8, layout: vtable::Layout) {
                     // because a sound implementation of the trait wouldn't allow unsound things here
                     ReturnType::Type(_, r) => quote!(core::mem::transmute::<#r, #r>),
                 };
-                vtable_ctor.push(quote!(#ident: {
+                vtable_ctor.push(quote!(#(#cfg_attrs)* #ident: {
                     #sig_extern {
                         // This is safe since the self must be a instance of our type
                         #[allow(unsafe_code)]
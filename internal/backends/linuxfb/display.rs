@@ -0,0 +1,108 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! The screens the linuxfb backend renders into.
+
+use crate::Output;
+use i_slint_core::api::PhysicalSize;
+use i_slint_core::software_renderer::{
+    PremultipliedRgbaColor, Rgb565Pixel, SoftwareRenderer, TargetPixel,
+};
+
+/// A screen that the window can be rendered into
+pub trait Display {
+    /// The size of the screen, in pixels
+    fn size(&self) -> PhysicalSize;
+    /// Render the window into the screen
+    fn render(&mut self, renderer: &SoftwareRenderer<2>);
+}
+
+/// Open the display according to the backend configuration
+pub fn open(output: Output) -> Result<Box<dyn Display>, String> {
+    let force_fbdev = std::env::var_os("SLINT_FRAMEBUFFER").is_some();
+    match output {
+        Output::Drm if !force_fbdev => Ok(Box::new(crate::drmoutput::DrmOutput::open()?)),
+        Output::Auto if !force_fbdev => crate::drmoutput::DrmOutput::open()
+            .map(|drm| Box::new(drm) as Box<dyn Display>)
+            .or_else(|drm_err| {
+                crate::fbdev::FbDevice::open()
+                    .map(|fb| Box::new(fb) as Box<dyn Display>)
+                    .map_err(|fb_err| format!("{} and {}", drm_err, fb_err))
+            }),
+        _ => Ok(Box::new(crate::fbdev::FbDevice::open()?)),
+    }
+}
+
+/// The layout of the pixels in memory
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PixelFormat {
+    /// 32 bits per pixel: blue, green, red and an unused byte in memory
+    Xrgb8888,
+    /// 16 bits per pixel: 5 bits of red, 6 bits of green and 5 bits of blue
+    Rgb565,
+}
+
+impl PixelFormat {
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            PixelFormat::Xrgb8888 => 4,
+            PixelFormat::Rgb565 => 2,
+        }
+    }
+}
+
+/// A pixel in the [`PixelFormat::Xrgb8888`] format
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Xrgb8888Pixel {
+    pub blue: u8,
+    pub green: u8,
+    pub red: u8,
+    pub unused: u8,
+}
+
+impl TargetPixel for Xrgb8888Pixel {
+    fn blend(&mut self, color: PremultipliedRgbaColor) {
+        let a = (u8::MAX - color.alpha) as u16;
+        self.red = (self.red as u16 * a / 255) as u8 + color.red;
+        self.green = (self.green as u16 * a / 255) as u8 + color.green;
+        self.blue = (self.blue as u16 * a / 255) as u8 + color.blue;
+    }
+
+    fn from_rgb(red: u8, green: u8, blue: u8) -> Self {
+        Self { blue, green, red, unused: 0xff }
+    }
+}
+
+/// Render the window in the pixels of the memory of a screen. `stride` is the number of bytes per line.
+pub fn render_into(
+    renderer: &SoftwareRenderer<2>,
+    data: &mut [u8],
+    stride: usize,
+    format: PixelFormat,
+) {
+    match format {
+        PixelFormat::Xrgb8888 => renderer.render(cast_pixels::<Xrgb8888Pixel>(data), stride / 4),
+        PixelFormat::Rgb565 => renderer.render(cast_pixels::<Rgb565Pixel>(data), stride / 2),
+    }
+}
+
+fn cast_pixels<T: TargetPixel>(data: &mut [u8]) -> &mut [T] {
+    assert_eq!(data.as_ptr() as usize % core::mem::align_of::<T>(), 0, "unaligned frame buffer");
+    // Safety: the pixel types are plain old data for which any bit pattern is valid,
+    // and the alignment was checked above
+    unsafe {
+        core::slice::from_raw_parts_mut(
+            data.as_mut_ptr() as *mut T,
+            data.len() / core::mem::size_of::<T>(),
+        )
+    }
+}
+
+#[test]
+fn xrgb8888_pixel() {
+    let mut pixel = Xrgb8888Pixel::from_rgb(0x10, 0x20, 0x30);
+    assert_eq!(pixel, Xrgb8888Pixel { blue: 0x30, green: 0x20, red: 0x10, unused: 0xff });
+    pixel.blend(PremultipliedRgbaColor { red: 0x80, green: 0, blue: 0, alpha: 0xff });
+    assert_eq!(pixel, Xrgb8888Pixel { blue: 0, green: 0, red: 0x80, unused: 0xff });
+}
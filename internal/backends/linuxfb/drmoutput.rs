@@ -0,0 +1,248 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Rendering into the dumb buffers scanned out by a DRM/KMS device such as `/dev/dri/card0`
+
+// cspell:ignore crtc fourcc
+
+use crate::display::{Display, PixelFormat};
+use drm::buffer::{Buffer, DrmFourcc};
+use drm::control::{
+    connector, crtc, dumbbuffer::DumbBuffer, framebuffer, Device as _, Event, PageFlipFlags,
+};
+use i_slint_core::api::PhysicalSize;
+use i_slint_core::software_renderer::SoftwareRenderer;
+use std::fs::File;
+use std::os::unix::io::{AsRawFd, RawFd};
+
+const DRM_IOCTL_MODE_MAP_DUMB: libc::c_ulong = 0xc01064b3;
+
+/// `struct drm_mode_map_dumb` from `drm/drm_mode.h`
+#[repr(C)]
+#[derive(Default)]
+struct DrmModeMapDumb {
+    handle: u32,
+    pad: u32,
+    offset: u64,
+}
+
+struct Card(File);
+
+impl AsRawFd for Card {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0.as_raw_fd()
+    }
+}
+
+impl drm::Device for Card {}
+impl drm::control::Device for Card {}
+
+/// A dumb buffer, mapped in memory for as long as it exists
+struct MappedBuffer {
+    /// Always set, only taken when dropping
+    buffer: Option<DumbBuffer>,
+    framebuffer: framebuffer::Handle,
+    mapping: memmap2::MmapMut,
+}
+
+impl MappedBuffer {
+    fn new(card: &Card, size: PhysicalSize) -> Result<Self, String> {
+        let buffer = card
+            .create_dumb_buffer((size.width, size.height), DrmFourcc::Xrgb8888, 32)
+            .map_err(|err| format!("cannot create the buffer: {}", err))?;
+        let mut map_request = DrmModeMapDumb {
+            handle: drm::control::RawResourceHandle::from(buffer.handle()).get(),
+            ..Default::default()
+        };
+        // Safety: the structure matches the one from drm/drm_mode.h that the ioctl fills
+        if unsafe { libc::ioctl(card.as_raw_fd(), DRM_IOCTL_MODE_MAP_DUMB as _, &mut map_request) }
+            != 0
+        {
+            let err = std::io::Error::last_os_error();
+            let _ = card.destroy_dumb_buffer(buffer);
+            return Err(format!("cannot map the buffer: {}", err));
+        }
+        let len = buffer.pitch() as usize * size.height as usize;
+        // Safety: the buffer is only accessed by the kernel to scan it out, and the mapping
+        // is dropped before the buffer is destroyed
+        let mapping = unsafe {
+            memmap2::MmapOptions::new().offset(map_request.offset).len(len).map_mut(&card.0)
+        };
+        let mut mapping = match mapping {
+            Ok(mapping) => mapping,
+            Err(err) => {
+                let _ = card.destroy_dumb_buffer(buffer);
+                return Err(format!("cannot map the buffer: {}", err));
+            }
+        };
+        // Start from a black screen
+        mapping.fill(0);
+        let framebuffer = match card.add_framebuffer(&buffer, 24, 32) {
+            Ok(framebuffer) => framebuffer,
+            Err(err) => {
+                drop(mapping);
+                let _ = card.destroy_dumb_buffer(buffer);
+                return Err(format!("cannot create the framebuffer: {}", err));
+            }
+        };
+        Ok(Self { buffer: Some(buffer), framebuffer, mapping })
+    }
+
+    fn stride(&self) -> usize {
+        self.buffer.as_ref().unwrap().pitch() as usize
+    }
+
+    fn destroy(mut self, card: &Card) {
+        let _ = card.destroy_framebuffer(self.framebuffer);
+        let buffer = self.buffer.take().unwrap();
+        drop(self);
+        let _ = card.destroy_dumb_buffer(buffer);
+    }
+}
+
+/// The first connected output of a DRM device. The window is rendered into one of two dumb
+/// buffers while the other one is scanned out, and they are swapped with a page flip.
+pub struct DrmOutput {
+    card: Card,
+    crtc: crtc::Handle,
+    /// The mode the crtc had before, restored when the output is dropped
+    saved_crtc: crtc::Info,
+    connector: connector::Handle,
+    /// The buffer at index `front` is scanned out, the other one is rendered into
+    buffers: Vec<MappedBuffer>,
+    front: usize,
+    /// Set between a page flip and the event telling that it happened
+    page_flip_pending: bool,
+    size: PhysicalSize,
+}
+
+impl DrmOutput {
+    /// Open the device set with `SLINT_DRM_DEVICE`, or `/dev/dri/card0`
+    pub fn open() -> Result<Self, String> {
+        let path = std::env::var("SLINT_DRM_DEVICE").unwrap_or_else(|_| "/dev/dri/card0".into());
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&path)
+            .map_err(|err| format!("cannot open the DRM device {}: {}", path, err))?;
+        let card = Card(file);
+        let error = |what: &str, err: &dyn std::fmt::Display| {
+            format!("DRM device {}: cannot {}: {}", path, what, err)
+        };
+
+        let resources =
+            card.resource_handles().map_err(|err| error("query the resources", &err))?;
+        let connector = resources
+            .connectors()
+            .iter()
+            .filter_map(|handle| card.get_connector(*handle).ok())
+            .find(|info| info.state() == connector::State::Connected)
+            .ok_or_else(|| error("find a connected output", &"no connector is connected"))?;
+        let mode = *connector
+            .modes()
+            .first()
+            .ok_or_else(|| error("find a mode", &"the connector has no mode"))?;
+
+        let crtc = connector
+            .current_encoder()
+            .and_then(|encoder| card.get_encoder(encoder).ok())
+            .and_then(|encoder| encoder.crtc())
+            .or_else(|| resources.crtcs().first().copied())
+            .ok_or_else(|| error("find a crtc", &"the device has no crtc"))?;
+        let saved_crtc = card.get_crtc(crtc).map_err(|err| error("query the crtc", &err))?;
+
+        let (width, height) = mode.size();
+        let size = PhysicalSize::new(width as u32, height as u32);
+
+        let mut buffers = Vec::with_capacity(2);
+        for _ in 0..2 {
+            match MappedBuffer::new(&card, size) {
+                Ok(buffer) => buffers.push(buffer),
+                Err(err) => {
+                    buffers.into_iter().for_each(|buffer| buffer.destroy(&card));
+                    return Err(format!("DRM device {}: {}", path, err));
+                }
+            }
+        }
+        if let Err(err) = card.set_crtc(
+            crtc,
+            Some(buffers[0].framebuffer),
+            (0, 0),
+            &[connector.handle()],
+            Some(mode),
+        ) {
+            buffers.into_iter().for_each(|buffer| buffer.destroy(&card));
+            return Err(error("set the mode", &err));
+        }
+
+        Ok(Self {
+            card,
+            crtc,
+            saved_crtc,
+            connector: connector.handle(),
+            buffers,
+            front: 0,
+            page_flip_pending: false,
+            size,
+        })
+    }
+
+    /// Block until the last page flip happened, so that the back buffer is no longer scanned out
+    fn wait_for_page_flip(&mut self) {
+        while self.page_flip_pending {
+            match self.card.receive_events() {
+                Ok(events) => {
+                    if events.into_iter().any(|event| matches!(event, Event::PageFlip(_))) {
+                        self.page_flip_pending = false;
+                    }
+                }
+                Err(err) => {
+                    i_slint_core::debug_log!("slint linuxfb: cannot read the DRM events: {}", err);
+                    self.page_flip_pending = false;
+                }
+            }
+        }
+    }
+}
+
+impl Display for DrmOutput {
+    fn size(&self) -> PhysicalSize {
+        self.size
+    }
+
+    fn render(&mut self, renderer: &SoftwareRenderer<2>) {
+        self.wait_for_page_flip();
+        let back = 1 - self.front;
+        let buffer = &mut self.buffers[back];
+        let stride = buffer.stride();
+        crate::display::render_into(renderer, &mut buffer.mapping, stride, PixelFormat::Xrgb8888);
+        match self.card.page_flip(
+            self.crtc,
+            buffer.framebuffer,
+            &[PageFlipFlags::PageFlipEvent],
+            None,
+        ) {
+            Ok(()) => {
+                self.front = back;
+                self.page_flip_pending = true;
+            }
+            Err(err) => i_slint_core::debug_log!("slint linuxfb: cannot flip the page: {}", err),
+        }
+    }
+}
+
+impl Drop for DrmOutput {
+    fn drop(&mut self) {
+        self.wait_for_page_flip();
+        let _ = self.card.set_crtc(
+            self.crtc,
+            self.saved_crtc.framebuffer(),
+            self.saved_crtc.position(),
+            &[self.connector],
+            self.saved_crtc.mode(),
+        );
+        for buffer in std::mem::take(&mut self.buffers) {
+            buffer.destroy(&self.card);
+        }
+    }
+}
@@ -0,0 +1,204 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Rendering into the memory of a Linux framebuffer device such as `/dev/fb0`
+
+// cspell:ignore fbdev screeninfo xres yres xoffset yoffset smem mmio transp pixclock vmode
+
+use crate::display::{Display, PixelFormat};
+use i_slint_core::api::PhysicalSize;
+use i_slint_core::software_renderer::SoftwareRenderer;
+use std::fs::File;
+use std::os::unix::io::AsRawFd;
+
+const FBIOGET_VSCREENINFO: libc::c_ulong = 0x4600;
+const FBIOGET_FSCREENINFO: libc::c_ulong = 0x4602;
+
+/// `struct fb_bitfield` from `linux/fb.h`
+#[repr(C)]
+#[derive(Default)]
+struct FbBitfield {
+    offset: u32,
+    length: u32,
+    msb_right: u32,
+}
+
+/// `struct fb_var_screeninfo` from `linux/fb.h`
+#[repr(C)]
+#[derive(Default)]
+struct FbVarScreenInfo {
+    xres: u32,
+    yres: u32,
+    xres_virtual: u32,
+    yres_virtual: u32,
+    xoffset: u32,
+    yoffset: u32,
+    bits_per_pixel: u32,
+    grayscale: u32,
+    red: FbBitfield,
+    green: FbBitfield,
+    blue: FbBitfield,
+    transp: FbBitfield,
+    nonstd: u32,
+    activate: u32,
+    height: u32,
+    width: u32,
+    accel_flags: u32,
+    pixclock: u32,
+    left_margin: u32,
+    right_margin: u32,
+    upper_margin: u32,
+    lower_margin: u32,
+    hsync_len: u32,
+    vsync_len: u32,
+    sync: u32,
+    vmode: u32,
+    rotate: u32,
+    colorspace: u32,
+    reserved: [u32; 4],
+}
+
+/// `struct fb_fix_screeninfo` from `linux/fb.h`
+#[repr(C)]
+#[derive(Default)]
+struct FbFixScreenInfo {
+    id: [u8; 16],
+    smem_start: libc::c_ulong,
+    smem_len: u32,
+    type_: u32,
+    type_aux: u32,
+    visual: u32,
+    xpanstep: u16,
+    ypanstep: u16,
+    ywrapstep: u16,
+    line_length: u32,
+    mmio_start: libc::c_ulong,
+    mmio_len: u32,
+    accel: u32,
+    capabilities: u16,
+    reserved: [u16; 2],
+}
+
+/// A framebuffer device, or a regular file that stands for one
+pub struct FbDevice {
+    mapping: memmap2::MmapMut,
+    /// The offset of the visible part of the framebuffer in the mapping
+    offset: usize,
+    size: PhysicalSize,
+    /// The number of bytes per line
+    stride: usize,
+    format: PixelFormat,
+}
+
+impl FbDevice {
+    /// Open the device set with `SLINT_FRAMEBUFFER`, or `/dev/fb0`
+    pub fn open() -> Result<Self, String> {
+        let path = std::env::var("SLINT_FRAMEBUFFER").unwrap_or_else(|_| "/dev/fb0".into());
+        let fake_size = std::env::var("SLINT_FRAMEBUFFER_SIZE").ok().and_then(|size| {
+            let (width, height) = size.split_once('x')?;
+            Some(PhysicalSize::new(width.trim().parse().ok()?, height.trim().parse().ok()?))
+        });
+        Self::open_path(&path, fake_size)
+    }
+
+    /// Open the framebuffer device at `path`. If `path` is a regular file, it is used as a
+    /// framebuffer in the XRGB8888 format, of the size `fake_size`.
+    pub fn open_path(path: &str, fake_size: Option<PhysicalSize>) -> Result<Self, String> {
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)
+            .map_err(|err| format!("cannot open the framebuffer {}: {}", path, err))?;
+
+        let is_regular_file = file.metadata().map_or(false, |m| m.is_file());
+        let (size, stride, format, offset, len) = if is_regular_file {
+            let size = fake_size.ok_or_else(|| {
+                format!(
+                    "{} is not a framebuffer device and SLINT_FRAMEBUFFER_SIZE is not set",
+                    path
+                )
+            })?;
+            let format = PixelFormat::Xrgb8888;
+            let stride = size.width as usize * format.bytes_per_pixel();
+            let len = stride * size.height as usize;
+            file.set_len(len as u64)
+                .map_err(|err| format!("cannot resize the framebuffer {}: {}", path, err))?;
+            (size, stride, format, 0, len)
+        } else {
+            let (var_info, fix_info) = query_screen_info(&file)
+                .map_err(|err| format!("{} is not a framebuffer device: {}", path, err))?;
+            let format = match (var_info.bits_per_pixel, var_info.red.offset) {
+                (32, 16) => PixelFormat::Xrgb8888,
+                (16, 11) => PixelFormat::Rgb565,
+                (bpp, _) => {
+                    return Err(format!(
+                    "the pixel format of the framebuffer {} is not supported ({} bits per pixel)",
+                    path, bpp
+                ))
+                }
+            };
+            let stride = fix_info.line_length as usize;
+            let offset = var_info.yoffset as usize * stride
+                + var_info.xoffset as usize * format.bytes_per_pixel();
+            (
+                PhysicalSize::new(var_info.xres, var_info.yres),
+                stride,
+                format,
+                offset,
+                fix_info.smem_len as usize,
+            )
+        };
+
+        // Safety: the framebuffer is shared with the kernel and the display, but nobody else
+        // is expected to modify it while Slint is rendering into it
+        let mapping = unsafe { memmap2::MmapOptions::new().len(len).map_mut(&file) }
+            .map_err(|err| format!("cannot map the framebuffer {}: {}", path, err))?;
+
+        if offset + stride * size.height as usize > mapping.len() {
+            return Err(format!("the framebuffer {} is too small for its resolution", path));
+        }
+
+        Ok(Self { mapping, offset, size, stride, format })
+    }
+}
+
+impl Display for FbDevice {
+    fn size(&self) -> PhysicalSize {
+        self.size
+    }
+
+    fn render(&mut self, renderer: &SoftwareRenderer<2>) {
+        let len = self.stride * self.size.height as usize;
+        let data = &mut self.mapping[self.offset..self.offset + len];
+        crate::display::render_into(renderer, data, self.stride, self.format);
+    }
+}
+
+fn query_screen_info(file: &File) -> std::io::Result<(FbVarScreenInfo, FbFixScreenInfo)> {
+    let mut var_info = FbVarScreenInfo::default();
+    let mut fix_info = FbFixScreenInfo::default();
+    // Safety: the structures match the ones from linux/fb.h that the ioctls fill
+    unsafe {
+        if libc::ioctl(file.as_raw_fd(), FBIOGET_VSCREENINFO as _, &mut var_info) != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        if libc::ioctl(file.as_raw_fd(), FBIOGET_FSCREENINFO as _, &mut fix_info) != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    Ok((var_info, fix_info))
+}
+
+#[test]
+fn file_backed_framebuffer() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let path = file.path().to_str().unwrap();
+
+    assert!(FbDevice::open_path(path, None).is_err());
+
+    let fb = FbDevice::open_path(path, Some(PhysicalSize::new(20, 10))).unwrap();
+    assert_eq!(fb.size(), PhysicalSize::new(20, 10));
+    assert_eq!(fb.stride, 80);
+    assert_eq!(fb.format, PixelFormat::Xrgb8888);
+    assert_eq!(file.as_file().metadata().unwrap().len(), 800);
+}
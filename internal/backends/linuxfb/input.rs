@@ -0,0 +1,635 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Reading the keyboards, mice and touch screens with evdev
+
+// cspell:ignore evdev absinfo hwheel
+
+use evdev::{AbsoluteAxisType, Device, InputEventKind, Key, RelativeAxisType, Synchronization};
+use i_slint_core::api::{PhysicalPosition, PhysicalSize, PointerEventButton, WindowEvent};
+use i_slint_core::input::{key_codes, KeyEvent, KeyEventType, KeyboardModifiers};
+use i_slint_core::SharedString;
+use std::os::unix::io::{AsRawFd, RawFd};
+
+/// The number of logical pixels scrolled for one step of the mouse wheel
+const WHEEL_STEP: f32 = 60.;
+
+/// A pointer event, in physical pixels of the screen
+#[derive(Debug, Clone, PartialEq)]
+pub enum PointerEvent {
+    Pressed {
+        position: PhysicalPosition,
+        button: PointerEventButton,
+    },
+    Released {
+        position: PhysicalPosition,
+        button: PointerEventButton,
+    },
+    Moved {
+        position: PhysicalPosition,
+    },
+    /// The deltas are in logical pixels
+    Scrolled {
+        position: PhysicalPosition,
+        delta_x: f32,
+        delta_y: f32,
+    },
+}
+
+impl PointerEvent {
+    pub fn to_window_event(&self, scale_factor: f32) -> WindowEvent {
+        match self {
+            PointerEvent::Pressed { position, button } => WindowEvent::PointerPressed {
+                position: position.to_logical(scale_factor),
+                button: *button,
+            },
+            PointerEvent::Released { position, button } => WindowEvent::PointerReleased {
+                position: position.to_logical(scale_factor),
+                button: *button,
+            },
+            PointerEvent::Moved { position } => {
+                WindowEvent::PointerMoved { position: position.to_logical(scale_factor) }
+            }
+            PointerEvent::Scrolled { position, delta_x, delta_y } => WindowEvent::PointerScrolled {
+                position: position.to_logical(scale_factor),
+                delta_x: *delta_x,
+                delta_y: *delta_y,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputEvent {
+    Pointer(PointerEvent),
+    Key(KeyEvent),
+}
+
+/// The range of an absolute axis, used to map it to the screen
+#[derive(Clone, Copy, Default)]
+struct AxisRange {
+    minimum: i32,
+    maximum: i32,
+}
+
+impl AxisRange {
+    fn map(&self, value: i32, screen_length: u32) -> i32 {
+        if self.maximum <= self.minimum {
+            return value;
+        }
+        ((value - self.minimum) as i64 * screen_length as i64
+            / (self.maximum - self.minimum + 1) as i64) as i32
+    }
+}
+
+/// The state of one input device that is needed to interpret its events
+#[derive(Default)]
+struct DeviceState {
+    x_range: AxisRange,
+    y_range: AxisRange,
+    /// Set when the touch screen was touched or released, and reported on the next SYN_REPORT
+    pending_touch: Option<bool>,
+}
+
+struct InputDevice {
+    device: Device,
+    state: DeviceState,
+}
+
+/// The state of the pointer and keyboard, shared by all the devices
+struct InputState {
+    screen_size: PhysicalSize,
+    position: PhysicalPosition,
+    modifiers: KeyboardModifiers,
+}
+
+/// All the input devices, and the state of the pointer and keyboard
+pub struct Input {
+    devices: Vec<InputDevice>,
+    state: InputState,
+}
+
+impl Input {
+    /// Open the devices listed in `SLINT_INPUT_DEVICES`, or all the keyboards, mice and
+    /// touch screens. Devices that cannot be opened are skipped.
+    pub fn open(screen_size: PhysicalSize) -> Self {
+        let devices: Vec<Device> = match std::env::var("SLINT_INPUT_DEVICES") {
+            Ok(paths) => paths
+                .split(',')
+                .map(str::trim)
+                .filter(|path| !path.is_empty())
+                .filter_map(|path| match Device::open(path) {
+                    Ok(device) => Some(device),
+                    Err(err) => {
+                        i_slint_core::debug_log!(
+                            "slint linuxfb: cannot open input device {}: {}",
+                            path,
+                            err
+                        );
+                        None
+                    }
+                })
+                .collect(),
+            Err(_) => evdev::enumerate().map(|(_, device)| device).filter(is_useful).collect(),
+        };
+        Self::from_devices(devices, screen_size)
+    }
+
+    /// Read the input from the given devices
+    pub fn from_devices(devices: Vec<Device>, screen_size: PhysicalSize) -> Self {
+        let devices = devices
+            .into_iter()
+            .map(|device| {
+                // The event loop polls all the devices, so reading must never block
+                // Safety: the file descriptor is owned by the device
+                unsafe {
+                    let fd = device.as_raw_fd();
+                    let flags = libc::fcntl(fd, libc::F_GETFL);
+                    libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK);
+                }
+                let (x_range, y_range) = axis_ranges(&device);
+                InputDevice { device, state: DeviceState { x_range, y_range, pending_touch: None } }
+            })
+            .collect();
+
+        Self { devices, state: InputState::new(screen_size) }
+    }
+
+    /// The file descriptors to wait on for new events
+    pub fn fds(&self) -> impl Iterator<Item = RawFd> + '_ {
+        self.devices.iter().map(|d| d.device.as_raw_fd())
+    }
+
+    /// Returns the events that are available without blocking
+    pub fn read_events(&mut self) -> Vec<InputEvent> {
+        let mut result = Vec::new();
+        for device in &mut self.devices {
+            let events = match device.device.fetch_events() {
+                Ok(events) => events.collect::<Vec<_>>(),
+                Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => continue,
+                Err(err) => {
+                    i_slint_core::debug_log!(
+                        "slint linuxfb: error reading an input device: {}",
+                        err
+                    );
+                    continue;
+                }
+            };
+            for event in events {
+                self.state.process_event(&mut device.state, event, &mut result);
+            }
+        }
+        result
+    }
+}
+
+impl InputState {
+    fn new(screen_size: PhysicalSize) -> Self {
+        Self {
+            screen_size,
+            position: PhysicalPosition::new(
+                screen_size.width as i32 / 2,
+                screen_size.height as i32 / 2,
+            ),
+            modifiers: Default::default(),
+        }
+    }
+
+    /// Interpret an event of the device, and append the resulting events to `result`
+    fn process_event(
+        &mut self,
+        device: &mut DeviceState,
+        event: evdev::InputEvent,
+        result: &mut Vec<InputEvent>,
+    ) {
+        let value = event.value();
+        let screen_size = self.screen_size;
+        let clamp = |position: PhysicalPosition| {
+            PhysicalPosition::new(
+                position.x.clamp(0, screen_size.width as i32 - 1),
+                position.y.clamp(0, screen_size.height as i32 - 1),
+            )
+        };
+        match event.kind() {
+            InputEventKind::RelAxis(axis) => match axis {
+                RelativeAxisType::REL_X => {
+                    self.position =
+                        clamp(PhysicalPosition::new(self.position.x + value, self.position.y));
+                    result
+                        .push(InputEvent::Pointer(PointerEvent::Moved { position: self.position }));
+                }
+                RelativeAxisType::REL_Y => {
+                    self.position =
+                        clamp(PhysicalPosition::new(self.position.x, self.position.y + value));
+                    result
+                        .push(InputEvent::Pointer(PointerEvent::Moved { position: self.position }));
+                }
+                RelativeAxisType::REL_WHEEL => {
+                    result.push(InputEvent::Pointer(PointerEvent::Scrolled {
+                        position: self.position,
+                        delta_x: 0.,
+                        delta_y: value as f32 * WHEEL_STEP,
+                    }));
+                }
+                RelativeAxisType::REL_HWHEEL => {
+                    result.push(InputEvent::Pointer(PointerEvent::Scrolled {
+                        position: self.position,
+                        delta_x: value as f32 * WHEEL_STEP,
+                        delta_y: 0.,
+                    }));
+                }
+                _ => {}
+            },
+            InputEventKind::AbsAxis(axis) => {
+                match axis {
+                    AbsoluteAxisType::ABS_X | AbsoluteAxisType::ABS_MT_POSITION_X => {
+                        let x = device.x_range.map(value, screen_size.width);
+                        self.position = clamp(PhysicalPosition::new(x, self.position.y));
+                    }
+                    AbsoluteAxisType::ABS_Y | AbsoluteAxisType::ABS_MT_POSITION_Y => {
+                        let y = device.y_range.map(value, screen_size.height);
+                        self.position = clamp(PhysicalPosition::new(self.position.x, y));
+                    }
+                    _ => return,
+                }
+                if device.pending_touch.is_none() {
+                    result
+                        .push(InputEvent::Pointer(PointerEvent::Moved { position: self.position }));
+                }
+            }
+            InputEventKind::Synchronization(Synchronization::SYN_REPORT) => {
+                // The position of a touch comes after the touch itself, so the press is only
+                // sent once the whole report was read
+                if let Some(pressed) = device.pending_touch.take() {
+                    let position = self.position;
+                    let button = PointerEventButton::Left;
+                    result.push(InputEvent::Pointer(if pressed {
+                        PointerEvent::Pressed { position, button }
+                    } else {
+                        PointerEvent::Released { position, button }
+                    }));
+                }
+            }
+            InputEventKind::Key(Key::BTN_TOUCH) => {
+                device.pending_touch = Some(value != 0);
+            }
+            InputEventKind::Key(key) => {
+                let button = match key {
+                    Key::BTN_LEFT => Some(PointerEventButton::Left),
+                    Key::BTN_RIGHT => Some(PointerEventButton::Right),
+                    Key::BTN_MIDDLE => Some(PointerEventButton::Middle),
+                    _ => None,
+                };
+                if let Some(button) = button {
+                    let position = self.position;
+                    result.push(InputEvent::Pointer(if value != 0 {
+                        PointerEvent::Pressed { position, button }
+                    } else {
+                        PointerEvent::Released { position, button }
+                    }));
+                    return;
+                }
+
+                let pressed = value != 0;
+                match key {
+                    Key::KEY_LEFTSHIFT | Key::KEY_RIGHTSHIFT => self.modifiers.shift = pressed,
+                    Key::KEY_LEFTCTRL | Key::KEY_RIGHTCTRL => self.modifiers.control = pressed,
+                    Key::KEY_LEFTALT | Key::KEY_RIGHTALT => self.modifiers.alt = pressed,
+                    Key::KEY_LEFTMETA | Key::KEY_RIGHTMETA => self.modifiers.meta = pressed,
+                    _ => {}
+                }
+
+                if let Some(text) = key_to_text(key, self.modifiers.shift) {
+                    result.push(InputEvent::Key(KeyEvent {
+                        modifiers: self.modifiers,
+                        text,
+                        // 1 is a press and 2 an auto-repeat
+                        event_type: if pressed {
+                            KeyEventType::KeyPressed
+                        } else {
+                            KeyEventType::KeyReleased
+                        },
+                    }));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns true for the devices that can be used as a keyboard, a mouse or a touch screen
+fn is_useful(device: &Device) -> bool {
+    let keys = device.supported_keys();
+    let has_key = |key| keys.map_or(false, |keys| keys.contains(key));
+    let is_keyboard = has_key(Key::KEY_ENTER) && has_key(Key::KEY_A);
+    let is_mouse = has_key(Key::BTN_LEFT)
+        && device
+            .supported_relative_axes()
+            .map_or(false, |axes| axes.contains(RelativeAxisType::REL_X));
+    let is_touch = has_key(Key::BTN_TOUCH)
+        && device
+            .supported_absolute_axes()
+            .map_or(false, |axes| axes.contains(AbsoluteAxisType::ABS_X));
+    is_keyboard || is_mouse || is_touch
+}
+
+fn axis_ranges(device: &Device) -> (AxisRange, AxisRange) {
+    let has_axis =
+        |axis| device.supported_absolute_axes().map_or(false, |axes| axes.contains(axis));
+    if !has_axis(AbsoluteAxisType::ABS_X) && !has_axis(AbsoluteAxisType::ABS_MT_POSITION_X) {
+        return Default::default();
+    }
+    let state = match device.get_abs_state() {
+        Ok(state) => state,
+        Err(_) => return Default::default(),
+    };
+    let range = |axis: AbsoluteAxisType| {
+        let info = &state[axis.0 as usize];
+        AxisRange { minimum: info.minimum, maximum: info.maximum }
+    };
+    if has_axis(AbsoluteAxisType::ABS_X) {
+        (range(AbsoluteAxisType::ABS_X), range(AbsoluteAxisType::ABS_Y))
+    } else {
+        (range(AbsoluteAxisType::ABS_MT_POSITION_X), range(AbsoluteAxisType::ABS_MT_POSITION_Y))
+    }
+}
+
+/// Returns the text of a key, using a US keyboard layout
+fn key_to_text(key: Key, shift: bool) -> Option<SharedString> {
+    let char = match key {
+        Key::KEY_BACKSPACE => key_codes::Backspace,
+        Key::KEY_TAB if shift => key_codes::Backtab,
+        Key::KEY_TAB => key_codes::Tab,
+        Key::KEY_ENTER | Key::KEY_KPENTER => key_codes::Return,
+        Key::KEY_ESC => key_codes::Escape,
+        Key::KEY_DELETE => key_codes::Delete,
+        Key::KEY_UP => key_codes::UpArrow,
+        Key::KEY_DOWN => key_codes::DownArrow,
+        Key::KEY_LEFT => key_codes::LeftArrow,
+        Key::KEY_RIGHT => key_codes::RightArrow,
+        Key::KEY_HOME => key_codes::Home,
+        Key::KEY_END => key_codes::End,
+        Key::KEY_PAGEUP => key_codes::PageUp,
+        Key::KEY_PAGEDOWN => key_codes::PageDown,
+        Key::KEY_INSERT => key_codes::Insert,
+        Key::KEY_F1 => key_codes::F1,
+        Key::KEY_F2 => key_codes::F2,
+        Key::KEY_F3 => key_codes::F3,
+        Key::KEY_F4 => key_codes::F4,
+        Key::KEY_F5 => key_codes::F5,
+        Key::KEY_F6 => key_codes::F6,
+        Key::KEY_F7 => key_codes::F7,
+        Key::KEY_F8 => key_codes::F8,
+        Key::KEY_F9 => key_codes::F9,
+        Key::KEY_F10 => key_codes::F10,
+        Key::KEY_F11 => key_codes::F11,
+        Key::KEY_F12 => key_codes::F12,
+        _ => {
+            let (normal, shifted) = match key {
+                Key::KEY_A => ('a', 'A'),
+                Key::KEY_B => ('b', 'B'),
+                Key::KEY_C => ('c', 'C'),
+                Key::KEY_D => ('d', 'D'),
+                Key::KEY_E => ('e', 'E'),
+                Key::KEY_F => ('f', 'F'),
+                Key::KEY_G => ('g', 'G'),
+                Key::KEY_H => ('h', 'H'),
+                Key::KEY_I => ('i', 'I'),
+                Key::KEY_J => ('j', 'J'),
+                Key::KEY_K => ('k', 'K'),
+                Key::KEY_L => ('l', 'L'),
+                Key::KEY_M => ('m', 'M'),
+                Key::KEY_N => ('n', 'N'),
+                Key::KEY_O => ('o', 'O'),
+                Key::KEY_P => ('p', 'P'),
+                Key::KEY_Q => ('q', 'Q'),
+                Key::KEY_R => ('r', 'R'),
+                Key::KEY_S => ('s', 'S'),
+                Key::KEY_T => ('t', 'T'),
+                Key::KEY_U => ('u', 'U'),
+                Key::KEY_V => ('v', 'V'),
+                Key::KEY_W => ('w', 'W'),
+                Key::KEY_X => ('x', 'X'),
+                Key::KEY_Y => ('y', 'Y'),
+                Key::KEY_Z => ('z', 'Z'),
+                Key::KEY_1 => ('1', '!'),
+                Key::KEY_2 => ('2', '@'),
+                Key::KEY_3 => ('3', '#'),
+                Key::KEY_4 => ('4', '$'),
+                Key::KEY_5 => ('5', '%'),
+                Key::KEY_6 => ('6', '^'),
+                Key::KEY_7 => ('7', '&'),
+                Key::KEY_8 => ('8', '*'),
+                Key::KEY_9 => ('9', '('),
+                Key::KEY_0 => ('0', ')'),
+                Key::KEY_MINUS => ('-', '_'),
+                Key::KEY_EQUAL => ('=', '+'),
+                Key::KEY_LEFTBRACE => ('[', '{'),
+                Key::KEY_RIGHTBRACE => (']', '}'),
+                Key::KEY_SEMICOLON => (';', ':'),
+                Key::KEY_APOSTROPHE => ('\'', '"'),
+                Key::KEY_GRAVE => ('`', '~'),
+                Key::KEY_BACKSLASH => ('\\', '|'),
+                Key::KEY_COMMA => (',', '<'),
+                Key::KEY_DOT => ('.', '>'),
+                Key::KEY_SLASH => ('/', '?'),
+                Key::KEY_SPACE => (' ', ' '),
+                _ => return None,
+            };
+            if shift {
+                shifted
+            } else {
+                normal
+            }
+        }
+    };
+    let mut buffer = [0; 6];
+    Some(SharedString::from(char.encode_utf8(&mut buffer) as &str))
+}
+
+#[test]
+fn keys_to_text() {
+    assert_eq!(key_to_text(Key::KEY_A, false).as_deref(), Some("a"));
+    assert_eq!(key_to_text(Key::KEY_A, true).as_deref(), Some("A"));
+    assert_eq!(key_to_text(Key::KEY_2, true).as_deref(), Some("@"));
+    assert_eq!(key_to_text(Key::KEY_ENTER, false).as_deref(), Some("\n"));
+    assert_eq!(key_to_text(Key::KEY_LEFTSHIFT, false), None);
+}
+
+#[test]
+fn axis_range() {
+    let range = AxisRange { minimum: 0, maximum: 4095 };
+    assert_eq!(range.map(0, 800), 0);
+    assert_eq!(range.map(2048, 800), 400);
+    assert_eq!(range.map(4095, 800), 799);
+}
+
+#[test]
+fn synthetic_events() {
+    use evdev::EventType;
+
+    let range = AxisRange { minimum: 0, maximum: 4095 };
+    let mut device = DeviceState { x_range: range, y_range: range, ..Default::default() };
+    let mut state = InputState::new(PhysicalSize::new(800, 600));
+    let mut process = |events: &[(EventType, u16, i32)]| {
+        let mut result = Vec::new();
+        for (type_, code, value) in events {
+            let event = evdev::InputEvent::new(*type_, *code, *value);
+            state.process_event(&mut device, event, &mut result);
+        }
+        result
+    };
+    let pointer = |x, y| PhysicalPosition::new(x, y);
+    let left = PointerEventButton::Left;
+
+    // A mouse starts in the middle of the screen, and doesn't leave it
+    assert_eq!(
+        process(&[
+            (EventType::RELATIVE, RelativeAxisType::REL_X.0, 10),
+            (EventType::KEY, Key::BTN_LEFT.code(), 1),
+            (EventType::RELATIVE, RelativeAxisType::REL_Y.0, -1000),
+            (EventType::KEY, Key::BTN_LEFT.code(), 0),
+            (EventType::RELATIVE, RelativeAxisType::REL_WHEEL.0, -1),
+        ]),
+        vec![
+            InputEvent::Pointer(PointerEvent::Moved { position: pointer(410, 300) }),
+            InputEvent::Pointer(PointerEvent::Pressed {
+                position: pointer(410, 300),
+                button: left
+            }),
+            InputEvent::Pointer(PointerEvent::Moved { position: pointer(410, 0) }),
+            InputEvent::Pointer(PointerEvent::Released { position: pointer(410, 0), button: left }),
+            InputEvent::Pointer(PointerEvent::Scrolled {
+                position: pointer(410, 0),
+                delta_x: 0.,
+                delta_y: -WHEEL_STEP,
+            }),
+        ]
+    );
+
+    // A touch is pressed at the position reported with it
+    assert_eq!(
+        process(&[
+            (EventType::KEY, Key::BTN_TOUCH.code(), 1),
+            (EventType::ABSOLUTE, AbsoluteAxisType::ABS_X.0, 2048),
+            (EventType::ABSOLUTE, AbsoluteAxisType::ABS_Y.0, 1024),
+            (EventType::SYNCHRONIZATION, Synchronization::SYN_REPORT.0, 0),
+            (EventType::ABSOLUTE, AbsoluteAxisType::ABS_Y.0, 2048),
+            (EventType::SYNCHRONIZATION, Synchronization::SYN_REPORT.0, 0),
+            (EventType::KEY, Key::BTN_TOUCH.code(), 0),
+            (EventType::SYNCHRONIZATION, Synchronization::SYN_REPORT.0, 0),
+        ]),
+        vec![
+            InputEvent::Pointer(PointerEvent::Pressed {
+                position: pointer(400, 150),
+                button: left
+            }),
+            InputEvent::Pointer(PointerEvent::Moved { position: pointer(400, 300) }),
+            InputEvent::Pointer(PointerEvent::Released {
+                position: pointer(400, 300),
+                button: left
+            }),
+        ]
+    );
+
+    // The keys are translated with the modifiers
+    let key = |text: &str, shift, event_type| {
+        InputEvent::Key(KeyEvent {
+            modifiers: KeyboardModifiers { shift, ..Default::default() },
+            text: text.into(),
+            event_type,
+        })
+    };
+    assert_eq!(
+        process(&[
+            (EventType::KEY, Key::KEY_LEFTSHIFT.code(), 1),
+            (EventType::KEY, Key::KEY_A.code(), 1),
+            (EventType::KEY, Key::KEY_A.code(), 2),
+            (EventType::KEY, Key::KEY_LEFTSHIFT.code(), 0),
+            (EventType::KEY, Key::KEY_A.code(), 0),
+            (EventType::KEY, Key::KEY_LEFT.code(), 1),
+        ]),
+        vec![
+            key("A", true, KeyEventType::KeyPressed),
+            key("A", true, KeyEventType::KeyPressed),
+            key("a", false, KeyEventType::KeyReleased),
+            key(key_codes::LeftArrow.encode_utf8(&mut [0; 6]), false, KeyEventType::KeyPressed),
+        ]
+    );
+}
+
+#[test]
+#[ignore = "needs write access to /dev/uinput"]
+fn uinput_device() {
+    use evdev::uinput::VirtualDeviceBuilder;
+    use evdev::{AttributeSet, EventType};
+
+    let mut keys = AttributeSet::<Key>::new();
+    keys.insert(Key::KEY_A);
+    keys.insert(Key::KEY_LEFTSHIFT);
+    keys.insert(Key::BTN_LEFT);
+    let mut axes = AttributeSet::<RelativeAxisType>::new();
+    axes.insert(RelativeAxisType::REL_X);
+    axes.insert(RelativeAxisType::REL_Y);
+    let mut virtual_device = VirtualDeviceBuilder::new()
+        .unwrap()
+        .name("Slint linuxfb test device")
+        .with_keys(&keys)
+        .unwrap()
+        .with_relative_axes(&axes)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let path = virtual_device.enumerate_dev_nodes_blocking().unwrap().next().unwrap().unwrap();
+    // The device node may be created a bit after the device
+    let mut device = (0..50)
+        .find_map(|_| {
+            Device::open(&path)
+                .map_err(|_| std::thread::sleep(std::time::Duration::from_millis(20)))
+                .ok()
+        })
+        .unwrap();
+    // Don't send the events to the rest of the system
+    device.grab().unwrap();
+    assert!(is_useful(&device));
+    let mut input = Input::from_devices(vec![device], PhysicalSize::new(800, 600));
+
+    virtual_device
+        .emit(&[
+            evdev::InputEvent::new(EventType::KEY, Key::KEY_LEFTSHIFT.code(), 1),
+            evdev::InputEvent::new(EventType::KEY, Key::KEY_A.code(), 1),
+        ])
+        .unwrap();
+    virtual_device
+        .emit(&[
+            evdev::InputEvent::new(EventType::RELATIVE, RelativeAxisType::REL_X.0, 10),
+            evdev::InputEvent::new(EventType::KEY, Key::BTN_LEFT.code(), 1),
+        ])
+        .unwrap();
+
+    let mut events = Vec::new();
+    while events.len() < 3 {
+        let fds = input.fds().collect::<Vec<_>>();
+        crate::wait_for_events(&fds, Some(std::time::Duration::from_secs(5)));
+        let new_events = input.read_events();
+        assert!(!new_events.is_empty(), "timeout while waiting for the input events");
+        events.extend(new_events);
+    }
+    let position = PhysicalPosition::new(410, 300);
+    assert_eq!(
+        events,
+        vec![
+            InputEvent::Key(KeyEvent {
+                modifiers: KeyboardModifiers { shift: true, ..Default::default() },
+                text: "A".into(),
+                event_type: KeyEventType::KeyPressed,
+            }),
+            InputEvent::Pointer(PointerEvent::Moved { position }),
+            InputEvent::Pointer(PointerEvent::Pressed {
+                position,
+                button: PointerEventButton::Left
+            }),
+        ]
+    );
+}
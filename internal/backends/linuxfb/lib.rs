@@ -0,0 +1,305 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+#![doc = include_str!("README.md")]
+#![doc(html_logo_url = "https://slint-ui.com/logo/slint-logo-square-light.svg")]
+#![cfg(target_os = "linux")]
+
+use i_slint_core::api::EventLoopError;
+use i_slint_core::platform::{EventLoopProxy, Platform, PlatformError};
+use i_slint_core::window::WindowAdapter;
+use std::cell::{Cell, RefCell};
+use std::io::{Read, Write};
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::os::unix::net::UnixStream;
+use std::rc::{Rc, Weak};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+mod display;
+mod drmoutput;
+mod fbdev;
+mod input;
+mod window;
+
+use window::LinuxFbWindow;
+
+#[doc(hidden)]
+pub fn use_modules() {}
+
+pub type NativeWidgets = ();
+pub type NativeGlobals = ();
+pub mod native_widgets {
+    pub struct NativeStyleMetrics {}
+}
+pub const HAS_NATIVE_STYLE: bool = false;
+
+pub fn native_style_metrics_init(_: core::pin::Pin<&native_widgets::NativeStyleMetrics>) {}
+pub fn native_style_metrics_deinit(_: core::pin::Pin<&mut native_widgets::NativeStyleMetrics>) {}
+
+/// The kind of device the backend renders into
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Output {
+    /// Try DRM first, and fall back to the framebuffer device
+    Auto,
+    Drm,
+    FbDev,
+}
+
+/// The state shared with the [`EventLoopProxy`], that may be used from other threads
+struct SharedState {
+    quit: AtomicBool,
+    pending_events: Mutex<Vec<Box<dyn FnOnce() + Send>>>,
+    /// Writing to this socket wakes up the event loop
+    wake_up: Mutex<UnixStream>,
+}
+
+impl SharedState {
+    fn wake_up(&self) -> Result<(), EventLoopError> {
+        // The event loop reads everything once it wakes up, so a full socket buffer is fine
+        match self.wake_up.lock().unwrap().write(&[0]) {
+            Ok(_) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => Ok(()),
+            Err(_) => Err(EventLoopError::EventLoopTerminated),
+        }
+    }
+}
+
+pub struct Backend {
+    output: Output,
+    windows: RefCell<Vec<Weak<LinuxFbWindow>>>,
+    quit_on_last_window_closed: Cell<bool>,
+    shared_state: Arc<SharedState>,
+    /// The event loop waits on this socket, in addition to the input devices
+    wake_up_receiver: UnixStream,
+}
+
+impl Backend {
+    /// Create the backend. `output` is the part after `linuxfb-` in `SLINT_BACKEND`
+    /// and selects the kind of output: `drm` or `fbdev`.
+    ///
+    /// Returns an error if the socket that wakes up the event loop cannot be created.
+    pub fn new(output: Option<&str>) -> Result<Self, PlatformError> {
+        let output = match output {
+            Some("drm") => Output::Drm,
+            Some("fb") | Some("fbdev") => Output::FbDev,
+            None | Some("") => Output::Auto,
+            Some(output) => {
+                i_slint_core::debug_log!(
+                    "slint linuxfb: unrecognized output {}, falling back to auto",
+                    output
+                );
+                Output::Auto
+            }
+        };
+        let socket_error = |err: std::io::Error| {
+            PlatformError::Other(format!(
+                "slint linuxfb: cannot create the event loop socket: {}",
+                err
+            ))
+        };
+        let (wake_up_sender, wake_up_receiver) = UnixStream::pair().map_err(socket_error)?;
+        wake_up_sender.set_nonblocking(true).map_err(socket_error)?;
+        wake_up_receiver.set_nonblocking(true).map_err(socket_error)?;
+        Ok(Self {
+            output,
+            windows: Default::default(),
+            quit_on_last_window_closed: Cell::new(true),
+            shared_state: Arc::new(SharedState {
+                quit: AtomicBool::new(false),
+                pending_events: Default::default(),
+                wake_up: Mutex::new(wake_up_sender),
+            }),
+            wake_up_receiver,
+        })
+    }
+
+    /// Returns the window that is shown on the screen: the last one that was shown
+    fn top_window(&self) -> Option<Rc<LinuxFbWindow>> {
+        let mut windows = self.windows.borrow_mut();
+        windows.retain(|w| w.strong_count() > 0);
+        windows
+            .iter()
+            .filter_map(|w| w.upgrade())
+            .filter(|w| w.is_visible())
+            .max_by_key(|w| w.shown_serial())
+    }
+
+    fn process_pending_events(&self) {
+        let mut buffer = [0; 64];
+        while matches!((&self.wake_up_receiver).read(&mut buffer), Ok(n) if n > 0) {}
+        let events = std::mem::take(&mut *self.shared_state.pending_events.lock().unwrap());
+        for event in events {
+            event();
+        }
+    }
+
+    /// Run the event loop, rendering the top window into `display`, until it quits
+    fn run_with(&self, mut display: Box<dyn display::Display>, mut input: input::Input) {
+        let screen_size = display.size();
+        self.shared_state.quit.store(false, Ordering::Relaxed);
+        let mut last_window = Weak::new();
+
+        loop {
+            i_slint_core::platform::update_timers_and_animations();
+            self.process_pending_events();
+
+            if self.shared_state.quit.load(Ordering::Relaxed) {
+                break;
+            }
+
+            let window = self.top_window();
+            let window = match window {
+                Some(window) => window,
+                None if self.quit_on_last_window_closed.get() => break,
+                None => {
+                    wait_for_events(&[self.wake_up_receiver.as_raw_fd()], None);
+                    continue;
+                }
+            };
+
+            // The whole screen must be redrawn when showing another window
+            if !Weak::ptr_eq(&last_window, &Rc::downgrade(&window)) {
+                window.window().set_size(screen_size);
+                window.request_full_redraw();
+                last_window = Rc::downgrade(&window);
+            }
+
+            for event in input.read_events() {
+                window.dispatch_input_event(event);
+            }
+
+            window.draw_if_needed(|renderer| display.render(renderer));
+
+            let timeout = if window.window().has_active_animations() {
+                // Render the next frame of the animations at 60 frames per seconds
+                Some(Duration::from_millis(16))
+            } else {
+                i_slint_core::platform::duration_until_next_timer_update()
+            };
+
+            let fds = input
+                .fds()
+                .chain(std::iter::once(self.wake_up_receiver.as_raw_fd()))
+                .collect::<Vec<_>>();
+            wait_for_events(&fds, timeout);
+        }
+    }
+}
+
+impl Platform for Backend {
+    fn create_window_adapter(&self) -> Rc<dyn WindowAdapter> {
+        let window = LinuxFbWindow::new();
+        self.windows.borrow_mut().push(Rc::downgrade(&window));
+        window
+    }
+
+    #[doc(hidden)]
+    fn set_event_loop_quit_on_last_window_closed(&self, quit_on_last_window_closed: bool) {
+        self.quit_on_last_window_closed.set(quit_on_last_window_closed);
+    }
+
+    fn run_event_loop(&self) {
+        self.try_run_event_loop().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_run_event_loop(&self) -> Result<(), PlatformError> {
+        let display = display::open(self.output).map_err(|err| {
+            PlatformError::Other(format!("slint linuxfb: could not open the display: {}", err))
+        })?;
+        let input = input::Input::open(display.size());
+        self.run_with(display, input);
+        Ok(())
+    }
+
+    fn new_event_loop_proxy(&self) -> Option<Box<dyn EventLoopProxy>> {
+        struct Proxy(Arc<SharedState>);
+        impl EventLoopProxy for Proxy {
+            fn quit_event_loop(&self) -> Result<(), EventLoopError> {
+                self.0.quit.store(true, Ordering::Relaxed);
+                self.0.wake_up()
+            }
+
+            fn invoke_from_event_loop(
+                &self,
+                event: Box<dyn FnOnce() + Send>,
+            ) -> Result<(), EventLoopError> {
+                self.0.pending_events.lock().unwrap().push(event);
+                self.0.wake_up()
+            }
+        }
+        Some(Box::new(Proxy(self.shared_state.clone())))
+    }
+}
+
+/// Block until one of the file descriptors is readable, or until the timeout elapsed
+fn wait_for_events(fds: &[RawFd], timeout: Option<Duration>) {
+    let mut poll_fds = fds
+        .iter()
+        .map(|fd| libc::pollfd { fd: *fd, events: libc::POLLIN, revents: 0 })
+        .collect::<Vec<_>>();
+    let timeout = timeout.map_or(-1, |t| t.as_millis().min(i32::MAX as u128) as libc::c_int);
+    // Safety: poll_fds is a valid array of pollfd of the given length
+    unsafe {
+        libc::poll(poll_fds.as_mut_ptr(), poll_fds.len() as libc::nfds_t, timeout);
+    }
+}
+
+/// Returns the scale factor set with the SLINT_SCALE_FACTOR environment variable, or 1
+fn scale_factor() -> f32 {
+    std::env::var("SLINT_SCALE_FACTOR")
+        .ok()
+        .and_then(|x| x.parse::<f32>().ok())
+        .filter(|f| *f > 0.)
+        .unwrap_or(1.)
+}
+
+#[test]
+fn render_into_file() {
+    use i_slint_core::api::PhysicalSize;
+    use slint_interpreter::{ComponentCompiler, ComponentHandle};
+
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let size = PhysicalSize::new(20, 10);
+    let display = fbdev::FbDevice::open_path(file.path().to_str().unwrap(), Some(size)).unwrap();
+
+    let backend = Backend::new(Some("fbdev")).unwrap();
+    let window = backend.create_window_adapter();
+    let definition = spin_on::spin_on(
+        ComponentCompiler::default().build_from_source(
+            r#"
+        export Test := Window {
+            background: #102030;
+            Rectangle { x: 0; y: 0; width: 5phx; height: 10phx; background: #ff0000; }
+        }"#
+            .into(),
+            Default::default(),
+        ),
+    )
+    .unwrap();
+    let instance = definition.create_with_existing_window(window.window());
+    instance.show();
+
+    // The event queued by the first event is only processed on the next iteration of the
+    // event loop, after the window was rendered
+    let proxy = backend.new_event_loop_proxy().unwrap();
+    let queue_proxy = backend.new_event_loop_proxy().unwrap();
+    let quit_proxy = backend.new_event_loop_proxy().unwrap();
+    proxy
+        .invoke_from_event_loop(Box::new(move || {
+            queue_proxy
+                .invoke_from_event_loop(Box::new(move || quit_proxy.quit_event_loop().unwrap()))
+                .unwrap()
+        }))
+        .unwrap();
+    backend.run_with(Box::new(display), input::Input::from_devices(Vec::new(), size));
+
+    let pixels = std::fs::read(file.path()).unwrap();
+    assert_eq!(pixels.len(), 20 * 10 * 4);
+    let pixel = |x: usize, y: usize| &pixels[(y * 20 + x) * 4..][..3];
+    // The bytes are blue, green and red
+    assert_eq!(pixel(0, 0), [0, 0, 0xff]);
+    assert_eq!(pixel(4, 9), [0, 0, 0xff]);
+    assert_eq!(pixel(10, 5), [0x30, 0x20, 0x10]);
+}
@@ -0,0 +1,112 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! The window adapter of the linuxfb backend. All the windows are shown full screen.
+
+use crate::input::InputEvent;
+use i_slint_core::api::Window;
+use i_slint_core::graphics::Point;
+use i_slint_core::item_rendering::DirtyRegion;
+use i_slint_core::renderer::Renderer;
+use i_slint_core::software_renderer::SoftwareRenderer;
+use i_slint_core::window::{WindowAdapter, WindowAdapterSealed, WindowInner};
+use std::cell::Cell;
+use std::rc::Rc;
+
+thread_local! {
+    /// Incremented each time a window is shown, so the last window that was shown is on top.
+    static NEXT_SHOWN_SERIAL: Cell<u64> = Cell::new(0);
+}
+
+pub struct LinuxFbWindow {
+    window: Window,
+    /// The DRM output swaps two buffers, so the renderer redraws what changed during the two last
+    /// frames. The framebuffer device keeps the previous frame, and this is a superset of what it needs.
+    renderer: SoftwareRenderer<2>,
+    needs_redraw: Cell<bool>,
+    visible: Cell<bool>,
+    shown_serial: Cell<u64>,
+}
+
+impl LinuxFbWindow {
+    pub fn new() -> Rc<Self> {
+        let window = Rc::<Self>::new_cyclic(|self_weak| Self {
+            window: Window::new(self_weak.clone()),
+            renderer: SoftwareRenderer::new(self_weak.clone()),
+            needs_redraw: Default::default(),
+            visible: Default::default(),
+            shown_serial: Default::default(),
+        });
+        WindowInner::from_pub(&window.window).set_scale_factor(crate::scale_factor());
+        window
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible.get()
+    }
+
+    pub fn shown_serial(&self) -> u64 {
+        self.shown_serial.get()
+    }
+
+    /// Redraw the whole window on the next frame, regardless of what changed.
+    pub fn request_full_redraw(&self) {
+        let size = self.window.size().to_logical(self.window.scale_factor());
+        self.renderer.mark_dirty_region(DirtyRegion::new(
+            Point::default(),
+            Point::new(size.width as _, size.height as _),
+        ));
+        self.needs_redraw.set(true);
+    }
+
+    /// Call the callback with the renderer if the window needs to be redrawn.
+    pub fn draw_if_needed(&self, render_callback: impl FnOnce(&SoftwareRenderer<2>)) {
+        if self.needs_redraw.replace(false) {
+            render_callback(&self.renderer);
+        }
+    }
+
+    pub fn dispatch_input_event(&self, event: InputEvent) {
+        let scale_factor = self.window.scale_factor();
+        match event {
+            InputEvent::Pointer(event) => {
+                self.window.dispatch_event(event.to_window_event(scale_factor))
+            }
+            InputEvent::Key(event) => WindowInner::from_pub(&self.window).process_key_input(&event),
+        }
+    }
+}
+
+impl WindowAdapterSealed for LinuxFbWindow {
+    fn show(&self) {
+        let serial = NEXT_SHOWN_SERIAL.with(|serial| {
+            serial.set(serial.get() + 1);
+            serial.get()
+        });
+        self.shown_serial.set(serial);
+        self.visible.set(true);
+        self.needs_redraw.set(true);
+    }
+
+    fn hide(&self) {
+        self.visible.set(false);
+    }
+
+    fn request_redraw(&self) {
+        self.needs_redraw.set(true);
+    }
+
+    fn renderer(&self) -> &dyn Renderer {
+        &self.renderer
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl WindowAdapter for LinuxFbWindow {
+    fn window(&self) -> &Window {
+        &self.window
+    }
+}
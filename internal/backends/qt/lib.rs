@@ -77,11 +77,12 @@ pub extern "C" fn slint_qt_get_widget(
     (qt_widgets::NativeLineEdit,
     (qt_widgets::NativeScrollView,
     (qt_widgets::NativeStandardListViewItem,
+    (qt_widgets::NativeTableHeaderSection,
     (qt_widgets::NativeComboBox,
     (qt_widgets::NativeComboBoxPopup,
     (qt_widgets::NativeTabWidget,
     (qt_widgets::NativeTab,
-            ()))))))))))));
+            ())))))))))))));
 
 #[cfg(not(no_qt))]
 #[rustfmt::skip]
@@ -194,6 +194,9 @@ pub fn draw(&self, callback: &mut dyn FnMut(u32, u32, &[u8])) {
 mod listviewitem;
 pub use listviewitem::*;
 
+mod tableheadersection;
+pub use tableheadersection::*;
+
 mod combobox;
 pub use combobox::*;
 
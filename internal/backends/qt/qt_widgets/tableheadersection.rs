@@ -0,0 +1,143 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+use i_slint_core::input::FocusEventResult;
+use i_slint_core::items::SortOrder;
+
+use super::*;
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+pub struct NativeTableHeaderSection {
+    pub x: Property<f32>,
+    pub y: Property<f32>,
+    pub width: Property<f32>,
+    pub height: Property<f32>,
+    pub index: Property<i32>,
+    pub item: Property<i_slint_core::model::TableColumn>,
+    pub sort_order: Property<SortOrder>,
+    pub has_hover: Property<bool>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for NativeTableHeaderSection {
+    fn init(self: Pin<&Self>, _window_adapter: &Rc<dyn WindowAdapter>) {}
+
+    fn geometry(self: Pin<&Self>) -> Rect {
+        euclid::rect(self.x(), self.y(), self.width(), self.height())
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        let index: i32 = self.index();
+        let item = self.item();
+        let text: qttypes::QString = item.title.as_str().into();
+
+        let s = cpp!(unsafe [
+            index as "int",
+            text as "QString"
+        ] -> qttypes::QSize as "QSize" {
+            ensure_initialized();
+            QStyleOptionHeader option;
+            option.state |= QStyle::State_Horizontal;
+            option.section = index;
+            option.text = text;
+            option.textAlignment = Qt::AlignLeft | Qt::AlignVCenter;
+            return qApp->style()->sizeFromContents(QStyle::CT_HeaderSection, &option, QSize{}, nullptr);
+        });
+        let min = match orientation {
+            Orientation::Horizontal => s.width,
+            Orientation::Vertical => s.height,
+        } as f32;
+        LayoutInfo { min, preferred: min, ..LayoutInfo::default() }
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _event: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &i_slint_core::items::ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn_render! { this dpr size painter widget initial_state =>
+        let index: i32 = this.index();
+        let has_hover: bool = this.has_hover();
+        let item = this.item();
+        let text: qttypes::QString = item.title.as_str().into();
+        let sortable = item.sortable;
+        let sort_order = this.sort_order();
+        let ascending = sort_order == SortOrder::Ascending;
+        let descending = sort_order == SortOrder::Descending;
+
+        cpp!(unsafe [
+            painter as "QPainterPtr*",
+            widget as "QWidget*",
+            size as "QSize",
+            dpr as "float",
+            index as "int",
+            has_hover as "bool",
+            text as "QString",
+            sortable as "bool",
+            ascending as "bool",
+            descending as "bool",
+            initial_state as "int"
+        ] {
+            QStyleOptionHeader option;
+            option.state |= QStyle::State(initial_state);
+            option.state |= QStyle::State_Enabled | QStyle::State_Horizontal;
+            option.rect = QRect(QPoint(), size / dpr);
+            option.section = index;
+            option.text = text;
+            option.textAlignment = Qt::AlignLeft | Qt::AlignVCenter;
+            if (has_hover && sortable) {
+                option.state |= QStyle::State_MouseOver;
+            }
+            if (ascending) {
+                option.sortIndicator = QStyleOptionHeader::SortDown;
+            } else if (descending) {
+                option.sortIndicator = QStyleOptionHeader::SortUp;
+            }
+            qApp->style()->drawControl(QStyle::CE_Header, &option, painter->get(), widget);
+        });
+    }
+}
+
+impl ItemConsts for NativeTableHeaderSection {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<Self, CachedRenderingData> =
+        Self::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+declare_item_vtable! {
+fn slint_get_NativeTableHeaderSectionVTable() -> NativeTableHeaderSectionVTable for NativeTableHeaderSection
+}
@@ -14,16 +14,20 @@
 };
 use i_slint_core::input::{KeyEvent, KeyEventType, MouseEvent};
 use i_slint_core::item_rendering::{ItemCache, ItemRenderer};
+use i_slint_core::item_tree::ItemWeak;
 use i_slint_core::items::{
     self, FillRule, ImageRendering, InputType, ItemRc, ItemRef, Layer, MouseCursor, Opacity,
     PointerEventButton, RenderingResult, TextOverflow, TextWrap, WindowItem,
 };
 use i_slint_core::layout::Orientation;
+use i_slint_core::properties::PropertyTracker;
+use i_slint_core::textlayout::styled_text::StyledText;
 use i_slint_core::window::{WindowAdapter, WindowAdapterSealed, WindowInner};
 use i_slint_core::{ImageInner, PathData, Property, SharedString};
 use items::{ImageFit, TextHorizontalAlignment, TextVerticalAlignment};
 
 use std::cell::RefCell;
+use std::ffi::c_void;
 use std::pin::Pin;
 use std::ptr::NonNull;
 use std::rc::{Rc, Weak};
@@ -79,6 +83,7 @@ struct TimerHandler : QObject {
     struct SlintWidget : QWidget {
         void *rust_window;
         bool isMouseButtonDown = false;
+        QPointer<QMenuBar> menubar;
 
         SlintWidget() {
             setMouseTracking(true);
@@ -97,6 +102,9 @@ struct SlintWidget : QWidget {
 
         void resizeEvent(QResizeEvent *event) override {
             QSize size = event->size();
+            if (menubar) {
+                menubar->setGeometry(0, 0, size.width(), menubar->sizeHint().height());
+            }
             rust!(Slint_resizeEvent [rust_window: &QtWindow as "void*", size: qttypes::QSize as "QSize"] {
                 rust_window.resize_event(size)
             });
@@ -200,13 +208,26 @@ struct SlintWidget : QWidget {
                 rust!(Slint_updateWindowActivation [rust_window: &QtWindow as "void*", active: bool as "bool"] {
                     WindowInner::from_pub(&rust_window.window).set_active(active)
                  });
+            } else if (event->type() == QEvent::WindowStateChange) {
+                auto state = windowState();
+                bool minimized = state & Qt::WindowMinimized;
+                bool maximized = state & Qt::WindowMaximized;
+                bool fullscreen = state & Qt::WindowFullScreen;
+                rust!(Slint_updateWindowState [rust_window: &QtWindow as "void*", minimized: bool as "bool", maximized: bool as "bool", fullscreen: bool as "bool"] {
+                    WindowInner::from_pub(&rust_window.window).set_window_item_state(minimized, maximized, fullscreen)
+                });
             }
             QWidget::changeEvent(event);
         }
 
         void closeEvent(QCloseEvent *event) override {
             bool accepted = rust!(Slint_requestClose [rust_window: &QtWindow as "void*"] -> bool as "bool" {
-                return WindowInner::from_pub(&rust_window.window).request_close();
+                let window_inner = WindowInner::from_pub(&rust_window.window);
+                let accepted = window_inner.request_close();
+                if accepted {
+                    window_inner.hide();
+                }
+                return accepted;
             });
             if (accepted) {
                 event->accept();
@@ -234,10 +255,33 @@ struct SlintWidget : QWidget {
         }
     };
 
+    // Add an entry for a MenuItem to a QMenuBar or a QMenu
+    static void add_menu_action(QWidget *parent, const QString &title, const QString &shortcut,
+            bool enabled, bool checkable, bool checked, SlintWidget *widget, size_t index) {
+        auto action = new QAction(title, parent);
+        action->setShortcut(QKeySequence(shortcut));
+        action->setEnabled(enabled);
+        action->setCheckable(checkable);
+        action->setChecked(checked);
+        parent->addAction(action);
+        // The widget is deleted with its window, so the window is gone when the pointer is null
+        QObject::connect(action, &QAction::triggered, [widget = QPointer<SlintWidget>(widget), index] {
+            if (!widget)
+                return;
+            void *rust_window = widget->rust_window;
+            rust!(Slint_menuEntryActivated [rust_window: &QtWindow as "void*", index: usize as "size_t"] {
+                if let Some(window) = rust_window.self_weak.upgrade() {
+                    window.activate_menu_entry(index);
+                }
+            });
+        });
+    }
+
     // Helper function used for the TextInput layouting
     //
+    // if line_height > 0, it is the distance between the lines, otherwise the height of the font is used.
     // if line_for_y_pos > 0, then the function will return the line at this y position
-    static int do_text_layout(QTextLayout &layout, int flags, const QRectF &rect, int line_for_y_pos = -1) {
+    static int do_text_layout(QTextLayout &layout, int flags, const QRectF &rect, qreal line_height, int line_for_y_pos = -1) {
         QTextOption options;
         options.setWrapMode((flags & Qt::TextWordWrap) ? QTextOption::WordWrap : QTextOption::NoWrap);
         options.setFlags(QTextOption::IncludeTrailingSpaces);
@@ -253,9 +297,14 @@ struct SlintWidget : QWidget {
             if (!line.isValid())
                 break;
             line.setLineWidth(rect.width());
-            height += leading;
-            line.setPosition(QPointF(0, height));
-            height += line.height();
+            if (line_height > 0) {
+                line.setPosition(QPointF(0, height));
+                height += line_height;
+            } else {
+                height += leading;
+                line.setPosition(QPointF(0, height));
+                height += line.height();
+            }
             if (line_for_y_pos >= 0 && height > line_for_y_pos) {
                 return count;
             }
@@ -269,6 +318,105 @@ struct SlintWidget : QWidget {
         }
         return -1;
     }
+
+    // Helper function used for the layouting of a Text with styled spans or a line height,
+    // that QPainter::drawText can't show.
+    //
+    // The lines are positioned from the top of the text. Returns the height of the text.
+    static qreal do_styled_text_layout(QTextLayout &layout, int flags, qreal width, qreal line_height) {
+        QTextOption options;
+        options.setWrapMode((flags & Qt::TextWordWrap) ? QTextOption::WordWrap : QTextOption::NoWrap);
+        options.setAlignment(Qt::Alignment(flags & Qt::AlignHorizontal_Mask));
+        layout.setTextOption(options);
+        layout.setCacheEnabled(true);
+        int leading = QFontMetrics(layout.font()).leading();
+        qreal height = 0;
+        layout.beginLayout();
+        while (true) {
+            auto line = layout.createLine();
+            if (!line.isValid())
+                break;
+            line.setLineWidth(width);
+            if (line_height > 0) {
+                line.setPosition(QPointF(0, height));
+                height += line_height;
+            } else {
+                height += leading;
+                line.setPosition(QPointF(0, height));
+                height += line.height();
+            }
+        }
+        layout.endLayout();
+        return height;
+    }
+
+    // The vertical offset of a text of the given height within its rectangle
+    static qreal styled_text_top(int flags, const QRectF &rect, qreal height, bool elide) {
+        // An elided text that doesn't fit starts at the top, so that its first lines are shown
+        if (elide && height > rect.height())
+            return 0;
+        if (flags & Qt::AlignVCenter)
+            return (rect.height() - height) / 2.;
+        if (flags & Qt::AlignBottom)
+            return rect.height() - height;
+        return 0;
+    }
+
+    // Draw a Text with styled spans or a line height
+    static void draw_styled_text(QPainter *painter, const QRectF &rect, int flags, QString string,
+            const QFont &font, const QVector<QTextLayout::FormatRange> &formats, qreal line_height, bool elide) {
+        string.replace(QChar('\n'), QChar::LineSeparator);
+        QTextLayout layout(string, font);
+        layout.setFormats(formats);
+        qreal height = do_styled_text_layout(layout, flags, rect.width(), line_height);
+        QPointF origin = rect.topLeft() + QPointF(0, styled_text_top(flags, rect, height, elide));
+        if (!elide) {
+            layout.draw(painter, origin);
+            return;
+        }
+        const QChar ellipsis(0x2026);
+        qreal ellipsis_width = QFontMetricsF(font).horizontalAdvance(ellipsis);
+        for (int i = 0; i < layout.lineCount(); ++i) {
+            QTextLine line = layout.lineAt(i);
+            // The lines that follow the last line that fits in the height are replaced by an ellipsis
+            bool last_visible = i + 1 < layout.lineCount()
+                && layout.lineAt(i + 1).y() + layout.lineAt(i + 1).height() > rect.height();
+            if (!last_visible && line.naturalTextWidth() <= rect.width()) {
+                line.draw(painter, origin);
+                continue;
+            }
+            // Lay out again the part of the line that fits with the ellipsis, with the formats of its spans
+            int end = line.textStart() + line.textLength();
+            if (line.naturalTextWidth() + ellipsis_width > rect.width())
+                end = line.xToCursor(line.x() + rect.width() - ellipsis_width, QTextLine::CursorBetweenCharacters);
+            while (end > line.textStart() && string[end - 1].isSpace())
+                end--;
+            QString elided = string.mid(line.textStart(), end - line.textStart()) + ellipsis;
+            QVector<QTextLayout::FormatRange> elided_formats;
+            for (auto range : formats) {
+                int start = qMax(range.start, line.textStart());
+                int range_end = qMin(range.start + range.length, end);
+                if (range_end > start) {
+                    range.start = start - line.textStart();
+                    range.length = range_end - start;
+                    elided_formats.append(range);
+                }
+            }
+            QTextLayout elided_layout(elided, font);
+            elided_layout.setFormats(elided_formats);
+            QTextOption options = layout.textOption();
+            options.setWrapMode(QTextOption::NoWrap);
+            elided_layout.setTextOption(options);
+            elided_layout.beginLayout();
+            auto elided_line = elided_layout.createLine();
+            elided_line.setLineWidth(rect.width());
+            elided_line.setPosition(line.position());
+            elided_layout.endLayout();
+            elided_layout.draw(painter, origin);
+            if (last_visible)
+                break;
+        }
+    }
 }}
 
 cpp_class!(
@@ -292,6 +440,35 @@ pub fn save(&mut self) {
 
 cpp_class! {pub unsafe struct QPainterPath as "QPainterPath"}
 
+cpp_class!(unsafe struct TextFormats as "QVector<QTextLayout::FormatRange>");
+
+/// The formats of the spans of a styled text, with the offsets in the UTF-16 encoded QString
+fn text_formats(styled_text: &StyledText, font_request: &FontRequest) -> TextFormats {
+    let mut formats = TextFormats::default();
+    let text = styled_text.text.as_str();
+    for span in &styled_text.spans {
+        let start = utf8_byte_offset_to_utf16_units(text, span.range.start) as i32;
+        let length = utf8_byte_offset_to_utf16_units(text, span.range.end) as i32 - start;
+        let changes_font = span.style.changes_font();
+        let font: QFont = get_font(span.style.font_request(font_request));
+        let has_color = span.style.color.is_some();
+        let color: u32 = span.style.color.unwrap_or_default().as_argb_encoded();
+        let formats = &mut formats;
+        cpp!(unsafe [formats as "QVector<QTextLayout::FormatRange>*", start as "int", length as "int",
+                     changes_font as "bool", font as "QFont", has_color as "bool", color as "QRgb"] {
+            QTextLayout::FormatRange range;
+            range.start = start;
+            range.length = length;
+            if (changes_font)
+                range.format.setFont(font);
+            if (has_color)
+                range.format.setForeground(QColor::fromRgba(color));
+            formats->append(range);
+        });
+    }
+    formats
+}
+
 impl QPainterPath {
     /*
     pub fn reserve(&mut self, size: usize) {
@@ -510,8 +687,12 @@ fn draw_clipped_image(&mut self, image: Pin<&items::ClippedImage>, item_rc: &Ite
     fn draw_text(&mut self, text: std::pin::Pin<&items::Text>, _: &ItemRc) {
         let rect: qttypes::QRectF = get_geometry!(items::Text, text);
         let fill_brush: qttypes::QBrush = into_qbrush(text.color(), rect.width, rect.height);
-        let mut string: qttypes::QString = text.text().as_str().into();
-        let font: QFont = get_font(text.font_request(WindowInner::from_pub(&self.window)));
+        let styled_text = text.styled_text();
+        let mut string: qttypes::QString = styled_text.text.as_str().into();
+        let font_request = text.font_request(WindowInner::from_pub(&self.window));
+        let formats = text_formats(&styled_text, &font_request);
+        let line_height: f32 = font_request.line_height.unwrap_or_default();
+        let font: QFont = get_font(font_request);
         let flags = match text.horizontal_alignment() {
             TextHorizontalAlignment::Left => key_generated::Qt_AlignmentFlag_AlignLeft,
             TextHorizontalAlignment::Center => key_generated::Qt_AlignmentFlag_AlignHCenter,
@@ -526,11 +707,14 @@ fn draw_text(&mut self, text: std::pin::Pin<&items::Text>, _: &ItemRc) {
         };
         let elide = text.overflow() == TextOverflow::Elide;
         let painter: &mut QPainterPtr = &mut self.painter;
-        cpp! { unsafe [painter as "QPainterPtr*", rect as "QRectF", fill_brush as "QBrush", mut string as "QString", flags as "int", font as "QFont", elide as "bool"] {
+        cpp! { unsafe [painter as "QPainterPtr*", rect as "QRectF", fill_brush as "QBrush", mut string as "QString", flags as "int", font as "QFont", elide as "bool",
+                       formats as "QVector<QTextLayout::FormatRange>", line_height as "float"] {
             (*painter)->setFont(font);
             (*painter)->setPen(QPen(fill_brush, 0));
             (*painter)->setBrush(Qt::NoBrush);
-            if (!elide) {
+            if (!formats.isEmpty() || line_height > 0) {
+                draw_styled_text(painter->get(), rect, flags, string, font, formats, line_height, elide);
+            } else if (!elide) {
                 (*painter)->drawText(rect, flags, string);
             } else if (!(flags & Qt::TextWordWrap)) {
                 QString elided;
@@ -602,9 +786,10 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>, _: &
             }}
         }
 
-        let font: QFont = get_font(
-            text_input.font_request(&WindowInner::from_pub(&self.window).window_adapter()),
-        );
+        let font_request =
+            text_input.font_request(&WindowInner::from_pub(&self.window).window_adapter());
+        let line_height: f32 = font_request.line_height.unwrap_or_default();
+        let font: QFont = get_font(font_request);
         let flags = match text_input.horizontal_alignment() {
             TextHorizontalAlignment::Left => key_generated::Qt_AlignmentFlag_AlignLeft,
             TextHorizontalAlignment::Center => key_generated::Qt_AlignmentFlag_AlignHCenter,
@@ -655,6 +840,7 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>, _: &
                 flags as "int",
                 single_line as "bool",
                 font as "QFont",
+                line_height as "float",
                 cursor_position as "int",
                 anchor_position as "int",
                 text_cursor_width as "float"] {
@@ -662,7 +848,7 @@ fn draw_text_input(&mut self, text_input: std::pin::Pin<&items::TextInput>, _: &
                 string.replace(QChar('\n'), QChar::LineSeparator);
             }
             QTextLayout layout(string, font);
-            do_text_layout(layout, flags, rect);
+            do_text_layout(layout, flags, rect, line_height);
             (*painter)->setPen(QPen(fill_brush, 0));
             QVector<QTextLayout::FormatRange> selections;
             if (anchor_position != cursor_position) {
@@ -1207,6 +1393,32 @@ pub struct QtWindow {
     cache: ItemCache<qttypes::QPixmap>,
 
     tree_structure_changed: RefCell<bool>,
+
+    native_menu_bar: RefCell<Option<NativeMenuBar>>,
+}
+
+/// Rebuild the menu bar from the event loop when the properties of its entries changed.
+/// It can't be rebuilt right away: the change may come from a QAction of the menu bar.
+struct MenuBarDirtyHandler(Weak<QtWindow>);
+
+impl i_slint_core::properties::PropertyDirtyHandler for MenuBarDirtyHandler {
+    fn notify(&self) {
+        let window = self.0.clone();
+        i_slint_core::timers::Timer::single_shot(Default::default(), move || {
+            if let Some(window) = window.upgrade() {
+                window.update_native_menu_bar();
+            }
+        });
+    }
+}
+
+/// The QMenuBar that shows the MenuBarItem
+struct NativeMenuBar {
+    menu_bar_item: ItemWeak,
+    /// The MenuEntryItem for each QAction, by the index given to the action.
+    entries: Vec<ItemWeak>,
+    /// Tracks the properties of the entries
+    tracker: Pin<Box<PropertyTracker<MenuBarDirtyHandler>>>,
 }
 
 impl QtWindow {
@@ -1230,6 +1442,7 @@ pub fn new() -> Rc<Self> {
                 rendering_metrics_collector: Default::default(),
                 cache: Default::default(),
                 tree_structure_changed: RefCell::new(false),
+                native_menu_bar: Default::default(),
             }
         });
         let widget_ptr = rc.widget_ptr();
@@ -1330,6 +1543,92 @@ fn close_popup(&self) {
         WindowInner::from_pub(&self.window).close_popup();
     }
 
+    /// Re-create the QMenuBar if any property of the menu entries changed since it was built
+    fn update_native_menu_bar(&self) {
+        let mut native_menu_bar = self.native_menu_bar.borrow_mut();
+        let native_menu_bar = match native_menu_bar.as_mut() {
+            Some(native_menu_bar) if native_menu_bar.tracker.is_dirty() => native_menu_bar,
+            _ => return,
+        };
+        let menu_bar_item = match native_menu_bar.menu_bar_item.upgrade() {
+            Some(menu_bar_item) => menu_bar_item,
+            None => return,
+        };
+
+        let widget_ptr = self.widget_ptr();
+        let menu_bar_ptr = cpp! {unsafe [widget_ptr as "SlintWidget*"] -> *mut c_void as "QWidget*" {
+            if (widget_ptr->menubar)
+                widget_ptr->menubar->deleteLater();
+            widget_ptr->menubar = new QMenuBar(widget_ptr);
+            widget_ptr->menubar->setGeometry(0, 0, widget_ptr->width(), widget_ptr->menubar->sizeHint().height());
+            widget_ptr->menubar->show();
+            return widget_ptr->menubar;
+        }};
+
+        let mut entries = Vec::new();
+        native_menu_bar.tracker.as_ref().evaluate_as_dependency_root(|| {
+            self.fill_native_menu(menu_bar_ptr, &menu_bar_item, &mut entries)
+        });
+        native_menu_bar.entries = entries;
+    }
+
+    /// Add the MenuEntryItem children of `item` to `menu_ptr`, which is a QMenuBar or a QMenu
+    fn fill_native_menu(&self, menu_ptr: *mut c_void, item: &ItemRc, entries: &mut Vec<ItemWeak>) {
+        for entry_rc in items::menu_entries(item) {
+            let entry = entry_rc.downcast::<items::MenuEntryItem>().unwrap();
+            let entry = entry.as_pin_ref();
+            let title: qttypes::QString = entry.title().as_str().into();
+            let enabled = entry.enabled();
+            if entry.is_separator() {
+                cpp! {unsafe [menu_ptr as "QWidget*"] {
+                    if (auto menu = qobject_cast<QMenu*>(menu_ptr)) {
+                        menu->addSeparator();
+                    } else if (auto bar = qobject_cast<QMenuBar*>(menu_ptr)) {
+                        bar->addSeparator();
+                    }
+                }};
+            } else if items::menu_entries(&entry_rc).next().is_some() {
+                let sub_menu_ptr = cpp! {unsafe [menu_ptr as "QWidget*", title as "QString", enabled as "bool"] -> *mut c_void as "QMenu*" {
+                    QMenu *sub_menu = nullptr;
+                    if (auto bar = qobject_cast<QMenuBar*>(menu_ptr)) {
+                        sub_menu = bar->addMenu(title);
+                    } else {
+                        sub_menu = static_cast<QMenu*>(menu_ptr)->addMenu(title);
+                    }
+                    sub_menu->setEnabled(enabled);
+                    return sub_menu;
+                }};
+                self.fill_native_menu(sub_menu_ptr, &entry_rc, entries);
+            } else {
+                let index = entries.len();
+                entries.push(entry_rc.downgrade());
+                let shortcut: qttypes::QString = entry.shortcut().as_str().into();
+                let checkable = entry.checkable();
+                let checked = entry.checked();
+                let widget_ptr = self.widget_ptr();
+                cpp! {unsafe [menu_ptr as "QWidget*", title as "QString", shortcut as "QString", enabled as "bool",
+                              checkable as "bool", checked as "bool", widget_ptr as "SlintWidget*", index as "size_t"] {
+                    add_menu_action(menu_ptr, title, shortcut, enabled, checkable, checked, widget_ptr, index);
+                }};
+            }
+        }
+    }
+
+    /// Called when a QAction of the native menu bar is triggered
+    fn activate_menu_entry(&self, index: usize) {
+        let entry = self
+            .native_menu_bar
+            .borrow()
+            .as_ref()
+            .and_then(|native_menu_bar| native_menu_bar.entries.get(index)?.upgrade());
+        if let Some(entry) =
+            entry.as_ref().and_then(|entry| entry.downcast::<items::MenuEntryItem>())
+        {
+            entry.as_pin_ref().activate();
+        }
+        timer_event();
+    }
+
     fn free_graphics_resources(&self, component: ComponentRef) {
         // Invalidate caches:
         self.cache.component_destroyed(component);
@@ -1405,6 +1704,11 @@ fn apply_window_properties(&self, window_item: Pin<&items::WindowItem>) {
         let widget_ptr = self.widget_ptr();
         let title: qttypes::QString = window_item.title().as_str().into();
         let no_frame = window_item.no_frame();
+        let resizable = window_item.resizable();
+        let fullscreen = window_item.fullscreen();
+        let maximized = window_item.maximized();
+        let minimized = window_item.minimized();
+        let always_on_top = window_item.always_on_top();
         let mut size = qttypes::QSize {
             width: window_item.width().ceil() as _,
             height: window_item.height().ceil() as _,
@@ -1435,11 +1739,32 @@ fn apply_window_properties(&self, window_item: Pin<&items::WindowItem>) {
             }
         };
 
-        cpp! {unsafe [widget_ptr as "QWidget*",  title as "QString", size as "QSize", background as "QRgb", no_frame as "bool"] {
+        cpp! {unsafe [widget_ptr as "QWidget*",  title as "QString", size as "QSize", background as "QRgb", no_frame as "bool",
+                      resizable as "bool", fullscreen as "bool", maximized as "bool", minimized as "bool", always_on_top as "bool"] {
             if (size != widget_ptr->size()) {
                 widget_ptr->resize(size.expandedTo({1, 1}));
             }
-            widget_ptr->setWindowFlag(Qt::FramelessWindowHint, no_frame);
+            if (!resizable) {
+                // apply_geometry_constraint was called before and restores the constraints when this changes
+                widget_ptr->setFixedSize(widget_ptr->size());
+            }
+            auto flags = widget_ptr->windowFlags();
+            flags.setFlag(Qt::FramelessWindowHint, no_frame);
+            flags.setFlag(Qt::WindowStaysOnTopHint, always_on_top);
+            if (flags != widget_ptr->windowFlags()) {
+                // Changing the flags hides the window
+                bool visible = widget_ptr->isVisible();
+                widget_ptr->setWindowFlags(flags);
+                if (visible)
+                    widget_ptr->show();
+            }
+            auto state = widget_ptr->windowState();
+            state.setFlag(Qt::WindowFullScreen, fullscreen);
+            state.setFlag(Qt::WindowMaximized, maximized);
+            state.setFlag(Qt::WindowMinimized, minimized);
+            if (state != widget_ptr->windowState()) {
+                widget_ptr->setWindowState(state);
+            }
             widget_ptr->setWindowTitle(title);
             auto pal = widget_ptr->palette();
 
@@ -1510,6 +1835,26 @@ fn create_popup(&self, geometry: Rect) -> Option<Rc<dyn WindowAdapter>> {
         Some(popup_window as _)
     }
 
+    fn setup_menu_bar(&self, menu_bar: &ItemRc) -> Option<i_slint_core::Coord> {
+        *self.native_menu_bar.borrow_mut() = Some(NativeMenuBar {
+            menu_bar_item: menu_bar.downgrade(),
+            entries: Vec::new(),
+            tracker: Box::pin(PropertyTracker::new_with_dirty_handler(MenuBarDirtyHandler(
+                self.self_weak.clone(),
+            ))),
+        });
+        self.update_native_menu_bar();
+
+        let widget_ptr = self.widget_ptr();
+        let height = cpp! {unsafe [widget_ptr as "SlintWidget*"] -> i32 as "int" {
+            // On macOS, the menu bar is shown at the top of the screen
+            if (!widget_ptr->menubar || widget_ptr->menubar->isNativeMenuBar())
+                return 0;
+            return widget_ptr->menubar->sizeHint().height();
+        }};
+        Some(height as _)
+    }
+
     fn set_mouse_cursor(&self, cursor: MouseCursor) {
         let widget_ptr = self.widget_ptr();
         //unidirectional resize cursors are replaced with bidirectional ones
@@ -1549,6 +1894,33 @@ fn set_mouse_cursor(&self, cursor: MouseCursor) {
         }};
     }
 
+    fn apply_transient_parent(&self, parent: Option<&Rc<dyn WindowAdapter>>, modal: bool) {
+        let widget_ptr = self.widget_ptr();
+        let parent_ptr = parent
+            .and_then(|parent| parent.as_any().downcast_ref::<QtWindow>())
+            .map_or(std::ptr::null_mut(), |parent| parent.widget_ptr().as_ptr());
+        cpp! {unsafe [widget_ptr as "QWidget*", parent_ptr as "QWidget*", modal as "bool"] {
+            // Make sure the QWindow exist
+            widget_ptr->winId();
+            QWindow *parent_window = nullptr;
+            if (parent_ptr) {
+                parent_ptr->winId();
+                parent_window = parent_ptr->windowHandle();
+            }
+            widget_ptr->windowHandle()->setTransientParent(parent_window);
+            // Qt only applies the modality when the window is shown
+            widget_ptr->setWindowModality(modal && parent_window ? Qt::WindowModal : Qt::NonModal);
+        }};
+    }
+
+    fn raise(&self) {
+        let widget_ptr = self.widget_ptr();
+        cpp! {unsafe [widget_ptr as "QWidget*"] {
+            widget_ptr->raise();
+            widget_ptr->activateWindow();
+        }};
+    }
+
     fn renderer(&self) -> &dyn Renderer {
         self
     }
@@ -1610,9 +1982,94 @@ fn text_size(
         font_request: i_slint_core::graphics::FontRequest,
         text: &str,
         max_width: Option<f32>,
+        scale_factor: f32,
+    ) -> Size {
+        self.styled_text_size(
+            font_request,
+            &StyledText::plain(text.into()),
+            max_width,
+            scale_factor,
+        )
+    }
+
+    fn styled_text_size(
+        &self,
+        font_request: FontRequest,
+        text: &StyledText,
+        max_width: Option<f32>,
         _scale_factor: f32,
     ) -> Size {
-        get_font(font_request).text_size(text, max_width)
+        let line_height: f32 = font_request.line_height.unwrap_or_default();
+        if text.spans.is_empty() && line_height <= 0. {
+            return get_font(font_request).text_size(&text.text, max_width);
+        }
+        let formats = text_formats(text, &font_request);
+        let font: QFont = get_font(font_request);
+        let string = qttypes::QString::from(text.text.as_str());
+        let width: f32 = max_width.unwrap_or_default();
+        let flags = if max_width.is_some() { key_generated::Qt_TextFlag_TextWordWrap } else { 0 };
+        let size = cpp! { unsafe [font as "QFont", mut string as "QString", formats as "QVector<QTextLayout::FormatRange>",
+                                  width as "float", flags as "int", line_height as "float"] -> qttypes::QSizeF as "QSizeF" {
+            string.replace(QChar('\n'), QChar::LineSeparator);
+            QTextLayout layout(string, font);
+            layout.setFormats(formats);
+            qreal height = do_styled_text_layout(layout, flags, width, line_height);
+            qreal text_width = 0;
+            for (int i = 0; i < layout.lineCount(); ++i)
+                text_width = qMax(text_width, layout.lineAt(i).naturalTextWidth());
+            return QSizeF(text_width, height);
+        }};
+        Size::new(size.width as _, size.height as _)
+    }
+
+    fn text_byte_offset_for_position(
+        &self,
+        text: Pin<&i_slint_core::items::Text>,
+        pos: Point,
+    ) -> Option<usize> {
+        let rect: qttypes::QRectF = get_geometry!(items::Text, text);
+        let pos = qttypes::QPointF { x: pos.x as _, y: pos.y as _ };
+        let styled_text = text.styled_text();
+        let font_request = text.font_request(WindowInner::from_pub(&self.window));
+        let formats = text_formats(&styled_text, &font_request);
+        let line_height: f32 = font_request.line_height.unwrap_or_default();
+        let font: QFont = get_font(font_request);
+        let string = qttypes::QString::from(styled_text.text.as_str());
+        let flags = match text.horizontal_alignment() {
+            TextHorizontalAlignment::Left => key_generated::Qt_AlignmentFlag_AlignLeft,
+            TextHorizontalAlignment::Center => key_generated::Qt_AlignmentFlag_AlignHCenter,
+            TextHorizontalAlignment::Right => key_generated::Qt_AlignmentFlag_AlignRight,
+        } | match text.vertical_alignment() {
+            TextVerticalAlignment::Top => key_generated::Qt_AlignmentFlag_AlignTop,
+            TextVerticalAlignment::Center => key_generated::Qt_AlignmentFlag_AlignVCenter,
+            TextVerticalAlignment::Bottom => key_generated::Qt_AlignmentFlag_AlignBottom,
+        } | match text.wrap() {
+            TextWrap::NoWrap => 0,
+            TextWrap::WordWrap => key_generated::Qt_TextFlag_TextWordWrap,
+        };
+        let elide = text.overflow() == TextOverflow::Elide;
+        let offset = cpp! { unsafe [font as "QFont", string as "QString", formats as "QVector<QTextLayout::FormatRange>",
+                                    pos as "QPointF", rect as "QRectF", flags as "int", line_height as "float", elide as "bool"] -> i64 as "int64_t" {
+            // The \n replacement is done in a copy because the original is needed to know the utf8 offset
+            auto copy = string;
+            copy.replace(QChar('\n'), QChar::LineSeparator);
+            QTextLayout layout(copy, font);
+            layout.setFormats(formats);
+            qreal height = do_styled_text_layout(layout, flags, rect.width(), line_height);
+            qreal y = pos.y() - styled_text_top(flags, rect, height, elide);
+            for (int i = 0; i < layout.lineCount(); ++i) {
+                QTextLine line = layout.lineAt(i);
+                qreal line_end = i + 1 < layout.lineCount() ? layout.lineAt(i + 1).y() : height;
+                if (y < line.y() || y >= line_end)
+                    continue;
+                if (pos.x() < line.x() || pos.x() >= line.x() + line.naturalTextWidth())
+                    return -1;
+                int cur = line.xToCursor(pos.x(), QTextLine::CursorOnCharacter);
+                return QStringView(string).left(cur).toUtf8().size();
+            }
+            return -1;
+        }};
+        usize::try_from(offset).ok()
     }
 
     fn text_input_byte_offset_for_position(
@@ -1625,9 +2082,10 @@ fn text_input_byte_offset_for_position(
         }
         let rect: qttypes::QRectF = get_geometry!(items::TextInput, text_input);
         let pos = qttypes::QPointF { x: pos.x as _, y: pos.y as _ };
-        let font: QFont = get_font(
-            text_input.font_request(&WindowInner::from_pub(&self.window).window_adapter()),
-        );
+        let font_request =
+            text_input.font_request(&WindowInner::from_pub(&self.window).window_adapter());
+        let line_height: f32 = font_request.line_height.unwrap_or_default();
+        let font: QFont = get_font(font_request);
         let string = qttypes::QString::from(text_input.text().as_str());
         let flags = match text_input.horizontal_alignment() {
             TextHorizontalAlignment::Left => key_generated::Qt_AlignmentFlag_AlignLeft,
@@ -1644,7 +2102,7 @@ fn text_input_byte_offset_for_position(
         let single_line: bool = text_input.single_line();
         let is_password: bool = matches!(text_input.input_type(), InputType::Password);
         cpp! { unsafe [font as "QFont", string as "QString", pos as "QPointF", flags as "int",
-                rect as "QRectF", single_line as "bool", is_password as "bool"] -> usize as "size_t" {
+                rect as "QRectF", single_line as "bool", is_password as "bool", line_height as "float"] -> usize as "size_t" {
             // we need to do the \n replacement in a copy because the original need to be kept to know the utf8 offset
             auto copy = string;
             if (is_password) {
@@ -1654,7 +2112,7 @@ fn text_input_byte_offset_for_position(
                 copy.replace(QChar('\n'), QChar::LineSeparator);
             }
             QTextLayout layout(copy, font);
-            auto line = do_text_layout(layout, flags, rect, pos.y());
+            auto line = do_text_layout(layout, flags, rect, line_height, pos.y());
             if (line < 0 || layout.lineCount() <= line)
                 return string.toUtf8().size();
             QTextLine textLine = layout.lineAt(line);
@@ -1682,9 +2140,10 @@ fn text_input_cursor_rect_for_byte_offset(
         byte_offset: usize,
     ) -> Rect {
         let rect: qttypes::QRectF = get_geometry!(items::TextInput, text_input);
-        let font: QFont = get_font(
-            text_input.font_request(&WindowInner::from_pub(&self.window).window_adapter()),
-        );
+        let font_request =
+            text_input.font_request(&WindowInner::from_pub(&self.window).window_adapter());
+        let line_height: f32 = font_request.line_height.unwrap_or_default();
+        let font: QFont = get_font(font_request);
         let text = text_input.text();
         let mut string = qttypes::QString::from(text.as_str());
         let offset: u32 = utf8_byte_offset_to_utf16_units(text.as_str(), byte_offset) as _;
@@ -1701,13 +2160,13 @@ fn text_input_cursor_rect_for_byte_offset(
             TextWrap::WordWrap => key_generated::Qt_TextFlag_TextWordWrap,
         };
         let single_line: bool = text_input.single_line();
-        let r = cpp! { unsafe [font as "QFont", mut string as "QString", offset as "int", flags as "int", rect as "QRectF", single_line as "bool"]
+        let r = cpp! { unsafe [font as "QFont", mut string as "QString", offset as "int", flags as "int", rect as "QRectF", single_line as "bool", line_height as "float"]
                 -> qttypes::QPointF as "QPointF" {
             if (!single_line) {
                 string.replace(QChar('\n'), QChar::LineSeparator);
             }
             QTextLayout layout(string, font);
-            do_text_layout(layout, flags, rect);
+            do_text_layout(layout, flags, rect, line_height);
 
             QTextLine textLine = layout.lineForTextPosition(offset);
             if (!textLine.isValid())
@@ -1772,8 +2231,13 @@ fn get_font(request: FontRequest) -> QFont {
     let family: qttypes::QString = request.family.unwrap_or_default().as_str().into();
     let pixel_size: f32 = request.pixel_size.unwrap_or(0.);
     let weight: i32 = request.weight.unwrap_or(0);
+    let italic: bool = request.italic;
     let letter_spacing: f32 = request.letter_spacing.unwrap_or_default();
-    cpp!(unsafe [family as "QString", pixel_size as "float", weight as "int", letter_spacing as "float"] -> QFont as "QFont" {
+    // The line height is not a property of the QFont, it is applied when laying out the lines
+    let underline = request.decoration == items::TextDecoration::Underline;
+    let overline = request.decoration == items::TextDecoration::Overline;
+    let strike_out = request.decoration == items::TextDecoration::LineThrough;
+    cpp!(unsafe [family as "QString", pixel_size as "float", weight as "int", italic as "bool", letter_spacing as "float", underline as "bool", overline as "bool", strike_out as "bool"] -> QFont as "QFont" {
         QFont f;
         if (!family.isEmpty())
             f.setFamily(family);
@@ -1786,6 +2250,10 @@ fn get_font(request: FontRequest) -> QFont {
             f.setWeight(QFont::Weight(weight));
     #endif
         }
+        f.setItalic(italic);
+        f.setUnderline(underline);
+        f.setOverline(overline);
+        f.setStrikeOut(strike_out);
         f.setLetterSpacing(QFont::AbsoluteSpacing, letter_spacing);
         // Mark all font properties as resolved, to avoid inheriting font properties
         // from the widget hierarchy. Later we call QPainter::setFont, which would
@@ -3,7 +3,14 @@
 
 #![doc = include_str!("README.md")]
 #![doc(html_logo_url = "https://slint-ui.com/logo/slint-logo-square-light.svg")]
-#![cfg_attr(not(any(feature = "i-slint-backend-qt", feature = "i-slint-backend-winit")), no_std)]
+#![cfg_attr(
+    not(any(
+        feature = "i-slint-backend-qt",
+        feature = "i-slint-backend-winit",
+        all(feature = "i-slint-backend-linuxfb", target_os = "linux")
+    )),
+    no_std
+)]
 
 extern crate alloc;
 
@@ -23,6 +30,14 @@ fn create_default_backend() -> Box<dyn Platform + 'static> {
         fn create_default_backend() -> Box<dyn Platform + 'static> {
             Box::new(i_slint_backend_winit::Backend::new(None))
         }
+    } else if #[cfg(all(feature = "i-slint-backend-linuxfb", target_os = "linux"))] {
+        use i_slint_backend_linuxfb as default_backend;
+        fn create_default_backend() -> Box<dyn Platform + 'static> {
+            match i_slint_backend_linuxfb::Backend::new(None) {
+                Ok(backend) => Box::new(backend),
+                Err(err) => panic!("{}", err),
+            }
+        }
     } else {
 
     }
@@ -31,7 +46,8 @@ fn create_default_backend() -> Box<dyn Platform + 'static> {
 cfg_if::cfg_if! {
     if #[cfg(any(
             all(feature = "i-slint-backend-qt", not(no_qt)),
-            feature = "i-slint-backend-winit"
+            feature = "i-slint-backend-winit",
+            all(feature = "i-slint-backend-linuxfb", target_os = "linux")
         ))] {
         pub fn create_backend() -> Box<dyn Platform + 'static>  {
 
@@ -49,6 +65,7 @@ pub fn create_backend() -> Box<dyn Platform + 'static>  {
                 "gl" => Some(("winit", "femtovg")),
                 "skia" => Some(("winit", "skia")),
                 "sw" | "software" => Some(("winit", "software")),
+                "linuxfb" => Some(("linuxfb", "")),
                 _ => None,
             }) {
                 match event_loop {
@@ -56,6 +73,11 @@ pub fn create_backend() -> Box<dyn Platform + 'static>  {
                     "qt" => return Box::new(i_slint_backend_qt::Backend),
                     #[cfg(feature = "i-slint-backend-winit")]
                     "winit" => return Box::new(i_slint_backend_winit::Backend::new(Some(_renderer))),
+                    #[cfg(all(feature = "i-slint-backend-linuxfb", target_os = "linux"))]
+                    "linuxfb" => match i_slint_backend_linuxfb::Backend::new(Some(_renderer)) {
+                        Ok(backend) => return Box::new(backend),
+                        Err(err) => eprintln!("{}", err),
+                    },
                     _ => {},
                 }
             };
@@ -96,13 +118,16 @@ pub fn use_modules() {
     i_slint_backend_qt::use_modules();
     #[cfg(feature = "i-slint-backend-winit")]
     i_slint_backend_winit::use_modules();
+    #[cfg(all(feature = "i-slint-backend-linuxfb", target_os = "linux"))]
+    i_slint_backend_linuxfb::use_modules();
 }
 
 #[no_mangle]
 pub extern "C" fn slint_native_style_metrics_init(_self: Pin<&native_widgets::NativeStyleMetrics>) {
     #[cfg(any(
         all(feature = "i-slint-backend-qt", not(no_qt)),
-        feature = "i-slint-backend-winit"
+        feature = "i-slint-backend-winit",
+        all(feature = "i-slint-backend-linuxfb", target_os = "linux")
     ))]
     default_backend::native_style_metrics_init(_self);
 }
@@ -113,7 +138,8 @@ pub extern "C" fn slint_native_style_metrics_deinit(
 ) {
     #[cfg(any(
         all(feature = "i-slint-backend-qt", not(no_qt)),
-        feature = "i-slint-backend-winit"
+        feature = "i-slint-backend-winit",
+        all(feature = "i-slint-backend-linuxfb", target_os = "linux")
     ))]
     default_backend::native_style_metrics_deinit(_self);
 }
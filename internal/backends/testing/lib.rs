@@ -4,10 +4,12 @@
 #![doc = include_str!("README.md")]
 #![doc(html_logo_url = "https://slint-ui.com/logo/slint-logo-square-light.svg")]
 
+use i_slint_core::api::LogicalSize;
 use i_slint_core::graphics::{Point, Rect, Size};
 use i_slint_core::renderer::Renderer;
 use i_slint_core::window::WindowAdapter;
 use i_slint_core::window::WindowAdapterSealed;
+use std::cell::RefCell;
 use std::pin::Pin;
 use std::rc::Rc;
 use std::sync::Mutex;
@@ -15,12 +17,18 @@
 #[derive(Default)]
 pub struct TestingBackend {
     clipboard: Mutex<Option<String>>,
+    /// Measure the text with the software renderer instead of the fixed-size glyphs of the tests
+    software_text_layout: bool,
 }
 
 impl i_slint_core::platform::Platform for TestingBackend {
     fn create_window_adapter(&self) -> Rc<dyn WindowAdapter> {
         Rc::new_cyclic(|self_weak| TestingWindow {
             window: i_slint_core::api::Window::new(self_weak.clone() as _),
+            applied_properties: Default::default(),
+            software_renderer: self.software_text_layout.then(|| {
+                i_slint_core::software_renderer::SoftwareRenderer::new(self_weak.clone() as _)
+            }),
         })
     }
 
@@ -38,8 +46,23 @@ fn clipboard_text(&self) -> Option<String> {
     }
 }
 
+/// The properties of the window, as they were last passed to the windowing system.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AppliedWindowProperties {
+    pub title: String,
+    pub min_size: LogicalSize,
+    pub max_size: LogicalSize,
+    pub resizable: bool,
+    pub fullscreen: bool,
+    pub maximized: bool,
+    pub minimized: bool,
+    pub always_on_top: bool,
+}
+
 pub struct TestingWindow {
     window: i_slint_core::api::Window,
+    applied_properties: RefCell<AppliedWindowProperties>,
+    software_renderer: Option<i_slint_core::software_renderer::SoftwareRenderer<0>>,
 }
 
 impl WindowAdapterSealed for TestingWindow {
@@ -48,7 +71,10 @@ fn show(&self) {
     }
 
     fn renderer(&self) -> &dyn Renderer {
-        self
+        match &self.software_renderer {
+            Some(renderer) => renderer,
+            None => self,
+        }
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -62,6 +88,28 @@ fn position(&self) -> i_slint_core::api::PhysicalPosition {
     fn set_position(&self, _position: i_slint_core::api::WindowPosition) {
         unimplemented!()
     }
+
+    fn apply_window_properties(&self, window_item: Pin<&i_slint_core::items::WindowItem>) {
+        let mut applied_properties = self.applied_properties.borrow_mut();
+        applied_properties.title = window_item.title().into();
+        applied_properties.resizable = window_item.resizable();
+        applied_properties.fullscreen = window_item.fullscreen();
+        applied_properties.maximized = window_item.maximized();
+        applied_properties.minimized = window_item.minimized();
+        applied_properties.always_on_top = window_item.always_on_top();
+    }
+
+    fn apply_geometry_constraint(
+        &self,
+        constraints_horizontal: i_slint_core::layout::LayoutInfo,
+        constraints_vertical: i_slint_core::layout::LayoutInfo,
+    ) {
+        let mut applied_properties = self.applied_properties.borrow_mut();
+        applied_properties.min_size =
+            LogicalSize::new(constraints_horizontal.min as _, constraints_vertical.min as _);
+        applied_properties.max_size =
+            LogicalSize::new(constraints_horizontal.max as _, constraints_vertical.max as _);
+    }
 }
 
 impl WindowAdapter for TestingWindow {
@@ -81,6 +129,25 @@ fn text_size(
         Size::new(text.len() as f32 * 10., 10.)
     }
 
+    fn text_byte_offset_for_position(
+        &self,
+        text: Pin<&i_slint_core::items::Text>,
+        pos: Point,
+    ) -> Option<usize> {
+        // Matches text_size: one line, with every byte 10 pixels wide
+        if pos.x < 0. || !(0. ..10.).contains(&pos.y) {
+            return None;
+        }
+        let string = text.styled_text().text;
+        let byte_offset = (pos.x / 10.) as usize;
+        string
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .take_while(|offset| *offset <= byte_offset)
+            .last()
+            .filter(|_| byte_offset < string.len())
+    }
+
     fn text_input_byte_offset_for_position(
         &self,
         _text_input: Pin<&i_slint_core::items::TextInput>,
@@ -120,6 +187,17 @@ pub fn init() {
         .expect("platform already initialized");
 }
 
+/// Initialize the testing backend to render components without a windowing system, for example
+/// to take screenshots with [`render_window`]. Unlike with [`init`], the text is measured and laid
+/// out with the fonts of the software renderer.
+/// Returns an error if the rendering backend is already initialized.
+pub fn init_headless() -> Result<(), i_slint_core::platform::SetPlatformError> {
+    i_slint_core::platform::set_platform(Box::new(TestingBackend {
+        software_text_layout: true,
+        ..Default::default()
+    }))
+}
+
 /// This module contains functions useful for unit tests
 mod for_unit_test {
     use core::cell::Cell;
@@ -186,6 +264,87 @@ pub fn set_window_scale_factor<
     ) {
         WindowInner::from_pub(component.window()).set_scale_factor(factor)
     }
+
+    /// Simulate the user closing the window that's associated with the given component, for
+    /// example with the close button of the title bar. Returns true if the window was hidden, or
+    /// false if the request was rejected.
+    pub fn send_close_request<
+        X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable>,
+        Component: Into<vtable::VRc<i_slint_core::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+    ) -> bool {
+        let window_inner = WindowInner::from_pub(component.window());
+        let accepted = window_inner.request_close();
+        if accepted {
+            window_inner.hide();
+        }
+        accepted
+    }
+
+    /// Simulate the user or the windowing system changing the state of the window that's associated
+    /// with the given component, for example by maximizing it with the title bar.
+    pub fn set_window_state<
+        X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable>,
+        Component: Into<vtable::VRc<i_slint_core::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+        minimized: bool,
+        maximized: bool,
+        fullscreen: bool,
+    ) {
+        WindowInner::from_pub(component.window())
+            .set_window_item_state(minimized, maximized, fullscreen)
+    }
+
+    /// Returns the properties of the window that's associated with the given component, as a
+    /// windowing system would see them.
+    pub fn applied_window_properties<
+        X: vtable::HasStaticVTable<i_slint_core::component::ComponentVTable>,
+        Component: Into<vtable::VRc<i_slint_core::component::ComponentVTable, X>> + ComponentHandle,
+    >(
+        component: &Component,
+    ) -> super::AppliedWindowProperties {
+        let window_inner = WindowInner::from_pub(component.window());
+        window_inner.update_window_properties();
+        let window_adapter = window_inner.window_adapter();
+        let testing_window = window_adapter
+            .as_any()
+            .downcast_ref::<super::TestingWindow>()
+            .expect("the window is not a testing window");
+        let applied_properties = testing_window.applied_properties.borrow().clone();
+        applied_properties
+    }
+
+    /// Renders the content of the window with the software renderer and returns the pixels.
+    /// The image has the size of the window.
+    ///
+    /// Note that the text layout of the testing backend initialized with [`init`](super::init)
+    /// does not use real fonts, unlike with [`init_headless`](super::init_headless), and that text
+    /// is only drawn if the software renderer has fonts available, for example with the
+    /// `software-renderer-systemfonts` feature of i-slint-core.
+    pub fn render_window(
+        window: &i_slint_core::api::Window,
+    ) -> i_slint_core::graphics::SharedPixelBuffer<i_slint_core::graphics::Rgb8Pixel> {
+        let window_inner = WindowInner::from_pub(window);
+        let scale_factor = window_inner.scale_factor();
+        let (width, height) = window_inner.window_item().map_or((0, 0), |item| {
+            let item = item.as_pin_ref();
+            (
+                (item.width() as f32 * scale_factor).ceil() as u32,
+                (item.height() as f32 * scale_factor).ceil() as u32,
+            )
+        });
+        let mut buffer = i_slint_core::graphics::SharedPixelBuffer::new(width, height);
+        if width == 0 || height == 0 {
+            return buffer;
+        }
+        let renderer = i_slint_core::software_renderer::SoftwareRenderer::<0>::new(
+            std::rc::Rc::downgrade(&window_inner.window_adapter()),
+        );
+        renderer.render(buffer.make_mut_slice(), width as usize);
+        buffer
+    }
 }
 
 pub use for_unit_test::*;
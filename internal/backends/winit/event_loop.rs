@@ -189,6 +189,18 @@ fn window_by_id(id: winit::window::WindowId) -> Option<Rc<dyn WinitWindow>> {
     ALL_WINDOWS.with(|windows| windows.borrow().get(&id).and_then(|weakref| weakref.upgrade()))
 }
 
+/// Returns the registered window of this window adapter, if it is shown
+pub fn window_by_adapter(window_adapter: &Rc<dyn WindowAdapter>) -> Option<Rc<dyn WinitWindow>> {
+    let adapter_ptr = Rc::as_ptr(window_adapter) as *const u8;
+    ALL_WINDOWS.with(|windows| {
+        windows
+            .borrow()
+            .values()
+            .filter_map(|weakref| weakref.upgrade())
+            .find(|window| Rc::as_ptr(window) as *const u8 == adapter_ptr)
+    })
+}
+
 /// This enum captures run-time specific events that can be dispatched to the event loop in
 /// addition to the winit events.
 pub enum CustomEvent {
@@ -276,10 +288,29 @@ fn key_event(
     match event {
         WindowEvent::Resized(size) => {
             window.resize_event(size);
+            // The user may have maximized the window or left the fullscreen mode.
+            // winit cannot tell whether the window is minimized, so keep the property as is,
+            // and so is the fullscreen property when SLINT_FULLSCREEN forces the fullscreen mode.
+            if let Some(window_item) = runtime_window.window_item() {
+                let window_item = window_item.as_pin_ref();
+                let minimized = window_item.minimized();
+                let fullscreen_property = window_item.fullscreen();
+                window.with_window_handle(&mut |winit_window| {
+                    runtime_window.set_window_item_state(
+                        minimized,
+                        winit_window.is_maximized(),
+                        if crate::glwindow::fullscreen_from_env() {
+                            fullscreen_property
+                        } else {
+                            winit_window.fullscreen().is_some()
+                        },
+                    );
+                });
+            }
         }
         WindowEvent::CloseRequested => {
             if runtime_window.request_close() {
-                window.hide();
+                runtime_window.hide();
             }
         }
         WindowEvent::ReceivedCharacter(ch) => {
@@ -76,6 +76,69 @@ fn icon_to_winit(icon: corelib::graphics::Image) -> Option<winit::window::Icon>
     winit::window::Icon::from_rgba(rgba_pixels, pixel_buffer.width(), pixel_buffer.height()).ok()
 }
 
+/// Returns true if the `SLINT_FULLSCREEN` environment variable shows the windows in fullscreen mode,
+/// regardless of their `fullscreen` property.
+pub(crate) fn fullscreen_from_env() -> bool {
+    std::env::var("SLINT_FULLSCREEN").is_ok()
+}
+
+/// Tell the windowing system that `window` is a transient child of `parent`, so that it is kept
+/// on top of it, or is no longer a transient window if `parent` is None.
+/// On Windows the owner can only be set when the window is created, and Wayland is not supported.
+#[allow(unused_variables)]
+fn set_winit_transient_parent(
+    window: &winit::window::Window,
+    parent: Option<&winit::window::Window>,
+) {
+    #[cfg(all(
+        feature = "x11",
+        any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        )
+    ))]
+    {
+        use winit::platform::unix::{x11::ffi, WindowExtUnix};
+        if let (Some(xconn), Some(x_window)) = (window.xlib_xconnection(), window.xlib_window()) {
+            unsafe {
+                match parent.and_then(|parent| parent.xlib_window()) {
+                    Some(parent_window) => {
+                        (xconn.xlib.XSetTransientForHint)(xconn.display, x_window, parent_window);
+                    }
+                    None => {
+                        (xconn.xlib.XDeleteProperty)(
+                            xconn.display,
+                            x_window,
+                            ffi::XA_WM_TRANSIENT_FOR,
+                        );
+                    }
+                }
+                (xconn.xlib.XFlush)(xconn.display);
+            }
+        }
+    }
+    #[cfg(target_os = "macos")]
+    {
+        use objc::{msg_send, sel, sel_impl};
+        use winit::platform::macos::WindowExtMacOS;
+        let ns_window = window.ns_window() as cocoa::base::id;
+        unsafe {
+            let previous_parent: cocoa::base::id = msg_send![ns_window, parentWindow];
+            if previous_parent != cocoa::base::nil {
+                let () = msg_send![previous_parent, removeChildWindow: ns_window];
+            }
+            if let Some(parent) = parent {
+                let parent_ns_window = parent.ns_window() as cocoa::base::id;
+                let ordering = cocoa::appkit::NSWindowOrderingMode::NSWindowAbove.bits();
+                let () = msg_send![parent_ns_window, addChildWindow: ns_window ordered: ordering];
+            }
+        }
+    }
+}
+
 /// GraphicsWindow is an implementation of the [WindowAdapter][`crate::eventloop::WindowAdapter`] trait. This is
 /// typically instantiated by entry factory functions of the different graphics back ends.
 pub(crate) struct GLWindow<Renderer: WinitCompatibleRenderer + 'static> {
@@ -85,6 +148,9 @@ pub(crate) struct GLWindow<Renderer: WinitCompatibleRenderer + 'static> {
     keyboard_modifiers: std::cell::Cell<KeyboardModifiers>,
     currently_pressed_key_code: std::cell::Cell<Option<winit::event::VirtualKeyCode>>,
     pending_redraw: Cell<bool>,
+    /// The last value of the `minimized` property applied to the winit window, since
+    /// winit cannot tell whether the window is minimized.
+    minimized: Cell<bool>,
 
     renderer: Renderer,
 
@@ -110,6 +176,7 @@ pub(crate) fn new(#[cfg(target_arch = "wasm32")] canvas_id: String) -> Rc<dyn Wi
             keyboard_modifiers: Default::default(),
             currently_pressed_key_code: Default::default(),
             pending_redraw: Cell::new(false),
+            minimized: Cell::new(false),
             renderer: Renderer::new(
                 &(self_weak.clone() as _),
                 #[cfg(target_arch = "wasm32")]
@@ -292,6 +359,27 @@ fn apply_window_properties(&self, window_item: Pin<&i_slint_core::items::WindowI
             winit_window.set_title(&window_item.title());
             winit_window
                 .set_decorations(!window_item.no_frame() || winit_window.fullscreen().is_some());
+            winit_window.set_always_on_top(window_item.always_on_top());
+
+            let (constraints_horizontal, constraints_vertical) = self.constraints();
+            winit_window.set_resizable(
+                window_item.resizable()
+                    && (constraints_horizontal.min < constraints_horizontal.max
+                        || constraints_vertical.min < constraints_vertical.max),
+            );
+
+            let fullscreen = window_item.fullscreen() || fullscreen_from_env();
+            if fullscreen != winit_window.fullscreen().is_some() {
+                winit_window.set_fullscreen(
+                    fullscreen.then(|| winit::window::Fullscreen::Borderless(None)),
+                );
+            }
+            if window_item.maximized() != winit_window.is_maximized() {
+                winit_window.set_maximized(window_item.maximized());
+            }
+            if window_item.minimized() != self.minimized.replace(window_item.minimized()) {
+                winit_window.set_minimized(window_item.minimized());
+            }
 
             if width <= 0. || height <= 0. {
                 must_resize = true;
@@ -408,9 +496,14 @@ fn show(&self) {
             {
                 window_builder
                     .with_title(window_item.title().to_string())
-                    .with_resizable(window_item.height() <= 0 as _ || window_item.width() <= 0 as _)
+                    .with_resizable(
+                        window_item.resizable()
+                            && (window_item.height() <= 0 as _ || window_item.width() <= 0 as _),
+                    )
                     .with_decorations(!window_item.no_frame())
                     .with_window_icon(icon_to_winit(window_item.icon()))
+                    .with_maximized(window_item.maximized())
+                    .with_always_on_top(window_item.always_on_top())
             } else {
                 window_builder.with_title("Slint Window".to_string())
             };
@@ -447,7 +540,11 @@ fn show(&self) {
                 layout_info_v.preferred_bounded(),
             );
 
-            let window_builder = if std::env::var("SLINT_FULLSCREEN").is_ok() {
+            let fullscreen = fullscreen_from_env()
+                || runtime_window
+                    .window_item()
+                    .map_or(false, |window_item| window_item.as_pin_ref().fullscreen());
+            let window_builder = if fullscreen {
                 window_builder.with_fullscreen(Some(winit::window::Fullscreen::Borderless(None)))
             } else {
                 if layout_info_h.min >= 1. || layout_info_v.min >= 1. {
@@ -491,8 +588,33 @@ fn show(&self) {
                 window_builder
             };
 
+            let transient_parent = runtime_window
+                .transient_parent()
+                .and_then(|parent| crate::event_loop::window_by_adapter(&parent));
+
+            #[cfg(target_family = "windows")]
+            let window_builder = {
+                use winit::platform::windows::{WindowBuilderExtWindows, WindowExtWindows};
+                let mut owner = None;
+                if let Some(parent) = &transient_parent {
+                    parent.with_window_handle(&mut |parent| owner = Some(parent.hwnd()));
+                }
+                match owner {
+                    Some(owner) => window_builder.with_owner_window(owner),
+                    None => window_builder,
+                }
+            };
+
             let canvas = self_.renderer.create_canvas(window_builder);
 
+            if let Some(parent) = &transient_parent {
+                canvas.with_window_handle(|winit_window| {
+                    parent.with_window_handle(&mut |parent| {
+                        set_winit_transient_parent(winit_window, Some(parent))
+                    })
+                });
+            }
+
             let id = canvas.with_window_handle(|winit_window| {
                 WindowInner::from_pub(&self_.window).set_scale_factor(
                     scale_factor_override.unwrap_or_else(|| winit_window.scale_factor()) as _,
@@ -569,6 +691,37 @@ fn set_mouse_cursor(&self, cursor: MouseCursor) {
         });
     }
 
+    fn apply_transient_parent(&self, parent: Option<&Rc<dyn WindowAdapter>>, _modal: bool) {
+        // The input to the parent of a modal window is blocked by the WindowInner, and the
+        // parent is applied in show() if the window is not shown yet
+        let parent = parent.and_then(crate::event_loop::window_by_adapter);
+        self.with_window_handle(&mut |winit_window| match &parent {
+            Some(parent) => parent.with_window_handle(&mut |parent| {
+                set_winit_transient_parent(winit_window, Some(parent))
+            }),
+            None => set_winit_transient_parent(winit_window, None),
+        });
+    }
+
+    fn raise(&self) {
+        let runtime_window = WindowInner::from_pub(self.window());
+        if let Some(window_item) = runtime_window.window_item() {
+            let window_item = window_item.as_pin_ref();
+            if window_item.minimized() {
+                runtime_window.set_window_item_state(
+                    false,
+                    window_item.maximized(),
+                    window_item.fullscreen(),
+                );
+            }
+        }
+        self.minimized.set(false);
+        self.with_window_handle(&mut |winit_window| {
+            winit_window.set_minimized(false);
+            winit_window.focus_window();
+        });
+    }
+
     fn renderer(&self) -> &dyn i_slint_core::renderer::Renderer {
         &self.renderer
     }
@@ -218,6 +218,79 @@ fn text_size(
         crate::renderer::femtovg::fonts::text_size(&font_request, scale_factor, text, max_width)
     }
 
+    fn text_byte_offset_for_position(
+        &self,
+        text: Pin<&i_slint_core::items::Text>,
+        pos: Point,
+    ) -> Option<usize> {
+        let window_adapter = self.window_adapter_weak.upgrade()?;
+        let window = WindowInner::from_pub(window_adapter.window());
+
+        let scale_factor = window.scale_factor();
+        let pos = pos * scale_factor;
+
+        let width = text.width() * scale_factor;
+        let height = text.height() * scale_factor;
+        if width <= 0. || height <= 0. {
+            return None;
+        }
+
+        let styled_text = text.styled_text();
+        let font_request = text.font_request(window);
+        let font = crate::renderer::femtovg::fonts::FONT_CACHE.with(|cache| {
+            cache.borrow_mut().font(font_request.clone(), scale_factor, &styled_text.text)
+        });
+
+        let letter_spacing = text.letter_spacing() * scale_factor;
+        let paint = font.init_paint(letter_spacing, Default::default());
+        let span_paints = crate::renderer::femtovg::fonts::span_paints(
+            &styled_text,
+            &font,
+            &font_request,
+            scale_factor,
+            letter_spacing,
+            paint,
+        );
+        let text_context = crate::renderer::femtovg::fonts::FONT_CACHE
+            .with(|cache| cache.borrow().text_context.clone());
+        let font_height = text_context.measure_font(paint).unwrap().height();
+        let mut result = None;
+        crate::renderer::femtovg::fonts::layout_text_lines(
+            &styled_text.text,
+            &font,
+            Size::new(width, height),
+            (text.horizontal_alignment(), text.vertical_alignment()),
+            text.wrap(),
+            text.overflow(),
+            false,
+            paint,
+            |line_text, line_pos, start, _, rtl| {
+                if result.is_some() || !(line_pos.y..(line_pos.y + font_height)).contains(&pos.y) {
+                    return;
+                }
+                crate::renderer::femtovg::fonts::layout_styled_segments(
+                    line_text.trim_end(),
+                    line_pos,
+                    start,
+                    rtl,
+                    &span_paints,
+                    paint,
+                    |_, segment_pos, segment_start, _, metrics| {
+                        let mut current_x = segment_pos.x;
+                        for glyph in &metrics.glyphs {
+                            if (current_x..current_x + glyph.advance_x).contains(&pos.x) {
+                                result = Some(segment_start + glyph.byte_index);
+                                return;
+                            }
+                            current_x += glyph.advance_x;
+                        }
+                    },
+                );
+            },
+        );
+        result
+    }
+
     fn text_input_byte_offset_for_position(
         &self,
         text_input: Pin<&i_slint_core::items::TextInput>,
@@ -264,6 +337,7 @@ fn text_input_byte_offset_for_position(
         let text_context = crate::renderer::femtovg::fonts::FONT_CACHE
             .with(|cache| cache.borrow().text_context.clone());
         let font_height = text_context.measure_font(paint).unwrap().height();
+        let mut found = false;
         crate::renderer::femtovg::fonts::layout_text_lines(
             actual_text,
             &font,
@@ -273,18 +347,31 @@ fn text_input_byte_offset_for_position(
             i_slint_core::items::TextOverflow::Clip,
             text_input.single_line(),
             paint,
-            |line_text, line_pos, start, metrics| {
-                if (line_pos.y..(line_pos.y + font_height)).contains(&pos.y) {
-                    let mut current_x = 0.;
-                    for glyph in &metrics.glyphs {
-                        if line_pos.x + current_x + glyph.advance_x / 2. >= pos.x {
-                            result = start + glyph.byte_index;
-                            return;
-                        }
-                        current_x += glyph.advance_x;
+            |line_text, line_pos, start, metrics, rtl| {
+                // Lines with bidirectional text are reported in several runs, from left to right
+                if found || !(line_pos.y..(line_pos.y + font_height)).contains(&pos.y) {
+                    return;
+                }
+                let mut current_x = 0.;
+                for glyph in &metrics.glyphs {
+                    if line_pos.x + current_x + glyph.advance_x / 2. >= pos.x {
+                        // The left half of a right-to-left glyph is after it
+                        result = if rtl {
+                            start
+                                + glyph.byte_index
+                                + line_text[glyph.byte_index..]
+                                    .chars()
+                                    .next()
+                                    .map_or(0, char::len_utf8)
+                        } else {
+                            start + glyph.byte_index
+                        };
+                        found = true;
+                        return;
                     }
-                    result = start + line_text.trim_end().len();
+                    current_x += glyph.advance_x;
                 }
+                result = if rtl { start } else { start + line_text.trim_end().len() };
             },
         );
 
@@ -341,15 +428,19 @@ fn text_input_cursor_rect_for_byte_offset(
             i_slint_core::items::TextOverflow::Clip,
             text_input.single_line(),
             paint,
-            |line_text, line_pos, start, metrics| {
+            |line_text, line_pos, start, metrics, rtl| {
                 if (start..=(start + line_text.len())).contains(&byte_offset) {
                     for glyph in &metrics.glyphs {
                         if glyph.byte_index == (byte_offset - start) {
-                            result = line_pos + euclid::vec2(glyph.x, 0.0);
+                            // The cursor is on the right of a right-to-left glyph
+                            let x = if rtl { glyph.x + glyph.advance_x } else { glyph.x };
+                            result = line_pos + euclid::vec2(x, 0.0);
                             return;
                         }
                     }
-                    if let Some(last) = metrics.glyphs.last() {
+                    if rtl {
+                        result = line_pos;
+                    } else if let Some(last) = metrics.glyphs.last() {
                         result = line_pos + euclid::vec2(last.x + last.advance_x, last.y);
                     }
                 }
@@ -7,9 +7,12 @@
 use i_slint_core::graphics::euclid;
 use i_slint_core::graphics::{FontRequest, Point, Size};
 use i_slint_core::items::{TextHorizontalAlignment, TextOverflow, TextVerticalAlignment, TextWrap};
+use i_slint_core::textlayout::bidi::BidiInfo;
+use i_slint_core::textlayout::styled_text::StyledText;
 use i_slint_core::{SharedString, SharedVector};
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
+use std::ops::Range;
 
 pub const DEFAULT_FONT_SIZE: f32 = 12.;
 pub const DEFAULT_FONT_WEIGHT: i32 = 400; // CSS normal
@@ -67,12 +70,15 @@ pub fn register_font_from_path(_path: &std::path::Path) -> Result<(), Box<dyn st
 struct FontCacheKey {
     family: SharedString,
     weight: i32,
+    italic: bool,
 }
 
 #[derive(Clone)]
 pub struct Font {
     fonts: SharedVector<femtovg::FontId>,
     pixel_size: f32,
+    /// The line height in physical pixels, if it is not the height of the font
+    line_height: Option<f32>,
     text_context: TextContext,
 }
 
@@ -85,6 +91,11 @@ pub fn init_paint(&self, letter_spacing: f32, mut paint: femtovg::Paint) -> femt
         paint
     }
 
+    /// Returns the distance between the baselines of two lines, given the metrics of this font
+    pub fn line_height(&self, font_metrics: &femtovg::FontMetrics) -> f32 {
+        self.line_height.unwrap_or_else(|| font_metrics.height())
+    }
+
     pub fn text_size(&self, letter_spacing: f32, text: &str, max_width: Option<f32>) -> Size {
         let paint = self.init_paint(letter_spacing, femtovg::Paint::default());
         let font_metrics = self.text_context.measure_font(paint).unwrap();
@@ -111,7 +122,7 @@ pub fn text_size(&self, letter_spacing: f32, text: &str, max_width: Option<f32>)
                 width = measure.width().max(width);
             }
         }
-        euclid::size2(width, lines as f32 * font_metrics.height())
+        euclid::size2(width, lines as f32 * self.line_height(&font_metrics))
     }
 }
 
@@ -244,6 +255,7 @@ fn load_single_font(&mut self, request: &FontRequest) -> LoadedFont {
         let cache_key = FontCacheKey {
             family: request.family.clone().unwrap_or_default(),
             weight: request.weight.unwrap(),
+            italic: request.italic,
         };
 
         if let Some(loaded_font) = self.loaded_fonts.get(&cache_key) {
@@ -259,6 +271,7 @@ fn load_single_font(&mut self, request: &FontRequest) -> LoadedFont {
         let query = fontdb::Query {
             families: &[family],
             weight: fontdb::Weight(request.weight.unwrap() as u16),
+            style: if request.italic { fontdb::Style::Italic } else { fontdb::Style::Normal },
             ..Default::default()
         };
 
@@ -382,6 +395,7 @@ pub fn font(
             fonts,
             text_context: self.text_context.clone(),
             pixel_size: request.pixel_size.unwrap(),
+            line_height: request.line_height.map(|line_height| line_height * scale_factor),
         }
     }
 
@@ -408,8 +422,11 @@ fn font_fallbacks_for_request(
         .map(|fallback_descriptor| FontRequest {
             family: Some(fallback_descriptor.family_name().into()),
             weight: _request.weight,
+            italic: _request.italic,
             pixel_size: _request.pixel_size,
             letter_spacing: _request.letter_spacing,
+            line_height: _request.line_height,
+            decoration: _request.decoration,
         })
         .filter(|request| self.is_known_family(request))
         .collect::<Vec<_>>()
@@ -484,8 +501,11 @@ fn get_paragraph_reading_direction(
                 let fallback = FontRequest {
                     family: Some(family.into()),
                     weight: request.weight,
+                    italic: request.italic,
                     pixel_size: request.pixel_size,
                     letter_spacing: request.letter_spacing,
+                    line_height: request.line_height,
+                    decoration: request.decoration,
                 };
                 if self.is_known_family(&fallback) {
                     fallback_fonts.push(fallback)
@@ -512,8 +532,11 @@ fn font_fallbacks_for_request(
             .map(|family_name| FontRequest {
                 family: Some(family_name.into()),
                 weight: _request.weight,
+                italic: _request.italic,
                 pixel_size: _request.pixel_size,
                 letter_spacing: _request.letter_spacing,
+                line_height: _request.line_height,
+                decoration: _request.decoration,
             })
             .filter(|request| self.is_known_family(request))
             .collect()
@@ -529,8 +552,11 @@ fn font_fallbacks_for_request(
         [FontRequest {
             family: Some("DejaVu Sans".into()),
             weight: _request.weight,
+            italic: _request.italic,
             pixel_size: _request.pixel_size,
             letter_spacing: _request.letter_spacing,
+            line_height: _request.line_height,
+            decoration: _request.decoration,
         }]
         .iter()
         .filter(|request| self.is_known_family(request))
@@ -624,8 +650,11 @@ fn check_and_update_script_coverage(
 }
 
 /// Layout the given string in lines, and call the `layout_line` callback with the line to draw at position y.
-/// The signature of the `layout_line` function is: `(text, pos, start_index, line_metrics)`.
+/// The signature of the `layout_line` function is: `(text, pos, start_index, line_metrics, rtl)`.
 /// start index is the starting byte of the text in the string.
+/// Lines that contain bidirectional text are split in runs of a single direction, and the callback is
+/// called for each run, from left to right. `rtl` is true for the runs of right-to-left text.
+/// The glyphs are vertically centered in the line height of the font.
 /// Returns the y coordinate of where to place the cursor if it is at the end of the text
 pub(crate) fn layout_text_lines(
     string: &str,
@@ -636,14 +665,16 @@ pub(crate) fn layout_text_lines(
     overflow: TextOverflow,
     single_line: bool,
     paint: femtovg::Paint,
-    mut layout_line: impl FnMut(&str, Point, usize, &femtovg::TextMetrics),
+    mut layout_line: impl FnMut(&str, Point, usize, &femtovg::TextMetrics, bool),
 ) -> f32 {
     let wrap = wrap == TextWrap::WordWrap;
     let elide = overflow == TextOverflow::Elide;
 
     let text_context = FONT_CACHE.with(|cache| cache.borrow().text_context.clone());
     let font_metrics = text_context.measure_font(paint).unwrap();
-    let font_height = font_metrics.height();
+    let font_height = font.line_height(&font_metrics);
+    let half_leading = (font_height - font_metrics.height()) / 2.;
+    let bidi = BidiInfo::new(string);
 
     let text_height = || {
         if single_line {
@@ -663,6 +694,7 @@ pub(crate) fn layout_text_lines(
                             y: f32,
                             start: usize,
                             line_metrics: &femtovg::TextMetrics| {
+        let y = y + half_leading;
         let x = match horizontal_alignment {
             TextHorizontalAlignment::Left => 0.,
             TextHorizontalAlignment::Center => {
@@ -670,7 +702,32 @@ pub(crate) fn layout_text_lines(
             }
             TextHorizontalAlignment::Right => max_width - f32::min(max_width, line_metrics.width()),
         };
-        layout_line(text, Point::new(x, y), start, line_metrics);
+        if !bidi.has_rtl() {
+            layout_line(text, Point::new(x, y), start, line_metrics, false);
+            return;
+        }
+        // femtovg lays out every string as a left-to-right paragraph, so the runs are
+        // reordered here, according to the direction of the paragraph that contains the line
+        let paragraph_rtl = bidi.is_rtl_paragraph_at(start);
+        let runs = BidiInfo::with_direction(text, paragraph_rtl)
+            .visual_runs(text, 0..text.trim_end().len());
+        if runs.is_empty() {
+            layout_line(text, Point::new(x, y), start, line_metrics, paragraph_rtl);
+            return;
+        }
+        let mut run_x = x;
+        for run in runs {
+            let run_text = &text[run.byte_range.clone()];
+            let run_metrics = text_context.measure_text(0., 0., run_text, paint).unwrap();
+            layout_line(
+                run_text,
+                Point::new(run_x, y),
+                start + run.byte_range.start,
+                &run_metrics,
+                run.rtl,
+            );
+            run_x += run_metrics.width();
+        }
     };
 
     let baseline_y = match vertical_alignment {
@@ -741,3 +798,82 @@ pub(crate) fn layout_text_lines(
     }
     y
 }
+
+/// Returns the paints of the spans of a styled text that are not drawn with `paint`, the paint
+/// of the text initialized with `font`.
+pub(crate) fn span_paints(
+    styled_text: &StyledText,
+    font: &Font,
+    font_request: &FontRequest,
+    scale_factor: f32,
+    letter_spacing: f32,
+    paint: femtovg::Paint,
+) -> Vec<(Range<usize>, femtovg::Paint)> {
+    styled_text
+        .spans
+        .iter()
+        .filter(|span| span.style.changes_font() || span.style.color.is_some())
+        .map(|span| {
+            let span_paint = span.style.color.map_or(paint, |color| {
+                femtovg::Paint::color(super::itemrenderer::to_femtovg_color(&color))
+            });
+            let span_paint = if span.style.changes_font() {
+                FONT_CACHE
+                    .with(|cache| {
+                        cache.borrow_mut().font(
+                            span.style.font_request(font_request),
+                            scale_factor,
+                            &styled_text.text[span.range.clone()],
+                        )
+                    })
+                    .init_paint(letter_spacing, span_paint)
+            } else {
+                font.init_paint(letter_spacing, span_paint)
+            };
+            (span.range.clone(), span_paint)
+        })
+        .collect()
+}
+
+/// Splits a line (or a run of a line) reported by [`layout_text_lines`] in segments of the same
+/// span paint, and calls `layout_segment` with each segment, its position, its byte offset in the
+/// text, its paint and its metrics. The line breaking is done with the font of the text, so
+/// segments with a wider font may exceed the width of the text.
+pub(crate) fn layout_styled_segments(
+    text: &str,
+    pos: Point,
+    start: usize,
+    rtl: bool,
+    span_paints: &[(Range<usize>, femtovg::Paint)],
+    paint: femtovg::Paint,
+    mut layout_segment: impl FnMut(&str, Point, usize, femtovg::Paint, &femtovg::TextMetrics),
+) {
+    let end = start + text.len();
+    let mut segments = Vec::new();
+    let mut offset = start;
+    for (range, span_paint) in
+        span_paints.iter().filter(|(range, _)| range.end > start && range.start < end)
+    {
+        if range.start > offset {
+            segments.push((offset..range.start, paint));
+        }
+        offset = range.end.min(end);
+        segments.push((range.start.max(start)..offset, *span_paint));
+    }
+    if offset < end {
+        segments.push((offset..end, paint));
+    }
+    // The segments of a right-to-left run are placed from right to left
+    if rtl {
+        segments.reverse();
+    }
+
+    let text_context = FONT_CACHE.with(|cache| cache.borrow().text_context.clone());
+    let mut x = pos.x;
+    for (range, segment_paint) in segments {
+        let segment = &text[range.start - start..range.end - start];
+        let metrics = text_context.measure_text(0., 0., segment, segment_paint).unwrap();
+        layout_segment(segment, Point::new(x, pos.y), range.start, segment_paint, &metrics);
+        x += metrics.width();
+    }
+}
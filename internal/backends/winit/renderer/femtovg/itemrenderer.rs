@@ -12,7 +12,7 @@
 use i_slint_core::item_rendering::{ItemCache, ItemRenderer};
 use i_slint_core::items::{
     self, Clip, FillRule, ImageFit, ImageRendering, InputType, Item, ItemRc, Layer, Opacity,
-    RenderingResult,
+    RenderingResult, TextDecoration,
 };
 use i_slint_core::window::WindowInner;
 use i_slint_core::{Brush, Color, ImageInner, Property, SharedString};
@@ -109,6 +109,24 @@ fn adjust_rect_and_border_for_inner_drawing(rect: &mut Rect, border_width: &mut
     rect.size.height -= *border_width;
 }
 
+/// Returns the path of the line that decorates a line of text drawn at `pos`, or None if the
+/// text is not decorated.
+fn text_decoration_path(
+    decoration: TextDecoration,
+    pos: Point,
+    width: f32,
+    font_metrics: &femtovg::FontMetrics,
+) -> Option<femtovg::Path> {
+    let thickness = (font_metrics.height() / 14.).max(1.);
+    let y = match decoration {
+        TextDecoration::None => return None,
+        TextDecoration::Underline => font_metrics.ascender() - font_metrics.descender() / 2.,
+        TextDecoration::Overline => thickness / 2.,
+        TextDecoration::LineThrough => font_metrics.ascender() * 2. / 3.,
+    };
+    Some(rect_to_path(euclid::rect(pos.x, pos.y + y - thickness / 2., width, thickness)))
+}
+
 fn item_rect<Item: items::Item>(item: Pin<&Item>, scale_factor: f32) -> Rect {
     let geometry = item.geometry();
     euclid::rect(0., 0., geometry.width() * scale_factor, geometry.height() * scale_factor)
@@ -241,24 +259,30 @@ fn draw_text(&mut self, text: Pin<&items::Text>, _: &ItemRc) {
             return;
         }
 
-        let string = text.text();
-        let string = string.as_str();
-        let font = fonts::FONT_CACHE.with(|cache| {
-            cache.borrow_mut().font(
-                text.font_request(WindowInner::from_pub(&self.window)),
-                self.scale_factor,
-                &text.text(),
-            )
-        });
+        let styled_text = text.styled_text();
+        let string = styled_text.text.as_str();
+        let font_request = text.font_request(WindowInner::from_pub(&self.window));
+        let font = fonts::FONT_CACHE
+            .with(|cache| cache.borrow_mut().font(font_request.clone(), self.scale_factor, string));
 
+        let letter_spacing = text.letter_spacing() * self.scale_factor;
         let paint = match self
             .brush_to_paint(text.color(), &mut rect_to_path(item_rect(text, self.scale_factor)))
         {
-            Some(paint) => font.init_paint(text.letter_spacing() * self.scale_factor, paint),
+            Some(paint) => font.init_paint(letter_spacing, paint),
             None => return,
         };
+        let span_paints = fonts::span_paints(
+            &styled_text,
+            &font,
+            &font_request,
+            self.scale_factor,
+            letter_spacing,
+            paint,
+        );
 
         let mut canvas = self.canvas.borrow_mut();
+        let font_metrics = canvas.measure_font(paint).unwrap();
         fonts::layout_text_lines(
             string,
             &font,
@@ -268,8 +292,33 @@ fn draw_text(&mut self, text: Pin<&items::Text>, _: &ItemRc) {
             text.overflow(),
             false,
             paint,
-            |to_draw, pos, _, _| {
-                canvas.fill_text(pos.x, pos.y, to_draw.trim_end(), paint).unwrap();
+            |to_draw, pos, start, _, rtl| {
+                if font_request.decoration != TextDecoration::None {
+                    let width =
+                        canvas.measure_text(0., 0., to_draw.trim_end(), paint).unwrap().width();
+                    if let Some(mut path) =
+                        text_decoration_path(font_request.decoration, pos, width, &font_metrics)
+                    {
+                        canvas.fill_path(&mut path, paint);
+                    }
+                }
+                if span_paints.is_empty() {
+                    canvas.fill_text(pos.x, pos.y, to_draw.trim_end(), paint).unwrap();
+                    return;
+                }
+                fonts::layout_styled_segments(
+                    to_draw.trim_end(),
+                    pos,
+                    start,
+                    rtl,
+                    &span_paints,
+                    paint,
+                    |segment, segment_pos, _, segment_paint, _| {
+                        canvas
+                            .fill_text(segment_pos.x, segment_pos.y, segment, segment_paint)
+                            .unwrap();
+                    },
+                );
             },
         );
     }
@@ -281,12 +330,11 @@ fn draw_text_input(&mut self, text_input: Pin<&items::TextInput>, _: &ItemRc) {
             return;
         }
 
+        let font_request =
+            text_input.font_request(&WindowInner::from_pub(&self.window).window_adapter());
+        let decoration = font_request.decoration;
         let font = fonts::FONT_CACHE.with(|cache| {
-            cache.borrow_mut().font(
-                text_input.font_request(&WindowInner::from_pub(&self.window).window_adapter()),
-                self.scale_factor,
-                &text_input.text(),
-            )
+            cache.borrow_mut().font(font_request, self.scale_factor, &text_input.text())
         });
 
         let paint = match self.brush_to_paint(
@@ -305,7 +353,8 @@ fn draw_text_input(&mut self, text_input: Pin<&items::TextInput>, _: &ItemRc) {
             && !text_input.read_only();
         let mut cursor_pos = cursor_pos as usize;
         let mut canvas = self.canvas.borrow_mut();
-        let font_height = canvas.measure_font(paint).unwrap().height();
+        let font_metrics = canvas.measure_font(paint).unwrap();
+        let font_height = font_metrics.height();
         let mut text = text_input.text();
 
         if let InputType::Password = text_input.input_type() {
@@ -326,13 +375,67 @@ fn draw_text_input(&mut self, text_input: Pin<&items::TextInput>, _: &ItemRc) {
             items::TextOverflow::Clip,
             text_input.single_line(),
             paint,
-            |to_draw, pos, start, metrics| {
+            |to_draw, pos, start, metrics, rtl| {
                 let range = start..(start + to_draw.len());
-                if min_select != max_select
+                if let Some(mut path) = text_decoration_path(
+                    decoration,
+                    pos,
+                    canvas.measure_text(0., 0., to_draw.trim_end(), paint).unwrap().width(),
+                    &font_metrics,
+                ) {
+                    canvas.fill_path(&mut path, paint);
+                }
+                let selection_in_range = min_select != max_select
                     && (range.contains(&min_select)
                         || range.contains(&max_select)
-                        || (min_select..max_select).contains(&start))
-                {
+                        || (min_select..max_select).contains(&start));
+                if selection_in_range && rtl {
+                    // In right-to-left text, the text before the selection is on the right
+                    // and the text after the selection on the left.
+                    let selection_start = min_select.saturating_sub(start).min(to_draw.len());
+                    let selection_end = (max_select - start).min(to_draw.len());
+                    let selected_glyphs = metrics.glyphs.iter().filter(|glyph| {
+                        (selection_start..selection_end).contains(&glyph.byte_index)
+                    });
+                    let (selection_left_x, selection_right_x) =
+                        selected_glyphs.fold((f32::MAX, f32::MIN), |(left, right), glyph| {
+                            (left.min(glyph.x), right.max(glyph.x + glyph.advance_x))
+                        });
+                    if selection_left_x <= selection_right_x {
+                        let selection_rect = Rect::new(
+                            pos + euclid::vec2(selection_left_x, 0.),
+                            Size::new(selection_right_x - selection_left_x, font_height),
+                        );
+                        canvas.fill_path(
+                            &mut rect_to_path(selection_rect),
+                            femtovg::Paint::color(to_femtovg_color(
+                                &text_input.selection_background_color(),
+                            )),
+                        );
+                    }
+                    let mut selected_paint = paint;
+                    selected_paint
+                        .set_color(to_femtovg_color(&text_input.selection_foreground_color()));
+                    canvas
+                        .fill_text(
+                            pos.x + selection_right_x.max(0.),
+                            pos.y,
+                            &to_draw[..selection_start].trim_end(),
+                            paint,
+                        )
+                        .unwrap();
+                    canvas
+                        .fill_text(
+                            pos.x + selection_left_x.min(metrics.width()),
+                            pos.y,
+                            &to_draw[selection_start..selection_end].trim_end(),
+                            selected_paint,
+                        )
+                        .unwrap();
+                    canvas
+                        .fill_text(pos.x, pos.y, &to_draw[selection_end..].trim_end(), paint)
+                        .unwrap();
+                } else if selection_in_range {
                     let mut selection_start_x = 0.;
                     let mut selection_end_x = 0.;
                     let mut after_selection_x = 0.;
@@ -406,17 +509,18 @@ fn draw_text_input(&mut self, text_input: Pin<&items::TextInput>, _: &ItemRc) {
                             && cursor_pos == text.len()
                             && !text.ends_with('\n')))
                 {
+                    // The cursor is on the right of a right-to-left glyph
                     let cursor_x = metrics
                         .glyphs
                         .iter()
                         .find_map(|glyph| {
                             if glyph.byte_index == (cursor_pos as usize - start) {
-                                Some(glyph.x)
+                                Some(if rtl { glyph.x + glyph.advance_x } else { glyph.x })
                             } else {
                                 None
                             }
                         })
-                        .unwrap_or_else(|| metrics.width());
+                        .unwrap_or_else(|| if rtl { 0. } else { metrics.width() });
                     cursor_point = Some([pos.x + cursor_x, pos.y].into());
                 }
             },
@@ -145,6 +145,7 @@ fn text_size(
             font_request,
             scale_factor,
             text,
+            &[],
             None,
             max_width.map(|w| w * scale_factor),
             Default::default(),
@@ -155,6 +156,80 @@ fn text_size(
             .into()
     }
 
+    fn styled_text_size(
+        &self,
+        font_request: i_slint_core::graphics::FontRequest,
+        text: &i_slint_core::textlayout::styled_text::StyledText,
+        max_width: Option<i_slint_core::Coord>,
+        scale_factor: f32,
+    ) -> i_slint_core::graphics::Size {
+        let layout = textlayout::create_layout(
+            font_request,
+            scale_factor,
+            &text.text,
+            &text.spans,
+            None,
+            max_width.map(|w| w * scale_factor),
+            Default::default(),
+            Default::default(),
+        );
+
+        [layout.max_intrinsic_width().ceil() / scale_factor, layout.height().ceil() / scale_factor]
+            .into()
+    }
+
+    fn text_byte_offset_for_position(
+        &self,
+        text: std::pin::Pin<&i_slint_core::items::Text>,
+        pos: i_slint_core::graphics::Point,
+    ) -> Option<usize> {
+        let window_adapter = self.window_adapter_weak.upgrade()?;
+        let window = WindowInner::from_pub(window_adapter.window());
+
+        let scale_factor = window.scale_factor();
+
+        let max_width = text.width() * scale_factor;
+        let max_height = text.height() * scale_factor;
+        let pos = pos * scale_factor;
+
+        if max_width <= 0. || max_height <= 0. {
+            return None;
+        }
+
+        let styled_text = text.styled_text();
+        let layout = textlayout::create_layout(
+            text.font_request(window),
+            scale_factor,
+            &styled_text.text,
+            &styled_text.spans,
+            None,
+            Some(max_width),
+            text.horizontal_alignment(),
+            text.overflow(),
+        );
+
+        let layout_top_y = match text.vertical_alignment() {
+            i_slint_core::items::TextVerticalAlignment::Top => 0.,
+            i_slint_core::items::TextVerticalAlignment::Center => {
+                (max_height - layout.height()) / 2.
+            }
+            i_slint_core::items::TextVerticalAlignment::Bottom => max_height - layout.height(),
+        };
+
+        let pos = skia_safe::Point::new(pos.x, pos.y - layout_top_y);
+        let utf16_index = layout.get_glyph_position_at_coordinate(pos).position.max(0) as usize;
+        // That is the nearest cursor position, before or after the character at the coordinate
+        [Some(utf16_index), utf16_index.checked_sub(1)].into_iter().flatten().find_map(|index| {
+            let (byte_offset, ch) = textlayout::char_at_utf16_index(&styled_text.text, index)?;
+            let boxes = layout.get_rects_for_range(
+                index..index + ch.len_utf16(),
+                skia_safe::textlayout::RectHeightStyle::Max,
+                skia_safe::textlayout::RectWidthStyle::Tight,
+            );
+            boxes.iter().any(|text_box| text_box.rect.contains(pos)).then(|| byte_offset)
+        })
+    }
+
     fn text_input_byte_offset_for_position(
         &self,
         text_input: std::pin::Pin<&i_slint_core::items::TextInput>,
@@ -185,6 +260,7 @@ fn text_input_byte_offset_for_position(
             font_request,
             scale_factor,
             string,
+            &[],
             None,
             Some(max_width),
             text_input.horizontal_alignment(),
@@ -242,6 +318,7 @@ fn text_input_cursor_rect_for_byte_offset(
             font_request,
             scale_factor,
             string,
+            &[],
             None,
             Some(max_width),
             text_input.horizontal_alignment(),
@@ -357,8 +357,7 @@ fn draw_text(
             return;
         }
 
-        let string = text.text();
-        let string = string.as_str();
+        let styled_text = text.styled_text();
         let font_request = text.font_request(WindowInner::from_pub(&self.window));
 
         let paint = match self.brush_to_paint(text.color(), max_width, max_height) {
@@ -367,12 +366,14 @@ fn draw_text(
         };
 
         let mut text_style = skia_safe::textlayout::TextStyle::new();
+        text_style.set_decoration_color(paint.color());
         text_style.set_foreground_color(paint);
 
         let layout = super::textlayout::create_layout(
             font_request,
             self.scale_factor,
-            string,
+            &styled_text.text,
+            &styled_text.spans,
             Some(text_style),
             Some(max_width),
             text.horizontal_alignment(),
@@ -411,12 +412,14 @@ fn draw_text_input(
         };
 
         let mut text_style = skia_safe::textlayout::TextStyle::new();
+        text_style.set_decoration_color(paint.color());
         text_style.set_foreground_color(paint);
 
         let layout = super::textlayout::create_layout(
             font_request,
             self.scale_factor,
             string,
+            &[],
             Some(text_style),
             Some(max_width),
             text_input.horizontal_alignment(),
@@ -5,6 +5,7 @@
 use std::collections::HashMap;
 
 use i_slint_core::items;
+use i_slint_core::textlayout::styled_text::TextSpan;
 use i_slint_core::{graphics::FontRequest, Coord};
 
 pub const DEFAULT_FONT_SIZE: f32 = 12.;
@@ -41,6 +42,7 @@ pub fn create_layout(
     font_request: FontRequest,
     scale_factor: f32,
     text: &str,
+    spans: &[TextSpan],
     text_style: Option<skia_safe::textlayout::TextStyle>,
     max_width: Option<Coord>,
     h_align: items::TextHorizontalAlignment,
@@ -58,11 +60,19 @@ pub fn create_layout(
         text_style.set_letter_spacing(letter_spacing * scale_factor);
     }
     text_style.set_font_size(pixel_size);
-    text_style.set_font_style(skia_safe::FontStyle::new(
-        font_request.weight.map_or(skia_safe::font_style::Weight::NORMAL, |w| w.into()),
-        skia_safe::font_style::Width::NORMAL,
-        skia_safe::font_style::Slant::Upright,
-    ));
+    text_style.set_font_style(font_style(&font_request));
+
+    if let Some(line_height) = font_request.line_height {
+        // The height is a multiple of the font size
+        text_style.set_height_override(true);
+        text_style.set_height(line_height * scale_factor / pixel_size);
+    }
+    text_style.set_decoration_type(match font_request.decoration {
+        items::TextDecoration::None => skia_safe::textlayout::TextDecoration::NO_DECORATION,
+        items::TextDecoration::Underline => skia_safe::textlayout::TextDecoration::UNDERLINE,
+        items::TextDecoration::Overline => skia_safe::textlayout::TextDecoration::OVERLINE,
+        items::TextDecoration::LineThrough => skia_safe::textlayout::TextDecoration::LINE_THROUGH,
+    });
 
     let mut style = skia_safe::textlayout::ParagraphStyle::new();
 
@@ -80,12 +90,39 @@ pub fn create_layout(
         skia_safe::textlayout::ParagraphBuilder::new(&style, font_cache.font_collection.clone())
     });
     builder.push_style(&text_style);
-    builder.add_text(text);
+    let mut text_start = 0;
+    for span in spans {
+        builder.add_text(&text[text_start..span.range.start]);
+        let mut span_style = text_style.clone();
+        span_style.set_font_style(font_style(&span.style.font_request(&font_request)));
+        if let Some(color) = span.style.color {
+            let mut paint = skia_safe::Paint::default();
+            paint.set_color(super::itemrenderer::to_skia_color(&color));
+            span_style.set_foreground_color(paint);
+        }
+        builder.push_style(&span_style);
+        builder.add_text(&text[span.range.clone()]);
+        builder.pop();
+        text_start = span.range.end;
+    }
+    builder.add_text(&text[text_start..]);
     let mut paragraph = builder.build();
     paragraph.layout(max_width.unwrap_or(core::f32::MAX));
     paragraph
 }
 
+fn font_style(font_request: &FontRequest) -> skia_safe::FontStyle {
+    skia_safe::FontStyle::new(
+        font_request.weight.map_or(skia_safe::font_style::Weight::NORMAL, |w| w.into()),
+        skia_safe::font_style::Width::NORMAL,
+        if font_request.italic {
+            skia_safe::font_style::Slant::Italic
+        } else {
+            skia_safe::font_style::Slant::Upright
+        },
+    )
+}
+
 fn register_font(source: CustomFontSource) -> Result<(), Box<dyn std::error::Error>> {
     FONT_CACHE.with(|font_cache| {
         if font_cache
@@ -144,3 +181,13 @@ pub fn cursor_rect(
     );
     boxes.into_iter().next()
 }
+
+/// Returns the byte offset and the character at the given UTF-16 index of the string
+pub fn char_at_utf16_index(string: &str, utf16_index: usize) -> Option<(usize, char)> {
+    let mut utf16_count = 0;
+    string.char_indices().find(|(_, c)| {
+        let found = utf16_count == utf16_index;
+        utf16_count += c.len_utf16();
+        found
+    })
+}
@@ -57,6 +57,27 @@ enum TextOverflow {
                 Elide,
             }
 
+            /// This enum describes the line that decorates a text.
+            enum TextDecoration {
+                /// The text is not decorated.
+                None,
+                /// A line is drawn under the text.
+                Underline,
+                /// A line is drawn over the text.
+                Overline,
+                /// A line is drawn through the middle of the text.
+                LineThrough,
+            }
+
+            /// This enum describes how the `text` property of a `Text` element is interpreted.
+            enum TextFormat {
+                /// The text is shown as is.
+                Plain,
+                /// The text is a markup with the tags `<b>`, `<i>`, `<font color="#rrggbb">` and `<a href="url">`,
+                /// and the entities `&lt;`, `&gt;`, `&amp;`, `&quot;` and `&apos;`.
+                Markup,
+            }
+
             /// This enum describes whether an event was rejected or accepted by an event handler.
             enum EventResult {
                 /// The event is rejected by this event handler and may then be handled by the parent item
@@ -238,6 +259,17 @@ enum PathEvent {
                 EndClosed,
             }
 
+            /// This enum represents the different values of the `current-sort-order` property.
+            /// It's used to sort a `StandardTableView` by a column.
+            enum SortOrder {
+                /// The column is unsorted.
+                Unsorted,
+                /// The column is sorted in ascending order.
+                Ascending,
+                /// The column is sorted in descending order.
+                Descending,
+            }
+
             /// This enum represents the different values for the `accessible-role` property, used to describe the
             /// role of an element in the context of assistive technology such as screen readers.
             enum AccessibleRole {
@@ -255,6 +287,8 @@ enum AccessibleRole {
                 Spinbox,
                 /// The element is a Tab or behaves like one.
                 Tab,
+                /// The element is a StandardTableView or behaves like one.
+                Table,
                 /// The role for a Text element. It is automatically applied.
                 Text,
             }
@@ -4,7 +4,7 @@
 //! This module contains the implementation of the builtin macros.
 //! They are just transformations that convert into some more complicated expression tree
 
-use crate::diagnostics::{BuildDiagnostics, Spanned};
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode, Spanned};
 use crate::expression_tree::{
     BuiltinFunction, BuiltinMacroFunction, EasingCurve, Expression, Unit,
 };
@@ -49,7 +49,7 @@ pub fn lower_macro(
                 has_error.get_or_insert((n, "Too many argument for bezier curve"));
             }
             if let Some((n, msg)) = has_error {
-                diag.push_error(msg.into(), &n);
+                diag.push_error_with_code(DiagnosticCode::InvalidArguments, msg.into(), &n);
             }
 
             expr
@@ -65,7 +65,11 @@ fn min_max_macro(
     diag: &mut BuildDiagnostics,
 ) -> Expression {
     if args.is_empty() {
-        diag.push_error("Needs at least one argument".into(), &node);
+        diag.push_error_with_code(
+            DiagnosticCode::InvalidArguments,
+            "Needs at least one argument".into(),
+            &node,
+        );
         return Expression::Invalid;
     }
     let mut args = args.into_iter();
@@ -80,7 +84,11 @@ fn min_max_macro(
         Type::Angle => Type::Angle,
         Type::Percent => Type::Float32,
         _ => {
-            diag.push_error("Invalid argument type".into(), &arg_node);
+            diag.push_error_with_code(
+                DiagnosticCode::InvalidArguments,
+                "Invalid argument type".into(),
+                &arg_node,
+            );
             return Expression::Invalid;
         }
     };
@@ -97,7 +105,11 @@ fn mod_macro(
     diag: &mut BuildDiagnostics,
 ) -> Expression {
     if args.len() != 2 {
-        diag.push_error("Needs 2 arguments".into(), &node);
+        diag.push_error_with_code(
+            DiagnosticCode::InvalidArguments,
+            "Needs 2 arguments".into(),
+            &node,
+        );
         return Expression::Invalid;
     }
     let (lhs_ty, rhs_ty) = (args[0].0.ty(), args[1].0.ty());
@@ -142,7 +154,11 @@ fn rgb_macro(
     diag: &mut BuildDiagnostics,
 ) -> Expression {
     if args.len() < 3 {
-        diag.push_error("Needs 3 or 4 argument".into(), &node);
+        diag.push_error_with_code(
+            DiagnosticCode::InvalidArguments,
+            "Needs 3 or 4 argument".into(),
+            &node,
+        );
         return Expression::Invalid;
     }
     let mut arguments: Vec<_> = args
@@ -229,7 +245,11 @@ fn to_debug_string(
         | Type::LayoutCache
         | Type::Model
         | Type::PathData => {
-            diag.push_error("Cannot debug this expression".into(), &node);
+            diag.push_error_with_code(
+                DiagnosticCode::InvalidArguments,
+                "Cannot debug this expression".into(),
+                &node,
+            );
             Expression::Invalid
         }
         Type::Float32 | Type::Int32 => expr.maybe_convert_to(Type::String, &node, diag),
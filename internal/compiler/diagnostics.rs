@@ -130,6 +130,9 @@ pub fn load_from_path(path: &Path) -> Result<String, Diagnostic> {
             span: Default::default(),
         },
         level: DiagnosticLevel::Error,
+        code: DiagnosticCode::IoError,
+        suggestion: None,
+        notes: Vec::new(),
     })
 }
 
@@ -175,6 +178,34 @@ fn default() -> Self {
     }
 }
 
+#[cfg(feature = "display-diagnostics")]
+/// Return the SARIF `location` object for this source location
+fn sarif_location(location: &SourceLocation) -> Option<serde_json::Value> {
+    let path = location.source_file()?.path();
+    let (line, column) = line_column(location);
+    let mut region = serde_json::json!({ "startLine": line.max(1) });
+    if location.span.is_valid() {
+        region["startColumn"] = column.max(1).into();
+        region["charOffset"] = location.span.offset.into();
+    }
+    Some(serde_json::json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": path.to_string_lossy() },
+            "region": region,
+        }
+    }))
+}
+
+impl DiagnosticLevel {
+    /// Returns `"error"` or `"warning"`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DiagnosticLevel::Error => "error",
+            DiagnosticLevel::Warning => "warning",
+        }
+    }
+}
+
 #[cfg(feature = "display-diagnostics")]
 impl From<DiagnosticLevel> for codemap_diagnostic::Level {
     fn from(l: DiagnosticLevel) -> Self {
@@ -185,6 +216,345 @@ fn from(l: DiagnosticLevel) -> Self {
     }
 }
 
+/// The format in which [`BuildDiagnostics::print_as`] prints the diagnostics
+#[cfg(feature = "display-diagnostics")]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum DiagnosticsFormat {
+    /// Human readable text, with the source code
+    Human,
+    /// A JSON array, see [`BuildDiagnostics::to_json`]
+    Json,
+    /// A SARIF log, see [`BuildDiagnostics::to_sarif`]
+    Sarif,
+}
+
+#[cfg(feature = "display-diagnostics")]
+impl Default for DiagnosticsFormat {
+    fn default() -> Self {
+        Self::Human
+    }
+}
+
+#[cfg(feature = "display-diagnostics")]
+impl std::str::FromStr for DiagnosticsFormat {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            "sarif" => Ok(Self::Sarif),
+            _ => Err(format!("Unknown diagnostics format {} (expected human, json or sarif)", s)),
+        }
+    }
+}
+
+/// The stable identifier of the kind of a diagnostic.
+///
+/// The string representation returned by [`DiagnosticCode::as_str`] is part of the
+/// machine-readable output, and can be used to silence warnings. It doesn't change
+/// between versions, even if the message does.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+#[non_exhaustive]
+pub enum DiagnosticCode {
+    /// The source code cannot be parsed
+    SyntaxError,
+    /// A file cannot be read
+    IoError,
+    /// An import cannot be resolved
+    ImportError,
+    /// The style is not known
+    UnknownStyle,
+    /// An identifier cannot be found
+    UnknownIdentifier,
+    /// An element does not have the property
+    UnknownProperty,
+    /// A type name cannot be found
+    UnknownType,
+    /// An expression cannot be converted to the expected type
+    TypeMismatch,
+    /// A binding depends on itself
+    BindingLoop,
+    /// A deprecated property or identifier is used
+    DeprecatedProperty,
+    /// A deprecated file name is imported
+    DeprecatedImport,
+    /// The deprecated `SIXTYFPS_STYLE` environment variable is used
+    DeprecatedStyleVariable,
+    /// No style was set, the default one is used
+    DefaultStyle,
+    /// A property set on the root element has no effect
+    IgnoredRootProperty,
+    /// An image file referenced with `@image-url` cannot be found or loaded
+    MissingImage,
+    /// No font provides the requested font family, or an imported font cannot be loaded
+    MissingFont,
+    /// A property has a type that cannot be exposed in the public API
+    UnsupportedPublicType,
+    /// A two way binding between a property with a default value and a property without
+    TwoWayBindingDefault,
+    /// The document does not contain a component to instantiate
+    NoComponent,
+    /// A function, callback or builtin macro is called with the wrong arguments
+    InvalidArguments,
+    /// A property is set together with a property, or in a context, that it conflicts with
+    ConflictingProperties,
+    /// A type cannot be used as an element
+    InvalidElement,
+    /// A global component uses something that is only available in components
+    InvalidGlobal,
+    /// A property or callback is declared twice
+    DuplicatedDeclaration,
+    /// A property, callback or animation is set twice
+    DuplicatedBinding,
+    /// Two elements have the same id
+    DuplicatedId,
+    /// Two exports have the same name
+    DuplicatedExport,
+    /// A name cannot be exported
+    InvalidExport,
+    /// An element id cannot be used
+    InvalidId,
+    /// The `@children` placeholder is misplaced
+    InvalidChildrenPlaceholder,
+    /// A state or a transition is invalid
+    InvalidState,
+    /// A property cannot be animated
+    InvalidAnimation,
+    /// A binding or a two way binding cannot be set on that property
+    InvalidBinding,
+    /// A type cannot be used or inferred for a property
+    InvalidType,
+    /// A property is set to a value that is out of its range or has no meaning
+    InvalidValue,
+    /// A property must be set to a value known at compile time
+    NotConstant,
+    /// A property that is required in that context is not set
+    MissingProperty,
+    /// A property is not supported on that element or in that context
+    UnsupportedProperty,
+    /// The construct is not supported yet
+    Unsupported,
+    /// A struct, enum or namespace does not have the member
+    UnknownMember,
+    /// An element, enum or namespace is used as a value
+    InvalidReference,
+    /// The left-hand side of an assignment is not a property
+    InvalidAssignment,
+    /// A function is referenced without being called
+    UncalledFunction,
+    /// An expression that is not a function is called
+    NotCallable,
+    /// An element that cannot have the focus is focused
+    NotFocusable,
+    /// A `PopupWindow` is misplaced
+    InvalidPopup,
+    /// The children of a `Dialog` are invalid
+    InvalidDialog,
+    /// A menu or a menu item is misplaced
+    InvalidMenu,
+    /// The elements or commands of a `Path` are invalid
+    InvalidPath,
+    /// A `slint-lint` comment cannot be parsed
+    InvalidPragma,
+    /// A declared property is never read (opt-in lint)
+    UnusedProperty,
+    /// A declared callback is never invoked (opt-in lint)
+    UnusedCallback,
+    /// An element id is never referenced (opt-in lint)
+    UnusedId,
+    /// An imported name is never used (opt-in lint)
+    UnusedImport,
+    /// A component is never instantiated (opt-in lint)
+    UnusedComponent,
+}
+
+impl DiagnosticCode {
+    /// All the codes, in the order of declaration
+    pub const ALL: &'static [DiagnosticCode] = &[
+        Self::SyntaxError,
+        Self::IoError,
+        Self::ImportError,
+        Self::UnknownStyle,
+        Self::UnknownIdentifier,
+        Self::UnknownProperty,
+        Self::UnknownType,
+        Self::TypeMismatch,
+        Self::BindingLoop,
+        Self::DeprecatedProperty,
+        Self::DeprecatedImport,
+        Self::DeprecatedStyleVariable,
+        Self::DefaultStyle,
+        Self::IgnoredRootProperty,
+        Self::MissingImage,
+        Self::MissingFont,
+        Self::UnsupportedPublicType,
+        Self::TwoWayBindingDefault,
+        Self::NoComponent,
+        Self::InvalidArguments,
+        Self::ConflictingProperties,
+        Self::InvalidElement,
+        Self::InvalidGlobal,
+        Self::DuplicatedDeclaration,
+        Self::DuplicatedBinding,
+        Self::DuplicatedId,
+        Self::DuplicatedExport,
+        Self::InvalidExport,
+        Self::InvalidId,
+        Self::InvalidChildrenPlaceholder,
+        Self::InvalidState,
+        Self::InvalidAnimation,
+        Self::InvalidBinding,
+        Self::InvalidType,
+        Self::InvalidValue,
+        Self::NotConstant,
+        Self::MissingProperty,
+        Self::UnsupportedProperty,
+        Self::Unsupported,
+        Self::UnknownMember,
+        Self::InvalidReference,
+        Self::InvalidAssignment,
+        Self::UncalledFunction,
+        Self::NotCallable,
+        Self::NotFocusable,
+        Self::InvalidPopup,
+        Self::InvalidDialog,
+        Self::InvalidMenu,
+        Self::InvalidPath,
+        Self::InvalidPragma,
+        Self::UnusedProperty,
+        Self::UnusedCallback,
+        Self::UnusedId,
+        Self::UnusedImport,
+        Self::UnusedComponent,
+    ];
+
+    /// Returns the stable kebab-case name of this code
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SyntaxError => "syntax-error",
+            Self::IoError => "io-error",
+            Self::ImportError => "import-error",
+            Self::UnknownStyle => "unknown-style",
+            Self::UnknownIdentifier => "unknown-identifier",
+            Self::UnknownProperty => "unknown-property",
+            Self::UnknownType => "unknown-type",
+            Self::TypeMismatch => "type-mismatch",
+            Self::BindingLoop => "binding-loop",
+            Self::DeprecatedProperty => "deprecated-property",
+            Self::DeprecatedImport => "deprecated-import",
+            Self::DeprecatedStyleVariable => "deprecated-style-variable",
+            Self::DefaultStyle => "default-style",
+            Self::IgnoredRootProperty => "ignored-root-property",
+            Self::MissingImage => "missing-image",
+            Self::MissingFont => "missing-font",
+            Self::UnsupportedPublicType => "unsupported-public-type",
+            Self::TwoWayBindingDefault => "two-way-binding-default",
+            Self::NoComponent => "no-component",
+            Self::InvalidArguments => "invalid-arguments",
+            Self::ConflictingProperties => "conflicting-properties",
+            Self::InvalidElement => "invalid-element",
+            Self::InvalidGlobal => "invalid-global",
+            Self::DuplicatedDeclaration => "duplicated-declaration",
+            Self::DuplicatedBinding => "duplicated-binding",
+            Self::DuplicatedId => "duplicated-id",
+            Self::DuplicatedExport => "duplicated-export",
+            Self::InvalidExport => "invalid-export",
+            Self::InvalidId => "invalid-id",
+            Self::InvalidChildrenPlaceholder => "invalid-children-placeholder",
+            Self::InvalidState => "invalid-state",
+            Self::InvalidAnimation => "invalid-animation",
+            Self::InvalidBinding => "invalid-binding",
+            Self::InvalidType => "invalid-type",
+            Self::InvalidValue => "invalid-value",
+            Self::NotConstant => "not-constant",
+            Self::MissingProperty => "missing-property",
+            Self::UnsupportedProperty => "unsupported-property",
+            Self::Unsupported => "unsupported",
+            Self::UnknownMember => "unknown-member",
+            Self::InvalidReference => "invalid-reference",
+            Self::InvalidAssignment => "invalid-assignment",
+            Self::UncalledFunction => "uncalled-function",
+            Self::NotCallable => "not-callable",
+            Self::NotFocusable => "not-focusable",
+            Self::InvalidPopup => "invalid-popup",
+            Self::InvalidDialog => "invalid-dialog",
+            Self::InvalidMenu => "invalid-menu",
+            Self::InvalidPath => "invalid-path",
+            Self::InvalidPragma => "invalid-pragma",
+            Self::UnusedProperty => "unused-property",
+            Self::UnusedCallback => "unused-callback",
+            Self::UnusedId => "unused-id",
+            Self::UnusedImport => "unused-import",
+            Self::UnusedComponent => "unused-component",
+        }
+    }
+}
+
+impl std::fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for DiagnosticCode {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|c| c.as_str() == s)
+            .ok_or_else(|| format!("Unknown diagnostic code '{}'", s))
+    }
+}
+
+/// Returns a tuple with the line (starting at 1) and column number (starting at 0)
+fn line_column(location: &SourceLocation) -> (usize, usize) {
+    let offset = location.span.offset;
+    let line_offsets = match &location.source_file {
+        None => return (0, 0),
+        Some(sl) => sl.line_offsets(),
+    };
+    line_offsets.binary_search(&offset).map_or_else(
+        |line| {
+            if line == 0 {
+                (line + 1, offset)
+            } else {
+                (line + 1, line_offsets.get(line - 1).map_or(0, |x| offset - x))
+            }
+        },
+        |line| (line + 1, 0),
+    )
+}
+
+/// A secondary location attached to a [`Diagnostic`], with a message that explains
+/// how it relates to the diagnostic. For example, one step of a binding loop.
+#[derive(Debug, Clone)]
+pub struct DiagnosticNote {
+    message: String,
+    span: SourceLocation,
+}
+
+impl DiagnosticNote {
+    pub fn new(message: String, span: SourceLocation) -> Self {
+        Self { message, span }
+    }
+
+    /// Return the message of this note
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns a tuple with the line (starting at 1) and column number (starting at 0)
+    pub fn line_column(&self) -> (usize, usize) {
+        line_column(&self.span)
+    }
+
+    /// return the path of the source file where this note is attached
+    pub fn source_file(&self) -> Option<&Path> {
+        self.span.source_file().map(|sf| sf.path())
+    }
+}
+
 /// This structure represent a diagnostic emitted while compiling .slint code.
 ///
 /// It is basically a message, a level (warning or error), attached to a
@@ -194,6 +564,9 @@ pub struct Diagnostic {
     message: String,
     span: SourceLocation,
     level: DiagnosticLevel,
+    code: DiagnosticCode,
+    suggestion: Option<String>,
+    notes: Vec<DiagnosticNote>,
 }
 
 impl Diagnostic {
@@ -207,23 +580,24 @@ pub fn message(&self) -> &str {
         &self.message
     }
 
+    /// Return the code identifying the kind of this diagnostic
+    pub fn code(&self) -> DiagnosticCode {
+        self.code
+    }
+
+    /// Return the text that should replace the code at the location of this diagnostic to fix it, if known
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestion.as_deref()
+    }
+
+    /// Return the other locations related to this diagnostic
+    pub fn notes(&self) -> &[DiagnosticNote] {
+        &self.notes
+    }
+
     /// Returns a tuple with the line (starting at 1) and column number (starting at 0)
     pub fn line_column(&self) -> (usize, usize) {
-        let offset = self.span.span.offset;
-        let line_offsets = match &self.span.source_file {
-            None => return (0, 0),
-            Some(sl) => sl.line_offsets(),
-        };
-        line_offsets.binary_search(&offset).map_or_else(
-            |line| {
-                if line == 0 {
-                    (line + 1, offset)
-                } else {
-                    (line + 1, line_offsets.get(line - 1).map_or(0, |x| offset - x))
-                }
-            },
-            |line| (line + 1, 0),
-        )
+        line_column(&self.span)
     }
 
     /// return the path of the source file where this error is attached
@@ -263,30 +637,60 @@ fn into_iter(self) -> Self::IntoIter {
 }
 
 impl BuildDiagnostics {
-    pub fn push_diagnostic_with_span(
+    /// Push a diagnostic with a specific code, and optionally the text that fixes it
+    pub fn push_coded_diagnostic(
         &mut self,
+        code: DiagnosticCode,
         message: String,
         span: SourceLocation,
         level: DiagnosticLevel,
+        suggestion: Option<String>,
     ) {
         debug_assert!(
             !message.as_str().ends_with('.'),
             "Error message should not end with a period: ({:?})",
             message
         );
-        self.inner.push(Diagnostic { message, span, level });
+        self.inner.push(Diagnostic { message, span, level, code, suggestion, notes: Vec::new() });
     }
-    pub fn push_error_with_span(&mut self, message: String, span: SourceLocation) {
-        self.push_diagnostic_with_span(message, span, DiagnosticLevel::Error)
-    }
-    pub fn push_error(&mut self, message: String, source: &dyn Spanned) {
-        self.push_error_with_span(message, source.to_source_location());
+    /// Push an error that involves several locations, each explained by a note
+    pub fn push_error_with_notes(
+        &mut self,
+        code: DiagnosticCode,
+        message: String,
+        span: SourceLocation,
+        notes: Vec<DiagnosticNote>,
+    ) {
+        self.push_coded_diagnostic(code, message, span, DiagnosticLevel::Error, None);
+        self.inner.last_mut().unwrap().notes = notes;
     }
-    pub fn push_warning_with_span(&mut self, message: String, span: SourceLocation) {
-        self.push_diagnostic_with_span(message, span, DiagnosticLevel::Warning)
+    pub fn push_error_with_code(
+        &mut self,
+        code: DiagnosticCode,
+        message: String,
+        source: &dyn Spanned,
+    ) {
+        self.push_coded_diagnostic(
+            code,
+            message,
+            source.to_source_location(),
+            DiagnosticLevel::Error,
+            None,
+        );
     }
-    pub fn push_warning(&mut self, message: String, source: &dyn Spanned) {
-        self.push_warning_with_span(message, source.to_source_location());
+    pub fn push_warning_with_code(
+        &mut self,
+        code: DiagnosticCode,
+        message: String,
+        source: &dyn Spanned,
+    ) {
+        self.push_coded_diagnostic(
+            code,
+            message,
+            source.to_source_location(),
+            DiagnosticLevel::Warning,
+            None,
+        );
     }
     pub fn push_compiler_error(&mut self, error: Diagnostic) {
         self.inner.push(error);
@@ -298,16 +702,39 @@ pub fn push_property_deprecation_warning(
         new_property: &str,
         source: &dyn Spanned,
     ) {
-        self.push_diagnostic_with_span(
+        self.push_coded_diagnostic(
+            DiagnosticCode::DeprecatedProperty,
             format!(
                 "The property '{}' has been deprecated. Please use '{}' instead",
                 old_property, new_property
             ),
             source.to_source_location(),
             crate::diagnostics::DiagnosticLevel::Warning,
+            Some(new_property.into()),
         )
     }
 
+    /// Add the diagnostics of another compilation of the same files, except the ones that
+    /// were already reported
+    pub fn extend_unique(&mut self, other: BuildDiagnostics) {
+        for diagnostic in other {
+            let already_reported = self.inner.iter().any(|d| {
+                d.level == diagnostic.level
+                    && d.message == diagnostic.message
+                    && d.source_file() == diagnostic.source_file()
+                    && d.span.span == diagnostic.span.span
+            });
+            if !already_reported {
+                self.inner.push(diagnostic);
+            }
+        }
+    }
+
+    /// Remove the warnings with one of the given codes
+    pub fn silence_warnings(&mut self, codes: &[DiagnosticCode]) {
+        self.inner.retain(|d| d.level != DiagnosticLevel::Warning || !codes.contains(&d.code));
+    }
+
     /// Return true if there is at least one compilation error for this file
     pub fn has_error(&self) -> bool {
         self.inner.iter().any(|diag| diag.level == DiagnosticLevel::Error)
@@ -334,46 +761,63 @@ fn call_diagnostics<Output>(
 
         let mut codemap = codemap::CodeMap::new();
         let mut codemap_files = std::collections::HashMap::new();
+        let mut file_span = |sf: &SourceFile| {
+            codemap_files
+                .entry(sf.path.to_string_lossy().into_owned())
+                .or_insert_with(|| {
+                    codemap.add_file(
+                        sf.path.to_string_lossy().into(),
+                        sf.source.clone().unwrap_or_default(),
+                    )
+                })
+                .span
+        };
 
-        let diags: Vec<_> = self
-            .inner
-            .into_iter()
-            .filter_map(|d| {
-                let spans = if !d.span.span.is_valid() {
-                    vec![]
-                } else if let Some(sf) = &d.span.source_file {
-                    if let Some(ref mut handle_no_source) = handle_no_source {
-                        if sf.source.is_none() {
-                            handle_no_source(d);
-                            return None;
+        let diags: Vec<_> =
+            self.inner
+                .into_iter()
+                .filter_map(|d| {
+                    let mut spans = if !d.span.span.is_valid() {
+                        vec![]
+                    } else if let Some(sf) = &d.span.source_file {
+                        if let Some(ref mut handle_no_source) = handle_no_source {
+                            if sf.source.is_none() {
+                                handle_no_source(d);
+                                return None;
+                            }
                         }
-                    }
-                    let path: String = sf.path.to_string_lossy().into();
-                    let file = codemap_files.entry(path).or_insert_with(|| {
-                        codemap.add_file(
-                            sf.path.to_string_lossy().into(),
-                            sf.source.clone().unwrap_or_default(),
-                        )
-                    });
-                    let file_span = file.span;
-                    let s = codemap_diagnostic::SpanLabel {
-                        span: file_span
-                            .subspan(d.span.span.offset as u64, d.span.span.offset as u64),
-                        style: codemap_diagnostic::SpanStyle::Primary,
-                        label: None,
+                        let s = codemap_diagnostic::SpanLabel {
+                            span: file_span(sf)
+                                .subspan(d.span.span.offset as u64, d.span.span.offset as u64),
+                            style: codemap_diagnostic::SpanStyle::Primary,
+                            label: None,
+                        };
+                        vec![s]
+                    } else {
+                        vec![]
                     };
-                    vec![s]
-                } else {
-                    vec![]
-                };
-                Some(codemap_diagnostic::Diagnostic {
-                    level: d.level.into(),
-                    message: d.message,
-                    code: None,
-                    spans,
+                    for note in &d.notes {
+                        match &note.span.source_file {
+                            Some(sf) if sf.source.is_some() && note.span.span.is_valid() => spans
+                                .push(codemap_diagnostic::SpanLabel {
+                                    span: file_span(sf).subspan(
+                                        note.span.span.offset as u64,
+                                        note.span.span.offset as u64,
+                                    ),
+                                    style: codemap_diagnostic::SpanStyle::Secondary,
+                                    label: Some(note.message.clone()),
+                                }),
+                            _ => (),
+                        }
+                    }
+                    Some(codemap_diagnostic::Diagnostic {
+                        level: d.level.into(),
+                        message: d.message,
+                        code: Some(d.code.as_str().to_owned()),
+                        spans,
+                    })
                 })
-            })
-            .collect();
+                .collect();
 
         let mut emitter = emitter_factory(output, Some(&codemap));
         emitter.emit(&diags);
@@ -400,6 +844,106 @@ pub fn diagnostics_as_string(self) -> String {
         )
     }
 
+    #[cfg(feature = "display-diagnostics")]
+    /// Return the diagnostics as a JSON array, with one object per diagnostic
+    pub fn to_json(&self) -> serde_json::Value {
+        self.inner
+            .iter()
+            .map(|d| {
+                let (line, column) = d.line_column();
+                serde_json::json!({
+                    "code": d.code.as_str(),
+                    "level": d.level.as_str(),
+                    "message": d.message,
+                    "file": d.source_file().map(|p| p.to_string_lossy()),
+                    "line": line,
+                    "column": column,
+                    "offset": d.span.span.is_valid().then(|| d.span.span.offset),
+                    "suggestion": d.suggestion,
+                    "notes": d.notes.iter().map(|n| {
+                        let (line, column) = n.line_column();
+                        serde_json::json!({
+                            "message": n.message,
+                            "file": n.source_file().map(|p| p.to_string_lossy()),
+                            "line": line,
+                            "column": column,
+                            "offset": n.span.span.is_valid().then(|| n.span.span.offset),
+                        })
+                    }).collect::<Vec<_>>(),
+                })
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "display-diagnostics")]
+    /// Return the diagnostics as a [SARIF](https://sarifweb.azurewebsites.net/) 2.1.0 log
+    pub fn to_sarif(&self) -> serde_json::Value {
+        let results = self
+            .inner
+            .iter()
+            .map(|d| {
+                let mut result = serde_json::json!({
+                    "ruleId": d.code.as_str(),
+                    "level": d.level.as_str(),
+                    "message": { "text": d.message },
+                });
+                if let Some(location) = sarif_location(&d.span) {
+                    result["locations"] = serde_json::json!([location]);
+                }
+                let related = d
+                    .notes
+                    .iter()
+                    .filter_map(|n| {
+                        let mut location = sarif_location(&n.span)?;
+                        location["message"] = serde_json::json!({ "text": n.message });
+                        Some(location)
+                    })
+                    .collect::<Vec<_>>();
+                if !related.is_empty() {
+                    result["relatedLocations"] = related.into();
+                }
+                if let Some(suggestion) = &d.suggestion {
+                    result["properties"] = serde_json::json!({ "suggestion": suggestion });
+                }
+                result
+            })
+            .collect::<Vec<_>>();
+        let rules = DiagnosticCode::ALL.iter().map(|c| serde_json::json!({ "id": c.as_str() }));
+        serde_json::json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "slint",
+                        "informationUri": "https://slint-ui.com",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules.collect::<Vec<_>>(),
+                    }
+                },
+                "results": results,
+            }]
+        })
+    }
+
+    #[cfg(feature = "display-diagnostics")]
+    /// Print the diagnostics in the given format. The human readable format is printed on stderr,
+    /// and the machine readable formats are written to `output`.
+    pub fn print_as(
+        self,
+        format: DiagnosticsFormat,
+        output: &mut dyn std::io::Write,
+    ) -> std::io::Result<()> {
+        match format {
+            DiagnosticsFormat::Human => {
+                self.print();
+                Ok(())
+            }
+            DiagnosticsFormat::Json => writeln!(output, "{:#}", self.to_json()),
+            DiagnosticsFormat::Sarif => writeln!(output, "{:#}", self.to_sarif()),
+        }
+    }
+
     #[cfg(all(feature = "proc_macro_span", feature = "display-diagnostics"))]
     /// Will convert the diagnostics that only have offsets to the actual proc_macro::Span
     pub fn report_macro_diagnostic(
@@ -458,15 +1002,6 @@ pub fn to_string_vec(&self) -> Vec<String> {
         self.inner.iter().map(|d| d.to_string()).collect()
     }
 
-    pub fn push_diagnostic(
-        &mut self,
-        message: String,
-        source: &dyn Spanned,
-        level: DiagnosticLevel,
-    ) {
-        self.push_diagnostic_with_span(message, source.to_source_location(), level)
-    }
-
     pub fn push_internal_error(&mut self, err: Diagnostic) {
         self.inner.push(err)
     }
@@ -494,3 +1029,83 @@ pub fn print_warnings_and_exit_on_error(self) {
         }
     }
 }
+
+#[test]
+fn diagnostic_code_names() {
+    for code in DiagnosticCode::ALL {
+        assert_eq!(code.as_str().parse::<DiagnosticCode>(), Ok(*code));
+    }
+    assert!("not-a-code".parse::<DiagnosticCode>().is_err());
+}
+
+#[cfg(feature = "display-diagnostics")]
+#[test]
+fn machine_readable_diagnostics() {
+    let source = r#"
+export Foo := Rectangle {
+    maximum-width: 100px;
+    property <int> a: b;
+    property <int> b: a;
+}
+"#;
+    let path = Path::new("/test/machine_readable.slint");
+    let mut diag = BuildDiagnostics::default();
+    let node = crate::parser::parse(source.into(), Some(path), &mut diag);
+    let mut config = crate::CompilerConfiguration::new(crate::generator::OutputFormat::Interpreter);
+    config.style = Some("fluent".into());
+    let (_, diag) = spin_on::spin_on(crate::compile_syntax_node(node, diag, config));
+
+    let json = diag.to_json();
+    let json = json.as_array().unwrap();
+    assert_eq!(json.len(), 2, "{:#}", serde_json::Value::from(json.clone()));
+    let deprecated = json.iter().find(|d| d["code"] == "deprecated-property").unwrap();
+    assert_eq!(deprecated["level"], "warning");
+    assert_eq!(deprecated["file"], path.to_string_lossy().as_ref());
+    assert_eq!(deprecated["line"], 3);
+    assert_eq!(deprecated["column"], 4);
+    assert_eq!(deprecated["suggestion"], "max-width");
+    assert_eq!(deprecated["notes"], serde_json::json!([]));
+    let binding_loop = json.iter().find(|d| d["code"] == "binding-loop").unwrap();
+    assert_eq!(binding_loop["level"], "error");
+    assert!(matches!(binding_loop["line"].as_u64(), Some(4 | 5)));
+    assert!(binding_loop["offset"].is_u64());
+    assert_eq!(binding_loop["suggestion"], serde_json::Value::Null);
+    let notes = binding_loop["notes"].as_array().unwrap();
+    assert_eq!(notes.len(), 2);
+    for note in notes {
+        assert!(note["message"].as_str().unwrap().contains("depends on"));
+        assert_eq!(note["file"], path.to_string_lossy().as_ref());
+        assert!(matches!(note["line"].as_u64(), Some(4 | 5)));
+    }
+
+    let sarif = diag.to_sarif();
+    assert_eq!(sarif["version"], "2.1.0");
+    let run = &sarif["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "slint");
+    let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+    assert_eq!(rules.len(), DiagnosticCode::ALL.len());
+    assert!(rules.iter().any(|r| r["id"] == "binding-loop"));
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    let deprecated = results.iter().find(|r| r["ruleId"] == "deprecated-property").unwrap();
+    assert_eq!(deprecated["level"], "warning");
+    assert!(deprecated["message"]["text"].as_str().unwrap().contains("maximum-width"));
+    let location = &deprecated["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], path.to_string_lossy().as_ref());
+    assert_eq!(location["region"]["startLine"], 3);
+    assert_eq!(location["region"]["startColumn"], 4);
+    assert_eq!(deprecated["properties"]["suggestion"], "max-width");
+    assert_eq!(deprecated.get("relatedLocations"), None);
+    let binding_loop = results.iter().find(|r| r["ruleId"] == "binding-loop").unwrap();
+    assert_eq!(binding_loop["level"], "error");
+    assert_eq!(binding_loop["locations"].as_array().unwrap().len(), 1);
+    let related = binding_loop["relatedLocations"].as_array().unwrap();
+    assert_eq!(related.len(), 2);
+    for location in related {
+        assert!(location["message"]["text"].as_str().unwrap().contains("depends on"));
+        assert!(matches!(
+            location["physicalLocation"]["region"]["startLine"].as_u64(),
+            Some(4 | 5)
+        ));
+    }
+}
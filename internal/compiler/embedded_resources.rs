@@ -20,6 +20,12 @@ pub enum PixelFormat {
     RgbaPremultiplied,
     // 8bit alpha map with a given color
     AlphaMap([u8; 3]),
+    // 8bit index in a palette of premultiplied RGBA colors
+    Indexed8,
+    // 16 bit RGB, run-length encoded
+    Rgb565Rle,
+    // 32 bit RGBA, pre-multiplied, run-length encoded
+    RgbaPremultipliedRle,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
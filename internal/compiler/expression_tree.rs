@@ -1,7 +1,7 @@
 // Copyright © SixtyFPS GmbH <info@slint-ui.com>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
-use crate::diagnostics::{BuildDiagnostics, SourceLocation, Spanned};
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode, SourceLocation, Spanned};
 use crate::langtype::{BuiltinElement, EnumerationValue, Type};
 use crate::layout::Orientation;
 use crate::object_tree::*;
@@ -36,6 +36,8 @@ pub enum BuiltinFunction {
     Pow,
     SetFocusItem,
     ShowPopupWindow,
+    /// Register the MenuBarItem given as argument with the window
+    RegisterMenuBar,
     /// the "42".to_float()
     StringToFloat,
     /// the "42".is_float()
@@ -113,6 +115,10 @@ pub fn ty(&self) -> Type {
                 return_type: Box::new(Type::Void),
                 args: vec![Type::ElementReference],
             },
+            BuiltinFunction::RegisterMenuBar => Type::Function {
+                return_type: Box::new(Type::Void),
+                args: vec![Type::ElementReference],
+            },
             BuiltinFunction::StringToFloat => {
                 Type::Function { return_type: Box::new(Type::Float32), args: vec![Type::String] }
             }
@@ -185,6 +191,7 @@ fn is_pure(&self) -> bool {
             | BuiltinFunction::ATan => true,
             BuiltinFunction::SetFocusItem => false,
             BuiltinFunction::ShowPopupWindow => false,
+            BuiltinFunction::RegisterMenuBar => false,
             BuiltinFunction::StringToFloat | BuiltinFunction::StringIsFloat => true,
             BuiltinFunction::ColorBrighter | BuiltinFunction::ColorDarker => true,
             // ImageSize is pure, except when loading images via the network. Then the initial size will be 0/0 and
@@ -1040,7 +1047,11 @@ pub fn maybe_convert_to(
                 if let Some(t) = fields.remove(f) {
                     new_values.insert(f.clone(), v.clone().maybe_convert_to(t, node, diag));
                 } else {
-                    diag.push_error(format!("Cannot convert {} to {}", ty, target_type), node);
+                    diag.push_error_with_code(
+                        DiagnosticCode::TypeMismatch,
+                        format!("Cannot convert {} to {}", ty, target_type),
+                        node,
+                    );
                     return self;
                 }
             }
@@ -1072,7 +1083,7 @@ pub fn maybe_convert_to(
                     );
                 }
             }
-            diag.push_error(message, node);
+            diag.push_error_with_code(DiagnosticCode::TypeMismatch, message, node);
             self
         }
     }
@@ -1168,6 +1179,10 @@ pub struct BindingExpression {
 
     /// The properties this expression is aliased with using two way bindings
     pub two_way_bindings: Vec<NamedReference>,
+
+    /// True if the binding is written in the source code, false if it was added by some passes.
+    /// Unlike a priority of 0, this stays true when the binding is inlined.
+    pub is_explicit: bool,
 }
 
 impl std::convert::From<Expression> for BindingExpression {
@@ -1179,6 +1194,7 @@ fn from(expression: Expression) -> Self {
             animation: Default::default(),
             analysis: Default::default(),
             two_way_bindings: Default::default(),
+            is_explicit: false,
         }
     }
 }
@@ -1192,6 +1208,7 @@ pub fn new_uncompiled(node: SyntaxNode) -> Self {
             animation: Default::default(),
             analysis: Default::default(),
             two_way_bindings: Default::default(),
+            is_explicit: true,
         }
     }
     pub fn new_with_span(expression: Expression, span: SourceLocation) -> Self {
@@ -1202,6 +1219,7 @@ pub fn new_with_span(expression: Expression, span: SourceLocation) -> Self {
             animation: Default::default(),
             analysis: Default::default(),
             two_way_bindings: Default::default(),
+            is_explicit: false,
         }
     }
 
@@ -1214,6 +1232,7 @@ pub fn new_two_way(other: NamedReference) -> Self {
             animation: Default::default(),
             analysis: Default::default(),
             two_way_bindings: vec![other],
+            is_explicit: false,
         }
     }
 
@@ -1231,6 +1250,7 @@ pub fn merge_with(&mut self, other: &Self) -> bool {
         self.two_way_bindings.extend_from_slice(&other.two_way_bindings);
         if matches!(self.expression, Expression::Invalid) {
             self.priority = other.priority;
+            self.is_explicit = other.is_explicit;
             self.expression = other.expression.clone();
             true
         } else {
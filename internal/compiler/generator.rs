@@ -21,18 +21,34 @@
 #[cfg(feature = "rust")]
 pub mod rust;
 
+#[cfg(feature = "rust")]
+mod wasm_bundle;
+#[cfg(feature = "rust")]
+pub use wasm_bundle::SlintDependency;
+
+mod typescript;
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum OutputFormat {
     #[cfg(feature = "cpp")]
     Cpp,
     #[cfg(feature = "rust")]
     Rust,
+    /// Rust code with wasm-bindgen wrappers, to be built with `wasm-pack` (see [`generate_wasm_bundle_crate`])
+    #[cfg(feature = "rust")]
+    WasmBundle,
+    /// TypeScript definitions of the component loaded with the Node.js API
+    TypeScript,
     Interpreter,
     Llr,
 }
 
 impl OutputFormat {
     pub fn guess_from_extension(path: &std::path::Path) -> Option<Self> {
+        // The TypeScript output is a declaration file, not a `.ts` module
+        if path.to_str().map_or(false, |p| p.ends_with(".d.ts")) {
+            return Some(Self::TypeScript);
+        }
         match path.extension().and_then(|ext| ext.to_str()) {
             #[cfg(feature = "cpp")]
             Some("cpp") | Some("cxx") | Some("h") | Some("hpp") => Some(Self::Cpp),
@@ -51,6 +67,9 @@ fn from_str(s: &str) -> Result<Self, Self::Err> {
             "cpp" => Ok(Self::Cpp),
             #[cfg(feature = "rust")]
             "rust" => Ok(Self::Rust),
+            #[cfg(feature = "rust")]
+            "wasm-bundle" => Ok(Self::WasmBundle),
+            "typescript" => Ok(Self::TypeScript),
             "llr" => Ok(Self::Llr),
             _ => Err(format!("Unknown outpout format {}", s)),
         }
@@ -81,6 +100,15 @@ pub fn generate(
             let output = rust::generate(doc);
             write!(destination, "{}", output)?;
         }
+        #[cfg(feature = "rust")]
+        OutputFormat::WasmBundle => {
+            let output = wasm_bundle::generate(doc);
+            write!(destination, "{}", output)?;
+        }
+        OutputFormat::TypeScript => {
+            let output = typescript::generate(doc)?;
+            write!(destination, "{}", output)?;
+        }
         OutputFormat::Interpreter => {
             return Err(std::io::Error::new(
                 std::io::ErrorKind::Other,
@@ -98,6 +126,18 @@ pub fn generate(
     Ok(())
 }
 
+/// Writes the crate of the [`OutputFormat::WasmBundle`] code in `directory`, so that building it with
+/// `wasm-pack build --target web` produces the WebAssembly module and its JavaScript glue.
+/// The crate depends on the `slint` crate given by `slint`.
+#[cfg(feature = "rust")]
+pub fn generate_wasm_bundle_crate(
+    doc: &Document,
+    directory: &std::path::Path,
+    slint: &SlintDependency,
+) -> std::io::Result<()> {
+    wasm_bundle::generate_crate(doc, directory, slint)
+}
+
 /// A reference to this trait is passed to the [`build_item_tree`] function.
 /// It can be used to build the array for the item tree.
 pub trait ItemTreeBuilder {
@@ -487,3 +527,11 @@ fn case_conversions() {
     assert_eq!(to_kebab_case("HelloWorld"), "hello-world");
     assert_eq!(to_pascal_case("hello-world"), "HelloWorld");
 }
+
+#[test]
+fn output_format_from_extension() {
+    let guess = |path: &str| OutputFormat::guess_from_extension(std::path::Path::new(path));
+    assert!(matches!(guess("ui/main.slint.d.ts"), Some(OutputFormat::TypeScript)));
+    assert!(guess("ui/main.ts").is_none());
+    assert!(guess("ui/main.slint").is_none());
+}
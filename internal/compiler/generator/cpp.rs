@@ -1054,6 +1054,21 @@ fn generate_item_tree(
         }),
     ));
 
+    // The element ids are only used by the introspection API which is not available in C++,
+    // so don't embed them. The slot must still be filled in case the runtime has it.
+    target_struct.members.push((
+        Access::Private,
+        Declaration::Function(Function {
+            name: "element_id".into(),
+            signature:
+                "(slint::private_api::ComponentRef, uintptr_t, slint::SharedString *) -> void"
+                    .into(),
+            is_static: true,
+            statements: Some(vec![]),
+            ..Default::default()
+        }),
+    ));
+
     target_struct.members.push((
         Access::Public,
         Declaration::Var(Var {
@@ -1070,7 +1085,7 @@ fn generate_item_tree(
             "{{ visit_children, get_item_ref, get_subtree_range, get_subtree_component, \
                 get_item_tree, parent_node, subtree_index, layout_info, \
                 accessible_role, accessible_string_property, \
-                slint::private_api::drop_in_place<{}>, slint::private_api::dealloc }}",
+                slint::private_api::drop_in_place<{}>, slint::private_api::dealloc, element_id }}",
             item_tree_class_name
         )),
         ..Default::default()
@@ -2387,6 +2402,15 @@ fn compile_builtin_function_call(
                 panic!("internal error: invalid args to SetFocusItem {:?}", arguments)
             }
         }
+        BuiltinFunction::RegisterMenuBar => {
+            if let [llr::Expression::PropertyReference(pr)] = arguments {
+                let window = access_window_field(ctx);
+                let menu_bar = access_item_rc(pr, ctx);
+                format!("{}.register_menu_bar({});", window, menu_bar)
+            } else {
+                panic!("internal error: invalid args to RegisterMenuBar {:?}", arguments)
+            }
+        }
         /*  std::from_chars is unfortunately not yet implemented in gcc
         BuiltinFunction::StringIsFloat => {
             "[](const auto &a){ double v; auto r = std::from_chars(std::begin(a), std::end(a), v); return r.ptr == std::end(a); }"
@@ -23,13 +23,13 @@
 use itertools::Either;
 use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::num::NonZeroUsize;
 
 type EvaluationContext<'a> = llr_EvaluationContext<'a, TokenStream>;
 type ParentCtx<'a> = llr_ParentCtx<'a, TokenStream>;
 
-fn ident(ident: &str) -> proc_macro2::Ident {
+pub(super) fn ident(ident: &str) -> proc_macro2::Ident {
     if ident.contains('-') {
         format_ident!("r#{}", ident.replace('-', "_"))
     } else {
@@ -61,6 +61,11 @@ fn to_tokens(&self, tokens: &mut TokenStream) {
                 quote!(slint::private_unstable_api::re_exports::PixelFormat::RgbaPremultiplied)
             }
             AlphaMap(_) => quote!(slint::private_unstable_api::re_exports::PixelFormat::AlphaMap),
+            Indexed8 => quote!(slint::private_unstable_api::re_exports::PixelFormat::Indexed8),
+            Rgb565Rle => quote!(slint::private_unstable_api::re_exports::PixelFormat::Rgb565Rle),
+            RgbaPremultipliedRle => {
+                quote!(slint::private_unstable_api::re_exports::PixelFormat::RgbaPremultipliedRle)
+            }
         };
         tokens.extend(tks);
     }
@@ -110,21 +115,100 @@ pub fn generate(doc: &Document) -> TokenStream {
         return TokenStream::default();
     }
 
-    let (structs_ids, structs): (Vec<_>, Vec<_>) = doc
-        .root_component
-        .used_types
-        .borrow()
-        .structs
-        .iter()
+    // The structs are generated once, in the module of the main component, for all the windows
+    let mut struct_names = HashSet::new();
+    let (structs_ids, structs): (Vec<_>, Vec<_>) = std::iter::once(doc)
+        .chain(doc.other_windows.iter())
+        .flat_map(used_structs)
         .filter_map(|ty| {
-            if let Type::Struct { fields, name: Some(name), node: Some(_) } = ty {
-                Some((ident(name), generate_struct(name, fields)))
+            if let Type::Struct { fields, name: Some(name), node: Some(_) } = &ty {
+                struct_names
+                    .insert(name.clone())
+                    .then(|| (ident(name), generate_struct(name, fields)))
             } else {
                 None
             }
         })
         .unzip();
 
+    let globals_mode = if doc.other_windows.is_empty() {
+        GlobalsMode::PerComponent
+    } else {
+        GlobalsMode::SharedInMainModule
+    };
+    let (compo_id, compo_module, compo_items, globals_ids) =
+        generate_component_module(doc, globals_mode);
+
+    // The other exported windows are compiled separately, and share the globals generated
+    // in the module of the main component
+    let (windows_ids, windows): (Vec<_>, Vec<_>) = doc
+        .other_windows
+        .iter()
+        .map(|window_doc| {
+            let window_structs_ids = used_structs(window_doc).into_iter().filter_map(|ty| {
+                if let Type::Struct { name: Some(name), node: Some(_), .. } = &ty {
+                    Some(ident(name))
+                } else {
+                    None
+                }
+            });
+            let (window_id, window_module, window_items, _) =
+                generate_component_module(window_doc, GlobalsMode::SharedFromMainModule(&compo_id));
+            (
+                quote!(#compo_module::#window_module::#window_id),
+                quote! {
+                    pub mod #window_module {
+                        use super::{#(#window_structs_ids),*};
+                        #window_items
+                    }
+                },
+            )
+        })
+        .unzip();
+
+    quote! {
+        #[allow(non_snake_case)]
+        #[allow(non_camel_case_types)]
+         // These make code generation easier
+        #[allow(clippy::style)]
+        #[allow(clippy::complexity)]
+        #[allow(unused_braces)]
+        #[allow(clippy::erasing_op)]
+        #[allow(clippy::approx_constant)] // We may get those from .slint inputs!
+        mod #compo_module {
+            #(#structs)*
+            #compo_items
+            #(#windows)*
+        }
+        pub use #compo_module::{#compo_id #(,#structs_ids)* #(,#globals_ids)* };
+        #(pub use #windows_ids;)*
+        pub use slint::{ComponentHandle, Global, ModelExt as _};
+    }
+}
+
+/// How the globals of a generated component are instantiated
+#[derive(Clone, Copy)]
+enum GlobalsMode<'a> {
+    /// Each instance of the component has its own globals
+    PerComponent,
+    /// The globals are shared by the windows of the file, and generated with the main component
+    SharedInMainModule,
+    /// The globals are shared by the windows of the file, and were generated in the parent
+    /// module, with the main component of the given name
+    SharedFromMainModule(&'a Ident),
+}
+
+/// Returns the structs used by the main component of the document
+fn used_structs(doc: &Document) -> Vec<Type> {
+    doc.root_component.used_types.borrow().structs.clone()
+}
+
+/// Generate the items of the module of the main component of the document, and return the
+/// name of the component, the name of its module, those items and the exported globals
+fn generate_component_module(
+    doc: &Document,
+    globals_mode: GlobalsMode,
+) -> (Ident, Ident, TokenStream, Vec<Ident>) {
     let llr = crate::llr::lower_to_item_tree::lower_to_item_tree(&doc.root_component);
 
     let sub_compos = llr
@@ -133,7 +217,7 @@ pub fn generate(doc: &Document) -> TokenStream {
         .map(|sub_compo| generate_sub_component(sub_compo, &llr, None, quote!(), None, false))
         .collect::<Vec<_>>();
 
-    let compo = generate_public_component(&llr);
+    let compo = generate_public_component(&llr, globals_mode);
     let compo_id = public_component_id(&llr.item_tree.root);
     let compo_module = format_ident!("slint_generated{}", compo_id);
     let version_check = format_ident!(
@@ -143,11 +227,39 @@ pub fn generate(doc: &Document) -> TokenStream {
         env!("CARGO_PKG_VERSION_PATCH"),
     );
 
-    let globals =
-        llr.globals.iter().filter(|glob| !glob.is_builtin).map(|glob| generate_global(glob, &llr));
-    let globals_ids = llr.globals.iter().filter(|glob| glob.exported).flat_map(|glob| {
-        std::iter::once(ident(&glob.name)).chain(glob.aliases.iter().map(|x| ident(x)))
-    });
+    let globals = if let GlobalsMode::SharedFromMainModule(main_component_id) = globals_mode {
+        // Use the globals generated in the parent module, and let this window access them too
+        let (_, shared_globals_ref_id) = shared_globals_ids(main_component_id);
+        let global_ids =
+            llr.globals.iter().filter(|glob| !glob.is_builtin).map(global_inner_name).chain(
+                llr.globals.iter().filter(|glob| glob.exported).map(|glob| ident(&glob.name)),
+            );
+        let global_getters = llr
+            .globals
+            .iter()
+            .filter(|glob| glob.exported)
+            .map(|glob| generate_global_getter(glob, &llr));
+        quote! {
+            #[allow(unused_imports)]
+            use super::{#(#global_ids,)* #shared_globals_ref_id};
+            #(#global_getters)*
+        }
+    } else {
+        let globals = llr
+            .globals
+            .iter()
+            .filter(|glob| !glob.is_builtin)
+            .map(|glob| generate_global(glob, &llr, globals_mode));
+        quote!(#(#globals)*)
+    };
+    let globals_ids = llr
+        .globals
+        .iter()
+        .filter(|glob| glob.exported)
+        .flat_map(|glob| {
+            std::iter::once(ident(&glob.name)).chain(glob.aliases.iter().map(|x| ident(x)))
+        })
+        .collect::<Vec<_>>();
 
     let link_section =
         std::env::var("SLINT_ASSET_SECTION").ok().map(|section| quote!(#[link_section = #section]));
@@ -259,36 +371,31 @@ pub fn generate(doc: &Document) -> TokenStream {
             }
         }).collect::<Vec<_>>();
 
-    quote! {
-        #[allow(non_snake_case)]
-        #[allow(non_camel_case_types)]
-         // These make code generation easier
-        #[allow(clippy::style)]
-        #[allow(clippy::complexity)]
-        #[allow(unused_braces)]
-        #[allow(clippy::erasing_op)]
-        #[allow(clippy::approx_constant)] // We may get those from .slint inputs!
-        mod #compo_module {
-            use slint::private_unstable_api::re_exports::*;
-            #(#structs)*
-            #(#globals)*
-            #(#sub_compos)*
-            #compo
-            #(#resource_symbols)*
-            const _THE_SAME_VERSION_MUST_BE_USED_FOR_THE_COMPILER_AND_THE_RUNTIME : slint::#version_check = slint::#version_check;
-        }
-        pub use #compo_module::{#compo_id #(,#structs_ids)* #(,#globals_ids)* };
-        pub use slint::{ComponentHandle, Global, ModelExt as _};
-    }
+    let items = quote! {
+        use slint::private_unstable_api::re_exports::*;
+        #globals
+        #(#sub_compos)*
+        #compo
+        #(#resource_symbols)*
+        const _THE_SAME_VERSION_MUST_BE_USED_FOR_THE_COMPILER_AND_THE_RUNTIME : slint::#version_check = slint::#version_check;
+    };
+    (compo_id, compo_module, items, globals_ids)
 }
 
-fn generate_public_component(llr: &llr::PublicComponent) -> TokenStream {
+fn generate_public_component(llr: &llr::PublicComponent, globals_mode: GlobalsMode) -> TokenStream {
     let public_component_id = public_component_id(&llr.item_tree.root);
     let inner_component_id = inner_component_id(&llr.item_tree.root);
     let global_container_id = format_ident!("Globals_{}", public_component_id);
+    let globals_field_type = match globals_mode {
+        GlobalsMode::PerComponent => global_container_id.clone(),
+        GlobalsMode::SharedInMainModule => shared_globals_ids(&public_component_id).1,
+        GlobalsMode::SharedFromMainModule(main_component_id) => {
+            shared_globals_ids(main_component_id).1
+        }
+    };
 
     let component =
-        generate_item_tree(&llr.item_tree, llr, None, quote!(globals: #global_container_id), None);
+        generate_item_tree(&llr.item_tree, llr, None, quote!(globals: #globals_field_type), None);
 
     let ctx = EvaluationContext {
         public_component: llr,
@@ -306,6 +413,72 @@ fn generate_public_component(llr: &llr::PublicComponent) -> TokenStream {
         llr.globals.iter().map(|g| format_ident!("global_{}", ident(&g.name))).collect::<Vec<_>>();
     let global_types = llr.globals.iter().map(global_inner_name).collect::<Vec<_>>();
 
+    let init_globals = match globals_mode {
+        GlobalsMode::PerComponent => quote!(#(inner.globals.#global_names.clone().init(&inner);)*),
+        _ => quote!(inner.globals.init(inner.window_adapter.get().unwrap());),
+    };
+
+    let global_container = match globals_mode {
+        GlobalsMode::PerComponent => quote! {
+            #[allow(dead_code)] // FIXME: some global are unused because of optimization, we should then remove them completely
+            struct #global_container_id {
+                #(#global_names : ::core::pin::Pin<slint::private_unstable_api::re_exports::Rc<#global_types>>,)*
+            }
+            impl Default for #global_container_id {
+                fn default() -> Self {
+                    Self {
+                        #(#global_names : #global_types::new(),)*
+                    }
+                }
+            }
+        },
+        GlobalsMode::SharedInMainModule => {
+            // The windows hold a reference to the globals, which are created by the first
+            // window and initialized with its window adapter.
+            let (shared_globals_id, shared_globals_ref_id) =
+                shared_globals_ids(&public_component_id);
+            quote! {
+                #[allow(dead_code)]
+                struct #global_container_id {
+                    #(#global_names : ::core::pin::Pin<slint::private_unstable_api::re_exports::Rc<#global_types>>,)*
+                }
+
+                struct #shared_globals_id {
+                    globals: #global_container_id,
+                    window_adapter: slint::private_unstable_api::re_exports::OnceCell<slint::private_unstable_api::re_exports::Rc<dyn slint::private_unstable_api::re_exports::WindowAdapter>>,
+                }
+
+                struct #shared_globals_ref_id(slint::private_unstable_api::re_exports::Rc<#shared_globals_id>);
+                impl Default for #shared_globals_ref_id {
+                    fn default() -> Self {
+                        Self(slint::private_unstable_api::re_exports::shared_globals(|| {
+                            slint::private_unstable_api::re_exports::Rc::new(#shared_globals_id {
+                                globals: #global_container_id {
+                                    #(#global_names : #global_types::new(),)*
+                                },
+                                window_adapter: Default::default(),
+                            })
+                        }))
+                    }
+                }
+                impl ::core::ops::Deref for #shared_globals_ref_id {
+                    type Target = #global_container_id;
+                    fn deref(&self) -> &Self::Target {
+                        &self.0.globals
+                    }
+                }
+                impl #shared_globals_ref_id {
+                    fn init(&self, window_adapter: &slint::private_unstable_api::re_exports::Rc<dyn slint::private_unstable_api::re_exports::WindowAdapter>) {
+                        if self.0.window_adapter.set(window_adapter.clone()).is_ok() {
+                            #(self.0.globals.#global_names.clone().init(&self.0);)*
+                        }
+                    }
+                }
+            }
+        }
+        GlobalsMode::SharedFromMainModule(_) => quote!(),
+    };
+
     quote!(
         #component
         pub struct #public_component_id(vtable::VRc<slint::private_unstable_api::re_exports::ComponentVTable, #inner_component_id>);
@@ -313,7 +486,7 @@ fn generate_public_component(llr: &llr::PublicComponent) -> TokenStream {
         impl #public_component_id {
             pub fn new() -> Self {
                 let inner = #inner_component_id::new();
-                #(inner.globals.#global_names.clone().init(&inner);)*
+                #init_globals
                 Self(inner)
             }
 
@@ -363,17 +536,16 @@ fn global<'a, T: slint::Global<'a, Self>>(&'a self) -> T {
             }
         }
 
-        #[allow(dead_code)] // FIXME: some global are unused because of optimization, we should then remove them completely
-        struct #global_container_id {
-            #(#global_names : ::core::pin::Pin<slint::private_unstable_api::re_exports::Rc<#global_types>>,)*
-        }
-        impl Default for #global_container_id {
-            fn default() -> Self {
-                Self {
-                    #(#global_names : #global_types::new(),)*
-                }
-            }
-        }
+        #global_container
+    )
+}
+
+/// Return the names of the struct holding the globals shared by the windows of a file, and
+/// of the reference to it that each window holds, given the name of the main component
+fn shared_globals_ids(main_component_id: &Ident) -> (Ident, Ident) {
+    (
+        format_ident!("SharedGlobals_{}", main_component_id),
+        format_ident!("SharedGlobalsRef_{}", main_component_id),
     )
 }
 
@@ -383,9 +555,11 @@ fn generate_struct(name: &str, fields: &BTreeMap<String, Type>) -> TokenStream {
         fields.iter().map(|(name, ty)| (ident(name), rust_type(ty).unwrap())).unzip();
 
     quote! {
-        #[derive(Default, PartialEq, Debug, Clone)]
-        pub struct #component_id {
-            #(pub #declared_property_vars : #declared_property_types),*
+        slint::private_unstable_api::declare_struct! {
+            #[derive(Default, PartialEq, Debug, Clone)]
+            pub struct #component_id {
+                #(pub #declared_property_vars : #declared_property_types),*
+            }
         }
     }
 }
@@ -722,6 +896,12 @@ fn generate_sub_component(
         }
     }
 
+    let mut element_id_branch = component
+        .element_ids
+        .iter()
+        .map(|(index, id)| quote!(#index => SharedString::from(#id),))
+        .collect::<Vec<_>>();
+
     let mut sub_component_names: Vec<Ident> = vec![];
     let mut sub_component_types: Vec<Ident> = vec![];
 
@@ -782,6 +962,9 @@ fn generate_sub_component(
         accessible_string_property_branch.push(quote!(
             (#local_tree_index, _) => #sub_compo_field.apply_pin(_self).accessible_string_property(0, what),
         ));
+        element_id_branch.push(quote!(
+            #local_tree_index => #sub_compo_field.apply_pin(_self).element_id(0),
+        ));
         if sub_items_count > 1 {
             let range_begin = local_index_of_first_child as usize;
             let range_end = range_begin + sub_items_count - 2;
@@ -791,6 +974,9 @@ fn generate_sub_component(
             accessible_string_property_branch.push(quote!(
                 (#range_begin..=#range_end, _) => #sub_compo_field.apply_pin(_self).accessible_string_property(index - #range_begin + 1, what),
             ));
+            element_id_branch.push(quote!(
+                #range_begin..=#range_end => #sub_compo_field.apply_pin(_self).element_id(index - #range_begin + 1),
+            ));
         }
 
         sub_component_names.push(field_name);
@@ -964,13 +1150,29 @@ fn accessible_string_property(
                     _ => Default::default(),
                 }
             }
+
+            slint::private_unstable_api::rtti_items! {
+                fn element_id(self: ::core::pin::Pin<&Self>, index: usize) -> slint::private_unstable_api::re_exports::SharedString {
+                    #![allow(unused)]
+                    use slint::private_unstable_api::re_exports::*;
+                    let _self = self;
+                    match index {
+                        #(#element_id_branch)*
+                        _ => Default::default(),
+                    }
+                }
+            }
         }
 
         #(#extra_components)*
     )
 }
 
-fn generate_global(global: &llr::GlobalComponent, root: &llr::PublicComponent) -> TokenStream {
+fn generate_global(
+    global: &llr::GlobalComponent,
+    root: &llr::PublicComponent,
+    globals_mode: GlobalsMode,
+) -> TokenStream {
     let mut declared_property_vars = vec![];
     let mut declared_property_types = vec![];
     let mut declared_callbacks = vec![];
@@ -1036,8 +1238,7 @@ fn generate_global(global: &llr::GlobalComponent, root: &llr::PublicComponent) -
     let public_interface = global.exported.then(|| {
         let property_and_callback_accessors = public_api(&global.public_properties, quote!(self.0.as_ref()), &ctx);
         let public_component_id = ident(&global.name);
-        let root_component_id = self::public_component_id(&root.item_tree.root);
-        let global_id = format_ident!("global_{}", public_component_id);
+        let global_getter = generate_global_getter(global, root);
 
         let aliases = global.aliases.iter().map(|name| ident(name));
         quote!(
@@ -1049,15 +1250,30 @@ impl<'a> #public_component_id<'a> {
 
             #(pub type #aliases<'a> = #public_component_id<'a>;)*
 
-            impl<'a> slint::Global<'a, #root_component_id> for #public_component_id<'a> {
-                fn get(component: &'a #root_component_id) -> Self {
-                    Self(&component.0 .globals.#global_id)
-                }
-            }
+            #global_getter
         )
     });
 
-    let root_component_id = self::inner_component_id(&root.item_tree.root);
+    // The root of the global is the component, or the globals shared by the windows of the file
+    let (root_type, root_weak_type, downgrade_root) = match globals_mode {
+        GlobalsMode::PerComponent => {
+            let root_component_id = self::inner_component_id(&root.item_tree.root);
+            (
+                quote!(slint::private_unstable_api::re_exports::VRc<slint::private_unstable_api::re_exports::ComponentVTable, #root_component_id>),
+                quote!(slint::private_unstable_api::re_exports::VWeak<slint::private_unstable_api::re_exports::ComponentVTable, #root_component_id>),
+                quote!(VRc::downgrade(root)),
+            )
+        }
+        _ => {
+            let (shared_globals_id, _) =
+                shared_globals_ids(&self::public_component_id(&root.item_tree.root));
+            (
+                quote!(slint::private_unstable_api::re_exports::Rc<#shared_globals_id>),
+                quote!(slint::private_unstable_api::re_exports::Weak<#shared_globals_id>),
+                quote!(slint::private_unstable_api::re_exports::Rc::downgrade(root)),
+            )
+        }
+    };
     quote!(
         #[derive(slint::private_unstable_api::re_exports::FieldOffsets, Default)]
         #[const_field_offset(slint::private_unstable_api::re_exports::const_field_offset)]
@@ -1066,16 +1282,16 @@ fn get(component: &'a #root_component_id) -> Self {
         struct #inner_component_id {
             #(#declared_property_vars: slint::private_unstable_api::re_exports::Property<#declared_property_types>,)*
             #(#declared_callbacks: slint::private_unstable_api::re_exports::Callback<(#(#declared_callbacks_types,)*), #declared_callbacks_ret>,)*
-            root : slint::private_unstable_api::re_exports::OnceCell<slint::private_unstable_api::re_exports::VWeak<slint::private_unstable_api::re_exports::ComponentVTable, #root_component_id>>,
+            root : slint::private_unstable_api::re_exports::OnceCell<#root_weak_type>,
         }
 
         impl #inner_component_id {
             fn new() -> ::core::pin::Pin<slint::private_unstable_api::re_exports::Rc<Self>> {
                 slint::private_unstable_api::re_exports::Rc::pin(Self::default())
             }
-            fn init(self: ::core::pin::Pin<slint::private_unstable_api::re_exports::Rc<Self>>, root: &slint::private_unstable_api::re_exports::VRc<slint::private_unstable_api::re_exports::ComponentVTable, #root_component_id>) {
+            fn init(self: ::core::pin::Pin<slint::private_unstable_api::re_exports::Rc<Self>>, root: &#root_type) {
                 #![allow(unused)]
-                self.root.set(VRc::downgrade(root));
+                self.root.set(#downgrade_root);
                 let self_rc = self;
                 let _self = self_rc.as_ref();
                 #(#init)*
@@ -1086,6 +1302,23 @@ fn init(self: ::core::pin::Pin<slint::private_unstable_api::re_exports::Rc<Self>
     )
 }
 
+/// Implement `slint::Global` for the public struct of the global, for the given root component
+fn generate_global_getter(
+    global: &llr::GlobalComponent,
+    root: &llr::PublicComponent,
+) -> TokenStream {
+    let public_component_id = ident(&global.name);
+    let root_component_id = self::public_component_id(&root.item_tree.root);
+    let global_id = format_ident!("global_{}", public_component_id);
+    quote!(
+        impl<'a> slint::Global<'a, #root_component_id> for #public_component_id<'a> {
+            fn get(component: &'a #root_component_id) -> Self {
+                Self(&component.0 .globals.#global_id)
+            }
+        }
+    )
+}
+
 fn generate_item_tree(
     sub_tree: &llr::ItemTree,
     root: &llr::PublicComponent,
@@ -1300,6 +1533,16 @@ fn accessible_string_property(
             ) {
                 *result = self.accessible_string_property(index, what);
             }
+
+            slint::private_unstable_api::rtti_items! {
+                fn element_id(
+                    self: ::core::pin::Pin<&Self>,
+                    index: usize,
+                    result: &mut slint::private_unstable_api::re_exports::SharedString,
+                ) {
+                    *result = self.element_id(index);
+                }
+            }
         }
 
 
@@ -2000,6 +2243,17 @@ fn compile_builtin_function_call(
                 panic!("internal error: invalid args to SetFocusItem {:?}", arguments)
             }
         }
+        BuiltinFunction::RegisterMenuBar => {
+            if let [Expression::PropertyReference(pr)] = arguments {
+                let window_tokens = access_window_adapter_field(ctx);
+                let menu_bar = access_item_rc(pr, ctx);
+                quote!(
+                    slint::private_unstable_api::re_exports::WindowInner::from_pub(#window_tokens.window()).register_menu_bar(#menu_bar);
+                )
+            } else {
+                panic!("internal error: invalid args to RegisterMenuBar {:?}", arguments)
+            }
+        }
         BuiltinFunction::ShowPopupWindow => {
             if let [Expression::NumberLiteral(popup_index), x, y, Expression::PropertyReference(parent_ref)] =
                 arguments
@@ -0,0 +1,325 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*! module for the TypeScript definitions generator
+
+The generated `.d.ts` file describes the module returned by the Node.js API when loading a `.slint`
+file with `require`: the exported component, with its properties, callbacks and exported globals,
+as well as the structs and enums used by them. Saved next to the `.slint` file with the name
+`<file>.slint.d.ts`, it is picked up by TypeScript when importing `<file>.slint`.
+
+The types follow the conversions done in `api/node/native/lib.rs`: the numbers and lengths are
+`number`, colors and brushes are strings, structs are objects with `-` replaced by `_` in the field
+names, arrays are either an array or a model object, and enums are the string of the value.
+*/
+
+use crate::langtype::Type;
+use crate::object_tree::{Component, Document, PropertyDeclaration};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// The declarations of the types of the Node.js API that are used by the generated definitions
+const PRELUDE: &str = r#"interface Point {
+    x: number;
+    y: number;
+}
+
+interface Size {
+    width: number;
+    height: number;
+}
+
+interface SlintWindow {
+    show(): void;
+    hide(): void;
+    logical_position: Point;
+    physical_position: Point;
+    logical_size: Size;
+    physical_size: Size;
+}
+
+interface ModelPeer {
+    rowDataChanged(row: number): void;
+    rowAdded(row: number, count: number): void;
+    rowRemoved(row: number, count: number): void;
+    reset(): void;
+}
+
+interface Model<T> {
+    rowCount(): number;
+    rowData(row: number): T;
+    setRowData(row: number, data: T): void;
+    notify: ModelPeer;
+}
+
+interface Callback<Args extends any[], Ret> {
+    (...args: Args): Ret;
+    setHandler(handler: (...args: Args) => Ret): void;
+}
+
+interface Component {
+    run(): void;
+    show(): void;
+    hide(): void;
+    readonly window: SlintWindow;
+    send_mouse_click(x: number, y: number): void;
+    send_keyboard_string_sequence(s: string): void;
+}
+"#;
+
+/// Generate the TypeScript definitions for the given document.
+///
+/// Fails if an exported global has the same name as a property or callback of the component,
+/// as the Node.js API cannot expose both.
+pub fn generate(doc: &Document) -> std::io::Result<String> {
+    let component = &doc.root_component;
+    let globals = component
+        .used_types
+        .borrow()
+        .globals
+        .iter()
+        .filter(|g| g.visible_in_public_api())
+        .cloned()
+        .collect::<Vec<_>>();
+
+    // The named types used in the API, by name, so that they are only declared once
+    let mut named_types = BTreeMap::new();
+    for (_, decl) in public_properties(component)
+        .into_iter()
+        .chain(globals.iter().flat_map(|g| public_properties(g)))
+    {
+        collect_named_types(&decl.property_type, &mut named_types);
+    }
+    for (_, ty) in doc.exports().iter() {
+        collect_named_types(ty, &mut named_types);
+    }
+
+    let mut out = String::new();
+    writeln!(out, "// This file is auto-generated by the Slint compiler, do not edit.").unwrap();
+    writeln!(out).unwrap();
+    out.push_str(PRELUDE);
+
+    for (name, ty) in &named_types {
+        writeln!(out).unwrap();
+        match ty {
+            Type::Struct { fields, .. } => {
+                writeln!(out, "export interface {} {{", name).unwrap();
+                for (field_name, field_type) in fields {
+                    writeln!(out, "    {}: {};", ts_ident(field_name), ts_type(field_type))
+                        .unwrap();
+                }
+                writeln!(out, "}}").unwrap();
+            }
+            Type::Enumeration(e) => {
+                let values = e.values.iter().map(|v| format!("{:?}", v)).collect::<Vec<_>>();
+                writeln!(out, "export type {} = {};", name, values.join(" | ")).unwrap();
+            }
+            _ => unreachable!("only structs and enums are collected"),
+        }
+    }
+
+    for global in &globals {
+        writeln!(out).unwrap();
+        writeln!(out, "export interface {} {{", global_interface_name(global)).unwrap();
+        generate_members(&mut out, global);
+        writeln!(out, "}}").unwrap();
+    }
+
+    let component_name = ts_ident(&component.id);
+    writeln!(out).unwrap();
+    writeln!(out, "export interface {}Properties {{", component_name).unwrap();
+    for (name, decl) in public_properties(component) {
+        // Callbacks are initialized with the handler
+        writeln!(out, "    {}?: {};", ts_ident(&name), ts_type(&decl.property_type)).unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(out, "export interface {} extends Component {{", component_name).unwrap();
+    generate_members(&mut out, component);
+    let property_names = public_properties(component)
+        .into_iter()
+        .map(|(name, _)| ts_ident(&name))
+        .collect::<Vec<_>>();
+    for global in &globals {
+        let interface_name = global_interface_name(global);
+        let mut names = global.global_aliases();
+        names.push(global.root_element.borrow().original_name());
+        for name in names {
+            if property_names.contains(&ts_ident(&name)) {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    format!(
+                        "The global {} has the same name as a property or callback of {}",
+                        name, component.id
+                    ),
+                ));
+            }
+            writeln!(out, "    readonly {}: {};", ts_ident(&name), interface_name).unwrap();
+        }
+    }
+    writeln!(out, "}}").unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(out, "export declare const {}: {{", component_name).unwrap();
+    writeln!(out, "    new (init_properties?: {}Properties): {};", component_name, component_name)
+        .unwrap();
+    writeln!(out, "}};").unwrap();
+
+    Ok(out)
+}
+
+/// The properties and callbacks of the root element of the component that are part of the API
+pub(super) fn public_properties(component: &Component) -> Vec<(String, PropertyDeclaration)> {
+    component
+        .root_element
+        .borrow()
+        .property_declarations
+        .iter()
+        .filter(|(_, decl)| {
+            decl.expose_in_public_api
+                && (decl.property_type.is_property_type()
+                    || matches!(decl.property_type, Type::Callback { .. }))
+        })
+        .map(|(name, decl)| (name.clone(), decl.clone()))
+        .collect()
+}
+
+/// Writes the properties and callbacks of the component as members of an interface
+fn generate_members(out: &mut String, component: &Component) {
+    for (name, decl) in public_properties(component) {
+        match &decl.property_type {
+            Type::Callback { args, return_type } => {
+                let args = args.iter().map(ts_type).collect::<Vec<_>>();
+                writeln!(
+                    out,
+                    "    readonly {}: Callback<[{}], {}>;",
+                    ts_ident(&name),
+                    args.join(", "),
+                    return_type.as_ref().map_or_else(|| "void".into(), |ty| ts_type(ty))
+                )
+                .unwrap();
+            }
+            ty => writeln!(out, "    {}: {};", ts_ident(&name), ts_type(ty)).unwrap(),
+        }
+    }
+}
+
+fn global_interface_name(global: &Component) -> String {
+    ts_ident(&global.root_element.borrow().original_name())
+}
+
+/// Adds the structs and enums used by `ty` to `named_types`
+pub(super) fn collect_named_types(ty: &Type, named_types: &mut BTreeMap<String, Type>) {
+    match ty {
+        Type::Struct { fields, name, .. } => {
+            if let Some(name) = name {
+                named_types.entry(struct_name(name)).or_insert_with(|| ty.clone());
+            }
+            for field_type in fields.values() {
+                collect_named_types(field_type, named_types);
+            }
+        }
+        Type::Enumeration(e) => {
+            named_types.entry(ts_ident(&e.name)).or_insert_with(|| ty.clone());
+        }
+        Type::Array(ty) => collect_named_types(ty, named_types),
+        Type::Callback { args, return_type } => {
+            for arg in args.iter().chain(return_type.as_deref()) {
+                collect_named_types(arg, named_types);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The TypeScript type of a value of type `ty` in the Node.js API
+fn ts_type(ty: &Type) -> String {
+    match ty {
+        Type::Float32
+        | Type::Int32
+        | Type::Duration
+        | Type::Angle
+        | Type::PhysicalLength
+        | Type::LogicalLength
+        | Type::Percent
+        | Type::UnitProduct(_) => "number".into(),
+        Type::String | Type::Color | Type::Brush => "string".into(),
+        Type::Bool => "boolean".into(),
+        // An image is set from the path of a file, but it reads as `null`, or as `undefined`
+        // when it is empty
+        Type::Image => "string | null | undefined".into(),
+        Type::Array(ty) => {
+            let ty = ts_type(ty);
+            format!("{}[] | Model<{}>", ty, ty)
+        }
+        Type::Struct { name: Some(name), .. } => struct_name(name),
+        Type::Struct { fields, name: None, .. } => {
+            let fields = fields
+                .iter()
+                .map(|(name, ty)| format!("{}: {}", ts_ident(name), ts_type(ty)))
+                .collect::<Vec<_>>();
+            format!("{{ {} }}", fields.join("; "))
+        }
+        Type::Enumeration(e) => ts_ident(&e.name),
+        Type::Callback { args, return_type } => function_type(args, return_type),
+        _ => "any".into(),
+    }
+}
+
+/// The type of a function that can be used as a handler of a callback
+fn function_type(args: &[Type], return_type: &Option<Box<Type>>) -> String {
+    let args = args
+        .iter()
+        .enumerate()
+        .map(|(i, ty)| format!("arg_{}: {}", i, ts_type(ty)))
+        .collect::<Vec<_>>();
+    format!(
+        "({}) => {}",
+        args.join(", "),
+        return_type.as_ref().map_or_else(|| "void".into(), |ty| ts_type(ty))
+    )
+}
+
+/// The name of a struct, without the namespace of builtin structs
+pub(super) fn struct_name(name: &str) -> String {
+    ts_ident(name.rsplit("::").next().unwrap_or(name))
+}
+
+/// The identifiers are exposed with `_` instead of `-` in the Node.js API
+pub(super) fn ts_ident(ident: &str) -> String {
+    ident.replace('-', "_")
+}
+
+#[test]
+fn golden_definitions() {
+    let source_path: std::path::PathBuf =
+        [env!("CARGO_MANIFEST_DIR"), "tests", "typescript", "golden.slint"].iter().collect();
+    let source = std::fs::read_to_string(&source_path).unwrap();
+    let mut diag = crate::diagnostics::BuildDiagnostics::default();
+    let node = crate::parser::parse(source, Some(&source_path), &mut diag);
+    let config = crate::CompilerConfiguration::new(crate::generator::OutputFormat::TypeScript);
+    let (doc, diag) = spin_on::spin_on(crate::compile_syntax_node(node, diag, config));
+    assert!(!diag.has_error(), "{:?}", diag.to_string_vec());
+
+    let expected = std::fs::read_to_string(source_path.with_extension("slint.d.ts")).unwrap();
+    assert_eq!(generate(&doc).unwrap(), expected);
+}
+
+#[test]
+fn global_name_clash() {
+    let source = r#"
+export global Settings := {
+    property <int> level;
+}
+export Main := Window {
+    property <int> Settings;
+}
+"#;
+    let mut diag = crate::diagnostics::BuildDiagnostics::default();
+    let node = crate::parser::parse(source.into(), None, &mut diag);
+    let config = crate::CompilerConfiguration::new(crate::generator::OutputFormat::TypeScript);
+    let (doc, diag) = spin_on::spin_on(crate::compile_syntax_node(node, diag, config));
+    assert!(!diag.has_error(), "{:?}", diag.to_string_vec());
+    assert!(generate(&doc).is_err());
+}
@@ -0,0 +1,526 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*! module for the WebAssembly bundle code generator
+
+The generated code is the Rust code of the component, followed by wrapper types exported with
+[wasm-bindgen](https://rustwasm.github.io/wasm-bindgen/). [`generate_crate`] writes it in a crate
+with the manifest returned by [`cargo_manifest`], and building that crate with `wasm-pack` produces
+the WebAssembly module and the JavaScript glue, with TypeScript definitions for the component.
+`slint-compiler -f wasm-bundle -o <directory>` generates the crate, and also builds it with
+`--build`.
+
+The wrappers need the `serde` feature of the `slint` crate, which is not in a released version yet,
+so the generated crate depends on the `slint` crate of a checkout or of a git repository, given by
+[`SlintDependency`].
+
+The properties are exposed as JavaScript properties, and the callbacks with an `on_<name>` method to
+set the handler and an `invoke_<name>` method to call it. The exported globals are exposed the same
+way, with a getter of the component for each exported name of the global. Numbers, strings and
+booleans map to the JavaScript types, the other types are converted with serde: colors are strings
+like `"#rrggbbaa"`, structs are objects with the field names of the .slint file, enums are the name
+of the value, images are the path of the file and arrays are arrays. The TypeScript definitions of
+these types are added to the ones generated by wasm-bindgen.
+*/
+
+use super::rust::ident;
+use super::typescript::{collect_named_types, public_properties, struct_name, ts_ident};
+use crate::langtype::Type;
+use crate::object_tree::{Component, Document, PropertyDeclaration};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// The TypeScript declarations of the builtin types that are converted with serde
+const PRELUDE: &str = r#"export interface GradientStop {
+    color: string;
+    position: number;
+}
+
+export type Brush =
+    | string
+    | { "linear-gradient": { angle: number; stops: GradientStop[] } }
+    | { "radial-gradient": { stops: GradientStop[] } };
+"#;
+
+/// The TypeScript types used in the signatures of the exported functions.
+///
+/// wasm-bindgen declares a `JsValue` as `any`, so each TypeScript type gets an imported type with
+/// the `typescript_type` attribute, that is used instead of `JsValue` in the signatures.
+#[derive(Default)]
+struct TypeScriptTypes {
+    types: BTreeMap<String, proc_macro2::Ident>,
+}
+
+impl TypeScriptTypes {
+    /// The imported type for the TypeScript type `ts_type`
+    fn get(&mut self, ts_type: String) -> proc_macro2::Ident {
+        let count = self.types.len();
+        self.types.entry(ts_type).or_insert_with(|| format_ident!("SlintTsType{}", count)).clone()
+    }
+
+    fn declarations(&self) -> TokenStream {
+        let (ts_types, idents): (Vec<_>, Vec<_>) = self.types.iter().unzip();
+        quote!(
+            #[wasm_bindgen::prelude::wasm_bindgen]
+            extern "C" {
+                #(
+                    #[wasm_bindgen(typescript_type = #ts_types)]
+                    pub type #idents;
+                )*
+            }
+        )
+    }
+}
+
+/// How a value of a .slint type is passed to JavaScript
+enum JsType {
+    Number,
+    String,
+    Bool,
+    /// Converted with serde-wasm-bindgen, and declared with the imported type
+    Serde(proc_macro2::Ident),
+}
+
+impl JsType {
+    fn of(ty: &Type, types: &mut TypeScriptTypes) -> Self {
+        match ty {
+            Type::Int32
+            | Type::Float32
+            | Type::Duration
+            | Type::Angle
+            | Type::PhysicalLength
+            | Type::LogicalLength
+            | Type::Percent => Self::Number,
+            Type::String => Self::String,
+            Type::Bool => Self::Bool,
+            _ => Self::Serde(types.get(ts_type(ty))),
+        }
+    }
+
+    /// The type used in the signature of the exported functions
+    fn abi_type(&self) -> TokenStream {
+        match self {
+            Self::Number => quote!(f64),
+            Self::String => quote!(String),
+            Self::Bool => quote!(bool),
+            Self::Serde(ts_type) => quote!(#ts_type),
+        }
+    }
+
+    /// Converts the Rust value `value` into the type returned by [`Self::abi_type`]
+    fn to_abi(&self, value: TokenStream) -> TokenStream {
+        match self {
+            Self::Number => quote!(#value as f64),
+            Self::String => quote!(#value.to_string()),
+            Self::Bool => value,
+            Self::Serde(ts_type) => {
+                let js_value = self.to_js_value(value);
+                quote!(wasm_bindgen::JsCast::unchecked_into::<#ts_type>(#js_value))
+            }
+        }
+    }
+
+    /// Converts `value` of the type returned by [`Self::abi_type`] into the Rust value
+    fn from_abi(&self, value: TokenStream) -> TokenStream {
+        match self {
+            Self::Number => quote!(#value as _),
+            Self::String => quote!(#value.into()),
+            Self::Bool => value,
+            Self::Serde(_) => quote!(wasm_bindgen::UnwrapThrowExt::unwrap_throw(
+                serde_wasm_bindgen::from_value(wasm_bindgen::JsValue::from(#value))
+            )),
+        }
+    }
+
+    /// Converts the Rust value `value` into a `JsValue`
+    fn to_js_value(&self, value: TokenStream) -> TokenStream {
+        match self {
+            Self::Number => quote!(wasm_bindgen::JsValue::from_f64(#value as f64)),
+            Self::String => quote!(wasm_bindgen::JsValue::from_str(&#value)),
+            Self::Bool => quote!(wasm_bindgen::JsValue::from_bool(#value)),
+            Self::Serde(_) => {
+                quote!(wasm_bindgen::UnwrapThrowExt::unwrap_throw(serde_wasm_bindgen::to_value(&#value)))
+            }
+        }
+    }
+
+    /// Converts the `JsValue` returned by a JavaScript function into the Rust value
+    fn from_js_value(&self, value: TokenStream) -> TokenStream {
+        match self {
+            Self::Number => quote!(#value.as_f64().unwrap_or_default() as _),
+            Self::String => quote!(#value.as_string().unwrap_or_default().into()),
+            Self::Bool => quote!(#value.is_truthy()),
+            Self::Serde(_) => quote!(serde_wasm_bindgen::from_value(#value).unwrap_or_default()),
+        }
+    }
+}
+
+/// Generate the code of the component, with the wasm-bindgen wrappers
+pub fn generate(doc: &Document) -> TokenStream {
+    let component_code = super::rust::generate(doc);
+
+    let component = &doc.root_component;
+    let component_id = ident(&component.id);
+    let wrapper_id = format_ident!("{}_Wasm", component.id.replace('-', "_"));
+    let js_name = ts_ident(&component.id);
+
+    let globals = component
+        .used_types
+        .borrow()
+        .globals
+        .iter()
+        .filter(|g| g.visible_in_public_api())
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let mut types = TypeScriptTypes::default();
+
+    let accessors = generate_accessors(&public_properties(component), quote!(self.0), &mut types);
+
+    let mut global_accessors = vec![];
+    let mut global_wrappers = vec![];
+    for global in &globals {
+        let global_name = global.root_element.borrow().id.clone();
+        let global_id = ident(&global_name);
+        let global_wrapper_id = format_ident!("{}_Wasm", global_name.replace('-', "_"));
+        let global_js_name = ts_ident(&global.root_element.borrow().original_name());
+        let accessors = generate_accessors(
+            &public_properties(global),
+            quote!(slint::ComponentHandle::global::<#global_id>(&self.0)),
+            &mut types,
+        );
+        global_wrappers.push(quote!(
+            #[wasm_bindgen::prelude::wasm_bindgen(js_name = #global_js_name)]
+            #[allow(non_camel_case_types)]
+            pub struct #global_wrapper_id(#component_id);
+
+            #[wasm_bindgen::prelude::wasm_bindgen(js_class = #global_js_name)]
+            #[allow(non_snake_case)]
+            impl #global_wrapper_id {
+                #(#accessors)*
+            }
+        ));
+
+        let mut names = global.global_aliases();
+        names.push(global.root_element.borrow().original_name());
+        for name in names {
+            let getter_ident = format_ident!("global_{}", ident(&name));
+            let js_getter_name = ts_ident(&name);
+            global_accessors.push(quote!(
+                #[wasm_bindgen(getter = #js_getter_name)]
+                pub fn #getter_ident(&self) -> #global_wrapper_id {
+                    #global_wrapper_id(slint::ComponentHandle::clone_strong(&self.0))
+                }
+            ));
+        }
+    }
+
+    let ts_type_declarations = types.declarations();
+    let ts_named_types = typescript_named_types(doc, &globals);
+
+    quote! {
+        #component_code
+
+        #[wasm_bindgen::prelude::wasm_bindgen(typescript_custom_section)]
+        const SLINT_TYPESCRIPT_TYPES: &'static str = #ts_named_types;
+
+        #ts_type_declarations
+
+        #[wasm_bindgen::prelude::wasm_bindgen(js_name = #js_name)]
+        #[allow(non_camel_case_types)]
+        pub struct #wrapper_id(#component_id);
+
+        #[wasm_bindgen::prelude::wasm_bindgen(js_class = #js_name)]
+        #[allow(non_snake_case)]
+        impl #wrapper_id {
+            #[wasm_bindgen(constructor)]
+            pub fn new() -> Self {
+                Self(#component_id::new())
+            }
+
+            pub fn show(&self) {
+                slint::ComponentHandle::show(&self.0)
+            }
+
+            pub fn hide(&self) {
+                slint::ComponentHandle::hide(&self.0)
+            }
+
+            pub fn run(&self) {
+                slint::ComponentHandle::run(&self.0)
+            }
+
+            #(#accessors)*
+
+            #(#global_accessors)*
+        }
+
+        #(#global_wrappers)*
+    }
+}
+
+/// Where the crate generated by [`generate_crate`] gets the `slint` crate from
+#[derive(Debug, Clone)]
+pub enum SlintDependency {
+    /// The directory of the `slint` crate, such as `api/rs/slint` in a checkout of the Slint
+    /// repository. It should be absolute, as the generated crate is in another directory.
+    Path(std::path::PathBuf),
+    /// The URL of a git repository that contains the `slint` crate
+    Git(String),
+}
+
+/// The `Cargo.toml` of the crate that contains the code returned by [`generate`]
+pub fn cargo_manifest(doc: &Document, slint: &SlintDependency) -> String {
+    let slint_source = match slint {
+        SlintDependency::Path(path) => format!("path = {:?}", path.to_string_lossy()),
+        SlintDependency::Git(url) => format!("git = {:?}", url),
+    };
+    format!(
+        r#"# This file is auto-generated by the Slint compiler, do not edit.
+
+[package]
+name = "{name}"
+version = "0.1.0"
+edition = "2021"
+publish = false
+
+[lib]
+crate-type = ["cdylib"]
+path = "lib.rs"
+
+[dependencies]
+slint = {{ {slint_source}, features = ["serde"] }}
+wasm-bindgen = "0.2"
+js-sys = "0.3"
+serde-wasm-bindgen = "0.4"
+
+# Not part of an enclosing workspace
+[workspace]
+"#,
+        name = super::to_kebab_case(&doc.root_component.id.replace('_', "-")),
+    )
+}
+
+/// Writes the `Cargo.toml` and the `lib.rs` of the crate to build with `wasm-pack` in `directory`
+pub fn generate_crate(
+    doc: &Document,
+    directory: &std::path::Path,
+    slint: &SlintDependency,
+) -> std::io::Result<()> {
+    std::fs::create_dir_all(directory)?;
+    std::fs::write(directory.join("Cargo.toml"), cargo_manifest(doc, slint))?;
+    std::fs::write(directory.join("lib.rs"), generate(doc).to_string())
+}
+
+/// The getters, setters and callback methods of the properties of a wrapper, that forward to
+/// the generated Rust API of `receiver`
+fn generate_accessors(
+    properties: &[(String, PropertyDeclaration)],
+    receiver: TokenStream,
+    types: &mut TypeScriptTypes,
+) -> Vec<TokenStream> {
+    let mut accessors = vec![];
+    for (name, decl) in properties {
+        let prop_ident = ident(name);
+        let js_prop_name = ts_ident(name);
+        if let Type::Callback { args, return_type } = &decl.property_type {
+            let on_ident = format_ident!("on_{}", prop_ident);
+            let invoke_ident = format_ident!("invoke_{}", prop_ident);
+            let handler_type = types.get(function_type(args, return_type));
+            let args_name = (0..args.len()).map(|i| format_ident!("arg_{}", i)).collect::<Vec<_>>();
+            let args_js = args.iter().map(|ty| JsType::of(ty, types)).collect::<Vec<_>>();
+            let args_abi_type = args_js.iter().map(JsType::abi_type);
+            let args_from_abi =
+                args_js.iter().zip(&args_name).map(|(js, name)| js.from_abi(quote!(#name)));
+            let args_to_js_value =
+                args_js.iter().zip(&args_name).map(|(js, name)| js.to_js_value(quote!(#name)));
+            let (return_abi_type, return_to_abi, return_from_js_value) = match return_type {
+                Some(ty) => {
+                    let js = JsType::of(ty, types);
+                    (js.abi_type(), js.to_abi(quote!(result)), js.from_js_value(quote!(result)))
+                }
+                None => (quote!(()), quote!(result), quote!(())),
+            };
+            accessors.push(quote!(
+                pub fn #on_ident(&self, handler: #handler_type) {
+                    let handler: js_sys::Function = wasm_bindgen::JsCast::unchecked_into(handler);
+                    #receiver.#on_ident(move |#(#args_name),*| {
+                        let args = js_sys::Array::new();
+                        #(args.push(&#args_to_js_value);)*
+                        #[allow(unused)]
+                        let result = wasm_bindgen::UnwrapThrowExt::unwrap_throw(
+                            handler.apply(&wasm_bindgen::JsValue::NULL, &args),
+                        );
+                        #return_from_js_value
+                    })
+                }
+                pub fn #invoke_ident(&self, #(#args_name: #args_abi_type),*) -> #return_abi_type {
+                    let result = #receiver.#invoke_ident(#(#args_from_abi),*);
+                    #return_to_abi
+                }
+            ));
+        } else {
+            let getter_ident = format_ident!("get_{}", prop_ident);
+            let setter_ident = format_ident!("set_{}", prop_ident);
+            let js = JsType::of(&decl.property_type, types);
+            let abi_type = js.abi_type();
+            let to_abi = js.to_abi(quote!(#receiver.#getter_ident()));
+            let from_abi = js.from_abi(quote!(value));
+            accessors.push(quote!(
+                #[wasm_bindgen(getter = #js_prop_name)]
+                pub fn #getter_ident(&self) -> #abi_type {
+                    #to_abi
+                }
+                #[wasm_bindgen(setter = #js_prop_name)]
+                pub fn #setter_ident(&self, value: #abi_type) {
+                    #receiver.#setter_ident(#from_abi)
+                }
+            ));
+        }
+    }
+    accessors
+}
+
+/// The TypeScript declarations of the structs and enums used by the component and the globals
+fn typescript_named_types(doc: &Document, globals: &[std::rc::Rc<Component>]) -> String {
+    let mut named_types = BTreeMap::new();
+    for (_, decl) in public_properties(&doc.root_component)
+        .into_iter()
+        .chain(globals.iter().flat_map(|g| public_properties(g)))
+    {
+        collect_named_types(&decl.property_type, &mut named_types);
+    }
+    for (_, ty) in doc.exports().iter() {
+        collect_named_types(ty, &mut named_types);
+    }
+
+    let mut out = String::from(PRELUDE);
+    for (name, ty) in &named_types {
+        writeln!(out).unwrap();
+        match ty {
+            Type::Struct { fields, .. } => {
+                writeln!(out, "export interface {} {{", name).unwrap();
+                for (field_name, field_type) in fields {
+                    writeln!(out, "    {:?}: {};", field_name, ts_type(field_type)).unwrap();
+                }
+                writeln!(out, "}}").unwrap();
+            }
+            Type::Enumeration(e) => {
+                let values = e.values.iter().map(|v| format!("{:?}", v)).collect::<Vec<_>>();
+                writeln!(out, "export type {} = {};", name, values.join(" | ")).unwrap();
+            }
+            _ => unreachable!("only structs and enums are collected"),
+        }
+    }
+    out
+}
+
+/// The TypeScript type of the serde representation of a value of type `ty`
+fn ts_type(ty: &Type) -> String {
+    match ty {
+        Type::Float32
+        | Type::Int32
+        | Type::Duration
+        | Type::Angle
+        | Type::PhysicalLength
+        | Type::LogicalLength
+        | Type::Percent
+        | Type::UnitProduct(_) => "number".into(),
+        Type::String | Type::Color => "string".into(),
+        Type::Brush => "Brush".into(),
+        Type::Bool => "boolean".into(),
+        Type::Image => "string | null".into(),
+        Type::Array(ty) => format!("Array<{}>", ts_type(ty)),
+        Type::Struct { name: Some(name), .. } => struct_name(name),
+        Type::Struct { fields, name: None, .. } => {
+            let fields = fields
+                .iter()
+                .map(|(name, ty)| format!("{:?}: {}", name, ts_type(ty)))
+                .collect::<Vec<_>>();
+            format!("{{ {} }}", fields.join("; "))
+        }
+        Type::Enumeration(e) => ts_ident(&e.name),
+        Type::Callback { args, return_type } => function_type(args, return_type),
+        _ => "any".into(),
+    }
+}
+
+/// The type of a function that can be used as a handler of a callback
+fn function_type(args: &[Type], return_type: &Option<Box<Type>>) -> String {
+    let args = args
+        .iter()
+        .enumerate()
+        .map(|(i, ty)| format!("arg_{}: {}", i, ts_type(ty)))
+        .collect::<Vec<_>>();
+    format!(
+        "({}) => {}",
+        args.join(", "),
+        return_type.as_ref().map_or_else(|| "void".into(), |ty| ts_type(ty))
+    )
+}
+
+#[test]
+fn generated_code_is_valid_rust() {
+    let source = r#"
+        export struct Contact := {
+            first-name: string,
+            age: int,
+            color: color,
+        }
+        export global Settings := {
+            property <bool> dark-mode;
+            property <[Contact]> contacts;
+            callback save(Contact) -> bool;
+        }
+        export { Settings as Preferences }
+        export App := Window {
+            property <string> title-text: "Hello";
+            property <Contact> current;
+            property <TextHorizontalAlignment> alignment;
+            callback clicked(int, Contact);
+            callback format(float) -> string;
+        }
+    "#;
+    let mut diag = crate::diagnostics::BuildDiagnostics::default();
+    let node = crate::parser::parse(source.into(), None, &mut diag);
+    let config = crate::CompilerConfiguration::new(super::OutputFormat::WasmBundle);
+    let (doc, diag) = spin_on::spin_on(crate::compile_syntax_node(node, diag, config));
+    assert!(!diag.has_error(), "{:?}", diag.to_string_vec());
+
+    let code = generate(&doc).to_string();
+    if let Err(err) = syn::parse_file(&code) {
+        panic!("The generated code does not parse: {}\n{}", err, code);
+    }
+
+    // The wrappers of the component and of the global, with a getter for each exported name
+    assert!(code.contains("pub struct App_Wasm"));
+    assert!(code.contains("pub struct Settings_Wasm"));
+    assert!(code.contains("getter = \"Settings\""));
+    assert!(code.contains("getter = \"Preferences\""));
+    assert!(code.contains("pub fn on_save"));
+    assert!(code.contains("pub fn invoke_clicked"));
+
+    // The TypeScript types of the values converted with serde
+    let ts = typescript_named_types(&doc, &doc.root_component.used_types.borrow().globals);
+    assert!(ts.contains("export interface Contact {\n"));
+    assert!(ts.contains("    \"first-name\": string;\n"));
+    assert!(ts.contains("    \"color\": string;\n"));
+    assert!(ts.contains("export type TextHorizontalAlignment = \"left\" | \"center\" | \"right\""));
+    assert!(code.contains("typescript_type = \"Array<Contact>\""));
+    assert!(code.contains("typescript_type = \"(arg_0: number, arg_1: Contact) => void\""));
+    assert!(code.contains("typescript_type = \"(arg_0: Contact) => boolean\""));
+
+    let manifest = cargo_manifest(&doc, &SlintDependency::Path("/src/slint/api/rs/slint".into()));
+    assert!(manifest.contains("name = \"app\""));
+    assert!(manifest.contains("crate-type = [\"cdylib\"]"));
+    assert!(
+        manifest.contains("slint = { path = \"/src/slint/api/rs/slint\", features = [\"serde\"] }")
+    );
+    let manifest =
+        cargo_manifest(&doc, &SlintDependency::Git("https://github.com/slint-ui/slint".into()));
+    assert!(manifest.contains(
+        "slint = { git = \"https://github.com/slint-ui/slint\", features = [\"serde\"] }"
+    ));
+}
@@ -3,7 +3,7 @@
 
 //! Datastructures used to represent layouts in the compiler
 
-use crate::diagnostics::BuildDiagnostics;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode};
 use crate::expression_tree::*;
 use crate::langtype::{PropertyLookupResult, Type};
 use crate::object_tree::{Component, ElementRc};
@@ -164,7 +164,8 @@ pub fn new(element: &ElementRc, diag: &mut BuildDiagnostics) -> Self {
                                 && old.priority.saturating_add(d2)
                                     <= binding.priority.saturating_add(depth)
                             {
-                                diag.push_error(
+                                diag.push_error_with_code(
+                                    DiagnosticCode::ConflictingProperties,
                                     format!(
                                         "Cannot specify both '{}' and '{}'",
                                         prop,
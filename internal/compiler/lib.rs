@@ -30,6 +30,7 @@
 pub mod namedreference;
 pub mod object_tree;
 pub mod parser;
+pub mod report;
 pub mod typeloader;
 pub mod typeregister;
 
@@ -54,6 +55,10 @@ pub struct CompilerConfiguration {
     pub embed_resources: EmbedResourcesKind,
     /// The compiler will look in these paths for components used in the file to compile.
     pub include_paths: Vec<std::path::PathBuf>,
+    /// Named libraries, which are imported with `import { X } from "@name/file.slint"`.
+    /// The name maps to the directory of the library, or to a file that is imported with `"@name"`.
+    /// Relative paths are relative to the current working directory.
+    pub library_paths: std::collections::HashMap<String, std::path::PathBuf>,
     /// the name of the style. (eg: "native")
     pub style: Option<String>,
 
@@ -74,8 +79,21 @@ pub struct CompilerConfiguration {
     /// Compile time scale factor to apply to embedded resources such as images and glyphs.
     pub scale_factor: f64,
 
+    /// When embedding textures, store them with a palette or run-length encoded when that is smaller.
+    /// Opaque images are then stored with 16 bits colors.
+    pub compress_textures: bool,
+
     /// expose the accessible role and properties
     pub accessibility: bool,
+
+    /// The name of the exported component to compile as the main component, instead of the
+    /// last component of the file.
+    pub main_component: Option<String>,
+
+    /// Also compile the other exported windows of the file, into
+    /// [`object_tree::Document::other_windows`], so that they can be generated too.
+    /// The windows then share the instances of their globals.
+    pub compile_other_windows: bool,
 }
 
 impl CompilerConfiguration {
@@ -96,6 +114,9 @@ pub fn new(output_format: crate::generator::OutputFormat) -> Self {
             match output_format {
                 #[cfg(feature = "rust")]
                 crate::generator::OutputFormat::Rust => EmbedResourcesKind::EmbedAllResources,
+                // There is no file system to load the resources from in the browser
+                #[cfg(feature = "rust")]
+                crate::generator::OutputFormat::WasmBundle => EmbedResourcesKind::EmbedAllResources,
                 _ => EmbedResourcesKind::OnlyBuiltinResources,
             }
         };
@@ -116,18 +137,36 @@ pub fn new(output_format: crate::generator::OutputFormat) -> Self {
             .filter(|f| *f > 0.)
             .unwrap_or(1.);
 
+        let compress_textures = std::env::var_os("SLINT_COMPRESS_TEXTURES").is_some();
+
         Self {
             embed_resources,
             include_paths: Default::default(),
+            library_paths: Default::default(),
             style: Default::default(),
             open_import_fallback: Default::default(),
             inline_all_elements,
             scale_factor,
+            compress_textures,
             accessibility: true,
+            main_component: None,
+            #[cfg(feature = "rust")]
+            compile_other_windows: output_format == crate::generator::OutputFormat::Rust,
+            #[cfg(not(feature = "rust"))]
+            compile_other_windows: false,
         }
     }
 }
 
+/// Parses a library path given on the command line as `name=path`, such as with the `-L` option
+/// of the tools, into an entry of [`CompilerConfiguration::library_paths`].
+/// A leading `@` in the name is ignored.
+pub fn parse_library_path(s: &str) -> Result<(String, std::path::PathBuf), String> {
+    let (name, path) =
+        s.split_once('=').ok_or_else(|| format!("expected NAME=PATH, got '{}'", s))?;
+    Ok((name.trim_start_matches('@').to_string(), path.into()))
+}
+
 pub async fn compile_syntax_node(
     doc_node: parser::SyntaxNode,
     mut diagnostics: diagnostics::BuildDiagnostics,
@@ -139,6 +178,31 @@ pub async fn compile_syntax_node(
         compiler_config.accessibility = false;
     }
 
+    let (mut doc, mut diagnostics) =
+        compile_document(doc_node.clone(), diagnostics, &compiler_config).await;
+
+    if compiler_config.compile_other_windows && !diagnostics.has_error() {
+        // The passes modify the components, so each window is compiled from the source again.
+        // compile_other_windows stays set so that the passes keep the globals shared with the
+        // other windows, but compile_document does not compile the other windows itself.
+        let mut window_config = compiler_config.clone();
+        for name in doc.exported_windows() {
+            window_config.main_component = Some(name);
+            let (window_doc, window_diagnostics) =
+                compile_document(doc_node.clone(), Default::default(), &window_config).await;
+            diagnostics.extend_unique(window_diagnostics);
+            doc.other_windows.push(window_doc);
+        }
+    }
+
+    (doc, diagnostics)
+}
+
+async fn compile_document(
+    doc_node: parser::SyntaxNode,
+    mut diagnostics: diagnostics::BuildDiagnostics,
+    compiler_config: &CompilerConfiguration,
+) -> (object_tree::Document, diagnostics::BuildDiagnostics) {
     let global_type_registry = typeregister::TypeRegister::builtin();
     let type_registry =
         Rc::new(RefCell::new(typeregister::TypeRegister::new(&global_type_registry)));
@@ -158,21 +222,38 @@ pub async fn compile_syntax_node(
     let foreign_imports =
         loader.load_dependencies_recursively(&doc_node, &mut diagnostics, &type_registry).await;
 
-    let doc = crate::object_tree::Document::from_node(
-        doc_node,
+    let mut doc = crate::object_tree::Document::from_node(
+        doc_node.clone(),
         foreign_imports,
         &mut diagnostics,
         &type_registry,
     );
 
+    if let Some(name) = &compiler_config.main_component {
+        let normalized_name = parser::normalize_identifier(name);
+        match doc.exports().iter().find(|(exported, _)| **exported == normalized_name) {
+            Some((_, langtype::Type::Component(c))) if !c.is_global() => {
+                doc.root_component = c.clone()
+            }
+            _ => diagnostics.push_error_with_code(
+                diagnostics::DiagnosticCode::NoComponent,
+                format!("No exported component named '{}'", name),
+                &doc_node,
+            ),
+        }
+    }
+
     if let Some((_, node)) = &*doc.root_component.child_insertion_point.borrow() {
-        diagnostics
-            .push_error("@children placeholder not allowed in the final component".into(), node)
+        diagnostics.push_error_with_code(
+            diagnostics::DiagnosticCode::InvalidChildrenPlaceholder,
+            "@children placeholder not allowed in the final component".into(),
+            node,
+        )
     }
 
     if !diagnostics.has_error() {
         // FIXME: ideally we would be able to run more passes, but currently we panic because invariant are not met.
-        passes::run_passes(&doc, &mut diagnostics, &mut loader, &compiler_config).await;
+        passes::run_passes(&doc, &mut diagnostics, &mut loader, compiler_config).await;
     }
 
     diagnostics.all_loaded_files = loader.all_files().cloned().collect();
@@ -226,6 +226,12 @@ pub struct SubComponent {
     /// Maps (item_index, property) to an expression
     pub accessible_prop: BTreeMap<(usize, String), MutExpression>,
 
+    /// Maps the item index to the id of the element as written in the .slint file,
+    /// for the elements that have an id. Only the Rust code generator emits them, for the
+    /// introspection API.
+    #[cfg(feature = "rust")]
+    pub element_ids: BTreeMap<usize, String>,
+
     pub prop_analysis: HashMap<PropertyReference, PropAnalysis>,
 }
 
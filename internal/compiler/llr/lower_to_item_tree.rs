@@ -159,7 +159,8 @@ fn sub_component(&self, component: &Rc<Component>) -> &LoweredSubComponent {
     }
 }
 
-fn component_id(component: &Rc<Component>) -> String {
+/// The name of the sub-component lowered from `component`
+pub(crate) fn component_id(component: &Rc<Component>) -> String {
     if component.is_global() {
         component.root_element.borrow().id.clone()
     } else if component.id.is_empty() {
@@ -192,6 +193,8 @@ fn lower_sub_component(
         layout_info_h: super::Expression::BoolLiteral(false).into(),
         layout_info_v: super::Expression::BoolLiteral(false).into(),
         accessible_prop: Default::default(),
+        #[cfg(feature = "rust")]
+        element_ids: Default::default(),
         prop_analysis: Default::default(),
     };
     let mut mapping = LoweredSubComponentMapping::default();
@@ -282,6 +285,13 @@ fn lower_sub_component(
             }
             _ => unreachable!(),
         };
+        #[cfg(feature = "rust")]
+        {
+            let original_id = elem.original_id();
+            if !original_id.is_empty() {
+                sub_component.element_ids.insert(*elem.item_index.get().unwrap(), original_id);
+            }
+        }
         for (key, nr) in &elem.accessibility_props.0 {
             // TODO: we also want to split by type (role/string/...)
             let enum_value =
@@ -20,6 +20,18 @@ pub fn count_property_use(root: &PublicComponent) {
             visit_property(pr, &ctx);
         }
     }
+    // 1b. the properties of the globals shared with other windows
+    for g in root.globals.iter() {
+        let ctx = EvaluationContext::new_global(root, g, ());
+        for (property_index, analysis) in g.prop_analysis.iter().enumerate() {
+            if analysis.is_read_externally {
+                visit_property(
+                    &PropertyReference::Local { sub_component_path: vec![], property_index },
+                    &ctx,
+                );
+            }
+        }
+    }
 
     root.for_each_sub_components(&mut |sc, ctx| {
         // 2. the native items and bindings of used properties
@@ -86,6 +86,7 @@ fn builtin_function_cost(function: BuiltinFunction) -> isize {
         BuiltinFunction::Pow => 10,
         BuiltinFunction::SetFocusItem => isize::MAX,
         BuiltinFunction::ShowPopupWindow => isize::MAX,
+        BuiltinFunction::RegisterMenuBar => isize::MAX,
         BuiltinFunction::StringToFloat => 50,
         BuiltinFunction::StringIsFloat => 50,
         BuiltinFunction::ColorBrighter => 50,
@@ -9,7 +9,7 @@
 
 use itertools::Either;
 
-use crate::diagnostics::{BuildDiagnostics, SourceLocation, Spanned};
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode, SourceLocation, Spanned};
 use crate::expression_tree::{self, BindingExpression, Expression, Unit};
 use crate::langtype::PropertyLookupResult;
 use crate::langtype::{BuiltinElement, NativeClass, Type};
@@ -48,6 +48,9 @@ pub struct Document {
     /// startup for custom font use.
     pub custom_fonts: Vec<(String, crate::parser::SyntaxToken)>,
     pub exports: Exports,
+    /// The other exported windows, each compiled as the main component of its own document.
+    /// Only filled when [`crate::CompilerConfiguration::compile_other_windows`] is set.
+    pub other_windows: Vec<Document>,
 }
 
 impl Document {
@@ -143,14 +146,16 @@ pub fn from_node(
                     {
                         Some((import.file, import.import_token))
                     } else {
-                        diag.push_error(
+                        diag.push_error_with_code(
+                            DiagnosticCode::ImportError,
                             format!("File \"{}\" not found", import.file),
                             &import.import_token,
                         );
                         None
                     }
                 } else {
-                    diag.push_error(
+                    diag.push_error_with_code(
+                        DiagnosticCode::ImportError,
                         format!("Unsupported foreign import \"{}\"", import.file),
                         &import.import_token,
                     );
@@ -167,12 +172,34 @@ pub fn from_node(
             local_registry,
             custom_fonts,
             exports,
+            other_windows: Default::default(),
         }
     }
 
     pub fn exports(&self) -> &Vec<(ExportedName, Type)> {
         &self.exports.0
     }
+
+    /// Returns the names of the exported components that inherit from `Window`, except the
+    /// root component and the windows with a `@children` placeholder, which are meant to be
+    /// inherited from
+    pub fn exported_windows(&self) -> Vec<String> {
+        self.exports()
+            .iter()
+            .filter_map(|(name, ty)| match ty {
+                Type::Component(c)
+                    if !Rc::ptr_eq(c, &self.root_component)
+                        && c.child_insertion_point.borrow().is_none()
+                        && c.root_element.borrow().builtin_type().map_or(false, |b| {
+                            matches!(b.name.as_str(), "Window" | "Dialog" | "WindowItem")
+                        }) =>
+                {
+                    Some(name.name.clone())
+                }
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -479,6 +506,10 @@ pub struct Element {
     /// How many times the element was inlined
     pub inline_depth: i32,
 
+    /// How many instances of sub-components were inlined into this element: the one of the
+    /// component that was inlined, and the ones that were inlined in the root element of that component
+    pub inlined_components: usize,
+
     /// The AST node, if available
     pub node: Option<syntax_nodes::Element>,
 }
@@ -647,7 +678,8 @@ pub fn from_node(
             let base_string = base.to_string();
             match parent_type.lookup_type_for_child_element(&base_string, tr) {
                 Ok(Type::Component(c)) if c.is_global() => {
-                    diag.push_error(
+                    diag.push_error_with_code(
+                        DiagnosticCode::InvalidElement,
                         "Cannot create an instance of a global component".into(),
                         &base_node,
                     );
@@ -655,11 +687,15 @@ pub fn from_node(
                 }
                 Ok(ty @ Type::Component(_)) | Ok(ty @ Type::Builtin(_)) => ty,
                 Ok(ty) => {
-                    diag.push_error(format!("'{}' cannot be used as an element", ty), &base_node);
+                    diag.push_error_with_code(
+                        DiagnosticCode::InvalidElement,
+                        format!("'{}' cannot be used as an element", ty),
+                        &base_node,
+                    );
                     Type::Invalid
                 }
                 Err(err) => {
-                    diag.push_error(err, &base_node);
+                    diag.push_error_with_code(DiagnosticCode::UnknownType, err, &base_node);
                     Type::Invalid
                 }
             }
@@ -672,7 +708,11 @@ pub fn from_node(
 
             // This must be a global component it can only have properties and callback
             let mut error_on = |node: &dyn Spanned, what: &str| {
-                diag.push_error(format!("A global component cannot have {}", what), node);
+                diag.push_error_with_code(
+                    DiagnosticCode::InvalidGlobal,
+                    format!("A global component cannot have {}", what),
+                    node,
+                );
             };
             node.SubElement().for_each(|n| error_on(&n, "sub elements"));
             node.RepeatedElement().for_each(|n| error_on(&n, "sub elements"));
@@ -700,7 +740,8 @@ pub fn from_node(
                 property_type: maybe_existing_prop_type,
             } = r.lookup_property(&unresolved_prop_name);
             if !matches!(maybe_existing_prop_type, Type::Invalid) {
-                diag.push_error(
+                diag.push_error_with_code(
+                    DiagnosticCode::DuplicatedDeclaration,
                     format!("Cannot override property '{}'", prop_name),
                     &prop_decl.DeclaredIdentifier().child_token(SyntaxKind::Identifier).unwrap(),
                 )
@@ -723,7 +764,8 @@ pub fn from_node(
                     )
                     .is_some()
                 {
-                    diag.push_error(
+                    diag.push_error_with_code(
+                        DiagnosticCode::DuplicatedBinding,
                         "Duplicated property binding".into(),
                         &prop_decl.DeclaredIdentifier(),
                     );
@@ -734,7 +776,8 @@ pub fn from_node(
                     .insert(prop_name.into(), BindingExpression::new_uncompiled(csn.into()).into())
                     .is_some()
                 {
-                    diag.push_error(
+                    diag.push_error_with_code(
+                        DiagnosticCode::DuplicatedBinding,
                         "Duplicated property binding".into(),
                         &prop_decl.DeclaredIdentifier(),
                     );
@@ -795,7 +838,8 @@ pub fn from_node(
             if let Type::Callback { args, .. } = &property_type {
                 let num_arg = con_node.DeclaredIdentifier().count();
                 if num_arg > args.len() {
-                    diag.push_error(
+                    diag.push_error_with_code(
+                        DiagnosticCode::InvalidArguments,
                         format!(
                             "'{}' only has {} arguments, but {} were provided",
                             unresolved_name,
@@ -808,7 +852,8 @@ pub fn from_node(
             } else if property_type == Type::InferredCallback {
                 // argument matching will happen later
             } else {
-                diag.push_error(
+                diag.push_error_with_code(
+                    DiagnosticCode::UnknownProperty,
                     format!("'{}' is not a callback in {}", unresolved_name, r.base_type),
                     &con_node.child_token(SyntaxKind::Identifier).unwrap(),
                 );
@@ -821,7 +866,8 @@ pub fn from_node(
                 )
                 .is_some()
             {
-                diag.push_error(
+                diag.push_error_with_code(
+                    DiagnosticCode::DuplicatedBinding,
                     "Duplicated callback".into(),
                     &con_node.child_token(SyntaxKind::Identifier).unwrap(),
                 );
@@ -830,7 +876,8 @@ pub fn from_node(
 
         for anim in node.PropertyAnimation() {
             if let Some(star) = anim.child_token(SyntaxKind::Star) {
-                diag.push_error(
+                diag.push_error_with_code(
+                    DiagnosticCode::InvalidState,
                     "catch-all property is only allowed within transitions".into(),
                     &star,
                 )
@@ -859,6 +906,7 @@ pub fn from_node(
                                 r.bindings.entry(resolved_name.to_string()).or_insert_with(|| {
                                     let mut r = BindingExpression::from(Expression::Invalid);
                                     r.priority = 1;
+                                    r.is_explicit = true;
                                     r.span = Some(prop_name_token.to_source_location());
                                     r.into()
                                 });
@@ -868,11 +916,16 @@ pub fn from_node(
                                 .replace(PropertyAnimation::Static(anim_element))
                                 .is_some()
                             {
-                                diag.push_error("Duplicated animation".into(), &prop_name_token)
+                                diag.push_error_with_code(
+                                    DiagnosticCode::DuplicatedBinding,
+                                    "Duplicated animation".into(),
+                                    &prop_name_token,
+                                )
                             }
                         }
                     }
-                    _ => diag.push_error(
+                    _ => diag.push_error_with_code(
+                        DiagnosticCode::InvalidAnimation,
                         "Can only refer to property in the current element".into(),
                         &prop_name_token,
                     ),
@@ -913,7 +966,8 @@ pub fn from_node(
                 r.borrow_mut().children.push(rep);
             } else if se.kind() == SyntaxKind::ChildrenPlaceholder {
                 if children_placeholder.is_some() {
-                    diag.push_error(
+                    diag.push_error_with_code(
+                        DiagnosticCode::InvalidChildrenPlaceholder,
                         "The @children placeholder can only appear once in an element".into(),
                         &se,
                     )
@@ -925,7 +979,8 @@ pub fn from_node(
 
         if let Some(children_placeholder) = children_placeholder {
             if component_child_insertion_point.is_some() {
-                diag.push_error(
+                diag.push_error_with_code(
+                    DiagnosticCode::InvalidChildrenPlaceholder,
                     "The @children placeholder can only appear once in an element hierarchy".into(),
                     &children_placeholder,
                 )
@@ -951,24 +1006,54 @@ pub fn from_node(
         }
 
         for trs in node.Transitions().flat_map(|s| s.Transition()) {
-            if let Some(star) = trs.child_token(SyntaxKind::Star) {
-                diag.push_error("TODO: catch-all not yet implemented".into(), &star);
-            };
+            let state_id = parser::identifier_text(&trs.DeclaredIdentifier()).unwrap_or_default();
+            let mut property_animations = trs
+                .PropertyAnimation()
+                .flat_map(|pa| pa.QualifiedName().map(move |qn| (pa.clone(), qn)))
+                .filter_map(|(pa, qn)| {
+                    lookup_property_from_qualified_name(qn.clone(), &r, diag).and_then(
+                        |(ne, prop_type)| {
+                            animation_element_from_node(&pa, &qn, prop_type, diag, tr)
+                                .map(|anim_element| (ne, qn.to_source_location(), anim_element))
+                        },
+                    )
+                })
+                .collect::<Vec<_>>();
+            // `animate *` animates the properties changed by the state that are not animated otherwise
+            for pa in trs.PropertyAnimation() {
+                let star = match pa.child_token(SyntaxKind::Star) {
+                    Some(star) => star,
+                    None => continue,
+                };
+                let changed_properties = r
+                    .borrow()
+                    .states
+                    .iter()
+                    .filter(|state| state.id == state_id)
+                    .flat_map(|state| state.property_changes.iter().map(|(ne, ..)| ne.clone()))
+                    .collect::<Vec<_>>();
+                // The bindings of the animation are the same for every property, so only report their errors once
+                let mut bindings_reported = false;
+                for ne in changed_properties {
+                    if property_animations.iter().any(|(animated, ..)| *animated == ne) {
+                        continue;
+                    }
+                    let anim_type = tr.property_animation_type_for_property(ne.ty());
+                    if !matches!(anim_type, Type::Builtin(..)) {
+                        continue;
+                    }
+                    let anim_element = if std::mem::replace(&mut bindings_reported, true) {
+                        new_animation_element(&pa, anim_type, &mut BuildDiagnostics::default())
+                    } else {
+                        new_animation_element(&pa, anim_type, diag)
+                    };
+                    property_animations.push((ne, star.to_source_location(), anim_element));
+                }
+            }
             let trans = Transition {
                 is_out: parser::identifier_text(&trs).unwrap_or_default() == "out",
-                state_id: parser::identifier_text(&trs.DeclaredIdentifier()).unwrap_or_default(),
-                property_animations: trs
-                    .PropertyAnimation()
-                    .flat_map(|pa| pa.QualifiedName().map(move |qn| (pa.clone(), qn)))
-                    .filter_map(|(pa, qn)| {
-                        lookup_property_from_qualified_name(qn.clone(), &r, diag).and_then(
-                            |(ne, prop_type)| {
-                                animation_element_from_node(&pa, &qn, prop_type, diag, tr)
-                                    .map(|anim_element| (ne, qn.to_source_location(), anim_element))
-                            },
-                        )
-                    })
-                    .collect(),
+                state_id,
+                property_animations,
                 node: trs.DeclaredIdentifier().into(),
             };
             r.borrow_mut().transitions.push(trans);
@@ -986,7 +1071,8 @@ fn from_sub_element_node(
     ) -> ElementRc {
         let id = parser::identifier_text(&node).unwrap_or_default();
         if matches!(id.as_ref(), "parent" | "self" | "root") {
-            diag.push_error(
+            diag.push_error_with_code(
+                DiagnosticCode::InvalidId,
                 format!("'{}' is a reserved id", id),
                 &node.child_token(SyntaxKind::Identifier).unwrap(),
             )
@@ -1093,28 +1179,30 @@ fn parse_bindings(
             let PropertyLookupResult { resolved_name, property_type } =
                 self.lookup_property(&unresolved_name);
             if !property_type.is_property_type() {
-                diag.push_error(
-                    match property_type {
-                        Type::Invalid => {
-                            if self.base_type != Type::Invalid {
-                                format!(
-                                    "Unknown property {} in {}",
-                                    unresolved_name, self.base_type
-                                )
-                            } else {
-                                continue;
-                            }
-                        }
-                        Type::Callback { .. } => {
-                            format!("'{}' is a callback. Use `=>` to connect", unresolved_name)
+                let (code, message) = match property_type {
+                    Type::Invalid => {
+                        if self.base_type != Type::Invalid {
+                            (
+                                DiagnosticCode::UnknownProperty,
+                                format!("Unknown property {} in {}", unresolved_name, self.base_type),
+                            )
+                        } else {
+                            continue;
                         }
-                        _ => format!(
+                    }
+                    Type::Callback { .. } => (
+                        DiagnosticCode::InvalidBinding,
+                        format!("'{}' is a callback. Use `=>` to connect", unresolved_name),
+                    ),
+                    _ => (
+                        DiagnosticCode::InvalidBinding,
+                        format!(
                             "Cannot assign to {} in {} because it does not have a valid property type",
                             unresolved_name, self.base_type,
                         ),
-                    },
-                    &name_token,
-                );
+                    ),
+                };
+                diag.push_error_with_code(code, message, &name_token);
             }
 
             if resolved_name != unresolved_name {
@@ -1130,7 +1218,11 @@ fn parse_bindings(
                 .insert(resolved_name.to_string(), BindingExpression::new_uncompiled(b).into())
                 .is_some()
             {
-                diag.push_error("Duplicated property binding".into(), &name_token);
+                diag.push_error_with_code(
+                    DiagnosticCode::DuplicatedBinding,
+                    "Duplicated property binding".into(),
+                    &name_token,
+                );
             }
         }
     }
@@ -1178,6 +1270,18 @@ pub fn original_name(&self) -> String {
             .unwrap_or_else(|| self.id.clone())
     }
 
+    /// Returns the id of the element as written in the .slint file, or an empty string if the
+    /// element has no id. Unlike [`Self::id`], it is not changed by the passes.
+    pub fn original_id(&self) -> String {
+        self.node
+            .as_ref()
+            .and_then(|n| n.parent())
+            .filter(|parent| parent.kind() == SyntaxKind::SubElement)
+            .and_then(|parent| parent.child_token(SyntaxKind::Identifier))
+            .map(|id| id.to_string())
+            .unwrap_or_default()
+    }
+
     /// Return true if the binding is set, either on this element or in a base
     ///
     /// If `need_explicit` is true, then only consider binding set in the code, not the ones set
@@ -1258,9 +1362,17 @@ pub fn type_from_node(
         let prop_type = tr.lookup_qualified(&qualified_type.members);
 
         if prop_type == Type::Invalid {
-            diag.push_error(format!("Unknown type '{}'", qualified_type), &qualified_type_node);
+            diag.push_error_with_code(
+                DiagnosticCode::UnknownType,
+                format!("Unknown type '{}'", qualified_type),
+                &qualified_type_node,
+            );
         } else if !prop_type.is_property_type() {
-            diag.push_error(format!("'{}' is not a valid type", prop_type), &qualified_type_node);
+            diag.push_error_with_code(
+                DiagnosticCode::InvalidType,
+                format!("'{}' is not a valid type", prop_type),
+                &qualified_type_node,
+            );
         }
         prop_type
     } else if let Some(object_node) = node.ObjectType() {
@@ -1300,7 +1412,8 @@ fn animation_element_from_node(
 ) -> Option<ElementRc> {
     let anim_type = tr.property_animation_type_for_property(prop_type);
     if !matches!(anim_type, Type::Builtin(..)) {
-        diag.push_error(
+        diag.push_error_with_code(
+            DiagnosticCode::InvalidAnimation,
             format!(
                 "'{}' is not a property that can be animated",
                 prop_name.text().to_string().trim()
@@ -1309,19 +1422,28 @@ fn animation_element_from_node(
         );
         None
     } else {
-        let mut anim_element =
-            Element { id: "".into(), base_type: anim_type, node: None, ..Default::default() };
-        anim_element.parse_bindings(
-            anim.Binding().filter_map(|b| {
-                Some((b.child_token(SyntaxKind::Identifier)?, b.BindingExpression().into()))
-            }),
-            diag,
-        );
+        Some(new_animation_element(anim, anim_type, diag))
+    }
+}
+
+/// Create the element of type `anim_type` with the bindings of the `animate` node
+fn new_animation_element(
+    anim: &syntax_nodes::PropertyAnimation,
+    anim_type: Type,
+    diag: &mut BuildDiagnostics,
+) -> ElementRc {
+    let mut anim_element =
+        Element { id: "".into(), base_type: anim_type, node: None, ..Default::default() };
+    anim_element.parse_bindings(
+        anim.Binding().filter_map(|b| {
+            Some((b.child_token(SyntaxKind::Identifier)?, b.BindingExpression().into()))
+        }),
+        diag,
+    );
 
-        apply_default_type_properties(&mut anim_element);
+    apply_default_type_properties(&mut anim_element);
 
-        Some(Rc::new(RefCell::new(anim_element)))
-    }
+    Rc::new(RefCell::new(anim_element))
 }
 
 #[derive(Default, Debug, Clone)]
@@ -1359,7 +1481,11 @@ fn lookup_property_from_qualified_name(
             let PropertyLookupResult { resolved_name, property_type } =
                 r.borrow().lookup_property(unresolved_prop_name.as_ref());
             if !property_type.is_property_type() {
-                diag.push_error(format!("'{}' is not a valid property", qualname), &node);
+                diag.push_error_with_code(
+                    DiagnosticCode::UnknownProperty,
+                    format!("'{}' is not a valid property", qualname),
+                    &node,
+                );
             }
             Some((NamedReference::new(r, &resolved_name), property_type))
         }
@@ -1368,19 +1494,28 @@ fn lookup_property_from_qualified_name(
                 let PropertyLookupResult { resolved_name, property_type } =
                     element.borrow().lookup_property(unresolved_prop_name.as_ref());
                 if !property_type.is_property_type() {
-                    diag.push_error(
+                    diag.push_error_with_code(
+                        DiagnosticCode::UnknownProperty,
                         format!("'{}' not found in '{}'", unresolved_prop_name, elem_id),
                         &node,
                     );
                 }
                 Some((NamedReference::new(&element, &resolved_name), property_type))
             } else {
-                diag.push_error(format!("'{}' is not a valid element id", elem_id), &node);
+                diag.push_error_with_code(
+                    DiagnosticCode::UnknownIdentifier,
+                    format!("'{}' is not a valid element id", elem_id),
+                    &node,
+                );
                 None
             }
         }
         _ => {
-            diag.push_error(format!("'{}' is not a valid property", qualname), &node);
+            diag.push_error_with_code(
+                DiagnosticCode::UnknownProperty,
+                format!("'{}' is not a valid property", qualname),
+                &node,
+            );
             None
         }
     }
@@ -1824,7 +1959,7 @@ struct SeenExport {
             .collect();
 
         for (message, location) in export_diagnostics {
-            diag.push_error(message, &location);
+            diag.push_error_with_code(DiagnosticCode::DuplicatedExport, message, &location);
         }
 
         if exports.is_empty() {
@@ -1842,14 +1977,16 @@ struct SeenExport {
             |export: &NamedExport| match type_registry.lookup(export.internal_name.as_str()) {
                 ty @ Type::Component(_) | ty @ Type::Struct { .. } => Some(ty),
                 Type::Invalid => {
-                    diag.push_error(
+                    diag.push_error_with_code(
+                        DiagnosticCode::UnknownIdentifier,
                         format!("'{}' not found", export.internal_name),
                         &export.internal_name_ident,
                     );
                     None
                 }
                 _ => {
-                    diag.push_error(
+                    diag.push_error_with_code(
+                        DiagnosticCode::InvalidExport,
                         format!(
                             "Cannot export '{}' because it is not a component",
                             export.internal_name,
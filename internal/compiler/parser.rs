@@ -644,12 +644,15 @@ fn error(&mut self, e: impl Into<String>) {
             span.span = current_token.span;
         }
 
-        self.diags.push_error_with_span(
+        self.diags.push_coded_diagnostic(
+            crate::diagnostics::DiagnosticCode::SyntaxError,
             e.into(),
             crate::diagnostics::SourceLocation {
                 source_file: Some(self.source_file.clone()),
                 span,
             },
+            crate::diagnostics::DiagnosticLevel::Error,
+            None,
         );
     }
 
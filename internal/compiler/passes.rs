@@ -7,6 +7,7 @@
 mod check_expressions;
 mod check_public_api;
 mod check_rotation;
+mod check_unused;
 mod clip;
 mod collect_custom_fonts;
 mod collect_globals;
@@ -26,6 +27,7 @@
 mod inlining;
 mod lower_accessibility;
 mod lower_layout;
+mod lower_menus;
 mod lower_popups;
 mod lower_property_to_element;
 mod lower_shadows;
@@ -81,6 +83,16 @@ pub async fn run_passes(
     check_rotation::check_rotation(doc, diag);
     unique_id::check_unique_id(doc, diag);
     check_public_api::check_public_api(doc, diag);
+    check_unused::check_unused(doc, true, diag);
+
+    // The windows compiled from the same file share their globals, so every compilation must
+    // keep all of them, with the same properties
+    let shared_globals = (compiler_config.compile_other_windows
+        && !doc.exported_windows().is_empty())
+    .then(|| collect_globals::collect_shared_globals(doc, &style_metrics));
+    if let Some(shared_globals) = &shared_globals {
+        collect_globals::mark_shared_globals(shared_globals);
+    }
 
     collect_subcomponents::collect_subcomponents(root_component);
     for component in (root_component.used_types.borrow().sub_components.iter())
@@ -88,6 +100,7 @@ pub async fn run_passes(
     {
         compile_paths::compile_paths(component, &doc.local_registry, diag);
         lower_tabwidget::lower_tabwidget(component, type_loader, diag).await;
+        lower_menus::lower_menus(component, type_loader, diag).await;
         apply_default_properties_from_style::apply_default_properties_from_style(
             component,
             &style_metrics,
@@ -104,6 +117,7 @@ pub async fn run_passes(
         root_component,
         compiler_config.embed_resources,
         compiler_config.scale_factor,
+        compiler_config.compress_textures,
         diag,
     );
 
@@ -179,12 +193,21 @@ pub async fn run_passes(
         materialize_fake_properties::materialize_fake_properties(component);
     }
     collect_globals::collect_globals(doc, diag);
+    if let Some(shared_globals) = &shared_globals {
+        collect_globals::add_shared_globals(doc, shared_globals);
+    }
 
     if compiler_config.inline_all_elements {
         inlining::inline(doc, inlining::InlineSelection::InlineAllComponents);
         root_component.used_types.borrow_mut().sub_components.clear();
     }
 
+    for component in (root_component.used_types.borrow().sub_components.iter())
+        .chain(std::iter::once(root_component))
+    {
+        lower_menus::register_menu_bars(component);
+    }
+
     binding_analysis::binding_analysis(doc, diag);
     unique_id::assign_unique_id(doc);
 
@@ -218,6 +241,9 @@ pub async fn run_passes(
 
     // collect globals once more: After optimizations we might have less globals
     collect_globals::collect_globals(doc, diag);
+    if let Some(shared_globals) = &shared_globals {
+        collect_globals::add_shared_globals(doc, shared_globals);
+    }
 
     if compiler_config.embed_resources == crate::EmbedResourcesKind::EmbedTextures {
         let mut characters_seen = HashSet::new();
@@ -266,4 +292,5 @@ pub fn run_import_passes(
     check_expressions::check_expressions(doc, diag);
     check_rotation::check_rotation(doc, diag);
     unique_id::check_unique_id(doc, diag);
+    check_unused::check_unused(doc, false, diag);
 }
@@ -9,8 +9,8 @@
 
 use by_address::ByAddress;
 
-use crate::diagnostics::BuildDiagnostics;
 use crate::diagnostics::Spanned;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode, DiagnosticNote, SourceLocation};
 use crate::expression_tree::BindingExpression;
 use crate::expression_tree::BuiltinFunction;
 use crate::expression_tree::Expression;
@@ -110,6 +110,9 @@ fn from(prop: NamedReference) -> Self {
 struct AnalysisContext {
     visited: HashSet<PropertyPath>,
     currently_analyzing: linked_hash_set::LinkedHashSet<PropertyPath>,
+    /// The binding loops found so far, each in the order of the dependencies.
+    /// They are reported when the whole component was analyzed.
+    binding_loops: Vec<Vec<PropertyPath>>,
 }
 
 fn perform_binding_analysis(
@@ -127,6 +130,7 @@ fn perform_binding_analysis(
         &(),
         &mut |e, _| analyze_element(e, &mut context, reverse_aliases, diag),
     );
+    report_binding_loops(context.binding_loops, diag);
 }
 
 fn analyze_element(
@@ -171,30 +175,31 @@ fn analyse_binding(
             .span
             .clone()
             .or_else(|| element.borrow().node.as_ref().map(|n| n.to_source_location()));
-        diag.push_error(format!("Property '{name}' cannot refer to itself"), &span);
+        diag.push_error_with_code(
+            DiagnosticCode::BindingLoop,
+            format!("Property '{name}' cannot refer to itself"),
+            &span,
+        );
         return depends_on_external;
     }
 
     if context.currently_analyzing.contains(current) {
-        for it in context.currently_analyzing.iter().rev() {
-            let p = &it.prop;
-            let elem = p.element();
+        let binding_loop = context
+            .currently_analyzing
+            .iter()
+            .skip_while(|it| *it != current)
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut has_new_binding = false;
+        for it in &binding_loop {
+            let elem = it.prop.element();
             let elem = elem.borrow();
-            let binding = elem.bindings[p.name()].borrow();
-            if binding.analysis.as_ref().unwrap().is_in_binding_loop.replace(true) {
-                break;
-            }
-
-            let span =
-                binding.span.clone().or_else(|| elem.node.as_ref().map(|n| n.to_source_location()));
-            diag.push_error(
-                format!("The binding for the property '{}' is part of a binding loop", p.name()),
-                &span,
-            );
-
-            if it == current {
-                break;
-            }
+            let binding = elem.bindings[it.prop.name()].borrow();
+            has_new_binding |= !binding.analysis.as_ref().unwrap().is_in_binding_loop.replace(true);
+        }
+        // Otherwise, it was already reported as part of another loop
+        if has_new_binding {
+            context.binding_loops.push(binding_loop);
         }
         return depends_on_external;
     }
@@ -259,6 +264,172 @@ fn analyse_binding(
     depends_on_external
 }
 
+/// One binding of a binding loop
+struct BindingLoopStep {
+    prop: NamedReference,
+    /// The property that the binding depends on, which is the next step of the loop
+    next: NamedReference,
+    location: Option<SourceLocation>,
+    is_two_way: bool,
+    /// When the binding was not written in the source code, describes what introduced it
+    introduced_by: Option<String>,
+}
+
+impl BindingLoopStep {
+    fn new(prop: &NamedReference, next: &NamedReference) -> Self {
+        let elem = prop.element();
+        let elem = elem.borrow();
+        let binding = elem.bindings[prop.name()].borrow();
+        let location =
+            binding.span.clone().or_else(|| elem.node.as_ref().map(|n| n.to_source_location()));
+        let is_two_way = binding.two_way_bindings.contains(next)
+            || next
+                .element()
+                .borrow()
+                .bindings
+                .get(next.name())
+                .map_or(false, |b| b.borrow().two_way_bindings.contains(prop));
+        let introduced_by = match &binding.expression {
+            Expression::LayoutCacheAccess { layout_cache_prop, .. } => {
+                Some(format!("the layout '{}'", element_name(&layout_cache_prop.element())))
+            }
+            Expression::SolveLayout(..) | Expression::ComputeLayoutInfo(..) => {
+                Some(format!("the layout '{}'", element_name(&prop.element())))
+            }
+            _ if !binding.is_explicit => {
+                Some(format!("the element '{}'", element_name(&prop.element())))
+            }
+            _ => None,
+        };
+        Self { prop: prop.clone(), next: next.clone(), location, is_two_way, introduced_by }
+    }
+
+    fn note(&self) -> Option<DiagnosticNote> {
+        let mut message = if self.is_two_way {
+            format!(
+                "'{}' is bound two-way to '{}'",
+                display_name(&self.prop),
+                display_name(&self.next)
+            )
+        } else {
+            format!("'{}' depends on '{}'", display_name(&self.prop), display_name(&self.next))
+        };
+        if let Some(introduced_by) = &self.introduced_by {
+            message += &format!(" (implicit binding introduced by {})", introduced_by);
+        }
+        Some(DiagnosticNote::new(message, self.location.clone()?))
+    }
+}
+
+/// The id of the element, or the name of its type for elements without id
+fn element_name(elem: &ElementRc) -> String {
+    let elem = elem.borrow();
+    if !elem.id.is_empty() {
+        return elem.id.clone();
+    }
+    elem.node
+        .as_ref()
+        .and_then(|n| n.QualifiedName())
+        .map_or_else(|| elem.base_type.to_string(), |q| q.text().to_string().trim().to_owned())
+}
+
+/// The name of the property in the diagnostics. The properties created by the passes for the
+/// layouts are shown as what they compute for their element, since they can't be named in .slint
+fn display_name(prop: &NamedReference) -> String {
+    let element = prop.element();
+    let ty = element.borrow().lookup_property(prop.name()).property_type;
+    let name = prop.name();
+    let description = if ty == crate::layout::layout_info_type() {
+        if name.starts_with("layoutinfo-v") {
+            "height constraints"
+        } else {
+            "width constraints"
+        }
+    } else if ty == Type::LayoutCache {
+        if name.starts_with("layout-cache-h") {
+            "horizontal geometry of the children"
+        } else if name.starts_with("layout-cache-v") {
+            "vertical geometry of the children"
+        } else {
+            "geometry of the children"
+        }
+    } else {
+        return format!("{}.{}", element_name(&element), name);
+    };
+    format!("{} ({})", element_name(&element), description)
+}
+
+/// Report one error for each set of intertwined binding loops, with a note for each binding
+/// involved. The error is placed on the first binding, in source order, that was written
+/// explicitly, as this is where the loop can be broken.
+fn report_binding_loops(binding_loops: Vec<Vec<PropertyPath>>, diag: &mut BuildDiagnostics) {
+    // Merge the loops that have a binding in common
+    let mut groups: Vec<(HashSet<NamedReference>, Vec<Vec<PropertyPath>>)> = vec![];
+    for binding_loop in binding_loops {
+        let mut props = binding_loop.iter().map(|p| p.prop.clone()).collect::<HashSet<_>>();
+        let mut loops = vec![binding_loop];
+        let mut i = 0;
+        while i < groups.len() {
+            if groups[i].0.is_disjoint(&props) {
+                i += 1;
+            } else {
+                let (other_props, other_loops) = groups.remove(i);
+                props.extend(other_props);
+                loops.splice(0..0, other_loops);
+            }
+        }
+        groups.push((props, loops));
+    }
+
+    for (_, loops) in groups {
+        let mut steps: Vec<BindingLoopStep> = vec![];
+        for binding_loop in &loops {
+            for (i, p) in binding_loop.iter().enumerate() {
+                if steps.iter().all(|s| s.prop != p.prop) {
+                    let next = &binding_loop[(i + 1) % binding_loop.len()].prop;
+                    steps.push(BindingLoopStep::new(&p.prop, next));
+                }
+            }
+        }
+        let source_order = |s: &&BindingLoopStep| {
+            s.location.as_ref().map_or((true, None, usize::MAX), |l| {
+                (false, l.source_file.as_ref().map(|f| f.path().to_owned()), l.span.offset)
+            })
+        };
+        let primary = steps
+            .iter()
+            .filter(|s| s.introduced_by.is_none())
+            .min_by_key(source_order)
+            .or_else(|| steps.iter().min_by_key(source_order))
+            .unwrap();
+
+        // Show the loop that goes through the primary binding, starting with it
+        let binding_loop = loops.iter().find(|l| l.iter().any(|p| p.prop == primary.prop)).unwrap();
+        let start = binding_loop.iter().position(|p| p.prop == primary.prop).unwrap();
+        let cycle = binding_loop[start..]
+            .iter()
+            .chain(&binding_loop[..=start])
+            .map(|p| display_name(&p.prop))
+            .collect::<Vec<_>>()
+            .join(" -> ");
+
+        let mut message = format!(
+            "The binding for the property '{}' is part of a binding loop: {}",
+            primary.prop.name(),
+            cycle
+        );
+        if primary.introduced_by.is_none() {
+            message += ". Change or remove one of the explicit bindings in the loop, such as this one, to break it";
+        }
+        diag.push_error_with_notes(
+            DiagnosticCode::BindingLoop,
+            message,
+            primary.location.clone().unwrap_or_default(),
+            steps.iter().filter_map(BindingLoopStep::note).collect(),
+        );
+    }
+}
+
 /// Process the property `prop`
 ///
 /// This will visit all the bindings from that property
@@ -403,6 +574,8 @@ fn visit_implicit_layout_info_dependencies(
             vis(&NamedReference::new(item, "font-size"));
             vis(&NamedReference::new(item, "font-weight"));
             vis(&NamedReference::new(item, "letter-spacing"));
+            vis(&NamedReference::new(item, "font-italic"));
+            vis(&NamedReference::new(item, "line-height"));
             vis(&NamedReference::new(item, "wrap"));
             let wrap_set = item.borrow().is_binding_set("wrap", false)
                 || item
@@ -418,6 +591,7 @@ fn visit_implicit_layout_info_dependencies(
                 vis(&NamedReference::new(item, "single-line"));
             } else {
                 vis(&NamedReference::new(item, "overflow"));
+                vis(&NamedReference::new(item, "text-format"));
             }
         }
 
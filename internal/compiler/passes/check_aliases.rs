@@ -3,7 +3,7 @@
 
 //! Verify that aliases have proper default values
 
-use crate::diagnostics::BuildDiagnostics;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode};
 use crate::expression_tree::Expression;
 use crate::langtype::Type;
 use crate::object_tree::{Component, ElementRc};
@@ -25,7 +25,8 @@ pub fn check_aliases(component: &Rc<Component>, diag: &mut BuildDiagnostics) {
                     if explicit_binding_priority(&nr.element(), nr.name())
                         .map_or(true, |rhs_prio| rhs_prio > lhs_prio.saturating_add(1))
                     {
-                        diag.push_warning(
+                        diag.push_warning_with_code(
+                        DiagnosticCode::TwoWayBindingDefault,
                         format!(
 r#"Two way binding between the property '{prop}' with a default value to the property '{nr:?}' without value.
 The current behavior is to keep the value from the left-hand-side, but this behavior will change in the next version to always keep the right-hand-side value.
@@ -3,7 +3,7 @@
 
 use std::rc::Rc;
 
-use crate::diagnostics::BuildDiagnostics;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode};
 use crate::expression_tree::{BuiltinFunction, Expression};
 use crate::object_tree::{visit_all_expressions, Component};
 
@@ -23,11 +23,15 @@ fn check_expression(component: &Rc<Component>, e: &Expression, diag: &mut BuildD
             debug_assert!(diag.has_error());
         }
         Expression::BuiltinMacroReference(_, node) => {
-            diag.push_error("Builtin function must be called".into(), node);
+            diag.push_error_with_code(
+                DiagnosticCode::UncalledFunction,
+                "Builtin function must be called".into(),
+                node,
+            );
         }
         Expression::BuiltinFunctionReference(BuiltinFunction::GetWindowScaleFactor, loc) => {
             if component.is_global() {
-                diag.push_error("Cannot convert between logical and physical length in a global component, because the scale factor is not known".into(), loc);
+                diag.push_error_with_code(DiagnosticCode::InvalidGlobal, "Cannot convert between logical and physical length in a global component, because the scale factor is not known".into(), loc);
             }
         }
         _ => e.visit(|e| check_expression(component, e, diag)),
@@ -5,7 +5,7 @@
 
 use std::rc::Rc;
 
-use crate::diagnostics::{BuildDiagnostics, DiagnosticLevel};
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode};
 use crate::langtype::Type;
 use crate::object_tree::{Component, Document};
 
@@ -31,10 +31,10 @@ fn check_public_api_component(root_component: &Rc<Component>, diag: &mut BuildDi
             d.expose_in_public_api = true;
             pa.entry(n.to_string()).or_default().is_set = true;
         } else {
-            diag.push_diagnostic(
+            diag.push_warning_with_code(
+                 DiagnosticCode::UnsupportedPublicType,
                  format!("Properties of type {} are not supported yet for public API. The property will not be exposed", d.property_type),
                  &d.type_node(),
-                 DiagnosticLevel::Warning
             );
         }
     });
@@ -1,8 +1,8 @@
 // Copyright © SixtyFPS GmbH <info@slint-ui.com>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
-use crate::diagnostics::BuildDiagnostics;
 use crate::diagnostics::Spanned;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode};
 use crate::langtype::Type;
 use crate::object_tree::Element;
 
@@ -22,14 +22,16 @@ pub fn check_rotation(doc: &crate::object_tree::Document, diag: &mut BuildDiagno
                         .and_then(|e| e.borrow().span.clone())
                         .unwrap_or_else(|| e.to_source_location());
 
-                    diag.push_error_with_span(
+                    diag.push_error_with_code(
+                        DiagnosticCode::UnsupportedProperty,
                         "rotation properties can only be applied to the Image element".into(),
-                        span,
+                        &span,
                     );
                 } else if has_any_children(&*e) {
-                    diag.push_error_with_span(
+                    diag.push_error_with_code(
+                        DiagnosticCode::UnsupportedProperty,
                         "Elements with rotation properties cannot have children elements".into(),
-                        e.to_source_location(),
+                        &e.to_source_location(),
                     );
                 }
             }
@@ -0,0 +1,307 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*! Opt-in lints that warn about what is declared but never used in a file:
+properties, callbacks, element ids, imports and components.
+
+They are enabled per file with a comment such as `// slint-lint: warn(unused)`, or with a list of
+the individual lints, named after their diagnostic code: `// slint-lint: warn(unused-property, unused-id)`.
+`allow(...)` disables them again. The comments are applied in the order of the file.
+
+This must run after the expressions were resolved, and before the inlining.
+*/
+
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode};
+use crate::langtype::Type;
+use crate::object_tree::{recurse_elem, Component, Document, ElementRc};
+use crate::parser::{normalize_identifier, syntax_nodes, SyntaxKind, SyntaxNode, SyntaxToken};
+use itertools::Either;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+const PRAGMA: &str = "slint-lint";
+
+/// The lints of this pass, which are enabled together with `unused`
+const LINTS: &[DiagnosticCode] = &[
+    DiagnosticCode::UnusedProperty,
+    DiagnosticCode::UnusedCallback,
+    DiagnosticCode::UnusedId,
+    DiagnosticCode::UnusedImport,
+    DiagnosticCode::UnusedComponent,
+];
+
+/// `is_main_document` is true for the document being compiled, in which the exported components
+/// other than the last one are only used if they are instantiated within that document.
+pub fn check_unused(doc: &Document, is_main_document: bool, diag: &mut BuildDiagnostics) {
+    let node = match &doc.node {
+        Some(node) => node,
+        None => return,
+    };
+    let enabled = enabled_lints(node, diag);
+    if enabled.is_empty() {
+        return;
+    }
+
+    let exported = doc
+        .exports()
+        .iter()
+        .filter_map(|(_, ty)| match ty {
+            Type::Component(c) => Some(Rc::as_ptr(c)),
+            _ => None,
+        })
+        .collect::<HashSet<_>>();
+
+    // The elements of each component of this document, and the instances of each component
+    let mut elements = vec![];
+    let mut instances = HashMap::<*const Component, Vec<ElementRc>>::new();
+    for component in &doc.inner_components {
+        let mut component_elements = vec![];
+        recurse_elem(&component.root_element, &(), &mut |elem, _| {
+            if let Type::Component(base) = &elem.borrow().base_type {
+                instances.entry(Rc::as_ptr(base)).or_default().push(elem.clone());
+            }
+            component_elements.push(elem.clone());
+        });
+        elements.push(component_elements);
+    }
+
+    for (component, component_elements) in doc.inner_components.iter().zip(&elements) {
+        let is_exported = exported.contains(&Rc::as_ptr(component));
+
+        if enabled.contains(&DiagnosticCode::UnusedComponent)
+            && !component.is_global()
+            && !Rc::ptr_eq(component, &doc.root_component)
+            && (!is_exported || is_main_document)
+            && !instances.contains_key(&Rc::as_ptr(component))
+        {
+            diag.push_warning_with_code(
+                DiagnosticCode::UnusedComponent,
+                format!("The component '{}' is never instantiated", component.id),
+                &component_identifier(component),
+            );
+        }
+
+        for elem in component_elements {
+            // The properties of the root element of exported components are their interface
+            if is_exported && Rc::ptr_eq(elem, &component.root_element) {
+                continue;
+            }
+            check_unused_properties(elem, &instances, &enabled, diag);
+        }
+
+        if enabled.contains(&DiagnosticCode::UnusedId) {
+            check_unused_ids(component, component_elements, diag);
+        }
+    }
+
+    if enabled.contains(&DiagnosticCode::UnusedImport) {
+        check_unused_imports(node, diag);
+    }
+}
+
+/// Parses the `slint-lint` comments of the document, and returns the lints that are enabled
+fn enabled_lints(
+    node: &syntax_nodes::Document,
+    diag: &mut BuildDiagnostics,
+) -> HashSet<DiagnosticCode> {
+    let mut enabled = HashSet::new();
+    let comments = node
+        .descendants_with_tokens()
+        .filter_map(|t| t.into_token())
+        .filter(|t| t.kind() == SyntaxKind::Comment);
+    for comment in comments {
+        let pragma = match comment
+            .text()
+            .strip_prefix("//")
+            .and_then(|c| c.trim().strip_prefix(PRAGMA))
+            .and_then(|c| c.trim_start().strip_prefix(':'))
+        {
+            Some(pragma) => pragma.trim(),
+            None => continue,
+        };
+        let token = SyntaxToken { token: comment.clone(), source_file: node.source_file.clone() };
+        let (enable, list) =
+            match pragma.split_once('(').map(|(kind, list)| (kind.trim_end(), list)) {
+                Some(("warn", list)) => (true, list),
+                Some(("allow", list)) => (false, list),
+                _ => {
+                    diag.push_warning_with_code(
+                        DiagnosticCode::InvalidPragma,
+                        format!("Invalid {} comment, expected 'warn(...)' or 'allow(...)'", PRAGMA),
+                        &token,
+                    );
+                    continue;
+                }
+            };
+        let list = match list.trim_end().strip_suffix(')') {
+            Some(list) => list,
+            None => {
+                diag.push_warning_with_code(
+                    DiagnosticCode::InvalidPragma,
+                    format!("Missing ')' in the {} comment", PRAGMA),
+                    &token,
+                );
+                continue;
+            }
+        };
+        for name in list.split(',').map(str::trim) {
+            let codes = if name == "unused" {
+                LINTS
+            } else if let Some(code) = LINTS.iter().find(|c| c.as_str() == name) {
+                std::slice::from_ref(code)
+            } else {
+                diag.push_warning_with_code(
+                    DiagnosticCode::InvalidPragma,
+                    format!("Unknown lint '{}'", name),
+                    &token,
+                );
+                continue;
+            };
+            for code in codes {
+                if enable {
+                    enabled.insert(*code);
+                } else {
+                    enabled.remove(code);
+                }
+            }
+        }
+    }
+    enabled
+}
+
+fn check_unused_properties(
+    elem: &ElementRc,
+    instances: &HashMap<*const Component, Vec<ElementRc>>,
+    enabled: &HashSet<DiagnosticCode>,
+    diag: &mut BuildDiagnostics,
+) {
+    for (name, decl) in &elem.borrow().property_declarations {
+        let node = match &decl.node {
+            Some(node) if !decl.expose_in_public_api => node,
+            _ => continue,
+        };
+        let (code, message, identifier) = match node {
+            Either::Left(property) => (
+                DiagnosticCode::UnusedProperty,
+                format!("The property '{}' is never read", name),
+                property.DeclaredIdentifier(),
+            ),
+            Either::Right(callback) => (
+                DiagnosticCode::UnusedCallback,
+                format!("The callback '{}' is never invoked", name),
+                callback.DeclaredIdentifier(),
+            ),
+        };
+        if enabled.contains(&code) && !is_property_used(elem, name, instances) {
+            diag.push_warning_with_code(
+                code,
+                message,
+                &identifier.child_token(SyntaxKind::Identifier),
+            );
+        }
+    }
+}
+
+/// Returns true if there is a reference to the property, either on that element, or on an
+/// instance of the component if the element is the root of a component.
+fn is_property_used(
+    elem: &ElementRc,
+    name: &str,
+    instances: &HashMap<*const Component, Vec<ElementRc>>,
+) -> bool {
+    if elem.borrow().named_references.is_referenced(name) {
+        return true;
+    }
+    let component = match elem.borrow().enclosing_component.upgrade() {
+        Some(component) if Rc::ptr_eq(&component.root_element, elem) => component,
+        _ => return false,
+    };
+    instances
+        .get(&Rc::as_ptr(&component))
+        .map_or(false, |elements| elements.iter().any(|i| is_property_used(i, name, instances)))
+}
+
+/// The ids are looked up by name, so an id is used if its name appears as an identifier
+/// anywhere in the component, other than in the declaration of an id.
+fn check_unused_ids(component: &Component, elements: &[ElementRc], diag: &mut BuildDiagnostics) {
+    let component_node = match &component.root_element.borrow().node {
+        Some(node) => node.parent().unwrap_or_else(|| SyntaxNode::clone(node)),
+        None => return,
+    };
+    let used_identifiers = identifiers(&component_node, |token| {
+        token.parent().map_or(false, |p| p.kind() == SyntaxKind::SubElement)
+    });
+
+    for elem in elements {
+        let id_token = match elem
+            .borrow()
+            .node
+            .as_ref()
+            .and_then(|n| n.parent())
+            .filter(|parent| parent.kind() == SyntaxKind::SubElement)
+            .and_then(|parent| parent.child_token(SyntaxKind::Identifier))
+        {
+            Some(id_token) => id_token,
+            None => continue,
+        };
+        if !used_identifiers.contains(&normalize_identifier(id_token.text())) {
+            diag.push_warning_with_code(
+                DiagnosticCode::UnusedId,
+                format!("The id '{}' is never used", id_token.text()),
+                &id_token,
+            );
+        }
+    }
+}
+
+fn check_unused_imports(node: &syntax_nodes::Document, diag: &mut BuildDiagnostics) {
+    let used_identifiers = identifiers(node, |token| {
+        token.parent_ancestors().any(|a| a.kind() == SyntaxKind::ImportSpecifier)
+    });
+
+    for import in node.ImportSpecifier() {
+        for identifier in
+            import.ImportIdentifierList().into_iter().flat_map(|l| l.ImportIdentifier())
+        {
+            let name_node = identifier
+                .InternalName()
+                .map_or_else(|| SyntaxNode::clone(&identifier.ExternalName()), |n| (*n).clone());
+            let name = match name_node.child_token(SyntaxKind::Identifier) {
+                Some(name) => name,
+                None => continue,
+            };
+            if !used_identifiers.contains(&normalize_identifier(name.text())) {
+                diag.push_warning_with_code(
+                    DiagnosticCode::UnusedImport,
+                    format!("'{}' is imported but never used", name.text()),
+                    &name,
+                );
+            }
+        }
+    }
+}
+
+/// The normalized text of all the identifiers within `node`, except the ones for which `skip` returns true
+fn identifiers(
+    node: &SyntaxNode,
+    skip: impl Fn(&rowan::SyntaxToken<crate::parser::Language>) -> bool,
+) -> HashSet<String> {
+    node.descendants_with_tokens()
+        .filter_map(|t| t.into_token())
+        .filter(|t| t.kind() == SyntaxKind::Identifier && !skip(t))
+        .map(|t| normalize_identifier(t.text()))
+        .collect()
+}
+
+/// The name of the component in its declaration, for the diagnostics
+fn component_identifier(component: &Component) -> Option<SyntaxToken> {
+    component
+        .root_element
+        .borrow()
+        .node
+        .as_ref()
+        .and_then(|n| n.parent())
+        .filter(|parent| parent.kind() == SyntaxKind::Component)
+        .and_then(|parent| parent.child_node(SyntaxKind::DeclaredIdentifier))
+        .and_then(|identifier| identifier.child_token(SyntaxKind::Identifier))
+}
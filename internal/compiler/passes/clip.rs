@@ -6,7 +6,7 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use crate::diagnostics::{BuildDiagnostics, Spanned};
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode, Spanned};
 use crate::expression_tree::{BindingExpression, Expression, NamedReference};
 use crate::langtype::{NativeClass, Type};
 use crate::object_tree::{Component, Element, ElementRc};
@@ -37,7 +37,8 @@ pub fn handle_clip(
                         return;
                     }
                     _ => {
-                        diag.push_error(
+                        diag.push_error_with_code(
+                            DiagnosticCode::UnsupportedProperty,
                             "The 'clip' property can only be applied to a Rectangle or a Path for now".into(),
                             &elem.bindings.get("clip").and_then(|x| x.borrow().span.clone()).or_else(|| elem.node.as_ref().map(|e| e.to_source_location())),
                         );
@@ -51,3 +51,76 @@ fn collect_in_component(
         visit_all_named_references(component, &mut maybe_collect_global);
     }
 }
+
+/// Return the globals used by the root component, the other exported components of the
+/// document, and the style metrics. These are the globals that are shared by the windows of
+/// the file when they are compiled together.
+pub fn collect_shared_globals(doc: &Document, style_metrics: &Rc<Component>) -> Vec<Rc<Component>> {
+    let mut visited = HashSet::new();
+    let mut set = HashSet::new();
+    let mut sorted_globals = vec![];
+    let exported_components = doc.exports().iter().filter_map(|(_, ty)| match ty {
+        Type::Component(c) => Some(c.clone()),
+        _ => None,
+    });
+    for component in
+        [doc.root_component.clone(), style_metrics.clone()].into_iter().chain(exported_components)
+    {
+        collect_recursively(&component, &mut visited, &mut set, &mut sorted_globals);
+    }
+    sorted_globals
+}
+
+/// Mark all the properties of the shared globals as set and read from outside, so that every
+/// compilation of the windows of the file keeps the same properties in them.
+pub fn mark_shared_globals(globals: &[Rc<Component>]) {
+    for global in globals {
+        let root_elem = global.root_element.borrow();
+        let mut pa = root_elem.property_analysis.borrow_mut();
+        for name in root_elem.property_declarations.keys() {
+            let analysis = pa.entry(name.clone()).or_default();
+            analysis.is_set_externally = true;
+            analysis.is_read_externally = true;
+        }
+    }
+}
+
+/// Add the shared globals that are not used by the root component to its used_types.globals
+pub fn add_shared_globals(doc: &Document, globals: &[Rc<Component>]) {
+    let mut used_types = doc.root_component.used_types.borrow_mut();
+    for global in globals {
+        if !used_types.globals.iter().any(|g| Rc::ptr_eq(g, global)) {
+            used_types.globals.push(global.clone());
+        }
+    }
+}
+
+/// Same as collect_in_component, but also look in the components used by the elements,
+/// since the used_types.sub_components are only known for the root component.
+fn collect_recursively(
+    component: &Rc<Component>,
+    visited: &mut HashSet<ByAddress<Rc<Component>>>,
+    global_set: &mut HashSet<ByAddress<Rc<Component>>>,
+    sorted_globals: &mut Vec<Rc<Component>>,
+) {
+    if !visited.insert(ByAddress(component.clone())) {
+        return;
+    }
+    if component.is_global() {
+        if global_set.insert(ByAddress(component.clone())) {
+            collect_in_component(component, global_set, sorted_globals);
+            sorted_globals.push(component.clone());
+        }
+        return;
+    }
+    collect_in_component(component, global_set, sorted_globals);
+    let mut used_components = vec![];
+    recurse_elem_including_sub_components(component, &(), &mut |elem, _| {
+        if let Type::Component(base) = &elem.borrow().base_type {
+            used_components.push(base.clone());
+        }
+    });
+    for used in used_components {
+        collect_recursively(&used, visited, global_set, sorted_globals);
+    }
+}
@@ -9,7 +9,7 @@
 //! elements property of the Path element. That way the generators have to deal
 //! with path embedding only as part of the property assignment.
 
-use crate::diagnostics::BuildDiagnostics;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode};
 use crate::expression_tree::*;
 use crate::langtype::Type;
 use crate::object_tree::*;
@@ -52,7 +52,8 @@ pub fn compile_paths(
                 element_types
                     .contains_key(&child.borrow().base_type.as_builtin().native_class.class_name)
             }) {
-                diag.push_error(
+                diag.push_error_with_code(
+                    DiagnosticCode::InvalidPath,
                     "Path elements cannot be mixed with the use of the SVG commands property"
                         .into(),
                     &*path_child.borrow(),
@@ -65,7 +66,8 @@ pub fn compile_paths(
                     match compile_path_from_string_literal(commands) {
                         Ok(binding) => binding,
                         Err(e) => {
-                            diag.push_error(
+                            diag.push_error_with_code(
+                                DiagnosticCode::InvalidPath,
                                 format!("Error parsing SVG commands ({e:?})"),
                                 &commands_expr,
                             );
@@ -78,7 +80,11 @@ pub fn compile_paths(
                 )
                 .into(),
                 _ => {
-                    diag.push_error("The commands property only accepts strings".into(), &*elem);
+                    diag.push_error_with_code(
+                        DiagnosticCode::TypeMismatch,
+                        "The commands property only accepts strings".into(),
+                        &*elem,
+                    );
                     return;
                 }
             }
@@ -102,7 +108,8 @@ pub fn compile_paths(
                     };
 
                     if child.borrow().repeated.is_some() {
-                        diag.push_error(
+                        diag.push_error_with_code(
+                            DiagnosticCode::Unsupported,
                             "Path elements are not supported with `for`-`in` syntax, yet (https://github.com/slint-ui/slint/issues/754)".into(),
                             &*child.borrow(),
                         );
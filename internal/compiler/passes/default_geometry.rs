@@ -12,8 +12,8 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use crate::diagnostics::BuildDiagnostics;
 use crate::diagnostics::Spanned;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode};
 use crate::expression_tree::{BindingExpression, BuiltinFunction, Expression, NamedReference};
 use crate::langtype::{DefaultSizeBinding, PropertyLookupResult, Type};
 use crate::layout::Orientation;
@@ -191,7 +191,11 @@ fn fix_percent_size(
             op: '*',
         }
     } else {
-        diag.push_error("Cannot find parent property to apply relative length".into(), &b.span);
+        diag.push_error_with_code(
+            DiagnosticCode::InvalidValue,
+            "Cannot find parent property to apply relative length".into(),
+            &b.span,
+        );
     }
 }
 
@@ -1,7 +1,7 @@
 // Copyright © SixtyFPS GmbH <info@slint-ui.com>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
-use crate::diagnostics::BuildDiagnostics;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode, DiagnosticLevel};
 #[cfg(not(target_arch = "wasm32"))]
 use crate::embedded_resources::{BitmapFont, BitmapGlyph, BitmapGlyphs, CharacterMapEntry};
 #[cfg(not(target_arch = "wasm32"))]
@@ -115,7 +115,11 @@ pub fn embed_glyphs<'a>(
         for (font_path, import_token) in doc.custom_fonts.iter() {
             let face_count = fontdb.faces().len();
             if let Err(e) = fontdb.load_font_file(&font_path) {
-                diag.push_error(format!("Error loading font: {}", e), import_token);
+                diag.push_error_with_code(
+                    DiagnosticCode::MissingFont,
+                    format!("Error loading font: {}", e),
+                    import_token,
+                );
             } else {
                 custom_fonts.extend(fontdb.faces()[face_count..].iter().map(|info| info.id))
             }
@@ -145,7 +149,13 @@ pub fn embed_glyphs<'a>(
         };
         let face_id = fontdb.query(&query).unwrap_or_else(|| {
             if let Some(source_location) = source_location {
-                diag.push_warning_with_span(format!("could not find font that provides specified family, falling back to Sans-Serif"), source_location);
+                diag.push_coded_diagnostic(
+                    DiagnosticCode::MissingFont,
+                    "could not find font that provides specified family, falling back to Sans-Serif".into(),
+                    source_location,
+                    DiagnosticLevel::Warning,
+                    None,
+                );
             }
             fallback_font
         });
@@ -3,7 +3,7 @@
 
 #![cfg_attr(target_arch = "wasm32", allow(unused))]
 
-use crate::diagnostics::BuildDiagnostics;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode};
 use crate::embedded_resources::*;
 use crate::expression_tree::{Expression, ImageReference};
 use crate::object_tree::*;
@@ -19,6 +19,7 @@ pub fn embed_images(
     component: &Rc<Component>,
     embed_files: EmbedResourcesKind,
     scale_factor: f64,
+    compress_textures: bool,
     diag: &mut BuildDiagnostics,
 ) {
     let global_embedded_resources = &component.embedded_file_resources;
@@ -32,6 +33,7 @@ pub fn embed_images(
                 global_embedded_resources,
                 embed_files,
                 scale_factor,
+                compress_textures,
                 diag,
             )
         });
@@ -43,6 +45,7 @@ fn embed_images_from_expression(
     global_embedded_resources: &RefCell<HashMap<String, EmbeddedResources>>,
     embed_files: EmbedResourcesKind,
     scale_factor: f64,
+    compress_textures: bool,
     diag: &mut BuildDiagnostics,
 ) {
     if let Expression::ImageReference { ref mut resource_ref, source_location } = e {
@@ -56,6 +59,7 @@ fn embed_images_from_expression(
                     embed_files,
                     path,
                     scale_factor,
+                    compress_textures,
                     diag,
                     source_location,
                 );
@@ -65,7 +69,14 @@ fn embed_images_from_expression(
     };
 
     e.visit_mut(|e| {
-        embed_images_from_expression(e, global_embedded_resources, embed_files, scale_factor, diag)
+        embed_images_from_expression(
+            e,
+            global_embedded_resources,
+            embed_files,
+            scale_factor,
+            compress_textures,
+            diag,
+        )
     });
 }
 
@@ -74,6 +85,7 @@ fn embed_image(
     embed_files: EmbedResourcesKind,
     path: &str,
     _scale_factor: f64,
+    _compress_textures: bool,
     diag: &mut BuildDiagnostics,
     source_location: &Option<crate::diagnostics::SourceLocation>,
 ) -> ImageReference {
@@ -92,10 +104,12 @@ fn embed_image(
                             kind = EmbeddedResourcesKind::TextureData(generate_texture(
                                 img,
                                 original_size,
+                                _compress_textures,
                             ))
                         }
                         Err(err) => {
-                            diag.push_error(
+                            diag.push_error_with_code(
+                                DiagnosticCode::MissingImage,
                                 format!("Cannot load image file {}: {}", path, err),
                                 source_location,
                             );
@@ -105,7 +119,11 @@ fn embed_image(
                 }
                 e.insert(EmbeddedResources { id: maybe_id, kind })
             } else {
-                diag.push_warning(format!("Cannot find image file {}", path), source_location);
+                diag.push_warning_with_code(
+                    DiagnosticCode::MissingImage,
+                    format!("Cannot find image file {}", path),
+                    source_location,
+                );
                 return ImageReference::None;
             }
         }
@@ -140,7 +158,11 @@ fn is_transparent(&self) -> bool {
 }
 
 #[cfg(not(target_arch = "wasm32"))]
-pub fn generate_texture(image: image::RgbaImage, original_size: Size) -> Texture {
+pub fn generate_texture(
+    image: image::RgbaImage,
+    original_size: Size,
+    compress_textures: bool,
+) -> Texture {
     // Analyze each pixels
     let mut top = 0;
     let is_line_transparent = |y| {
@@ -228,18 +250,112 @@ enum ColorState {
     };
 
     let rect = Rect::from_ltrb(left as _, top as _, (right + 1) as _, (bottom + 1) as _).unwrap();
+    let (format, data) = if compress_textures && !matches!(format, PixelFormat::AlphaMap(_)) {
+        compress_texture(&image, format, rect)
+    } else {
+        (format, convert_image(&image, format, rect))
+    };
     Texture {
         total_size: Size { width: image.width(), height: image.height() },
         original_size,
         rect,
-        data: convert_image(image, format, rect),
+        data,
         format,
     }
 }
 
+/// Returns the smallest of the raw data in the given format, the data with a palette, and the
+/// run-length encoded data. Opaque images are run-length encoded with 16 bits colors.
 #[cfg(not(target_arch = "wasm32"))]
-fn convert_image(image: image::RgbaImage, format: PixelFormat, rect: Rect) -> Vec<u8> {
-    let i = image::SubImage::new(&image, rect.x() as _, rect.y() as _, rect.width(), rect.height());
+fn compress_texture(
+    image: &image::RgbaImage,
+    format: PixelFormat,
+    rect: Rect,
+) -> (PixelFormat, Vec<u8>) {
+    let premultiplied = convert_image(image, PixelFormat::RgbaPremultiplied, rect);
+    let pixels = premultiplied.chunks_exact(4).collect::<Vec<_>>();
+    let lines = pixels.chunks(rect.width() as usize);
+    let rle = if matches!(format, PixelFormat::Rgb) {
+        let rgb565 = |p: &&[u8]| {
+            let v = (p[0] as u16 >> 3) << 11 | (p[1] as u16 >> 2) << 5 | p[2] as u16 >> 3;
+            v.to_le_bytes()
+        };
+        let lines = lines.map(|line| line.iter().map(rgb565).collect::<Vec<_>>());
+        (PixelFormat::Rgb565Rle, encode_rle(lines))
+    } else {
+        let lines = lines
+            .map(|line| line.iter().map(|p| <[u8; 4]>::try_from(*p).unwrap()).collect::<Vec<_>>());
+        (PixelFormat::RgbaPremultipliedRle, encode_rle(lines))
+    };
+
+    let mut candidates = vec![(format, convert_image(image, format, rect)), rle];
+    if let Some(indexed) = encode_indexed(&pixels) {
+        candidates.push((PixelFormat::Indexed8, indexed));
+    }
+    candidates.into_iter().min_by_key(|(_, data)| data.len()).unwrap()
+}
+
+/// Encodes the pixels with a palette, unless they have more than 256 different colors.
+/// See `PixelFormat::Indexed8` in the runtime library for the layout.
+#[cfg(not(target_arch = "wasm32"))]
+fn encode_indexed(pixels: &[&[u8]]) -> Option<Vec<u8>> {
+    let mut palette = Vec::<&[u8]>::new();
+    let mut indices = HashMap::new();
+    let mut data = Vec::with_capacity(pixels.len());
+    for p in pixels {
+        let index = match indices.entry(*p) {
+            std::collections::hash_map::Entry::Occupied(e) => *e.get(),
+            std::collections::hash_map::Entry::Vacant(e) => {
+                if palette.len() == 256 {
+                    return None;
+                }
+                palette.push(*p);
+                *e.insert((palette.len() - 1) as u8)
+            }
+        };
+        data.push(index);
+    }
+    let mut result = vec![(palette.len() - 1) as u8];
+    result.extend(palette.into_iter().flatten());
+    result.extend(data);
+    Some(result)
+}
+
+/// Run-length encodes the lines of pixels.
+/// See `PixelFormat::Rgb565Rle` in the runtime library for the layout.
+#[cfg(not(target_arch = "wasm32"))]
+fn encode_rle<const N: usize>(lines: impl ExactSizeIterator<Item = Vec<[u8; N]>>) -> Vec<u8> {
+    const MAX_RUN: usize = 128;
+    let mut result = vec![0; lines.len() * 4];
+    for (y, line) in lines.enumerate() {
+        let offset = result.len() as u32;
+        result[y * 4..y * 4 + 4].copy_from_slice(&offset.to_le_bytes());
+        let mut i = 0;
+        while i < line.len() {
+            let repeat = line[i..].iter().take(MAX_RUN).take_while(|p| **p == line[i]).count();
+            if repeat > 1 {
+                result.push(0x80 | (repeat - 1) as u8);
+                result.extend(line[i]);
+                i += repeat;
+            } else {
+                // Copy the pixels until the next two equal pixels, which start a repeated run
+                let mut end = i + 1;
+                while end < line.len() && end - i < MAX_RUN && line.get(end + 1) != Some(&line[end])
+                {
+                    end += 1;
+                }
+                result.push((end - i - 1) as u8);
+                result.extend(line[i..end].iter().flatten());
+                i = end;
+            }
+        }
+    }
+    result
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn convert_image(image: &image::RgbaImage, format: PixelFormat, rect: Rect) -> Vec<u8> {
+    let i = image::SubImage::new(image, rect.x() as _, rect.y() as _, rect.width(), rect.height());
     match format {
         PixelFormat::Rgb => {
             i.pixels().flat_map(|(_, _, p)| IntoIterator::into_iter(p.0).take(3)).collect()
@@ -258,6 +374,9 @@ fn convert_image(image: image::RgbaImage, format: PixelFormat, rect: Rect) -> Ve
             })
             .collect(),
         PixelFormat::AlphaMap(_) => i.pixels().map(|(_, _, p)| p[3]).collect(),
+        PixelFormat::Indexed8 | PixelFormat::Rgb565Rle | PixelFormat::RgbaPremultipliedRle => {
+            unreachable!("compressed formats are produced by compress_texture")
+        }
     }
 }
 
@@ -330,3 +449,69 @@ fn load_image(
         (image.to_rgba8(), Size { width: original_width, height: original_height })
     })
 }
+
+#[test]
+#[cfg(not(target_arch = "wasm32"))]
+fn encode_indexed_palette() {
+    let colors = [[0, 0, 0, 0], [10, 20, 30, 255], [5, 5, 5, 128]];
+    let pixels = [colors[1], colors[0], colors[1], colors[2], colors[2], colors[0]];
+    let pixels = pixels.iter().map(|p| &p[..]).collect::<Vec<_>>();
+    let data = encode_indexed(&pixels).unwrap();
+    // The number of colors minus one, the palette in the order of appearance, and the indices
+    assert_eq!(data[0], 2);
+    assert_eq!(data[1..13], [10, 20, 30, 255, 0, 0, 0, 0, 5, 5, 5, 128]);
+    assert_eq!(data[13..], [0, 1, 0, 2, 2, 1]);
+
+    // The palette holds up to 256 colors
+    let colors =
+        (0..=256u32).map(|i| [(i % 256) as u8, (i / 256) as u8, 0, 255]).collect::<Vec<_>>();
+    let pixels = colors.iter().map(|p| &p[..]).collect::<Vec<_>>();
+    let data = encode_indexed(&pixels[..256]).unwrap();
+    assert_eq!(data[0], 255);
+    assert_eq!(data.len(), 1 + 256 * 4 + 256);
+    assert_eq!(encode_indexed(&pixels), None);
+}
+
+#[test]
+#[cfg(not(target_arch = "wasm32"))]
+fn compress_texture_without_palette() {
+    // More than 256 colors, that don't lose precision in 16 bits: a palette can't be used, and the
+    // run-length encoding is smaller than the raw pixels
+    let expand = |v: u32, bits: u32| (v << (8 - bits) | v >> (2 * bits - 8)) as u8;
+    let image = image::RgbaImage::from_fn(300, 2, |x, y| {
+        image::Rgba([expand(x % 32, 5), expand(x / 32 + y * 10, 6), 0, 255])
+    });
+    let rect = Rect::from_xywh(0, 0, 300, 2).unwrap();
+    let (format, data) = compress_texture(&image, PixelFormat::Rgb, rect);
+    assert!(matches!(format, PixelFormat::Rgb565Rle));
+    assert!(data.len() < 300 * 2 * 3);
+
+    // Few colors with long runs
+    let image =
+        image::RgbaImage::from_fn(300, 2, |x, _| image::Rgba([(x / 100) as u8 * 50, 0, 0, 255]));
+    let (format, data) = compress_texture(&image, PixelFormat::Rgb, rect);
+    assert!(matches!(format, PixelFormat::Rgb565Rle | PixelFormat::Indexed8));
+    assert!(data.len() < 300 * 2 * 3 / 10);
+}
+
+#[test]
+#[cfg(not(target_arch = "wasm32"))]
+fn encode_rle_runs() {
+    let (a, b, c) = ([1, 0], [2, 0], [3, 0]);
+    let lines = [
+        // A run longer than the maximum run length: the rest is copied with the next pixel
+        std::iter::repeat(a).take(129).chain([b]).collect::<Vec<_>>(),
+        // Literal pixels up to the next two equal pixels
+        [a, b, c, c, c].into(),
+        // More different pixels than the maximum run length
+        (0..130u8).map(|x| [x, 0]).collect(),
+    ];
+    let data = encode_rle(lines.iter().cloned());
+    // The offsets of the lines, from the start of the data
+    assert_eq!(data[0..12], [12, 0, 0, 0, 20, 0, 0, 0, 28, 0, 0, 0]);
+    assert_eq!(data[12..20], [0xff, 1, 0, 0x01, 1, 0, 2, 0]);
+    assert_eq!(data[20..28], [0x01, 1, 0, 2, 0, 0x82, 3, 0]);
+    assert_eq!(data[28], 127);
+    assert_eq!(data[28 + 1 + 128 * 2], 1);
+    assert_eq!(data.len(), 28 + 1 + 128 * 2 + 1 + 2 * 2);
+}
@@ -6,7 +6,7 @@
 
 use std::rc::Rc;
 
-use crate::diagnostics::{BuildDiagnostics, SourceLocation, Spanned};
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode, SourceLocation, Spanned};
 use crate::expression_tree::{BindingExpression, BuiltinFunction, Expression};
 use crate::langtype::Type;
 use crate::object_tree::*;
@@ -62,7 +62,11 @@ fn find_focusable_element(
             }
             FocusCheckResult::ElementIsNotFocusable => {
                 if let Some(location) = last_focus_forward_location {
-                    diag.push_error("element is not focusable".into(), &location);
+                    diag.push_error_with_code(
+                        DiagnosticCode::NotFocusable,
+                        "element is not focusable".into(),
+                        &location,
+                    );
                 }
                 break None;
             }
@@ -88,7 +92,8 @@ fn resolve_element_reference_in_set_focus_call(expr: &mut Expression, diag: &mut
                     Some(new_focus_target) => {
                         *weak_focus_target = Rc::downgrade(&new_focus_target);
                     }
-                    None => diag.push_error(
+                    None => diag.push_error_with_code(
+                        DiagnosticCode::NotFocusable,
                         "focus() can only be called on focusable elements".into(),
                         source_location,
                     ),
@@ -7,7 +7,7 @@
 //! type and their bindings are still a Expression::Uncompiled,
 //! this pass will attempt to assign a type to these based on the type of property they alias.
 
-use crate::diagnostics::BuildDiagnostics;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode};
 use crate::expression_tree::Expression;
 use crate::langtype::Type;
 use crate::lookup::LookupCtx;
@@ -92,7 +92,8 @@ fn resolve_alias(
     }
 
     if ty == Type::Invalid && old_type == Type::InferredProperty {
-        diag.push_error(
+        diag.push_error_with_code(
+            DiagnosticCode::InvalidType,
             format!("Could not infer type of property '{}'", prop),
             &elem.borrow().property_declarations[prop].type_node(),
         );
@@ -78,6 +78,7 @@ fn inline_element(
     let mut elem_mut = elem.borrow_mut();
     let priority_delta = 1 + elem_mut.inline_depth;
     elem_mut.base_type = inlined_component.root_element.borrow().base_type.clone();
+    elem_mut.inlined_components += 1 + inlined_component.root_element.borrow().inlined_components;
     elem_mut.property_declarations.extend(
         inlined_component.root_element.borrow().property_declarations.iter().map(clone_tuple),
     );
@@ -221,6 +222,7 @@ fn duplicate_element_with_mapping(
         is_flickable_viewport: elem.is_flickable_viewport,
         has_popup_child: elem.has_popup_child,
         inline_depth: elem.inline_depth + 1,
+        inlined_components: elem.inlined_components,
     }));
     mapping.insert(element_key(element.clone()), new.clone());
     if let Type::Component(c) = &mut new.borrow_mut().base_type {
@@ -336,6 +338,7 @@ fn duplicate_binding(
             .map(|pa| duplicate_property_animation(pa, mapping, root_component, priority_delta)),
         analysis: b.analysis.clone(),
         two_way_bindings: b.two_way_bindings.clone(),
+        is_explicit: b.is_explicit,
     };
     (k.clone(), b.into())
 }
@@ -3,7 +3,7 @@
 
 //! Pass that lowers synthetic `accessible-*` properties
 
-use crate::diagnostics::BuildDiagnostics;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode};
 use crate::expression_tree::{Expression, NamedReference};
 use crate::langtype::EnumerationValue;
 use crate::object_tree::{Component, ElementRc};
@@ -28,7 +28,8 @@ pub fn lower_accessibility_properties(component: &Rc<Component>, diag: &mut Buil
                             return;
                         }
                     } else {
-                        diag.push_error(
+                        diag.push_error_with_code(
+                            DiagnosticCode::NotConstant,
                             "The `accessible-role` property must be a constant expression".into(),
                             &*role.borrow(),
                         );
@@ -50,7 +51,8 @@ pub fn lower_accessibility_properties(component: &Rc<Component>, diag: &mut Buil
                         elem.borrow_mut().accessibility_props.0.insert(prop_name.into(), nr);
                     }
                 } else if let Some(b) = elem.borrow().bindings.get(prop_name) {
-                    diag.push_error(
+                    diag.push_error_with_code(
+                        DiagnosticCode::MissingProperty,
                         format!("The `{prop_name}` property can only be set in combination to `accessible-role`"),
                         &*b.borrow(),
                     );
@@ -5,8 +5,8 @@
 
 use lyon_path::geom::euclid::approxeq::ApproxEq;
 
-use crate::diagnostics::BuildDiagnostics;
 use crate::diagnostics::Spanned;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode};
 use crate::expression_tree::*;
 use crate::langtype::Type;
 use crate::layout::*;
@@ -247,7 +247,8 @@ fn add_element_with_coord(
         let index = self.elems.len();
         if let Some(layout_item) = create_layout_item(item_element, diag) {
             if layout_item.repeater_index.is_some() {
-                diag.push_error(
+                diag.push_error_with_code(
+                    DiagnosticCode::Unsupported,
                     "'if' or 'for' expressions are not currently supported in grid layouts"
                         .to_string(),
                     &*item_element.borrow(),
@@ -423,13 +424,15 @@ fn lower_dialog_layout(
                 debug_assert_eq!(en.name, "DialogButtonRole");
                 button_roles.push(en.values[val.value].clone());
                 if val.value == 0 {
-                    diag.push_error(
+                    diag.push_error_with_code(
+                        DiagnosticCode::InvalidValue,
                         "The `dialog-button-role` cannot be set explicitly to none".into(),
                         &role_binding,
                     );
                 }
             } else {
-                diag.push_error(
+                diag.push_error_with_code(
+                    DiagnosticCode::NotConstant,
                     "The `dialog-button-role` property must be known at compile-time".into(),
                     &role_binding,
                 );
@@ -439,7 +442,8 @@ fn lower_dialog_layout(
         {
             // layout_child is a StandardButton
             match layout_child.borrow().bindings.get("kind") {
-                None => diag.push_error(
+                None => diag.push_error_with_code(
+                    DiagnosticCode::InvalidDialog,
                     "The `kind` property of the StandardButton in a Dialog must be set".into(),
                     &*layout_child.borrow(),
                 ),
@@ -465,7 +469,7 @@ fn lower_dialog_layout(
                         };
                         button_roles.push(role.into());
                         if !seen_buttons.insert(val.value) {
-                            diag.push_error("Duplicated `kind`: There are two StandardButton in this Dialog with the same kind".into(), binding);
+                            diag.push_error_with_code(DiagnosticCode::InvalidDialog, "Duplicated `kind`: There are two StandardButton in this Dialog with the same kind".into(), binding);
                         } else if Rc::ptr_eq(
                             dialog_element,
                             &dialog_element
@@ -498,7 +502,8 @@ fn lower_dialog_layout(
                             }
                         }
                     } else {
-                        diag.push_error(
+                        diag.push_error_with_code(
+                            DiagnosticCode::NotConstant,
                             "The `kind` property of the StandardButton in a Dialog must be known at compile-time"
                                 .into(),
                             binding,
@@ -521,7 +526,8 @@ fn lower_dialog_layout(
                 diag,
             );
         } else if main_widget.is_some() {
-            diag.push_error(
+            diag.push_error_with_code(
+                DiagnosticCode::InvalidDialog,
                 "A Dialog can have only one child element that is not a StandardButton".into(),
                 &*layout_child.borrow(),
             );
@@ -541,7 +547,8 @@ fn lower_dialog_layout(
             diag,
         );
     } else {
-        diag.push_error(
+        diag.push_error_with_code(
+            DiagnosticCode::InvalidDialog,
             "A Dialog must have a single child element that is not StandardButton".into(),
             &*dialog_element.borrow(),
         );
@@ -735,7 +742,8 @@ fn set_prop_from_cache(
         .into(),
     );
     if let Some(old) = old.map(RefCell::into_inner) {
-        diag.push_error(
+        diag.push_error_with_code(
+            DiagnosticCode::ConflictingProperties,
             format!("The property '{}' cannot be set for elements placed in a layout, because the layout is already setting it", prop),
             &old,
         );
@@ -751,7 +759,11 @@ fn eval_const_expr(
     match expression {
         Expression::NumberLiteral(v, Unit::None) => {
             if *v < 0. || *v > u16::MAX as f64 || !v.trunc().approx_eq(v) {
-                diag.push_error(format!("'{}' must be a positive integer", name), span);
+                diag.push_error_with_code(
+                    DiagnosticCode::InvalidValue,
+                    format!("'{}' must be a positive integer", name),
+                    span,
+                );
                 None
             } else {
                 Some(*v as u16)
@@ -759,7 +771,11 @@ fn eval_const_expr(
         }
         Expression::Cast { from, .. } => eval_const_expr(from, name, span, diag),
         _ => {
-            diag.push_error(format!("'{}' must be an integer literal", name), span);
+            diag.push_error_with_code(
+                DiagnosticCode::NotConstant,
+                format!("'{}' must be an integer literal", name),
+                span,
+            );
             None
         }
     }
@@ -790,10 +806,18 @@ pub fn create_new_prop(elem: &ElementRc, tentative_name: &str, ty: Type) -> Name
 fn check_no_layout_properties(item: &ElementRc, diag: &mut BuildDiagnostics) {
     for (prop, expr) in item.borrow().bindings.iter() {
         if matches!(prop.as_ref(), "col" | "row" | "colspan" | "rowspan") {
-            diag.push_error(format!("{} used outside of a GridLayout", prop), &*expr.borrow());
+            diag.push_error_with_code(
+                DiagnosticCode::UnsupportedProperty,
+                format!("{} used outside of a GridLayout", prop),
+                &*expr.borrow(),
+            );
         }
         if matches!(prop.as_ref(), "dialog-button-role") {
-            diag.push_error(format!("{} used outside of a Dialog", prop), &*expr.borrow());
+            diag.push_error_with_code(
+                DiagnosticCode::UnsupportedProperty,
+                format!("{} used outside of a Dialog", prop),
+                &*expr.borrow(),
+            );
         }
     }
 }
@@ -0,0 +1,300 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Pass that lowers the MenuBar and ContextMenu elements.
+//!
+//! The `Menu`, `MenuItem` and `MenuSeparator` children become `MenuEntryItem`, and the
+//! structure of the menu is exposed to the MenuBarImpl and ContextMenuImpl from the style
+//! as a `[[MenuEntry]]` model, so they can render it in popups.
+//!
+//! Must be done before inlining and many other passes because the lowered code must
+//! be further inlined as it may expends to native widget that needs inlining
+
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode};
+use crate::expression_tree::{BuiltinFunction, Expression, NamedReference, Unit};
+use crate::langtype::Type;
+use crate::object_tree::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// The number of levels of popup that the MenuBarImpl and ContextMenuImpl can show.
+/// Must be kept in sync with the styles and with the documentation of `Menu`.
+const MAX_POPUP_DEPTH: usize = 3;
+
+pub async fn lower_menus(
+    component: &Rc<Component>,
+    type_loader: &mut crate::typeloader::TypeLoader,
+    diag: &mut BuildDiagnostics,
+) {
+    // Ignore import errors
+    let mut build_diags_to_ignore = BuildDiagnostics::default();
+    let menubar_impl = type_loader
+        .import_type("std-widgets.slint", "MenuBarImpl", &mut build_diags_to_ignore)
+        .await
+        .expect("can't load MenuBarImpl from std-widgets.slint");
+    let context_menu_impl = type_loader
+        .import_type("std-widgets.slint", "ContextMenuImpl", &mut build_diags_to_ignore)
+        .await
+        .expect("can't load ContextMenuImpl from std-widgets.slint");
+    let menu_entry_type =
+        type_loader.global_type_registry.borrow().lookup_element("MenuEntryItem").unwrap();
+
+    recurse_elem_including_sub_components_no_borrow(component, &(), &mut |elem, _| {
+        // The context menu must be on top of its siblings to receive the right clicks
+        // before any TouchArea
+        if elem.borrow().children.iter().any(|c| builtin_name(c).as_deref() == Some("ContextMenu"))
+        {
+            if super::lower_layout::is_layout_element(elem) {
+                diag.push_error_with_code(
+                    DiagnosticCode::InvalidMenu,
+                    "A ContextMenu cannot be placed directly in a layout".into(),
+                    &*elem.borrow(),
+                );
+            }
+            let mut elem = elem.borrow_mut();
+            let (context_menus, others): (Vec<_>, Vec<_>) = std::mem::take(&mut elem.children)
+                .into_iter()
+                .partition(|c| builtin_name(c).as_deref() == Some("ContextMenu"));
+            elem.children = others.into_iter().chain(context_menus).collect();
+        }
+
+        match builtin_name(elem).as_deref() {
+            Some("MenuBar") => {
+                elem.borrow_mut().base_type = menubar_impl.clone();
+                process_menu_root(elem, 0, &menu_entry_type, diag);
+            }
+            Some("ContextMenu") => {
+                elem.borrow_mut().base_type = context_menu_impl.clone();
+                process_menu_root(elem, 1, &menu_entry_type, diag);
+            }
+            _ => {}
+        }
+    });
+
+    recurse_elem_including_sub_components_no_borrow(component, &(), &mut |elem, _| {
+        if let Some(name @ ("Menu" | "MenuItem" | "MenuSeparator")) = builtin_name(elem).as_deref()
+        {
+            diag.push_error_with_code(
+                DiagnosticCode::InvalidMenu,
+                format!("{} can only be used inside a MenuBar, a ContextMenu or a Menu", name),
+                &*elem.borrow(),
+            );
+        }
+    })
+}
+
+/// Generate the setup code that registers each `MenuBarItem` with the window when its component
+/// is instantiated, so that the shortcuts of the menu entries work before anything is rendered.
+///
+/// Must be done after the last inlining, because the setup code of inlined components is dropped.
+pub fn register_menu_bars(component: &Rc<Component>) {
+    recurse_elem_including_sub_components(component, &(), &mut |elem, _| {
+        if builtin_name(elem).as_deref() == Some("MenuBarItem") {
+            let enclosing_component = elem.borrow().enclosing_component.upgrade().unwrap();
+            enclosing_component.setup_code.borrow_mut().push(Expression::FunctionCall {
+                function: Box::new(Expression::BuiltinFunctionReference(
+                    BuiltinFunction::RegisterMenuBar,
+                    None,
+                )),
+                arguments: vec![Expression::ElementReference(Rc::downgrade(elem))],
+                source_location: None,
+            });
+        }
+    });
+}
+
+/// Returns the name of the builtin element this element is based on, if it is a builtin element
+fn builtin_name(elem: &ElementRc) -> Option<String> {
+    match &elem.borrow().base_type {
+        Type::Builtin(b) => Some(b.name.clone()),
+        _ => None,
+    }
+}
+
+struct MenuEntry {
+    element: ElementRc,
+    /// The index in the `menus` model of the entries of this sub menu
+    sub_menu: Option<usize>,
+}
+
+/// Lower the children of a MenuBar or a ContextMenu, and set the `menus` and `activated` bindings.
+/// `depth` is the popup depth of the direct children.
+fn process_menu_root(
+    elem: &ElementRc,
+    depth: usize,
+    menu_entry_type: &Type,
+    diag: &mut BuildDiagnostics,
+) {
+    let mut menus = vec![vec![]];
+    collect_entries(elem, 0, depth, &mut menus, menu_entry_type, diag);
+
+    let menus_ty = elem.borrow().lookup_property("menus").property_type;
+    let (entries_ty, entry_ty) = match &menus_ty {
+        Type::Array(entries_ty) => match &**entries_ty {
+            Type::Array(entry_ty) => ((**entries_ty).clone(), (**entry_ty).clone()),
+            _ => panic!("MenuImpl's menus property is not a [[MenuEntry]]"),
+        },
+        _ => panic!("MenuImpl's menus property is not a [[MenuEntry]]"),
+    };
+
+    let menus_expr = Expression::Array {
+        element_ty: entries_ty,
+        values: menus
+            .iter()
+            .map(|entries| Expression::Array {
+                element_ty: entry_ty.clone(),
+                values: entries.iter().map(|entry| entry_struct(entry, &entry_ty)).collect(),
+            })
+            .collect(),
+    };
+    elem.borrow_mut().bindings.insert("menus".into(), RefCell::new(menus_expr.into()));
+
+    // activated(menu-index, entry-index) => { if (menu-index == 0 && entry-index == 0) { ... } else if ... }
+    let activated_expr = menus.iter().enumerate().rev().fold(
+        Expression::CodeBlock(vec![]),
+        |else_expr, (menu_index, entries)| {
+            entries.iter().enumerate().rev().filter(|(_, entry)| entry.sub_menu.is_none()).fold(
+                else_expr,
+                |else_expr, (entry_index, entry)| Expression::Condition {
+                    condition: Box::new(Expression::BinaryExpression {
+                        lhs: Box::new(index_matches(0, menu_index)),
+                        rhs: Box::new(index_matches(1, entry_index)),
+                        op: '&',
+                    }),
+                    true_expr: Box::new(activate_entry(&entry.element)),
+                    false_expr: Box::new(else_expr),
+                },
+            )
+        },
+    );
+    elem.borrow_mut().bindings.insert("activated".into(), RefCell::new(activated_expr.into()));
+}
+
+/// Lower the children of `parent` to MenuEntryItem and add them to `menus[menu_index]`
+fn collect_entries(
+    parent: &ElementRc,
+    menu_index: usize,
+    depth: usize,
+    menus: &mut Vec<Vec<MenuEntry>>,
+    menu_entry_type: &Type,
+    diag: &mut BuildDiagnostics,
+) {
+    let children = std::mem::take(&mut parent.borrow_mut().children);
+    let mut valid_children = Vec::with_capacity(children.len());
+    for child in children {
+        if child.borrow().repeated.is_some() {
+            diag.push_error_with_code(
+                DiagnosticCode::Unsupported,
+                "'if' and 'for' cannot be used in a menu. The entries of a menu are fixed".into(),
+                &*child.borrow(),
+            );
+            continue;
+        }
+        let sub_menu = match builtin_name(&child).as_deref() {
+            Some("MenuItem") => {
+                if !child.borrow().children.is_empty() {
+                    diag.push_error_with_code(
+                        DiagnosticCode::InvalidMenu,
+                        "A MenuItem cannot have children. Use a Menu for sub-menus".into(),
+                        &*child.borrow(),
+                    );
+                    continue;
+                }
+                None
+            }
+            Some("MenuSeparator") => {
+                child.borrow_mut().bindings.insert(
+                    "is-separator".into(),
+                    RefCell::new(Expression::BoolLiteral(true).into()),
+                );
+                None
+            }
+            Some("Menu") => {
+                if depth >= MAX_POPUP_DEPTH {
+                    diag.push_error_with_code(
+                        DiagnosticCode::InvalidMenu,
+                        format!("Menus cannot be nested more than {} levels deep", MAX_POPUP_DEPTH),
+                        &*child.borrow(),
+                    );
+                    continue;
+                }
+                Some(menus.len())
+            }
+            _ => {
+                diag.push_error_with_code(
+                    DiagnosticCode::InvalidMenu,
+                    format!(
+                        "{} is not allowed within a menu. Only Menu, MenuItem and MenuSeparator are valid children",
+                        child.borrow().base_type
+                    ),
+                    &*child.borrow(),
+                );
+                continue;
+            }
+        };
+        child.borrow_mut().base_type = menu_entry_type.clone();
+        if let Some(sub_menu) = sub_menu {
+            menus.push(vec![]);
+            collect_entries(&child, sub_menu, depth + 1, menus, menu_entry_type, diag);
+        }
+        menus[menu_index].push(MenuEntry { element: child.clone(), sub_menu });
+        valid_children.push(child);
+    }
+    parent.borrow_mut().children = valid_children;
+}
+
+/// The MenuEntry struct that describes the entry to the style
+fn entry_struct(entry: &MenuEntry, entry_ty: &Type) -> Expression {
+    let prop =
+        |name: &str| Expression::PropertyReference(NamedReference::new(&entry.element, name));
+    let values = HashMap::from([
+        ("title".to_owned(), prop("title")),
+        ("shortcut".to_owned(), prop("shortcut")),
+        ("enabled".to_owned(), prop("enabled")),
+        ("checkable".to_owned(), prop("checkable")),
+        ("checked".to_owned(), prop("checked")),
+        ("is-separator".to_owned(), prop("is-separator")),
+        (
+            "sub-menu".to_owned(),
+            Expression::NumberLiteral(entry.sub_menu.map_or(-1., |i| i as f64), Unit::None),
+        ),
+    ]);
+    Expression::Struct { ty: entry_ty.clone(), values }
+}
+
+/// `activated`'s argument `arg` == `index`
+fn index_matches(arg: usize, index: usize) -> Expression {
+    Expression::BinaryExpression {
+        lhs: Box::new(Expression::FunctionParameterReference { index: arg, ty: Type::Int32 }),
+        rhs: Box::new(Expression::NumberLiteral(index as _, Unit::None)),
+        op: '=',
+    }
+}
+
+/// Same as MenuEntryItem::activate: `if (checkable) { checked = !checked; } activated();`
+fn activate_entry(entry: &ElementRc) -> Expression {
+    let checked = || Expression::PropertyReference(NamedReference::new(entry, "checked"));
+    Expression::CodeBlock(vec![
+        Expression::Condition {
+            condition: Box::new(Expression::PropertyReference(NamedReference::new(
+                entry,
+                "checkable",
+            ))),
+            true_expr: Box::new(Expression::SelfAssignment {
+                lhs: Box::new(checked()),
+                rhs: Box::new(Expression::UnaryOp { sub: Box::new(checked()), op: '!' }),
+                op: '=',
+            }),
+            false_expr: Box::new(Expression::CodeBlock(vec![])),
+        },
+        Expression::FunctionCall {
+            function: Box::new(Expression::CallbackReference(NamedReference::new(
+                entry,
+                "activated",
+            ))),
+            arguments: vec![],
+            source_location: None,
+        },
+    ])
+}
@@ -3,7 +3,7 @@
 
 //! Passe that transform the PopupWindow element into a component
 
-use crate::diagnostics::BuildDiagnostics;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode};
 use crate::expression_tree::{Expression, NamedReference};
 use crate::langtype::Type;
 use crate::object_tree::*;
@@ -39,7 +39,8 @@ fn lower_popup_window(
 ) {
     let parent_element = match parent_element {
         None => {
-            diag.push_error(
+            diag.push_error_with_code(
+                DiagnosticCode::InvalidPopup,
                 "PopupWindow cannot be the top level".into(),
                 &*popup_window_element.borrow(),
             );
@@ -50,7 +51,8 @@ fn lower_popup_window(
 
     let parent_component = popup_window_element.borrow().enclosing_component.upgrade().unwrap();
     if Rc::ptr_eq(&parent_component.root_element, popup_window_element) {
-        diag.push_error(
+        diag.push_error_with_code(
+            DiagnosticCode::InvalidPopup,
             "PopupWindow cannot be directly repeated or conditional".into(),
             &*popup_window_element.borrow(),
         );
@@ -92,7 +94,8 @@ fn lower_popup_window(
     // - Maybe this should actually be allowed
     visit_all_named_references(&parent_component, &mut |nr| {
         if std::rc::Weak::ptr_eq(&nr.element().borrow().enclosing_component, &weak) {
-            diag.push_error(
+            diag.push_error_with_code(
+                DiagnosticCode::InvalidPopup,
                 "Cannot access the inside of a PopupWindow from enclosing component".into(),
                 &*popup_window_element.borrow(),
             );
@@ -7,7 +7,7 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use crate::diagnostics::BuildDiagnostics;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode};
 use crate::expression_tree::{BindingExpression, Expression, NamedReference};
 use crate::langtype::Type;
 use crate::object_tree::{self, Component, Element, ElementRc};
@@ -27,7 +27,8 @@ pub(crate) fn lower_property_to_element(
     diag: &mut BuildDiagnostics,
 ) {
     if let Some(b) = component.root_element.borrow().bindings.get(property_name) {
-        diag.push_warning(
+        diag.push_warning_with_code(
+            DiagnosticCode::IgnoredRootProperty,
             format!(
                 "The {} property cannot be used on the root element, it will not be applied",
                 property_name
@@ -5,7 +5,7 @@
 // At the moment only shadows on `Rectangle` elements are supported, i.e. the drop shadow
 // of a rectangle is a box shadow.
 
-use crate::diagnostics::BuildDiagnostics;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode};
 use crate::expression_tree::BindingExpression;
 use crate::{expression_tree::Expression, object_tree::*};
 use crate::{expression_tree::NamedReference, typeregister::TypeRegister};
@@ -25,7 +25,8 @@ fn create_box_shadow_element(
        if native.class_name != "Rectangle" && native.class_name != "BorderRectangle" && native.class_name != "Clip")
     {
         for (shadow_prop_name, shadow_prop_binding) in shadow_property_bindings {
-            diag.push_error(
+            diag.push_error_with_code(
+                DiagnosticCode::UnsupportedProperty,
                 format!(
                     "The {} property is only supported on Rectangle and Clip elements right now",
                     shadow_prop_name
@@ -116,7 +117,8 @@ pub fn lower_shadow_properties(
     for (shadow_prop_name, shadow_prop_binding) in
         take_shadow_property_bindings(&component.root_element)
     {
-        diag.push_warning(
+        diag.push_warning_with_code(
+            DiagnosticCode::IgnoredRootProperty,
             format!("The {} property cannot be used on the root element, the shadow will not be visible", shadow_prop_name),
             &shadow_prop_binding,
         );
@@ -3,8 +3,8 @@
 
 //! Pass that create a state property, and change all the binding to depend on that property
 
-use crate::diagnostics::BuildDiagnostics;
 use crate::diagnostics::SourceLocation;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode};
 use crate::expression_tree::*;
 use crate::langtype::Type;
 use crate::object_tree::*;
@@ -62,7 +62,8 @@ fn lower_state_in_element(
             let e = ne.element();
             let property_expr = match expression_for_property(&e, ne.name()) {
                 ExpressionForProperty::TwoWayBinding => {
-                    diag.push_error(
+                    diag.push_error_with_code(
+                    DiagnosticCode::InvalidState,
                     format!("Cannot change the property '{}' in a state because it is initialized with a two-way binding", ne.name()),
                     &node
                 );
@@ -70,7 +71,8 @@ fn lower_state_in_element(
                 }
                 ExpressionForProperty::Expression(e) => e,
                 ExpressionForProperty::InvalidBecauseOfIssue1461 => {
-                    diag.push_error(
+                    diag.push_error_with_code(
+                        DiagnosticCode::Unsupported,
                         format!("Internal error: The expression for the default state currently cannot be represented: https://github.com/slint-ui/slint/issues/1461\nAs a workaround, add a binding for property {}", ne.name()),
                         &node
                     );
@@ -93,6 +95,7 @@ fn lower_state_in_element(
                 std::collections::btree_map::Entry::Vacant(e) => {
                     let mut r = BindingExpression::from(new_expr);
                     r.priority = 1;
+                    r.is_explicit = true;
                     e.insert(r.into());
                 }
             };
@@ -133,7 +136,8 @@ fn lower_transitions_in_element(
         HashMap::<NamedReference, (SourceLocation, Vec<TransitionPropertyAnimation>)>::new();
     for transition in transitions {
         let state = states_id.get(&transition.state_id).unwrap_or_else(|| {
-            diag.push_error(
+            diag.push_error_with_code(
+                DiagnosticCode::InvalidState,
                 format!("State '{}' does not exist", transition.state_id),
                 &transition.node,
             );
@@ -142,7 +146,8 @@ fn lower_transitions_in_element(
 
         for (p, span, animation) in transition.property_animations {
             if !affected_properties.contains(&p) {
-                diag.push_error(
+                diag.push_error_with_code(
+                    DiagnosticCode::InvalidState,
                     "The property is not changed as part of this transition".into(),
                     &span,
                 );
@@ -164,7 +169,8 @@ fn lower_transitions_in_element(
             PropertyAnimation::Transition { state_ref: state_property.clone(), animations },
         );
         if old_anim.is_some() {
-            diag.push_error(
+            diag.push_error_with_code(
+                DiagnosticCode::InvalidAnimation,
                 format!(
                     "The property '{}' cannot have transition because it already has an animation",
                     ne.name()
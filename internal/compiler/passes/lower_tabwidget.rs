@@ -8,7 +8,7 @@
 //! Must be done before inlining and many other passes because the lowered code must
 //! be further inlined as it may expends to native widget that needs inlining
 
-use crate::diagnostics::BuildDiagnostics;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode};
 use crate::expression_tree::{BindingExpression, Expression, NamedReference, Unit};
 use crate::langtype::Type;
 use crate::object_tree::*;
@@ -65,7 +65,8 @@ fn process_tabwidget(
     let mut tabs = Vec::new();
     for child in &mut children {
         if child.borrow().repeated.is_some() {
-            diag.push_error(
+            diag.push_error_with_code(
+                DiagnosticCode::Unsupported,
                 "dynamic tabs ('if' or 'for') are currently not supported".into(),
                 &*child.borrow(),
             );
@@ -92,7 +93,8 @@ fn process_tabwidget(
             .bindings
             .insert("visible".to_owned(), RefCell::new(condition.into()));
         if let Some(old) = old {
-            diag.push_error(
+            diag.push_error_with_code(
+                DiagnosticCode::ConflictingProperties,
                 "The property 'visible' cannot be set for Tabs inside a TabWidget".to_owned(),
                 &old.into_inner(),
             );
@@ -195,7 +197,8 @@ fn set_geometry_prop(
         ),
     );
     if let Some(old) = old.map(RefCell::into_inner) {
-        diag.push_error(
+        diag.push_error_with_code(
+            DiagnosticCode::ConflictingProperties,
             format!("The property '{}' cannot be set for Tabs inside a TabWidget", prop),
             &old,
         );
@@ -3,7 +3,7 @@
 
 //! This pass removes the property used in a two ways bindings
 
-use crate::diagnostics::BuildDiagnostics;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode};
 use crate::expression_tree::{BindingExpression, NamedReference};
 use crate::object_tree::*;
 use std::cell::RefCell;
@@ -69,7 +69,11 @@ pub fn remove_aliases(component: &Rc<Component>, diag: &mut BuildDiagnostics) {
             for nr in &binding.borrow().two_way_bindings {
                 let other_e = nr.element();
                 if name == nr.name() && Rc::ptr_eq(e, &other_e) {
-                    diag.push_error("Property cannot alias to itself".into(), &*binding.borrow());
+                    diag.push_error_with_code(
+                        DiagnosticCode::BindingLoop,
+                        "Property cannot alias to itself".into(),
+                        &*binding.borrow(),
+                    );
                     continue 'bindings;
                 }
                 property_sets.add_link(NamedReference::new(e, name), nr.clone());
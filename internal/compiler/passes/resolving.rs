@@ -8,7 +8,7 @@
 //!
 //! Most of the code for the resolving actually lies in the expression_tree module
 
-use crate::diagnostics::{BuildDiagnostics, Spanned};
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode, Spanned};
 use crate::expression_tree::*;
 use crate::langtype::{PropertyLookupResult, Type};
 use crate::lookup::{LookupCtx, LookupObject, LookupResult};
@@ -153,7 +153,8 @@ pub fn from_binding_expression_node(node: SyntaxNode, ctx: &mut LookupCtx) -> Se
             if RELATIVE_TO_PARENT_PROPERTIES.contains(&property_name) {
                 return e;
             } else {
-                ctx.diag.push_error(
+                ctx.diag.push_error_with_code(
+                    DiagnosticCode::TypeMismatch,
                     format!(
                         "Automatic conversion from percentage to length is only possible for the properties {}",
                         RELATIVE_TO_PARENT_PROPERTIES.join(" and ")
@@ -241,7 +242,8 @@ fn from_expression_node(node: syntax_nodes::Expression, ctx: &mut LookupCtx) ->
                 node.QualifiedName().map(|n| {
                     let exp = Self::from_qualified_name_node(n.clone(), ctx);
                     if matches!(exp.ty(), Type::Function { .. } | Type::Callback { .. }) {
-                        ctx.diag.push_error(
+                        ctx.diag.push_error_with_code(
+                            DiagnosticCode::UncalledFunction,
                             format!(
                                 "'{}' must be called. Did you forgot the '()'?",
                                 QualifiedTypeName::from_node(n.clone())
@@ -256,7 +258,11 @@ fn from_expression_node(node: syntax_nodes::Expression, ctx: &mut LookupCtx) ->
                 node.child_text(SyntaxKind::StringLiteral).map(|s| {
                     crate::literals::unescape_string(&s).map(Self::StringLiteral).unwrap_or_else(
                         || {
-                            ctx.diag.push_error("Cannot parse string literal".into(), &node);
+                            ctx.diag.push_error_with_code(
+                                DiagnosticCode::SyntaxError,
+                                "Cannot parse string literal".into(),
+                                &node,
+                            );
                             Self::Invalid
                         },
                     )
@@ -267,7 +273,7 @@ fn from_expression_node(node: syntax_nodes::Expression, ctx: &mut LookupCtx) ->
                     .map(crate::literals::parse_number_literal)
                     .transpose()
                     .unwrap_or_else(|e| {
-                        ctx.diag.push_error(e, &node);
+                        ctx.diag.push_error_with_code(DiagnosticCode::SyntaxError, e, &node);
                         Some(Self::Invalid)
                     })
             })
@@ -279,7 +285,11 @@ fn from_expression_node(node: syntax_nodes::Expression, ctx: &mut LookupCtx) ->
                             to: Type::Color,
                         })
                         .unwrap_or_else(|| {
-                            ctx.diag.push_error("Invalid color literal".into(), &node);
+                            ctx.diag.push_error_with_code(
+                                DiagnosticCode::SyntaxError,
+                                "Invalid color literal".into(),
+                                &node,
+                            );
                             Self::Invalid
                         })
                 })
@@ -311,7 +321,11 @@ fn from_at_image_url_node(node: syntax_nodes::AtImageUrl, ctx: &mut LookupCtx) -
         {
             Some(s) => s,
             None => {
-                ctx.diag.push_error("Cannot parse string literal".into(), &node);
+                ctx.diag.push_error_with_code(
+                    DiagnosticCode::SyntaxError,
+                    "Cannot parse string literal".into(),
+                    &node,
+                );
                 return Self::Invalid;
             }
         };
@@ -365,12 +379,17 @@ enum GradKind {
                     syntax_nodes::Expression::from(e.into_node().unwrap())
                 }
                 _ => {
-                    ctx.diag.push_error("Expected angle expression".into(), &node);
+                    ctx.diag.push_error_with_code(
+                        DiagnosticCode::SyntaxError,
+                        "Expected angle expression".into(),
+                        &node,
+                    );
                     return Expression::Invalid;
                 }
             };
             if subs.next().map_or(false, |s| s.kind() != SyntaxKind::Comma) {
-                ctx.diag.push_error(
+                ctx.diag.push_error_with_code(
+                    DiagnosticCode::SyntaxError,
                     "Angle expression must be an angle followed by a comma".into(),
                     &node,
                 );
@@ -387,16 +406,21 @@ enum GradKind {
         } else if grad_text.starts_with("radial") {
             if !matches!(subs.next(), Some(NodeOrToken::Node(n)) if n.text().to_string().trim() == "circle")
             {
-                ctx.diag.push_error("Expected 'circle': currently, only @radial-gradient(circle, ...) are supported".into(), &node);
+                ctx.diag.push_error_with_code(DiagnosticCode::Unsupported, "Expected 'circle': currently, only @radial-gradient(circle, ...) are supported".into(), &node);
                 return Expression::Invalid;
             }
             let comma = subs.next();
             if matches!(&comma, Some(NodeOrToken::Node(n)) if n.text().to_string().trim() == "at") {
-                ctx.diag.push_error("'at' in @radial-gradient is not yet supported".into(), &comma);
+                ctx.diag.push_error_with_code(
+                    DiagnosticCode::Unsupported,
+                    "'at' in @radial-gradient is not yet supported".into(),
+                    &comma,
+                );
                 return Expression::Invalid;
             }
             if comma.as_ref().map_or(false, |s| s.kind() != SyntaxKind::Comma) {
-                ctx.diag.push_error(
+                ctx.diag.push_error_with_code(
+                    DiagnosticCode::SyntaxError,
                     "'circle' must be followed by a comma".into(),
                     comma.as_ref().map_or(&node, |x| x as &dyn Spanned),
                 );
@@ -419,7 +443,11 @@ enum Stop {
             if n.kind() == SyntaxKind::Comma {
                 match std::mem::replace(&mut current_stop, Stop::Empty) {
                     Stop::Empty => {
-                        ctx.diag.push_error("Expected expression".into(), &n);
+                        ctx.diag.push_error_with_code(
+                            DiagnosticCode::SyntaxError,
+                            "Expected expression".into(),
+                            &n,
+                        );
                         break;
                     }
                     Stop::Finished => {}
@@ -446,7 +474,11 @@ enum Stop {
                         current_stop = Stop::Color(e.maybe_convert_to(Type::Color, &n, ctx.diag))
                     }
                     Stop::Finished => {
-                        ctx.diag.push_error("Expected comma".into(), &n);
+                        ctx.diag.push_error_with_code(
+                            DiagnosticCode::SyntaxError,
+                            "Expected comma".into(),
+                            &n,
+                        );
                         break;
                     }
                     Stop::Color(col) => {
@@ -537,15 +569,20 @@ fn from_qualified_name_node(node: syntax_nodes::QualifiedName, ctx: &mut LookupC
                         .lookup(ctx, &crate::parser::normalize_identifier(first_str))
                         .is_some()
                     {
-                        ctx.diag.push_error(format!("Unknown unqualified identifier '{}'. Use space before the '-' if you meant a subtraction", first.text()), &node);
+                        ctx.diag.push_error_with_code(DiagnosticCode::UnknownIdentifier, format!("Unknown unqualified identifier '{}'. Use space before the '-' if you meant a subtraction", first.text()), &node);
                         return Expression::Invalid;
                     }
                 }
 
                 if it.next().is_some() {
-                    ctx.diag.push_error(format!("Cannot access id '{}'", first.text()), &node);
+                    ctx.diag.push_error_with_code(
+                        DiagnosticCode::UnknownIdentifier,
+                        format!("Cannot access id '{}'", first.text()),
+                        &node,
+                    );
                 } else {
-                    ctx.diag.push_error(
+                    ctx.diag.push_error_with_code(
+                        DiagnosticCode::UnknownIdentifier,
                         format!("Unknown unqualified identifier '{}'", first.text()),
                         &node,
                     );
@@ -567,7 +604,11 @@ fn from_qualified_name_node(node: syntax_nodes::QualifiedName, ctx: &mut LookupC
                 expression: r @ Expression::CallbackReference(..), ..
             } => {
                 if let Some(x) = it.next() {
-                    ctx.diag.push_error("Cannot access fields of callback".into(), &x)
+                    ctx.diag.push_error_with_code(
+                        DiagnosticCode::UnknownMember,
+                        "Cannot access fields of callback".into(),
+                        &x,
+                    )
                 }
                 r
             }
@@ -580,7 +621,8 @@ fn from_qualified_name_node(node: syntax_nodes::QualifiedName, ctx: &mut LookupC
                             maybe_lookup_object(expression, it, ctx)
                         }
                         _ => {
-                            ctx.diag.push_error(
+                            ctx.diag.push_error_with_code(
+                                DiagnosticCode::UnknownMember,
                                 format!(
                                     "'{}' is not a member of the enum {}",
                                     next_identifier.text(),
@@ -592,7 +634,11 @@ fn from_qualified_name_node(node: syntax_nodes::QualifiedName, ctx: &mut LookupC
                         }
                     }
                 } else {
-                    ctx.diag.push_error("Cannot take reference to an enum".to_string(), &node);
+                    ctx.diag.push_error_with_code(
+                        DiagnosticCode::InvalidReference,
+                        "Cannot take reference to an enum".to_string(),
+                        &node,
+                    );
                     Expression::Invalid
                 }
             }
@@ -606,7 +652,8 @@ fn from_qualified_name_node(node: syntax_nodes::QualifiedName, ctx: &mut LookupC
                             maybe_lookup_object(expression, it, ctx)
                         }
                         _ => {
-                            ctx.diag.push_error(
+                            ctx.diag.push_error_with_code(
+                                DiagnosticCode::UnknownMember,
                                 format!(
                                     "'{}' is not a member of the namespace {}",
                                     next_identifier.text(),
@@ -618,7 +665,11 @@ fn from_qualified_name_node(node: syntax_nodes::QualifiedName, ctx: &mut LookupC
                         }
                     }
                 } else {
-                    ctx.diag.push_error("Cannot take reference to a namespace".to_string(), &node);
+                    ctx.diag.push_error_with_code(
+                        DiagnosticCode::InvalidReference,
+                        "Cannot take reference to a namespace".to_string(),
+                        &node,
+                    );
                     Expression::Invalid
                 }
             }
@@ -660,7 +711,8 @@ fn from_function_call_node(
         let arguments = match function.ty() {
             Type::Function { args, .. } | Type::Callback { args, .. } => {
                 if arguments.len() != args.len() {
-                    ctx.diag.push_error(
+                    ctx.diag.push_error_with_code(
+                        DiagnosticCode::InvalidArguments,
                         format!(
                             "The callback or function expects {} arguments, but {} are provided",
                             args.len(),
@@ -678,7 +730,11 @@ fn from_function_call_node(
                 }
             }
             _ => {
-                ctx.diag.push_error("The expression is not a function".into(), &node);
+                ctx.diag.push_error_with_code(
+                    DiagnosticCode::NotCallable,
+                    "The expression is not a function".into(),
+                    &node,
+                );
                 arguments.into_iter().map(|x| x.0).collect()
             }
         };
@@ -712,7 +768,8 @@ fn from_self_assignment_node(
             .or_else(|| node.child_token(SyntaxKind::Equal).and(Some('=')))
             .unwrap_or('_');
         if !lhs.try_set_rw() && lhs.ty() != Type::Invalid {
-            ctx.diag.push_error(
+            ctx.diag.push_error_with_code(
+                DiagnosticCode::InvalidAssignment,
                 format!(
                     "{} needs to be done on a property",
                     if op == '=' { "Assignment" } else { "Self assignment" }
@@ -728,7 +785,8 @@ fn from_self_assignment_node(
             '/' | '*' if ty.as_unit_product().is_some() => Type::Float32,
             _ => {
                 if ty != Type::Invalid {
-                    ctx.diag.push_error(
+                    ctx.diag.push_error_with_code(
+                        DiagnosticCode::TypeMismatch,
                         format!("the {}= operation cannot be done on a {}", op, ty),
                         &lhs_n,
                     );
@@ -892,7 +950,11 @@ fn from_index_expression_node(
 
         let ty = array_expr.ty();
         if !matches!(ty, Type::Array(_) | Type::Invalid) {
-            ctx.diag.push_error(format!("{} is not an indexable type", ty), &node);
+            ctx.diag.push_error_with_code(
+                DiagnosticCode::TypeMismatch,
+                format!("{} is not an indexable type", ty),
+                &node,
+            );
         }
         Expression::ArrayIndex { array: Box::new(array_expr), index: Box::new(index_expr) }
     }
@@ -1031,7 +1093,11 @@ fn continue_lookup_within_element(
     } else if matches!(ctx.property_type, Type::ElementReference) {
         return Expression::ElementReference(Rc::downgrade(elem));
     } else {
-        ctx.diag.push_error("Cannot take reference of an element".into(), &node);
+        ctx.diag.push_error_with_code(
+            DiagnosticCode::InvalidReference,
+            "Cannot take reference of an element".into(),
+            &node,
+        );
         return Expression::Invalid;
     };
     let prop_name = crate::parser::normalize_identifier(second.text());
@@ -1046,7 +1112,11 @@ fn continue_lookup_within_element(
         maybe_lookup_object(prop, it, ctx)
     } else if matches!(property_type, Type::Callback { .. }) {
         if let Some(x) = it.next() {
-            ctx.diag.push_error("Cannot access fields of callback".into(), &x)
+            ctx.diag.push_error_with_code(
+                DiagnosticCode::UnknownMember,
+                "Cannot access fields of callback".into(),
+                &x,
+            )
         }
         Expression::CallbackReference(NamedReference::new(elem, &resolved_name))
     } else if matches!(property_type, Type::Function { .. }) {
@@ -1071,7 +1141,8 @@ fn continue_lookup_within_element(
                     return;
                 }
             };
-            ctx.diag.push_error(
+            ctx.diag.push_error_with_code(
+                DiagnosticCode::UnknownProperty,
                 format!("{} does not have a property '{}'{}", what, second.text(), extra),
                 &second,
             );
@@ -1108,7 +1179,7 @@ fn maybe_lookup_object(
             _ => {
                 if let Some(minus_pos) = next.text().find('-') {
                     if base.lookup(ctx, &next.text()[0..minus_pos]).is_some() {
-                        ctx.diag.push_error(format!("Cannot access the field '{}'. Use space before the '-' if you meant a subtraction", next.text()), &next);
+                        ctx.diag.push_error_with_code(DiagnosticCode::UnknownMember, format!("Cannot access the field '{}'. Use space before the '-' if you meant a subtraction", next.text()), &next);
                         return Expression::Invalid;
                     }
                 }
@@ -1116,7 +1187,8 @@ fn maybe_lookup_object(
                     Type::Struct { .. } => String::new(),
                     ty => format!(" of {}", ty),
                 };
-                ctx.diag.push_error(
+                ctx.diag.push_error_with_code(
+                    DiagnosticCode::UnknownMember,
                     format!("Cannot access the field '{}'{}", next.text(), ty_descr),
                     &next,
                 );
@@ -1139,7 +1211,8 @@ pub fn resolve_two_way_binding(
     match e {
         Expression::PropertyReference(n) => {
             if ty != ctx.property_type && ctx.property_type != Type::InferredProperty {
-                ctx.diag.push_error(
+                ctx.diag.push_error_with_code(
+                    DiagnosticCode::TypeMismatch,
                     "The property does not have the same type as the bound property".into(),
                     &node,
                 );
@@ -1148,14 +1221,19 @@ pub fn resolve_two_way_binding(
         }
         Expression::CallbackReference(n) => {
             if ctx.property_type != Type::InferredCallback && ty != ctx.property_type {
-                ctx.diag.push_error("Cannot bind to a callback".into(), &node);
+                ctx.diag.push_error_with_code(
+                    DiagnosticCode::InvalidBinding,
+                    "Cannot bind to a callback".into(),
+                    &node,
+                );
                 None
             } else {
                 Some(n)
             }
         }
         _ => {
-            ctx.diag.push_error(
+            ctx.diag.push_error_with_code(
+                DiagnosticCode::InvalidBinding,
                 "The expression in a two way binding must be a property reference".into(),
                 &node,
             );
@@ -1,7 +1,7 @@
 // Copyright © SixtyFPS GmbH <info@slint-ui.com>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
-use crate::diagnostics::BuildDiagnostics;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode};
 use crate::langtype::Type;
 use crate::object_tree::*;
 use std::collections::HashMap;
@@ -71,10 +71,14 @@ struct SeenId {
                 debug_assert!(!Rc::ptr_eq(&other_loc.element, elem));
                 let message = format!("duplicated element id '{}'", id);
                 if !other_loc.error_reported {
-                    diag.push_error(message.clone(), &*other_loc.element.borrow());
+                    diag.push_error_with_code(
+                        DiagnosticCode::DuplicatedId,
+                        message.clone(),
+                        &*other_loc.element.borrow(),
+                    );
                     other_loc.error_reported = true;
                 }
-                diag.push_error(message, &*elem_bor);
+                diag.push_error_with_code(DiagnosticCode::DuplicatedId, message, &*elem_bor);
             } else {
                 seen_ids
                     .insert(id.clone(), SeenId { element: elem.clone(), error_reported: false });
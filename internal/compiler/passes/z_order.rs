@@ -6,7 +6,7 @@
 
 use std::rc::Rc;
 
-use crate::diagnostics::BuildDiagnostics;
+use crate::diagnostics::{BuildDiagnostics, DiagnosticCode};
 use crate::expression_tree::{Expression, Unit};
 use crate::langtype::Type;
 use crate::object_tree::{Component, ElementRc};
@@ -80,7 +80,11 @@ fn eval_const_expr(
         Expression::UnaryOp { sub, op: '-' } => eval_const_expr(sub, name, span, diag).map(|v| -v),
         Expression::UnaryOp { sub, op: '+' } => eval_const_expr(sub, name, span, diag),
         _ => {
-            diag.push_error(format!("'{}' must be an number literal", name), span);
+            diag.push_error_with_code(
+                DiagnosticCode::NotConstant,
+                format!("'{}' must be an number literal", name),
+                span,
+            );
             None
         }
     }
@@ -0,0 +1,426 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*! Statistics about the size of a compiled document
+
+The report lists the sub-components of the lowered item tree, with the number of items, properties,
+bindings, repeaters and sub-components in each of them, and the embedded resources with the amount
+of bytes of their data. It helps to find out why the generated code and its data grew.
+*/
+
+use crate::embedded_resources::EmbeddedResourcesKind;
+use crate::langtype::Type;
+use crate::llr::lower_to_item_tree::{component_id, lower_to_item_tree};
+use crate::object_tree::{recurse_elem, Component, Document};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// The statistics of one sub-component of the lowered item tree
+#[derive(Debug, Clone, Default)]
+pub struct ComponentStats {
+    pub name: String,
+    /// Native items, including the ones of inlined sub-components
+    pub items: usize,
+    /// Items that come from sub-components that were inlined
+    pub inlined_items: usize,
+    /// Instances of sub-components that were inlined
+    pub inlined_sub_components: usize,
+    pub properties: usize,
+    /// Bindings and two-way bindings of properties
+    pub bindings: usize,
+    pub repeaters: usize,
+    pub popup_windows: usize,
+    /// Instances of sub-components that were not inlined
+    pub sub_components: usize,
+}
+
+/// The statistics of a global singleton
+#[derive(Debug, Clone, Default)]
+pub struct GlobalStats {
+    pub name: String,
+    pub properties: usize,
+    pub bindings: usize,
+}
+
+/// An embedded image, font, or file
+#[derive(Debug, Clone)]
+pub struct ResourceStats {
+    pub path: String,
+    /// "image" for images processed in a texture, "font" for the pre-rendered glyphs of a font,
+    /// and "file" for the raw content of a file
+    pub kind: &'static str,
+    /// The size of the embedded data. For fonts, this is the size of the bitmaps and of the
+    /// character map, without the other fields of the structures
+    pub bytes: usize,
+    /// The number of glyphs, for fonts
+    pub glyphs: Option<usize>,
+}
+
+/// The format in which [`SizeReport::write_as`] prints the report
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ReportFormat {
+    /// A human readable table
+    Human,
+    /// A JSON object, see [`SizeReport::to_json`]
+    Json,
+}
+
+impl std::str::FromStr for ReportFormat {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("Unknown report format {} (expected human or json)", s)),
+        }
+    }
+}
+
+/// The statistics of a compiled document, see [`size_report`]
+#[derive(Debug, Clone, Default)]
+pub struct SizeReport {
+    pub components: Vec<ComponentStats>,
+    pub globals: Vec<GlobalStats>,
+    pub resources: Vec<ResourceStats>,
+}
+
+impl SizeReport {
+    /// The sum of the bytes of all the embedded resources
+    pub fn total_resource_bytes(&self) -> usize {
+        self.resources.iter().map(|r| r.bytes).sum()
+    }
+
+    #[cfg(feature = "display-diagnostics")]
+    /// Return the report as a JSON object
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "components": self.components.iter().map(|c| serde_json::json!({
+                "name": c.name,
+                "items": c.items,
+                "inlined_items": c.inlined_items,
+                "inlined_sub_components": c.inlined_sub_components,
+                "properties": c.properties,
+                "bindings": c.bindings,
+                "repeaters": c.repeaters,
+                "popup_windows": c.popup_windows,
+                "sub_components": c.sub_components,
+            })).collect::<Vec<_>>(),
+            "globals": self.globals.iter().map(|g| serde_json::json!({
+                "name": g.name,
+                "properties": g.properties,
+                "bindings": g.bindings,
+            })).collect::<Vec<_>>(),
+            "resources": self.resources.iter().map(|r| serde_json::json!({
+                "path": r.path,
+                "kind": r.kind,
+                "bytes": r.bytes,
+                "glyphs": r.glyphs,
+            })).collect::<Vec<_>>(),
+            "total_resource_bytes": self.total_resource_bytes(),
+        })
+    }
+
+    /// Write the report as a human readable table
+    pub fn write_human(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        let name_width = self
+            .components
+            .iter()
+            .map(|c| c.name.len())
+            .chain(self.globals.iter().map(|g| g.name.len()))
+            .chain(std::iter::once("Component".len()))
+            .max()
+            .unwrap_or_default();
+        writeln!(
+            out,
+            "{:name_width$}  {:>6}  {:>13}  {:>10}  {:>8}  {:>9}  {:>6}  {:>14}  {:>22}",
+            "Component",
+            "Items",
+            "Inlined items",
+            "Properties",
+            "Bindings",
+            "Repeaters",
+            "Popups",
+            "Sub-components",
+            "Inlined sub-components",
+        )?;
+        for c in &self.components {
+            writeln!(
+                out,
+                "{:name_width$}  {:>6}  {:>13}  {:>10}  {:>8}  {:>9}  {:>6}  {:>14}  {:>22}",
+                c.name,
+                c.items,
+                c.inlined_items,
+                c.properties,
+                c.bindings,
+                c.repeaters,
+                c.popup_windows,
+                c.sub_components,
+                c.inlined_sub_components,
+            )?;
+        }
+        if !self.globals.is_empty() {
+            writeln!(out)?;
+            writeln!(out, "{:name_width$}  {:>10}  {:>8}", "Global", "Properties", "Bindings")?;
+            for g in &self.globals {
+                writeln!(out, "{:name_width$}  {:>10}  {:>8}", g.name, g.properties, g.bindings)?;
+            }
+        }
+        if !self.resources.is_empty() {
+            writeln!(out)?;
+            writeln!(out, "{:>10}  {:5}  Resource", "Bytes", "Kind")?;
+            for r in &self.resources {
+                write!(out, "{:>10}  {:5}  {}", r.bytes, r.kind, r.path)?;
+                if let Some(glyphs) = r.glyphs {
+                    write!(out, " ({} glyphs)", glyphs)?;
+                }
+                writeln!(out)?;
+            }
+            writeln!(out, "{:>10}  total", self.total_resource_bytes())?;
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "display-diagnostics")]
+    /// Write the report in the given format
+    pub fn write_as(
+        &self,
+        format: ReportFormat,
+        out: &mut dyn std::io::Write,
+    ) -> std::io::Result<()> {
+        match format {
+            ReportFormat::Human => self.write_human(out),
+            ReportFormat::Json => writeln!(out, "{:#}", self.to_json()),
+        }
+    }
+}
+
+/// Compute the statistics of a document that went through all the passes
+pub fn size_report(doc: &Document) -> SizeReport {
+    let mut report = SizeReport::default();
+    if matches!(doc.root_component.root_element.borrow().base_type, Type::Invalid | Type::Void) {
+        return report;
+    }
+
+    let mut inlined = HashMap::new();
+    for component in doc.root_component.used_types.borrow().sub_components.iter() {
+        count_inlined(component, &mut inlined);
+    }
+    count_inlined(&doc.root_component, &mut inlined);
+
+    let llr = lower_to_item_tree(&doc.root_component);
+    llr.for_each_sub_components(&mut |sc, _| {
+        let inlined = inlined.get(&sc.name).copied().unwrap_or_default();
+        report.components.push(ComponentStats {
+            name: sc.name.clone(),
+            items: sc.items.len(),
+            inlined_items: inlined.items,
+            inlined_sub_components: inlined.sub_components,
+            properties: sc.properties.len(),
+            bindings: sc.property_init.len() + sc.two_way_bindings.len(),
+            repeaters: sc.repeated.len(),
+            popup_windows: sc.popup_windows.len(),
+            sub_components: sc.sub_components.len(),
+        })
+    });
+    report.globals = llr
+        .globals
+        .iter()
+        .filter(|g| !g.is_builtin)
+        .map(|g| GlobalStats {
+            name: g.name.clone(),
+            properties: g.properties.len(),
+            bindings: g.init_values.iter().filter(|v| v.is_some()).count(),
+        })
+        .collect();
+
+    let mut resources = doc
+        .root_component
+        .embedded_file_resources
+        .borrow()
+        .iter()
+        .map(|(path, resource)| resource_stats(path, &resource.kind))
+        .collect::<Vec<_>>();
+    resources.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.path.cmp(&b.path)));
+    report.resources = resources;
+
+    report
+}
+
+/// What was inlined in a lowered sub-component
+#[derive(Debug, Clone, Copy, Default)]
+struct InlinedCounts {
+    items: usize,
+    sub_components: usize,
+}
+
+/// Count the native items that come from inlined components, and the inlined instances of
+/// sub-components, by name of the lowered sub-component
+fn count_inlined(component: &Rc<Component>, counts: &mut HashMap<String, InlinedCounts>) {
+    let name = component_id(component);
+    recurse_elem(&component.root_element, &(), &mut |elem, _| {
+        let elem = elem.borrow();
+        if elem.repeated.is_some() {
+            if let Type::Component(repeated) = &elem.base_type {
+                count_inlined(repeated, counts);
+            }
+            return;
+        }
+        let count = counts.entry(name.clone()).or_default();
+        count.sub_components += elem.inlined_components;
+        if elem.inline_depth > 0 && matches!(elem.base_type, Type::Native(_)) {
+            count.items += 1;
+        }
+    });
+    // The elements that were optimized out of the item tree can also come from inlined components
+    for elem in component.optimized_elements.borrow().iter() {
+        counts.entry(name.clone()).or_default().sub_components += elem.borrow().inlined_components;
+    }
+    for popup in component.popup_windows.borrow().iter() {
+        count_inlined(&popup.component, counts);
+    }
+}
+
+fn resource_stats(path: &str, kind: &EmbeddedResourcesKind) -> ResourceStats {
+    match kind {
+        EmbeddedResourcesKind::RawData => ResourceStats {
+            path: path.into(),
+            kind: "file",
+            bytes: crate::fileaccess::load_file(std::path::Path::new(path))
+                .map_or(0, |file| file.read().len()),
+            glyphs: None,
+        },
+        #[cfg(not(target_arch = "wasm32"))]
+        EmbeddedResourcesKind::TextureData(texture) => ResourceStats {
+            path: path.into(),
+            kind: "image",
+            bytes: texture.data.len(),
+            glyphs: None,
+        },
+        #[cfg(not(target_arch = "wasm32"))]
+        EmbeddedResourcesKind::BitmapFontData(font) => {
+            let glyphs = font.glyphs.iter().flat_map(|g| g.glyph_data.iter());
+            ResourceStats {
+                path: path.into(),
+                kind: "font",
+                bytes: glyphs.clone().map(|g| g.data.len()).sum::<usize>()
+                    + font.character_map.len()
+                        * std::mem::size_of::<crate::embedded_resources::CharacterMapEntry>(),
+                glyphs: Some(glyphs.count()),
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        EmbeddedResourcesKind::TextureData(..) => {
+            ResourceStats { path: path.into(), kind: "image", bytes: 0, glyphs: None }
+        }
+        #[cfg(target_arch = "wasm32")]
+        EmbeddedResourcesKind::BitmapFontData(..) => {
+            ResourceStats { path: path.into(), kind: "font", bytes: 0, glyphs: None }
+        }
+    }
+}
+
+#[test]
+fn size_report_counts() {
+    let source = r#"
+export global Palette := {
+    property <color> accent: #f00;
+}
+
+Label := Text {
+    color: Palette.accent;
+}
+
+Card := Rectangle {
+    background: blue;
+    Rectangle { background: red; }
+}
+
+export Main := Window {
+    Card {
+        Label { text: "in card"; }
+    }
+    for i in 3: Label { text: i; }
+    Image { source: @image-url("../../logo/slint-logo-small-light-128x128.png"); }
+    Label { text: "direct"; }
+}
+"#;
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("report.slint");
+    let mut diag = crate::diagnostics::BuildDiagnostics::default();
+    let node = crate::parser::parse(source.into(), Some(&path), &mut diag);
+    let mut config = crate::CompilerConfiguration::new(crate::generator::OutputFormat::Llr);
+    config.inline_all_elements = false;
+    config.embed_resources = crate::EmbedResourcesKind::EmbedAllResources;
+    config.style = Some("fluent".into());
+    let (doc, diag) = spin_on::spin_on(crate::compile_syntax_node(node, diag, config));
+    assert!(!diag.has_error(), "{:?}", diag.to_string_vec());
+
+    let report = size_report(&doc);
+    let main = report.components.iter().find(|c| c.name == "Main").unwrap();
+    // The Card has children, so it is inlined, with its inner Rectangle
+    assert_eq!(main.inlined_sub_components, 1);
+    assert_eq!(main.inlined_items, 1);
+    // The two Label that are not repeated
+    assert_eq!(main.sub_components, 2);
+    assert_eq!(main.repeaters, 1);
+    assert_eq!(main.popup_windows, 0);
+
+    let label = report.components.iter().find(|c| c.name.starts_with("Label")).unwrap();
+    assert_eq!(label.items, 1);
+    assert_eq!(label.inlined_items, 0);
+    assert_eq!(label.inlined_sub_components, 0);
+    assert_eq!(label.sub_components, 0);
+
+    // The component of the repeater, which instantiates a Label
+    let repeated = report.components.iter().find(|c| c.name.starts_with("Component_")).unwrap();
+    assert_eq!(repeated.sub_components, 1);
+    assert_eq!(repeated.repeaters, 0);
+    assert_eq!(report.components.len(), 3);
+
+    assert_eq!(report.globals.len(), 1);
+    assert_eq!(report.globals[0].name, "Palette");
+    assert_eq!(report.globals[0].properties, 1);
+    assert_eq!(report.globals[0].bindings, 1);
+
+    assert_eq!(report.resources.len(), 1);
+    let image = &report.resources[0];
+    assert!(image.path.ends_with("slint-logo-small-light-128x128.png"), "{}", image.path);
+    assert_eq!(image.kind, "file");
+    let file_size = std::fs::metadata(&image.path).unwrap().len() as usize;
+    assert_eq!(image.bytes, file_size);
+    assert_eq!(image.glyphs, None);
+    assert_eq!(report.total_resource_bytes(), file_size);
+
+    #[cfg(feature = "display-diagnostics")]
+    {
+        let json = report.to_json();
+        let keys = |value: &serde_json::Value| {
+            let mut keys = value.as_object().unwrap().keys().cloned().collect::<Vec<_>>();
+            keys.sort();
+            keys
+        };
+        assert_eq!(keys(&json), ["components", "globals", "resources", "total_resource_bytes"]);
+        let main = json["components"].as_array().unwrap().iter().find(|c| c["name"] == "Main");
+        let main = main.unwrap();
+        assert_eq!(
+            keys(main),
+            [
+                "bindings",
+                "inlined_items",
+                "inlined_sub_components",
+                "items",
+                "name",
+                "popup_windows",
+                "properties",
+                "repeaters",
+                "sub_components"
+            ]
+        );
+        assert_eq!(main["inlined_sub_components"], 1);
+        assert_eq!(main["repeaters"], 1);
+        assert_eq!(keys(&json["globals"][0]), ["bindings", "name", "properties"]);
+        assert_eq!(json["globals"][0]["name"], "Palette");
+        assert_eq!(keys(&json["resources"][0]), ["bytes", "glyphs", "kind", "path"]);
+        assert_eq!(json["resources"][0]["kind"], "file");
+        assert_eq!(json["total_resource_bytes"], file_size);
+    }
+}
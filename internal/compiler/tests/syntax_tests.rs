@@ -78,6 +78,13 @@ fn process_diagnostics(
         })
         .collect::<Vec<_>>();
 
+    // The notes are only checked when they are expected, they don't all need to be listed
+    let mut notes = compile_diagnostics
+        .iter()
+        .flat_map(|d| d.notes())
+        .filter(|n| n.source_file().map_or(false, |p| canonical(p) == path))
+        .collect::<Vec<_>>();
+
     let lines = source
         .bytes()
         .enumerate()
@@ -86,8 +93,8 @@ fn process_diagnostics(
 
     // Find expected errors in the file. The first caret (^) points to the expected column. The number of
     // carets refers to the number of lines to go back. This is useful when one line of code produces multiple
-    // errors or warnings.
-    let re = regex::Regex::new(r"\n *//[^\n\^]*(\^+)(error|warning)\{([^\n]*)\}").unwrap();
+    // errors or warnings. A `note` is a secondary location of an error, such as a step of a binding loop.
+    let re = regex::Regex::new(r"\n *//[^\n\^]*(\^+)(error|warning|note)\{([^\n]*)\}").unwrap();
     for m in re.captures_iter(source) {
         let line_begin_offset = m.get(0).unwrap().start();
         let column = m.get(1).unwrap().start() - line_begin_offset;
@@ -112,6 +119,24 @@ fn process_diagnostics(
             line_offset = source[..line_offset].rfind('\n').unwrap_or(0);
         } + column;
 
+        let offset_of = |(l, c): (usize, usize)| lines.get(l.wrapping_sub(2)).unwrap_or(&0) + c;
+
+        if warning_or_error == "note" {
+            match notes
+                .iter()
+                .position(|n| offset_of(n.line_column()) == offset && r.is_match(n.message()))
+            {
+                Some(idx) => {
+                    notes.remove(idx);
+                }
+                None => {
+                    success = false;
+                    println!("{:?}: note not found at offset {}: {:?}", path, offset, rx);
+                }
+            }
+            continue;
+        }
+
         let expected_diag_level = match warning_or_error {
             "warning" => i_slint_compiler::diagnostics::DiagnosticLevel::Warning,
             "error" => i_slint_compiler::diagnostics::DiagnosticLevel::Error,
@@ -119,9 +144,9 @@ fn process_diagnostics(
         };
 
         match diags.iter().position(|e| {
-            let (l, c) = e.line_column();
-            let o = lines.get(l.wrapping_sub(2)).unwrap_or(&0) + c;
-            o == offset && r.is_match(e.message()) && e.level() == expected_diag_level
+            offset_of(e.line_column()) == offset
+                && r.is_match(e.message())
+                && e.level() == expected_diag_level
         }) {
             Some(idx) => {
                 diags.remove(idx);
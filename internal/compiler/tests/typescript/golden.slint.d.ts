@@ -0,0 +1,83 @@
+// This file is auto-generated by the Slint compiler, do not edit.
+
+interface Point {
+    x: number;
+    y: number;
+}
+
+interface Size {
+    width: number;
+    height: number;
+}
+
+interface SlintWindow {
+    show(): void;
+    hide(): void;
+    logical_position: Point;
+    physical_position: Point;
+    logical_size: Size;
+    physical_size: Size;
+}
+
+interface ModelPeer {
+    rowDataChanged(row: number): void;
+    rowAdded(row: number, count: number): void;
+    rowRemoved(row: number, count: number): void;
+    reset(): void;
+}
+
+interface Model<T> {
+    rowCount(): number;
+    rowData(row: number): T;
+    setRowData(row: number, data: T): void;
+    notify: ModelPeer;
+}
+
+interface Callback<Args extends any[], Ret> {
+    (...args: Args): Ret;
+    setHandler(handler: (...args: Args) => Ret): void;
+}
+
+interface Component {
+    run(): void;
+    show(): void;
+    hide(): void;
+    readonly window: SlintWindow;
+    send_mouse_click(x: number, y: number): void;
+    send_keyboard_string_sequence(s: string): void;
+}
+
+export interface Item_Data {
+    item_name: string;
+    sub_items: number[] | Model<number>;
+}
+
+export type TextHorizontalAlignment = "left" | "center" | "right";
+
+export interface App_State {
+    item_count: number;
+    readonly select_item: Callback<[number], Item_Data>;
+}
+
+export interface Main_WindowProperties {
+    all_items?: Item_Data[] | Model<Item_Data>;
+    anonymous_struct?: { visible: boolean; x_pos: number };
+    compute_size?: (arg_0: string) => number;
+    item_clicked?: (arg_0: Item_Data) => void;
+    text_alignment?: TextHorizontalAlignment;
+    the_image?: string | null | undefined;
+}
+
+export interface Main_Window extends Component {
+    all_items: Item_Data[] | Model<Item_Data>;
+    anonymous_struct: { visible: boolean; x_pos: number };
+    readonly compute_size: Callback<[string], number>;
+    readonly item_clicked: Callback<[Item_Data], void>;
+    text_alignment: TextHorizontalAlignment;
+    the_image: string | null | undefined;
+    readonly App_State: App_State;
+}
+
+export declare const Main_Window: {
+    new (init_properties?: Main_WindowProperties): Main_Window;
+};
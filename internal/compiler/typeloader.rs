@@ -6,7 +6,7 @@
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
-use crate::diagnostics::{BuildDiagnostics, Spanned};
+use crate::diagnostics::{BuildDiagnostics, Diagnostic, DiagnosticCode, DiagnosticLevel, Spanned};
 use crate::object_tree::{self, Document};
 use crate::parser::{syntax_nodes, NodeOrToken, SyntaxKind, SyntaxToken};
 use crate::typeregister::TypeRegister;
@@ -18,7 +18,18 @@
 pub struct LoadedDocuments {
     /// maps from the canonical file name to the object_tree::Document
     docs: HashMap<PathBuf, Document>,
+    /// The canonical file names of the documents that were loaded as builtin, and that can use
+    /// the internal types. Kept to resolve them again with the same types when they are invalidated
+    builtin_docs: HashSet<PathBuf>,
     currently_loading: HashSet<PathBuf>,
+    /// maps from the canonical file name to the canonical file names of the documents it imports
+    dependencies: HashMap<PathBuf, HashSet<PathBuf>>,
+    /// The diagnostics of the parsing of the loaded documents, reported again when a document
+    /// is re-resolved without being parsed
+    parse_diagnostics: HashMap<PathBuf, Vec<Diagnostic>>,
+    /// Documents that import a file that was reloaded, and that must be re-resolved from their
+    /// syntax tree
+    invalidated: HashMap<PathBuf, syntax_nodes::Document>,
 }
 
 pub struct ImportedTypes {
@@ -77,10 +88,12 @@ pub fn new(
         .or_else(|| {
             let legacy_fallback = std::env::var("SIXTYFPS_STYLE").ok();
             if legacy_fallback.is_some() {
-                diag.push_diagnostic_with_span(
+                diag.push_coded_diagnostic(
+                    DiagnosticCode::DeprecatedStyleVariable,
                     "Using `SIXTYFPS_STYLE` environment variable for dynamic backend selection. This is deprecated, use `SLINT_STYLE` instead".to_owned(),
                     Default::default(),
-                    crate::diagnostics::DiagnosticLevel::Warning
+                    DiagnosticLevel::Warning,
+                    Some("SLINT_STYLE".into()),
                 )
             }
             legacy_fallback
@@ -89,9 +102,12 @@ pub fn new(
             let is_wasm = cfg!(target_arch = "wasm32")
                 || std::env::var("TARGET").map_or(false, |t| t.starts_with("wasm"));
             if !is_wasm {
-                diag.push_diagnostic_with_span("SLINT_STYLE not defined, defaulting to 'fluent', see https://github.com/slint-ui/slint/issues/83 for more info".to_owned(),
+                diag.push_coded_diagnostic(
+                    DiagnosticCode::DefaultStyle,
+                    "SLINT_STYLE not defined, defaulting to 'fluent', see https://github.com/slint-ui/slint/issues/83 for more info".to_owned(),
                     Default::default(),
-                    crate::diagnostics::DiagnosticLevel::Warning
+                    DiagnosticLevel::Warning,
+                    None,
                 );
             }
             String::from("fluent")
@@ -110,14 +126,16 @@ pub fn new(
                 .find_file_in_include_path(None, &format!("{}/std-widgets.slint", style))
                 .is_none()
         {
-            diag.push_diagnostic_with_span(
+            diag.push_coded_diagnostic(
+                DiagnosticCode::UnknownStyle,
                 format!(
                     "Style {} in not known. Use one of the builtin styles [{}] or make sure your custom style is found in the include directories",
                     &style,
                     known_styles.join(", ")
                 ),
                 Default::default(),
-                crate::diagnostics::DiagnosticLevel::Error,
+                DiagnosticLevel::Error,
+                None,
             );
         }
 
@@ -141,7 +159,8 @@ pub async fn load_dependencies_recursively(
                     self.load_dependency(import, imported_types, registry_to_populate, diagnostics)
                         .await;
                 } else {
-                    diagnostics.push_error(
+                    diagnostics.push_error_with_code(
+                    DiagnosticCode::ImportError,
                     "Import names are missing. Please specify which types you would like to import"
                         .into(),
                     &import.import_token,
@@ -188,6 +207,10 @@ pub fn resolve_import_path(
         import_token: Option<&NodeOrToken>,
         maybe_relative_path_or_url: &str,
     ) -> (std::path::PathBuf, Option<&'static [u8]>) {
+        if let Some(path) = self.resolve_library_path(maybe_relative_path_or_url) {
+            return (path, None);
+        }
+
         let referencing_file_or_url =
             import_token.and_then(|tok| tok.source_file().map(|s| s.path()));
 
@@ -205,9 +228,11 @@ pub fn resolve_import_path(
                                         .map(|url| url.to_string().into())
                                 })
                             } else {
+                                // A missing file is still given the path it will have once created
                                 base_path_or_url.parent().and_then(|base_dir| {
-                                    dunce::canonicalize(base_dir.join(maybe_relative_path_or_url))
-                                        .ok()
+                                    canonicalize_missing_path(
+                                        &base_dir.join(maybe_relative_path_or_url),
+                                    )
                                 })
                             }
                         })
@@ -217,6 +242,21 @@ pub fn resolve_import_path(
             })
     }
 
+    /// Resolves a path starting with `@name` with the library paths of the configuration.
+    /// Returns None if the path doesn't start with the name of a known library.
+    fn resolve_library_path(&self, import_path: &str) -> Option<PathBuf> {
+        let library = import_path.strip_prefix('@')?;
+        let (name, file) = match library.split_once('/') {
+            Some((name, file)) => (name, Some(file)),
+            None => (library, None),
+        };
+        let library_path = self.compiler_config.library_paths.get(name)?;
+        Some(match file {
+            Some(file) => library_path.join(file),
+            None => library_path.clone(),
+        })
+    }
+
     async fn ensure_document_loaded<'b>(
         &'b mut self,
         file_to_import: &'b str,
@@ -236,11 +276,21 @@ async fn ensure_document_loaded<'b>(
         let is_builtin = builtin.is_some();
 
         if !self.all_documents.currently_loading.insert(path_canon.clone()) {
-            diagnostics
-                .push_error(format!("Recursive import of \"{}\"", path.display()), &import_token);
+            diagnostics.push_error_with_code(
+                DiagnosticCode::ImportError,
+                format!("Recursive import of \"{}\"", path.display()),
+                &import_token,
+            );
             return None;
         }
 
+        if let Some(node) = self.all_documents.invalidated.remove(&path_canon) {
+            self.reload_invalidated_document(&path_canon, node, diagnostics).await;
+            let _ok = self.all_documents.currently_loading.remove(path_canon.as_path());
+            assert!(_ok);
+            return Some(path_canon);
+        }
+
         let source_code_result = if let Some(builtin) = builtin {
             Ok(String::from_utf8(builtin)
                 .expect("internal error: embedded file is not UTF-8 source code"))
@@ -254,17 +304,34 @@ async fn ensure_document_loaded<'b>(
         let source_code = match source_code_result {
             Ok(source) => source,
             Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
-                diagnostics.push_error(
-                    format!(
+                let message = match file_to_import.strip_prefix('@') {
+                    Some(library) if self.resolve_library_path(file_to_import).is_none() => {
+                        format!(
+                            "Cannot find requested import \"{}\": there is no library path for '@{}'",
+                            file_to_import,
+                            library.split('/').next().unwrap_or_default()
+                        )
+                    }
+                    Some(_) => format!(
+                        "Cannot find requested import \"{}\" in the library path {}",
+                        file_to_import,
+                        path.display()
+                    ),
+                    None => format!(
                         "Cannot find requested import \"{}\" in the include search path",
                         file_to_import
                     ),
+                };
+                diagnostics.push_error_with_code(
+                    DiagnosticCode::ImportError,
+                    message,
                     &import_token,
                 );
                 return None;
             }
             Err(err) => {
-                diagnostics.push_error(
+                diagnostics.push_error_with_code(
+                    DiagnosticCode::ImportError,
                     format!("Error reading requested import \"{}\": {}", path.display(), err),
                     &import_token,
                 );
@@ -280,6 +347,9 @@ async fn ensure_document_loaded<'b>(
 
     /// Load a file, and its dependency not run the passes.
     ///
+    /// If the file was already loaded, the documents that import it, directly or not, are
+    /// resolved again from their syntax tree, and their path is added to `diagnostics.all_loaded_files`.
+    ///
     /// the path must be the canonical path
     pub async fn load_file(
         &mut self,
@@ -289,8 +359,82 @@ pub async fn load_file(
         is_builtin: bool,
         diagnostics: &mut BuildDiagnostics,
     ) {
+        let diagnostics_before_parsing = diagnostics.iter().count();
         let dependency_doc: syntax_nodes::Document =
             crate::parser::parse(source_code, Some(source_path), diagnostics).into();
+        self.all_documents.parse_diagnostics.insert(
+            path.to_owned(),
+            diagnostics.iter().skip(diagnostics_before_parsing).cloned().collect(),
+        );
+
+        self.invalidate_dependents(path);
+        self.load_document(path, dependency_doc, is_builtin, diagnostics).await;
+
+        while let Some(dependent) = self.all_documents.invalidated.keys().next().cloned() {
+            let node = self.all_documents.invalidated.remove(&dependent).unwrap();
+            self.all_documents.currently_loading.insert(dependent.clone());
+            self.reload_invalidated_document(&dependent, node, diagnostics).await;
+            self.all_documents.currently_loading.remove(&dependent);
+        }
+    }
+
+    /// Remove the documents that import `path`, directly or not, from the loaded documents,
+    /// and keep their syntax tree to resolve them again.
+    fn invalidate_dependents(&mut self, path: &Path) {
+        let mut queue = vec![path.to_owned()];
+        while let Some(changed) = queue.pop() {
+            let dependents = self
+                .all_documents
+                .dependencies
+                .iter()
+                .filter(|(dependent, dependencies)| {
+                    dependencies.contains(&changed)
+                        && !self.all_documents.invalidated.contains_key(*dependent)
+                })
+                .map(|(dependent, _)| dependent.clone())
+                .collect::<Vec<_>>();
+            for dependent in dependents {
+                let node = match self.all_documents.docs.remove(&dependent) {
+                    Some(doc) => doc.node,
+                    None => None,
+                };
+                if let Some(node) = node {
+                    self.all_documents.invalidated.insert(dependent.clone(), node);
+                    queue.push(dependent);
+                }
+            }
+        }
+    }
+
+    /// Resolve again a document that was invalidated, without parsing it
+    async fn reload_invalidated_document(
+        &mut self,
+        path: &Path,
+        node: syntax_nodes::Document,
+        diagnostics: &mut BuildDiagnostics,
+    ) {
+        for diagnostic in self.all_documents.parse_diagnostics.get(path).into_iter().flatten() {
+            diagnostics.push_compiler_error(diagnostic.clone());
+        }
+        diagnostics.all_loaded_files.push(path.to_owned());
+        let is_builtin = self.all_documents.builtin_docs.contains(path);
+        self.load_document(path, node, is_builtin, diagnostics).await;
+    }
+
+    /// Resolve the document from its syntax tree, after loading its dependencies
+    async fn load_document(
+        &mut self,
+        path: &Path,
+        dependency_doc: syntax_nodes::Document,
+        is_builtin: bool,
+        diagnostics: &mut BuildDiagnostics,
+    ) {
+        self.all_documents.dependencies.remove(path);
+        if is_builtin {
+            self.all_documents.builtin_docs.insert(path.to_owned());
+        } else {
+            self.all_documents.builtin_docs.remove(path);
+        }
 
         let dependency_registry =
             Rc::new(RefCell::new(TypeRegister::new(&self.global_type_registry)));
@@ -304,9 +448,10 @@ pub async fn load_file(
             // because they might be nonsense (TODO: we should check that the parse error were really in this document).
             // But we still want to create a document to give better error messages in the root document.
             let mut ignore_diag = BuildDiagnostics::default();
-            ignore_diag.push_error_with_span(
+            ignore_diag.push_error_with_code(
+                DiagnosticCode::ImportError,
                 "Dummy error because some of the code asserts there was an error".into(),
-                Default::default(),
+                &crate::diagnostics::SourceLocation::default(),
             );
             let doc = crate::object_tree::Document::from_node(
                 dependency_doc,
@@ -339,23 +484,32 @@ fn load_dependency<'b>(
             let mut file = import.file.as_str();
             if file == "sixtyfps_widgets.60" {
                 file = "std-widgets.slint";
-                build_diagnostics.push_warning(
+                build_diagnostics.push_coded_diagnostic(
+                    DiagnosticCode::DeprecatedImport,
                     "\"sixtyfps_widgets.60\" was renamed \"std-widgets.slint\". Use of the old file name is deprecated".into(),
-                    &import.import_token,
+                    import.import_token.to_source_location(),
+                    DiagnosticLevel::Warning,
+                    Some("\"std-widgets.slint\"".into()),
                 );
             }
 
-            let doc_path = match self
-                .ensure_document_loaded(
-                    file,
-                    Some(import.import_token.clone().into()),
-                    build_diagnostics,
-                )
-                .await
-            {
-                Some(path) => path,
-                None => return,
-            };
+            // The dependency is recorded even if the file cannot be loaded, so that the importer
+            // is resolved again once the file is loaded
+            let import_token: Option<NodeOrToken> = Some(import.import_token.clone().into());
+            let (path, _) = self.resolve_import_path(import_token.as_ref(), file);
+            let path = dunce::canonicalize(&path).unwrap_or(path);
+            if let Some(importer) = import.import_token.source_file() {
+                let importer = importer.path();
+                let importer =
+                    dunce::canonicalize(importer).unwrap_or_else(|_| importer.to_owned());
+                self.all_documents.dependencies.entry(importer).or_default().insert(path);
+            }
+
+            let doc_path =
+                match self.ensure_document_loaded(file, import_token, build_diagnostics).await {
+                    Some(path) => path,
+                    None => return,
+                };
 
             let doc = self.all_documents.docs.get(&doc_path).unwrap();
             let exports = doc.exports();
@@ -372,7 +526,8 @@ fn load_dependency<'b>(
                 let imported_type = match imported_type {
                     Some(ty) => ty,
                     None => {
-                        build_diagnostics.push_error(
+                        build_diagnostics.push_error_with_code(
+                            DiagnosticCode::ImportError,
                             format!(
                                 "No exported type called '{}' found in \"{}\"",
                                 import_name.external_name, import.file
@@ -439,7 +594,11 @@ async fn collect_dependencies(
             let path_to_import = import_uri.text().to_string();
             let path_to_import = path_to_import.trim_matches('\"').to_string();
             if path_to_import.is_empty() {
-                doc_diagnostics.push_error("Unexpected empty import url".to_owned(), &import_uri);
+                doc_diagnostics.push_error_with_code(
+                    DiagnosticCode::ImportError,
+                    "Unexpected empty import url".to_owned(),
+                    &import_uri,
+                );
                 continue;
             }
 
@@ -472,6 +631,26 @@ pub fn all_documents(&self) -> impl Iterator<Item = &object_tree::Document> + '_
     }
 }
 
+/// Canonicalize the path of a file that may not exist yet: the longest existing ancestor is
+/// canonicalized, and the `.` and `..` components of the rest of the path are resolved.
+fn canonicalize_missing_path(path: &Path) -> Option<PathBuf> {
+    let components = path.components().collect::<Vec<_>>();
+    (1..=components.len()).rev().find_map(|existing| {
+        let mut canonical =
+            dunce::canonicalize(components[..existing].iter().collect::<PathBuf>()).ok()?;
+        for component in &components[existing..] {
+            match component {
+                std::path::Component::CurDir => {}
+                std::path::Component::ParentDir => {
+                    canonical.pop();
+                }
+                component => canonical.push(component),
+            }
+        }
+        Some(canonical)
+    })
+}
+
 #[test]
 fn test_dependency_loading() {
     let test_source_path: std::path::PathBuf =
@@ -556,6 +735,62 @@ fn test_load_from_callback_ok() {
     assert!(!build_diagnostics.has_error());
 }
 
+#[test]
+fn test_library_paths() {
+    let test_source_path: std::path::PathBuf =
+        [env!("CARGO_MANIFEST_DIR"), "tests", "typeloader"].iter().collect();
+
+    let mut compiler_config =
+        CompilerConfiguration::new(crate::generator::OutputFormat::Interpreter);
+    compiler_config.style = Some("fluent".into());
+    compiler_config.library_paths = HashMap::from([
+        ("lib".to_string(), test_source_path.join("incpath")),
+        ("helper".to_string(), test_source_path.join("incpath/local_helper_type.slint")),
+    ]);
+
+    let mut test_diags = crate::diagnostics::BuildDiagnostics::default();
+    let doc_node = crate::parser::parse(
+        r#"
+import { SomeRect } from "@lib/local_helper_type.slint";
+import { SomeRect as OtherRect } from "@helper";
+import { Foo } from "@unknown/foo.slint";
+X := SomeRect { OtherRect {} }
+"#
+        .into(),
+        Some(std::path::Path::new("HELLO")),
+        &mut test_diags,
+    );
+
+    let doc_node: syntax_nodes::Document = doc_node.into();
+    let global_registry = TypeRegister::builtin();
+    let registry = Rc::new(RefCell::new(TypeRegister::new(&global_registry)));
+    let mut build_diagnostics = BuildDiagnostics::default();
+    let mut loader = TypeLoader::new(global_registry, compiler_config, &mut build_diagnostics);
+    spin_on::spin_on(loader.load_dependencies_recursively(
+        &doc_node,
+        &mut build_diagnostics,
+        &registry,
+    ));
+    assert!(!test_diags.has_error());
+    assert!(matches!(registry.borrow().lookup("SomeRect"), crate::langtype::Type::Component(_)));
+    assert!(matches!(registry.borrow().lookup("OtherRect"), crate::langtype::Type::Component(_)));
+    let diags = build_diagnostics.to_string_vec();
+    assert_eq!(diags.len(), 1);
+    assert!(diags[0].contains(
+        "Cannot find requested import \"@unknown/foo.slint\": there is no library path for '@unknown'"
+    ));
+}
+
+#[test]
+fn test_parse_library_path() {
+    assert_eq!(
+        crate::parse_library_path("lib=/some/path"),
+        Ok(("lib".into(), "/some/path".into()))
+    );
+    assert_eq!(crate::parse_library_path("@lib=a=b"), Ok(("lib".into(), "a=b".into())));
+    assert!(crate::parse_library_path("lib").is_err());
+}
+
 #[test]
 fn test_manual_import() {
     let mut compiler_config =
@@ -631,3 +866,143 @@ fn test_unknown_style() {
     assert_eq!(diags.len(), 1);
     assert!(diags[0].starts_with("Style FooBar in not known. Use one of the builtin styles ["));
 }
+
+#[test]
+fn test_canonicalize_missing_path() {
+    let test_source_path: std::path::PathBuf =
+        [env!("CARGO_MANIFEST_DIR"), "tests", "typeloader"].iter().collect();
+    let canonical_dir = dunce::canonicalize(&test_source_path).unwrap();
+    assert_eq!(
+        canonicalize_missing_path(&test_source_path.join("incpath/../missing/./x.slint")),
+        Some(canonical_dir.join("missing/x.slint"))
+    );
+    assert_eq!(
+        canonicalize_missing_path(&test_source_path.join("missing/../incpath")),
+        Some(canonical_dir.join("incpath"))
+    );
+}
+
+#[test]
+fn test_reload_dependents() {
+    let test_source_path: std::path::PathBuf =
+        [env!("CARGO_MANIFEST_DIR"), "tests", "typeloader"].iter().collect();
+
+    let mut compiler_config =
+        CompilerConfiguration::new(crate::generator::OutputFormat::Interpreter);
+    compiler_config.include_paths = vec![test_source_path.join("incpath")];
+    compiler_config.style = Some("fluent".into());
+
+    let global_registry = TypeRegister::builtin();
+    let mut build_diagnostics = BuildDiagnostics::default();
+    let mut loader = TypeLoader::new(global_registry, compiler_config, &mut build_diagnostics);
+
+    let load = |loader: &mut TypeLoader, path: &Path, source: String| {
+        let path = dunce::canonicalize(path).unwrap_or_else(|_| path.to_owned());
+        let mut diag = BuildDiagnostics::default();
+        spin_on::spin_on(loader.load_file(&path, &path, source, false, &mut diag));
+        diag
+    };
+    let main_path = test_source_path.join("dependency_test_main.slint");
+    let helper_path = test_source_path.join("incpath/local_helper_type.slint");
+    let diag = load(&mut loader, &main_path, std::fs::read_to_string(&main_path).unwrap());
+    assert!(!diag.has_error());
+    assert!(diag.all_loaded_files.is_empty());
+
+    let exported_type = |loader: &TypeLoader, path: &Path, name: &str| {
+        let doc = loader.get_document(path).unwrap();
+        match doc.exports().iter().find(|(n, _)| n.as_str() == name) {
+            Some((_, crate::langtype::Type::Component(c))) => c.clone(),
+            _ => panic!("{} is not exported", name),
+        }
+    };
+    let base_of_another_type = |loader: &TypeLoader| {
+        let another_type = exported_type(
+            loader,
+            &test_source_path.join("incpath/dependency_from_incpath.slint"),
+            "AnotherType",
+        );
+        let base = another_type.root_element.borrow().base_type.clone();
+        match base {
+            crate::langtype::Type::Component(c) => c,
+            _ => panic!("AnotherType should inherit from a component"),
+        }
+    };
+    assert!(Rc::ptr_eq(
+        &base_of_another_type(&loader),
+        &exported_type(&loader, &helper_path, "SomeRect")
+    ));
+
+    // Reloading a file resolves again the files that import it, directly or not
+    let diag = load(
+        &mut loader,
+        &helper_path,
+        "export SomeRect := Rectangle { property <int> foo; }".into(),
+    );
+    assert!(!diag.has_error());
+    let reloaded = diag.all_loaded_files.iter().collect::<HashSet<_>>();
+    assert_eq!(reloaded.len(), 3);
+    assert!(reloaded.contains(&dunce::canonicalize(&main_path).unwrap()));
+    assert!(Rc::ptr_eq(
+        &base_of_another_type(&loader),
+        &exported_type(&loader, &helper_path, "SomeRect")
+    ));
+
+    // The errors in the files that import it are reported
+    let diag = load(&mut loader, &helper_path, "export OtherRect := Rectangle {}".into());
+    assert!(diag.has_error());
+    assert!(diag.to_string_vec().iter().any(|d| d.contains("No exported type called 'SomeRect'")));
+
+    // A file importing a missing file is resolved again once the missing file is loaded
+    let missing_path =
+        dunce::canonicalize(&test_source_path).unwrap().join("dependency_test_missing.slint");
+    let diag = load(
+        &mut loader,
+        &main_path,
+        "import { Missing } from \"dependency_test_missing.slint\"; export Main := Missing {}"
+            .into(),
+    );
+    assert!(diag.to_string_vec().iter().any(|d| d.contains("Cannot find requested import")));
+    let diag = load(&mut loader, &missing_path, "export Missing := Rectangle {}".into());
+    assert!(!diag.has_error());
+    assert_eq!(diag.all_loaded_files, vec![dunce::canonicalize(&main_path).unwrap()]);
+    exported_type(&loader, &main_path, "Main");
+}
+
+#[test]
+fn test_reload_when_missing_import_is_created() {
+    let dir = std::env::temp_dir().join(format!("slint_missing_import_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("sub")).unwrap();
+    let main_path = dir.join("sub/main.slint");
+    std::fs::write(
+        &main_path,
+        "import { Missing } from \"../missing.slint\"; export Main := Missing {}",
+    )
+    .unwrap();
+
+    let mut compiler_config =
+        CompilerConfiguration::new(crate::generator::OutputFormat::Interpreter);
+    compiler_config.style = Some("fluent".into());
+    let mut build_diagnostics = BuildDiagnostics::default();
+    let mut loader =
+        TypeLoader::new(TypeRegister::builtin(), compiler_config, &mut build_diagnostics);
+
+    let main_path = dunce::canonicalize(&main_path).unwrap();
+    let mut diag = BuildDiagnostics::default();
+    let source = std::fs::read_to_string(&main_path).unwrap();
+    spin_on::spin_on(loader.load_file(&main_path, &main_path, source, false, &mut diag));
+    assert!(diag.to_string_vec().iter().any(|d| d.contains("Cannot find requested import")));
+
+    // The file is created with the path of the import, and the importer is resolved again
+    let missing_path = dir.join("missing.slint");
+    std::fs::write(&missing_path, "export Missing := Rectangle {}").unwrap();
+    let missing_path = dunce::canonicalize(&missing_path).unwrap();
+    let mut diag = BuildDiagnostics::default();
+    let source = std::fs::read_to_string(&missing_path).unwrap();
+    spin_on::spin_on(loader.load_file(&missing_path, &missing_path, source, false, &mut diag));
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(!diag.has_error(), "{:?}", diag.to_string_vec());
+    assert_eq!(diag.all_loaded_files, vec![main_path.clone()]);
+    let doc = loader.get_document(&main_path).unwrap();
+    assert!(doc.exports().iter().any(|(name, _)| name.as_str() == "Main"));
+}
@@ -179,7 +179,7 @@ pub(crate) fn to_euclid(&self) -> crate::graphics::euclid::default::Size2D<u32>
 }
 
 /// The size of a window represented in either physical or logical pixels. This is used
-/// with [`Window::set_size`].
+/// with [`Window::set_size`], [`Window::set_min_size`] and [`Window::set_max_size`].
 #[derive(Clone, Debug, derive_more::From, PartialEq)]
 pub enum WindowSize {
     /// The size in physical pixels.
@@ -416,6 +416,94 @@ pub fn set_size(&self, size: impl Into<WindowSize>) {
         }
     }
 
+    /// Sets the minimum size of the window, excluding a window frame (if present). The window cannot
+    /// be resized below this size nor below the minimum size of the root element's layout.
+    /// Use `None` to only keep the constraints of the layout.
+    pub fn set_min_size(&self, size: Option<WindowSize>) {
+        self.0.set_min_size(size.map(|size| size.to_logical(self.scale_factor())));
+    }
+
+    /// Sets the maximum size of the window, excluding a window frame (if present). The window cannot
+    /// be resized above this size nor above the maximum size of the root element's layout.
+    /// Use `None` to only keep the constraints of the layout.
+    pub fn set_max_size(&self, size: Option<WindowSize>) {
+        self.0.set_max_size(size.map(|size| size.to_logical(self.scale_factor())));
+    }
+
+    /// Sets whether the user can resize the window. This is the `resizable` property of the
+    /// `Window` element.
+    pub fn set_resizable(&self, resizable: bool) {
+        if let Some(window_item) = self.0.window_item() {
+            window_item.as_pin_ref().resizable.set(resizable);
+        }
+    }
+
+    /// Returns true if the window is shown in fullscreen mode.
+    pub fn is_fullscreen(&self) -> bool {
+        self.0.window_item().map_or(false, |window_item| window_item.as_pin_ref().fullscreen())
+    }
+
+    /// Shows the window in fullscreen mode, or exits the fullscreen mode. This is the `fullscreen`
+    /// property of the `Window` element.
+    pub fn set_fullscreen(&self, fullscreen: bool) {
+        if let Some(window_item) = self.0.window_item() {
+            window_item.as_pin_ref().fullscreen.set(fullscreen);
+        }
+    }
+
+    /// Returns true if the window is maximized.
+    pub fn is_maximized(&self) -> bool {
+        self.0.window_item().map_or(false, |window_item| window_item.as_pin_ref().maximized())
+    }
+
+    /// Maximizes or restores the window. This is the `maximized` property of the `Window` element.
+    pub fn set_maximized(&self, maximized: bool) {
+        if let Some(window_item) = self.0.window_item() {
+            window_item.as_pin_ref().maximized.set(maximized);
+        }
+    }
+
+    /// Returns true if the window is minimized.
+    pub fn is_minimized(&self) -> bool {
+        self.0.window_item().map_or(false, |window_item| window_item.as_pin_ref().minimized())
+    }
+
+    /// Minimizes or restores the window. This is the `minimized` property of the `Window` element.
+    pub fn set_minimized(&self, minimized: bool) {
+        if let Some(window_item) = self.0.window_item() {
+            window_item.as_pin_ref().minimized.set(minimized);
+        }
+    }
+
+    /// Sets whether the window is kept above the other windows. This is the `always-on-top`
+    /// property of the `Window` element.
+    pub fn set_always_on_top(&self, always_on_top: bool) {
+        if let Some(window_item) = self.0.window_item() {
+            window_item.as_pin_ref().always_on_top.set(always_on_top);
+        }
+    }
+
+    /// Sets the window that this window is a transient child of, typically the main window of
+    /// an application for one of its dialogs. The windowing system keeps a transient window on
+    /// top of its parent. Call this function before showing the window.
+    ///
+    /// The call is ignored if `parent` is this window, or is itself a transient child of this
+    /// window.
+    pub fn set_transient_parent(&self, parent: Option<&Window>) {
+        self.0.set_transient_parent(parent.map(|parent| parent.0.window_adapter()));
+    }
+
+    /// Sets whether this window is modal. While a modal window is shown, its transient parent
+    /// (see [`Self::set_transient_parent`]) doesn't receive any input and cannot be closed.
+    pub fn set_modal(&self, modal: bool) {
+        self.0.set_modal(modal);
+    }
+
+    /// Returns true if the window is modal. See [`Self::set_modal`].
+    pub fn is_modal(&self) -> bool {
+        self.0.is_modal()
+    }
+
     /// Dispatch a window event to the scene.
     ///
     /// Use this when you're implementing your own backend and want to forward user input events.
@@ -569,6 +657,18 @@ fn as_weak(&self) -> Weak<Self>
     /// and [`Self::hide`].
     fn run(&self);
 
+    /// Makes the window of this component a transient child of `parent`, for example to show it as
+    /// a dialog of another component. See [`Window::set_transient_parent`].
+    fn set_transient_parent(&self, parent: Option<&Window>) {
+        self.window().set_transient_parent(parent);
+    }
+
+    /// Sets whether the window of this component blocks the input to its transient parent
+    /// while it is shown. See [`Window::set_modal`].
+    fn set_modal(&self, modal: bool) {
+        self.window().set_modal(modal);
+    }
+
     /// This function provides access to instances of global singletons exported in `.slint`.
     /// See [`Global`] for an example how to export and access globals from `.slint` markup.
     fn global<'a, T: Global<'a, Self>>(&'a self) -> T
@@ -94,6 +94,37 @@ pub struct ComponentVTable {
     pub drop_in_place: unsafe fn(VRefMut<ComponentVTable>) -> vtable::Layout,
     /// dealloc function (for VRc)
     pub dealloc: unsafe fn(&ComponentVTable, ptr: *mut u8, layout: vtable::Layout),
+
+    /// Returns the id of the element of the item at the given index, as written in the .slint
+    /// file. The result is empty if the element has no id.
+    ///
+    /// Only used for introspection, so it is only there with the `rtti` feature. It must stay
+    /// the last field so that the layout of the other fields doesn't depend on that feature.
+    #[cfg(feature = "rtti")]
+    pub element_id: extern "C" fn(
+        core::pin::Pin<VRef<ComponentVTable>>,
+        item_index: usize,
+        result: &mut SharedString,
+    ),
+}
+
+/// Expands to the given items only when the `rtti` feature of this crate is enabled.
+/// The generated code wraps its implementation of `ComponentVTable::element_id` with it,
+/// so that it matches the vtable regardless of which crate enabled the feature.
+#[cfg(feature = "rtti")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __rtti_items {
+    ($($tt:tt)*) => {
+        $($tt)*
+    };
+}
+
+#[cfg(not(feature = "rtti"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __rtti_items {
+    ($($tt:tt)*) => {};
 }
 
 #[cfg(test)]
@@ -132,6 +163,39 @@ pub fn unregister_component<Base>(
         .unregister_component(component, &mut item_array.iter().map(|item| item.apply_pin(base)));
 }
 
+#[cfg(all(not(feature = "std"), feature = "unsafe-single-threaded"))]
+use crate::unsafe_single_threaded::thread_local;
+
+thread_local!(
+    /// The globals that are shared by the windows generated from the same .slint file,
+    /// by type of the generated struct that holds them
+    static SHARED_GLOBALS: core::cell::RefCell<
+        alloc::vec::Vec<(core::any::TypeId, alloc::rc::Weak<dyn core::any::Any>)>,
+    > = Default::default()
+);
+
+/// Return the globals of type `T` shared by the windows generated from the same .slint file.
+/// They are created with `create` if no window holds them.
+pub fn shared_globals<T: 'static>(create: impl FnOnce() -> Rc<T>) -> Rc<T> {
+    let existing = SHARED_GLOBALS.with(|shared| {
+        let mut shared = shared.borrow_mut();
+        shared.retain(|(_, globals)| globals.strong_count() > 0);
+        shared
+            .iter()
+            .find(|(type_id, _)| *type_id == core::any::TypeId::of::<T>())
+            .and_then(|(_, globals)| globals.upgrade())
+    });
+    if let Some(globals) = existing {
+        return globals.downcast::<T>().unwrap();
+    }
+    let globals = create();
+    let weak: alloc::rc::Weak<dyn core::any::Any> = Rc::downgrade(&globals);
+    SHARED_GLOBALS.with(|shared| {
+        shared.borrow_mut().push((core::any::TypeId::of::<T>(), weak));
+    });
+    globals
+}
+
 #[cfg(feature = "ffi")]
 pub(crate) mod ffi {
     #![allow(unsafe_code)]
@@ -136,11 +136,18 @@ pub struct FontRequest {
     pub family: Option<SharedString>,
     /// If the weight is None, the system default font weight should be used.
     pub weight: Option<i32>,
+    /// Whether an italic (or oblique) face of the family should be used
+    pub italic: bool,
     /// If the pixel size is None, the system default font size should be used.
     pub pixel_size: Option<Coord>,
     /// The additional spacing (or shrinking if negative) between glyphs. This is usually not submitted to
     /// the font-subsystem but collected here for API convenience
     pub letter_spacing: Option<Coord>,
+    /// The distance between the baselines of two lines of text. If None, the height of the font is used.
+    /// Like the letter spacing, it is collected here for API convenience.
+    pub line_height: Option<Coord>,
+    /// The line drawn under, over or through the text
+    pub decoration: crate::items::TextDecoration,
 }
 
 #[cfg(feature = "ffi")]
@@ -17,7 +17,16 @@
 /// a shape, such as a rectangle, path or even text, shall be filled.
 /// A brush can also be applied to the outline of a shape, that means
 /// the fill of the outline itself.
+///
+/// With the `serde` feature, a solid color is serialized as a color string (such as `"#ff0000ff"`),
+/// and gradients as `{ "linear-gradient": { "angle": 90, "stops": [...] } }` or
+/// `{ "radial-gradient": { "stops": [...] } }`.
 #[derive(Clone, PartialEq, Debug, derive_more::From)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "BrushRepr", into = "BrushRepr")
+)]
 #[repr(C)]
 #[non_exhaustive]
 pub enum Brush {
@@ -164,6 +173,7 @@ pub fn stops(&self) -> impl Iterator<Item = &GradientStop> {
 /// stops are interpolated.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GradientStop {
     /// The color to draw at this stop.
     pub color: Color,
@@ -171,6 +181,54 @@ pub struct GradientStop {
     pub position: f32,
 }
 
+/// The serialized form of a [`Brush`]
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+enum BrushRepr {
+    SolidColor(Color),
+    Gradient(GradientRepr),
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum GradientRepr {
+    LinearGradient { angle: f32, stops: SharedVector<GradientStop> },
+    RadialGradient { stops: SharedVector<GradientStop> },
+}
+
+#[cfg(feature = "serde")]
+impl From<BrushRepr> for Brush {
+    fn from(repr: BrushRepr) -> Self {
+        match repr {
+            BrushRepr::SolidColor(color) => Brush::SolidColor(color),
+            BrushRepr::Gradient(GradientRepr::LinearGradient { angle, stops }) => {
+                Brush::LinearGradient(LinearGradientBrush::new(angle, stops))
+            }
+            BrushRepr::Gradient(GradientRepr::RadialGradient { stops }) => {
+                Brush::RadialGradient(RadialGradientBrush::new_circle(stops))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Brush> for BrushRepr {
+    fn from(brush: Brush) -> Self {
+        match brush {
+            Brush::SolidColor(color) => BrushRepr::SolidColor(color),
+            Brush::LinearGradient(gradient) => BrushRepr::Gradient(GradientRepr::LinearGradient {
+                angle: gradient.angle(),
+                stops: gradient.stops().cloned().collect(),
+            }),
+            Brush::RadialGradient(gradient) => BrushRepr::Gradient(GradientRepr::RadialGradient {
+                stops: gradient.stops().cloned().collect(),
+            }),
+        }
+    }
+}
+
 /// Returns the start / end points of a gradient within the [-0.5; 0.5] unit square, based on the angle (in degree).
 pub fn line_for_angle(angle: f32) -> (Point2D<f32>, Point2D<f32>) {
     let angle = angle.to_radians();
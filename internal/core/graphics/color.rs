@@ -211,6 +211,54 @@ fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
     }
 }
 
+/// Colors are serialized as a string with the same syntax as the color literals of the
+/// .slint language: `"#rrggbbaa"`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Color {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!(
+            "#{:02x}{:02x}{:02x}{:02x}",
+            self.red, self.green, self.blue, self.alpha
+        ))
+    }
+}
+
+/// Colors are deserialized from a string in the `"#rgb"`, `"#rgba"`, `"#rrggbb"` or
+/// `"#rrggbbaa"` format.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Color {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let string = alloc::string::String::deserialize(deserializer)?;
+        let parse = |str: &str| -> Option<Color> {
+            let str = str.strip_prefix('#').filter(|s| s.is_ascii())?;
+            let channel = |i: usize, len: usize| {
+                u8::from_str_radix(&str[i * len..(i + 1) * len], 16).ok().map(|v| {
+                    if len == 1 {
+                        v * 0x11
+                    } else {
+                        v
+                    }
+                })
+            };
+            let (len, alpha) = match str.len() {
+                3 => (1, Some(255)),
+                4 => (1, None),
+                6 => (2, Some(255)),
+                8 => (2, None),
+                _ => return None,
+            };
+            let alpha = alpha.or_else(|| channel(3, len))?;
+            Some(Color::from_argb_u8(alpha, channel(0, len)?, channel(1, len)?, channel(2, len)?))
+        };
+        parse(&string).ok_or_else(|| {
+            serde::de::Error::invalid_value(
+                serde::de::Unexpected::Str(&string),
+                &"a color in the #rrggbbaa format",
+            )
+        })
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 struct HsvaColor {
     h: f32,
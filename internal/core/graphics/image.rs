@@ -12,6 +12,7 @@
 
 #[cfg(feature = "image-decoders")]
 pub mod cache;
+pub(crate) mod compressed;
 #[cfg(target_arch = "wasm32")]
 mod htmlimage;
 #[cfg(feature = "svg")]
@@ -228,6 +229,7 @@ fn eq(&self, other: &Self) -> bool {
 
 #[repr(u8)]
 #[derive(Clone, PartialEq, Debug, Copy)]
+#[non_exhaustive]
 /// The pixel format of a StaticTexture
 pub enum PixelFormat {
     /// red, green, blue. 24bits.
@@ -238,16 +240,32 @@ pub enum PixelFormat {
     RgbaPremultiplied,
     /// Alpha map. 8bits. Each pixel is an alpha value. The color is specified separately.
     AlphaMap,
+    /// Index in a palette. 8bits. The data starts with the number of colors minus one (one byte),
+    /// followed by the palette of premultiplied RGBA colors (four bytes per color), and the pixels.
+    Indexed8,
+    /// Red, green, blue. 16bits (5, 6 and 5 bits), run-length encoded. The data starts with the
+    /// offset of each line as little endian `u32`. Each run starts with a byte `n`: if `n & 0x80` is
+    /// set, the next pixel is repeated `(n & 0x7f) + 1` times, otherwise `n + 1` pixels follow.
+    Rgb565Rle,
+    /// Red, green, blue, alpha. 32bits. The color are premultiplied by alpha. Run-length encoded
+    /// like [`Self::Rgb565Rle`].
+    RgbaPremultipliedRle,
 }
 
 impl PixelFormat {
-    /// The number of bytes in a pixel
-    pub fn bpp(self) -> usize {
+    /// The number of bytes in a pixel.
+    ///
+    /// Returns `None` for the indexed and run-length encoded formats, whose pixels can't be addressed
+    /// directly in the data.
+    pub fn bpp(self) -> Option<usize> {
         match self {
-            PixelFormat::Rgb => 3,
-            PixelFormat::Rgba => 4,
-            PixelFormat::RgbaPremultiplied => 4,
-            PixelFormat::AlphaMap => 1,
+            PixelFormat::Rgb => Some(3),
+            PixelFormat::Rgba => Some(4),
+            PixelFormat::RgbaPremultiplied => Some(4),
+            PixelFormat::AlphaMap => Some(1),
+            PixelFormat::Indexed8 | PixelFormat::Rgb565Rle | PixelFormat::RgbaPremultipliedRle => {
+                None
+            }
         }
     }
 }
@@ -381,10 +399,10 @@ pub fn render_to_buffer(
                     let rect = t.rect.to_usize();
                     for y in 0..rect.height() {
                         let slice = &mut slice[(rect.min_y() + y) * stride..][rect.x_range()];
-                        let source = &ts.data[t.index + y * rect.width() * t.format.bpp()..];
+                        let source = |bpp| &ts.data[t.index + y * rect.width() * bpp..];
                         match t.format {
                             PixelFormat::Rgb => {
-                                let mut iter = source.chunks_exact(3).map(|p| Rgba8Pixel {
+                                let mut iter = source(3).chunks_exact(3).map(|p| Rgba8Pixel {
                                     r: p[0],
                                     g: p[1],
                                     b: p[2],
@@ -393,7 +411,7 @@ pub fn render_to_buffer(
                                 slice.fill_with(|| iter.next().unwrap());
                             }
                             PixelFormat::RgbaPremultiplied => {
-                                let mut iter = source.chunks_exact(4).map(|p| Rgba8Pixel {
+                                let mut iter = source(4).chunks_exact(4).map(|p| Rgba8Pixel {
                                     r: p[0],
                                     g: p[1],
                                     b: p[2],
@@ -402,7 +420,7 @@ pub fn render_to_buffer(
                                 slice.fill_with(|| iter.next().unwrap());
                             }
                             PixelFormat::Rgba => {
-                                let mut iter = source.chunks_exact(4).map(|p| {
+                                let mut iter = source(4).chunks_exact(4).map(|p| {
                                     let a = p[3];
                                     Rgba8Pixel {
                                         r: (p[0] as u16 * a as u16 / 255) as u8,
@@ -415,7 +433,7 @@ pub fn render_to_buffer(
                             }
                             PixelFormat::AlphaMap => {
                                 let col = t.color.to_argb_u8();
-                                let mut iter = source.iter().map(|p| {
+                                let mut iter = source(1).iter().map(|p| {
                                     let a = *p as u32 * col.alpha as u32;
                                     Rgba8Pixel {
                                         r: (col.red as u32 * a / (255 * 255)) as u8,
@@ -426,6 +444,18 @@ pub fn render_to_buffer(
                                 });
                                 slice.fill_with(|| iter.next().unwrap());
                             }
+                            PixelFormat::Indexed8
+                            | PixelFormat::Rgb565Rle
+                            | PixelFormat::RgbaPremultipliedRle => {
+                                if let Some(mut iter) = compressed::line_pixels(
+                                    t.format,
+                                    &ts.data[t.index..],
+                                    rect.width(),
+                                    y,
+                                ) {
+                                    slice.fill_with(|| iter.next().unwrap_or_default());
+                                }
+                            }
                         };
                     }
                 }
@@ -626,6 +656,35 @@ pub fn path(&self) -> Option<&std::path::Path> {
     }
 }
 
+/// Images are serialized as the path of the file they were loaded from, or as `null` for
+/// the empty image. Images that were not loaded from a file cannot be serialized.
+#[cfg(all(feature = "serde", feature = "std"))]
+impl serde::Serialize for Image {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match (&self.0, self.path()) {
+            (ImageInner::None, _) => serializer.serialize_none(),
+            (_, Some(path)) => serializer.collect_str(&path.display()),
+            (_, None) => {
+                Err(serde::ser::Error::custom("only images loaded from a file can be serialized"))
+            }
+        }
+    }
+}
+
+/// Images are deserialized from a path, with [`Image::load_from_path`].
+#[cfg(all(feature = "serde", feature = "std"))]
+impl<'de> serde::Deserialize<'de> for Image {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::Deserialize;
+        match Option::<std::path::PathBuf>::deserialize(deserializer)? {
+            None => Ok(Image::default()),
+            Some(path) => Image::load_from_path(&path).map_err(|_| {
+                serde::de::Error::custom(format_args!("cannot load image from {}", path.display()))
+            }),
+        }
+    }
+}
+
 /// Load an image from an image embedded in the binary.
 /// This is called by the generated code.
 #[cfg(feature = "image-decoders")]
@@ -0,0 +1,197 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+Decoding of the indexed and run-length encoded [`PixelFormat`]s of the [`StaticTexture`](super::StaticTexture)s.
+
+The data of such a texture starts at [`StaticTexture::index`](super::StaticTexture::index):
+
+ - [`PixelFormat::Indexed8`]: one byte with the number of colors in the palette minus one, then the palette
+   with four bytes per color (premultiplied red, green, blue, alpha), then one byte per pixel with the index
+   of its color in the palette.
+ - [`PixelFormat::Rgb565Rle`] and [`PixelFormat::RgbaPremultipliedRle`]: the offset of each line from the
+   start of the texture data as a little endian `u32`, then the lines. A line is a sequence of runs, each
+   starting with a byte `n`: if `n & 0x80` is set, the next pixel is repeated `(n & 0x7f) + 1` times,
+   otherwise the `n + 1` next pixels are copied. A pixel is a little endian `u16` for `Rgb565Rle`, and four
+   bytes (premultiplied red, green, blue, alpha) for `RgbaPremultipliedRle`.
+
+Lines are decoded on the fly, so that no buffer is needed to draw these textures.
+*/
+
+use super::{PixelFormat, Rgba8Pixel};
+
+/// Iterator over the premultiplied pixels of one line of a texture in one of the formats of this module.
+pub enum LinePixels<'a> {
+    Indexed { palette: &'a [u8], pixels: core::slice::Iter<'a, u8> },
+    Rle(RleLine<'a>),
+}
+
+/// Returns an iterator over the pixels of the line `y` of a texture of the given `width`, starting
+/// with the first column. `data` is the data of the texture, starting at its index.
+///
+/// Returns `None` if the format isn't one of the formats of this module, or if the line isn't
+/// in the data. The iterator stops early if the line is truncated.
+pub fn line_pixels(format: PixelFormat, data: &[u8], width: usize, y: usize) -> Option<LinePixels> {
+    match format {
+        PixelFormat::Indexed8 => {
+            let palette_len = (*data.first()? as usize + 1) * 4;
+            let palette = data.get(1..1 + palette_len)?;
+            let pixels = data.get(1 + palette_len + y * width..)?;
+            let pixels = pixels.get(..width).unwrap_or(pixels);
+            Some(LinePixels::Indexed { palette, pixels: pixels.iter() })
+        }
+        PixelFormat::Rgb565Rle | PixelFormat::RgbaPremultipliedRle => {
+            let offset = data.get(y * 4..y * 4 + 4)?;
+            let offset = u32::from_le_bytes(offset.try_into().ok()?) as usize;
+            let pixel_size = if format == PixelFormat::Rgb565Rle { 2 } else { 4 };
+            Some(LinePixels::Rle(RleLine {
+                data: data.get(offset..)?,
+                pixel_size,
+                remaining_in_run: 0,
+                repeat: false,
+                remaining_in_line: width,
+            }))
+        }
+        PixelFormat::Rgb
+        | PixelFormat::Rgba
+        | PixelFormat::RgbaPremultiplied
+        | PixelFormat::AlphaMap => None,
+    }
+}
+
+impl<'a> Iterator for LinePixels<'a> {
+    type Item = Rgba8Pixel;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            LinePixels::Indexed { palette, pixels } => {
+                let index = *pixels.next()? as usize * 4;
+                match palette.get(index..index + 4)? {
+                    &[r, g, b, a] => Some(Rgba8Pixel { r, g, b, a }),
+                    _ => None,
+                }
+            }
+            LinePixels::Rle(rle) => rle.next(),
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match self {
+            LinePixels::Indexed { pixels, .. } => {
+                if n > 0 {
+                    pixels.nth(n - 1)?;
+                }
+                self.next()
+            }
+            LinePixels::Rle(rle) => {
+                rle.skip(n);
+                rle.next()
+            }
+        }
+    }
+}
+
+pub struct RleLine<'a> {
+    /// The data starting at the current pixel (or at the header of the next run)
+    data: &'a [u8],
+    pixel_size: usize,
+    remaining_in_run: usize,
+    repeat: bool,
+    remaining_in_line: usize,
+}
+
+impl<'a> RleLine<'a> {
+    fn start_run(&mut self) -> Option<()> {
+        let (header, rest) = self.data.split_first()?;
+        self.data = rest;
+        self.remaining_in_run = (header & 0x7f) as usize + 1;
+        self.repeat = header & 0x80 != 0;
+        Some(())
+    }
+
+    /// Skips `n` pixels, without decoding them
+    fn skip(&mut self, mut n: usize) {
+        n = n.min(self.remaining_in_line);
+        self.remaining_in_line -= n;
+        while n > 0 {
+            if self.remaining_in_run == 0 && self.start_run().is_none() {
+                return;
+            }
+            let count = n.min(self.remaining_in_run);
+            self.remaining_in_run -= count;
+            n -= count;
+            let consumed = if !self.repeat {
+                count * self.pixel_size
+            } else if self.remaining_in_run == 0 {
+                self.pixel_size
+            } else {
+                0
+            };
+            self.data = self.data.get(consumed..).unwrap_or_default();
+        }
+    }
+
+    fn next(&mut self) -> Option<Rgba8Pixel> {
+        if self.remaining_in_line == 0 {
+            return None;
+        }
+        if self.remaining_in_run == 0 {
+            self.start_run()?;
+        }
+        let p = self.data.get(..self.pixel_size)?;
+        let pixel = if self.pixel_size == 2 {
+            let v = u16::from_le_bytes([p[0], p[1]]);
+            let (r, g, b) = ((v >> 11) as u8, (v >> 5) as u8 & 0x3f, v as u8 & 0x1f);
+            Rgba8Pixel { r: r << 3 | r >> 2, g: g << 2 | g >> 4, b: b << 3 | b >> 2, a: 255 }
+        } else {
+            Rgba8Pixel { r: p[0], g: p[1], b: p[2], a: p[3] }
+        };
+        self.remaining_in_line -= 1;
+        self.remaining_in_run -= 1;
+        if !self.repeat || self.remaining_in_run == 0 {
+            self.data = self.data.get(self.pixel_size..).unwrap_or_default();
+        }
+        Some(pixel)
+    }
+}
+
+#[test]
+fn decode_lines() {
+    // 2x2 Rgb565Rle: line 0 is a repeated white pixel, line 1 is a red and a blue literal pixel
+    let data = [
+        8, 0, 0, 0, 11, 0, 0, 0, // offsets
+        0x81, 0xff, 0xff, // line 0
+        0x01, 0x00, 0xf8, 0x1f, 0x00, // line 1
+    ];
+    let white = Rgba8Pixel { r: 255, g: 255, b: 255, a: 255 };
+    let red = Rgba8Pixel { r: 255, g: 0, b: 0, a: 255 };
+    let blue = Rgba8Pixel { r: 0, g: 0, b: 255, a: 255 };
+    let line = |y| {
+        line_pixels(PixelFormat::Rgb565Rle, &data, 2, y).unwrap().collect::<alloc::vec::Vec<_>>()
+    };
+    assert_eq!(line(0), [white, white]);
+    assert_eq!(line(1), [red, blue]);
+    assert_eq!(line_pixels(PixelFormat::Rgb565Rle, &data, 2, 1).unwrap().nth(1), Some(blue));
+    assert_eq!(line_pixels(PixelFormat::Rgb565Rle, &data, 2, 0).unwrap().nth(1), Some(white));
+    assert_eq!(line_pixels(PixelFormat::Rgb565Rle, &data, 2, 0).unwrap().nth(2), None);
+
+    // 3x1 Indexed8 with two colors
+    let data = [1, 0, 0, 0, 0, 10, 20, 30, 255, 1, 0, 1];
+    let color = Rgba8Pixel { r: 10, g: 20, b: 30, a: 255 };
+    let transparent = Rgba8Pixel { r: 0, g: 0, b: 0, a: 0 };
+    assert_eq!(
+        line_pixels(PixelFormat::Indexed8, &data, 3, 0).unwrap().collect::<alloc::vec::Vec<_>>(),
+        [color, transparent, color]
+    );
+    assert_eq!(line_pixels(PixelFormat::Indexed8, &data, 3, 0).unwrap().nth(2), Some(color));
+
+    // Truncated or invalid data doesn't panic
+    assert!(line_pixels(PixelFormat::Indexed8, &[], 3, 0).is_none());
+    assert_eq!(line_pixels(PixelFormat::Indexed8, &data[..9], 3, 0).unwrap().count(), 0);
+    assert_eq!(line_pixels(PixelFormat::Indexed8, &data, 3, 1).unwrap().count(), 0);
+    let data = [4, 0, 0, 0, 0x85, 0xff];
+    assert!(line_pixels(PixelFormat::Rgb565Rle, &data, 6, 1).is_none());
+    assert_eq!(line_pixels(PixelFormat::Rgb565Rle, &data, 6, 0).unwrap().count(), 0);
+    assert_eq!(line_pixels(PixelFormat::Rgb565Rle, &data, 6, 0).unwrap().nth(3), None);
+    assert!(line_pixels(PixelFormat::Rgb, &data, 2, 0).is_none());
+}
@@ -398,6 +398,14 @@ pub struct MouseInputState {
     grabbed: bool,
 }
 
+impl MouseInputState {
+    /// Returns true if an item has grabbed the mouse, meaning it should receive the
+    /// next events even if they are outside of its geometry.
+    pub(crate) fn is_grabbed(&self) -> bool {
+        self.grabbed && !self.item_stack.is_empty()
+    }
+}
+
 /// Try to handle the mouse grabber. Return true if the event has handled, or false otherwise
 fn handle_mouse_grab(
     mouse_event: &MouseEvent,
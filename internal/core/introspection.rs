@@ -0,0 +1,190 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+ This module provides a way to inspect the tree of elements of a running component,
+ for example from tests or from tools that drive an application.
+
+ It works the same way for generated code and for the interpreter, as it only relies on
+ the [`ComponentVTable`](crate::component::ComponentVTable) and the run-time type information
+ of the builtin items.
+*/
+
+use crate::accessibility::AccessibleStringProperty;
+use crate::api::Window;
+use crate::graphics::Rect;
+use crate::item_tree::ItemRc;
+use crate::items::{AccessibleRole, ItemRef};
+use crate::rtti::{BuiltinItem, ValueType};
+use crate::window::WindowInner;
+use crate::SharedString;
+use alloc::vec::Vec;
+
+/// Calls `$callback!` with the list of all the builtin items that can be inspected
+macro_rules! for_each_builtin_item {
+    ($callback:ident) => {
+        $callback![
+            crate::items::ImageItem,
+            crate::items::ClippedImage,
+            crate::items::Text,
+            crate::items::Rectangle,
+            crate::items::BorderRectangle,
+            crate::items::TouchArea,
+            crate::items::FocusScope,
+            crate::items::Path,
+            crate::items::Flickable,
+            crate::items::WindowItem,
+            crate::items::TextInput,
+            crate::items::Clip,
+            crate::items::BoxShadow,
+            crate::items::Rotate,
+            crate::items::Opacity,
+            crate::items::Layer,
+            crate::items::MenuEntryItem,
+            crate::items::MenuBarItem,
+            crate::items::ContextMenuArea,
+        ]
+    };
+}
+
+/// A handle to an element in the tree of a running component.
+///
+/// The handle keeps the component that contains the element alive, but the element may
+/// still go away if it is in a repeater whose model changes.
+#[derive(Clone)]
+pub struct ElementHandle(ItemRc);
+
+impl ElementHandle {
+    /// Returns a handle to the root element of the component shown in the given window,
+    /// or None if the window does not show any component.
+    pub fn root_of(window: &Window) -> Option<Self> {
+        let component = WindowInner::from_pub(window).try_component()?;
+        Some(Self(ItemRc::new(component, 0)))
+    }
+
+    /// Returns the underlying item
+    pub fn item_rc(&self) -> &ItemRc {
+        &self.0
+    }
+
+    /// The id of the element, as written in the .slint file.
+    /// This is empty if the element does not have an id.
+    pub fn id(&self) -> SharedString {
+        self.0.element_id()
+    }
+
+    /// The name of the builtin item of this element (for example `"Rectangle"` or `"Text"`),
+    /// or None if this is not one of the builtin items of the core library (for example
+    /// the native widgets of a style).
+    pub fn type_name(&self) -> Option<&'static str> {
+        let item = self.0.borrow();
+        macro_rules! type_name {
+            ($($ty:ty,)*) => {
+                $(if ItemRef::downcast_pin::<$ty>(item).is_some() {
+                    return Some(<$ty as BuiltinItem>::name());
+                })*
+            };
+        }
+        for_each_builtin_item!(type_name);
+        None
+    }
+
+    /// The geometry of the element, relative to the window
+    pub fn geometry(&self) -> Rect {
+        let geometry = self.0.geometry();
+        Rect::new(self.0.map_to_window(geometry.origin), geometry.size)
+    }
+
+    /// The value of the `accessible-role` property of this element
+    pub fn accessible_role(&self) -> AccessibleRole {
+        self.0.accessible_role()
+    }
+
+    /// The value of an `accessible-` string property of this element, such as its label
+    pub fn accessible_string_property(&self, what: AccessibleStringProperty) -> SharedString {
+        self.0.accessible_string_property(what)
+    }
+
+    /// Returns the parent element, or None if this is the root element
+    pub fn parent(&self) -> Option<Self> {
+        self.0.parent_item().map(Self)
+    }
+
+    /// Returns the children of this element, including the ones created by repeaters
+    pub fn children(&self) -> impl Iterator<Item = Self> {
+        core::iter::successors(self.0.first_child(), |item| item.next_sibling()).map(Self)
+    }
+
+    /// Returns this element and all the elements below it, in depth-first order
+    pub fn descendants(&self) -> Vec<Self> {
+        let mut result = Vec::new();
+        let mut stack = alloc::vec![self.clone()];
+        while let Some(element) = stack.pop() {
+            let first_child = stack.len();
+            stack.extend(element.children());
+            stack[first_child..].reverse();
+            result.push(element);
+        }
+        result
+    }
+
+    /// Returns the first element with the given id in this element and its descendants
+    pub fn find_by_id(&self, id: &str) -> Option<Self> {
+        self.descendants().into_iter().find(|element| element.id() == id)
+    }
+
+    /// Returns the value of the property with the given name (for example `"background"`)
+    /// of the builtin item of this element.
+    ///
+    /// Only the properties of the builtin item are available: the properties declared with
+    /// `property <...>` in the .slint file, on this element or in the component it instantiates,
+    /// are not part of the item and cannot be accessed with this function.
+    ///
+    /// Returns None if the element is not a builtin item, if the property does not exist,
+    /// or if it cannot be converted to `V`.
+    pub fn get_property<V: ValueType>(&self, name: &str) -> Option<V> {
+        let item = self.0.borrow();
+        macro_rules! get_property {
+            ($($ty:ty,)*) => {
+                $(if let Some(item) = ItemRef::downcast_pin::<$ty>(item) {
+                    let (_, prop) = <$ty as BuiltinItem>::properties::<V>()
+                        .into_iter()
+                        .find(|(n, _)| *n == name)?;
+                    return prop.get(item).ok();
+                })*
+            };
+        }
+        for_each_builtin_item!(get_property);
+        None
+    }
+
+    /// Sets the property with the given name of the builtin item of this element.
+    ///
+    /// This replaces any binding of the property. Like for [`Self::get_property()`], only the
+    /// properties of the builtin item are available, and an error is returned for any other.
+    pub fn set_property<V: ValueType>(&self, name: &str, value: V) -> Result<(), ()> {
+        let item = self.0.borrow();
+        macro_rules! set_property {
+            ($($ty:ty,)*) => {
+                $(if let Some(item) = ItemRef::downcast_pin::<$ty>(item) {
+                    let (_, prop) = <$ty as BuiltinItem>::properties::<V>()
+                        .into_iter()
+                        .find(|(n, _)| *n == name)
+                        .ok_or(())?;
+                    return prop.set(item, value, None);
+                })*
+            };
+        }
+        for_each_builtin_item!(set_property);
+        Err(())
+    }
+}
+
+impl core::fmt::Debug for ElementHandle {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ElementHandle")
+            .field("id", &self.id())
+            .field("type_name", &self.type_name())
+            .finish()
+    }
+}
@@ -180,6 +180,15 @@ pub fn accessible_string_property(
         result
     }
 
+    /// The id of the element of this item, as written in the .slint file, or an empty string
+    #[cfg(feature = "rtti")]
+    pub fn element_id(&self) -> SharedString {
+        let comp_ref_pin = vtable::VRc::borrow_pin(&self.component);
+        let mut result = Default::default();
+        comp_ref_pin.as_ref().element_id(self.index, &mut result);
+        result
+    }
+
     pub fn geometry(&self) -> Rect {
         self.borrow().as_ref().geometry()
     }
@@ -969,6 +978,9 @@ fn accessible_string_property(
             _: &mut SharedString,
         ) {
         }
+
+        #[cfg(feature = "rtti")]
+        fn element_id(self: Pin<&Self>, _: usize, _: &mut SharedString) {}
     }
 
     crate::component::ComponentVTable_static!(static TEST_COMPONENT_VT for TestComponent);
@@ -45,6 +45,8 @@
 pub use text::*;
 mod image;
 pub use self::image::*;
+mod menu;
+pub use menu::*;
 #[cfg(feature = "std")]
 mod path;
 #[cfg(feature = "std")]
@@ -59,6 +61,7 @@
 pub type KeyEventArg = (KeyEvent,);
 type PointerEventArg = (PointerEvent,);
 type PointArg = (Point,);
+type StringArg = (SharedString,);
 
 #[cfg(all(feature = "ffi", windows))]
 #[macro_export]
@@ -1037,6 +1040,11 @@ pub struct WindowItem {
     pub background: Property<Color>,
     pub title: Property<SharedString>,
     pub no_frame: Property<bool>,
+    pub resizable: Property<bool>,
+    pub fullscreen: Property<bool>,
+    pub maximized: Property<bool>,
+    pub minimized: Property<bool>,
+    pub always_on_top: Property<bool>,
     pub icon: Property<crate::graphics::Image>,
     pub default_font_family: Property<SharedString>,
     pub default_font_size: Property<Coord>,
@@ -1252,6 +1260,11 @@ macro_rules! declare_enums {
     ($( $(#[$enum_doc:meta])* enum $Name:ident { $( $(#[$value_doc:meta])* $Value:ident,)* })*) => {
         $(
             #[derive(Copy, Clone, Debug, PartialEq, Eq, strum::EnumString, strum::Display, Hash)]
+            #[cfg_attr(
+                feature = "serde",
+                derive(serde::Serialize, serde::Deserialize),
+                serde(rename_all = "kebab-case")
+            )]
             #[repr(C)]
             #[strum(serialize_all = "kebab-case")]
             $(#[$enum_doc])*
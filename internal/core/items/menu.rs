@@ -0,0 +1,461 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+/*!
+This module contains the builtin items used to implement the menus.
+
+The `MenuBar`, `Menu`, `MenuItem`, `MenuSeparator` and `ContextMenu` elements are lowered
+by the compiler to the items of this module. The `MenuEntryItem` are never rendered: they keep
+the structure of the menu in the item tree so that backends with native menus, and the
+handling of the keyboard shortcuts, can walk it.
+
+When adding an item or a property, it needs to be kept in sync with different place.
+Lookup the [`crate::items`] module documentation.
+*/
+
+use super::{
+    Item, ItemConsts, ItemRc, ItemRendererRef, KeyEventResult, PointArg, PointerEventButton,
+    RenderingResult, VoidArg,
+};
+use crate::graphics::Rect;
+use crate::input::{
+    FocusEvent, FocusEventResult, InputEventFilterResult, InputEventResult, KeyEvent,
+    KeyboardModifiers, MouseEvent,
+};
+use crate::item_rendering::CachedRenderingData;
+use crate::layout::{LayoutInfo, Orientation};
+#[cfg(feature = "rtti")]
+use crate::rtti::*;
+use crate::window::WindowAdapter;
+use crate::{Callback, Coord, Property, SharedString};
+use alloc::rc::Rc;
+use const_field_offset::FieldOffsets;
+use core::pin::Pin;
+use i_slint_core_macros::*;
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+/// The implementation of an entry of a menu. The `Menu`, `MenuItem` and `MenuSeparator` elements
+/// all get lowered to this item. A `Menu` is an entry that has other entries as children.
+pub struct MenuEntryItem {
+    pub title: Property<SharedString>,
+    pub shortcut: Property<SharedString>,
+    pub enabled: Property<bool>,
+    pub checkable: Property<bool>,
+    pub checked: Property<bool>,
+    pub is_separator: Property<bool>,
+    pub activated: Callback<VoidArg>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for MenuEntryItem {
+    fn init(self: Pin<&Self>, _window_adapter: &Rc<dyn WindowAdapter>) {}
+
+    fn geometry(self: Pin<&Self>) -> Rect {
+        Default::default()
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        _orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        LayoutInfo::default()
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn render(
+        self: Pin<&Self>,
+        _backend: &mut ItemRendererRef,
+        _self_rc: &ItemRc,
+    ) -> RenderingResult {
+        RenderingResult::ContinueRenderingWithoutChildren
+    }
+}
+
+impl MenuEntryItem {
+    /// Activate this entry the same way as if the user had clicked on it: the `checked`
+    /// state of checkable entries is toggled, and the `activated` callback is invoked.
+    pub fn activate(self: Pin<&Self>) {
+        if !self.enabled() || self.is_separator() {
+            return;
+        }
+        if self.checkable() {
+            Self::FIELD_OFFSETS.checked.apply_pin(self).set(!self.checked());
+        }
+        Self::FIELD_OFFSETS.activated.apply_pin(self).call(&());
+    }
+}
+
+impl ItemConsts for MenuEntryItem {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        MenuEntryItem,
+        CachedRenderingData,
+    > = MenuEntryItem::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+declare_item_vtable! {
+    fn slint_get_MenuEntryItemVTable() -> MenuEntryItemVTable for MenuEntryItem
+}
+
+/// Returns an iterator over the [`MenuEntryItem`] that are direct children of `parent`.
+pub fn menu_entries(parent: &ItemRc) -> impl Iterator<Item = ItemRc> {
+    core::iter::successors(parent.first_child(), |item| item.next_sibling())
+        .filter(|item| item.downcast::<MenuEntryItem>().is_some())
+}
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+/// The root item of the `MenuBar` element.
+///
+/// When the component is instantiated, the item is registered with the window, which offers the
+/// backend to show its entries as a native menu bar, and sets the `native` property if the
+/// backend did so. See [`WindowInner::register_menu_bar`](crate::window::WindowInner::register_menu_bar).
+pub struct MenuBarItem {
+    pub x: Property<Coord>,
+    pub y: Property<Coord>,
+    pub width: Property<Coord>,
+    pub height: Property<Coord>,
+    pub native: Property<bool>,
+    pub native_height: Property<Coord>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for MenuBarItem {
+    fn init(self: Pin<&Self>, _window_adapter: &Rc<dyn WindowAdapter>) {}
+
+    fn geometry(self: Pin<&Self>) -> Rect {
+        euclid::rect(self.x(), self.y(), self.width(), self.height())
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        _orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        LayoutInfo::default()
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardAndIgnore
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        InputEventResult::EventIgnored
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn render(
+        self: Pin<&Self>,
+        _backend: &mut ItemRendererRef,
+        _self_rc: &ItemRc,
+    ) -> RenderingResult {
+        RenderingResult::ContinueRenderingChildren
+    }
+}
+
+impl MenuBarItem {
+    /// Sets the `native` and `native_height` properties from the height of the native
+    /// menu bar, or `None` if the menu bar is rendered within the window.
+    pub(crate) fn set_native_height(self: Pin<&Self>, native_height: Option<Coord>) {
+        Self::FIELD_OFFSETS.native.apply_pin(self).set(native_height.is_some());
+        Self::FIELD_OFFSETS.native_height.apply_pin(self).set(native_height.unwrap_or_default());
+    }
+}
+
+impl ItemConsts for MenuBarItem {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        MenuBarItem,
+        CachedRenderingData,
+    > = MenuBarItem::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+declare_item_vtable! {
+    fn slint_get_MenuBarItemVTable() -> MenuBarItemVTable for MenuBarItem
+}
+
+/// Activates the entry of the menu bar whose shortcut matches the key event.
+/// Returns true if an entry was activated.
+///
+/// Native menu bars handle their shortcuts themselves, so nothing is done for them.
+pub fn activate_menu_shortcut(menu_bar: &ItemRc, event: &KeyEvent) -> bool {
+    match menu_bar.downcast::<MenuBarItem>() {
+        Some(bar) if !bar.as_pin_ref().native() => activate_shortcut_in(menu_bar, event),
+        _ => false,
+    }
+}
+
+fn activate_shortcut_in(parent: &ItemRc, event: &KeyEvent) -> bool {
+    for item in menu_entries(parent) {
+        let entry = item.downcast::<MenuEntryItem>().unwrap();
+        let entry = entry.as_pin_ref();
+        if !entry.enabled() {
+            continue;
+        }
+        if shortcut_matches(entry.shortcut().as_str(), event) {
+            entry.activate();
+            return true;
+        }
+        if activate_shortcut_in(&item, event) {
+            return true;
+        }
+    }
+    false
+}
+
+macro_rules! declare_special_key_from_name {
+    ($($char:literal # $name:ident # $($_qt:ident)|* # $($_winit:ident)|* ;)*) => {
+        fn special_key_from_name(name: &str) -> Option<char> {
+            $(if name.eq_ignore_ascii_case(stringify!($name)) {
+                return Some($char);
+            })*
+            const ALIASES: &[(&str, char)] = &[
+                ("Esc", crate::input::key_codes::Escape),
+                ("Del", crate::input::key_codes::Delete),
+                ("Enter", crate::input::key_codes::Return),
+                ("Ins", crate::input::key_codes::Insert),
+                ("Up", crate::input::key_codes::UpArrow),
+                ("Down", crate::input::key_codes::DownArrow),
+                ("Left", crate::input::key_codes::LeftArrow),
+                ("Right", crate::input::key_codes::RightArrow),
+                ("PgUp", crate::input::key_codes::PageUp),
+                ("PgDown", crate::input::key_codes::PageDown),
+                ("Space", ' '),
+            ];
+            ALIASES.iter().find(|(alias, _)| name.eq_ignore_ascii_case(alias)).map(|(_, c)| *c)
+        }
+    };
+}
+i_slint_common::for_each_special_keys!(declare_special_key_from_name);
+
+/// Returns true if the key event corresponds to the shortcut, written as in the `shortcut`
+/// property of a `MenuItem`: modifiers and a key separated by `+`, for example `"Ctrl+Shift+S"`
+/// or `"F5"`. The comparison of the key is case insensitive.
+pub fn shortcut_matches(shortcut: &str, event: &KeyEvent) -> bool {
+    let mut parts = shortcut.split('+').map(str::trim).collect::<alloc::vec::Vec<_>>();
+    let mut key = match parts.pop() {
+        Some(key) => key,
+        None => return false,
+    };
+    if key.is_empty() && parts.last() == Some(&"") {
+        // "Ctrl++"
+        parts.pop();
+        key = "+";
+    }
+
+    let mut modifiers = KeyboardModifiers::default();
+    for modifier in parts {
+        let is_any_of = |names: &[&str]| names.iter().any(|n| modifier.eq_ignore_ascii_case(n));
+        let flag = if is_any_of(&["Ctrl", "Control"]) {
+            &mut modifiers.control
+        } else if is_any_of(&["Shift"]) {
+            &mut modifiers.shift
+        } else if is_any_of(&["Alt", "Option"]) {
+            &mut modifiers.alt
+        } else if is_any_of(&["Meta", "Cmd", "Command", "Super", "Win"]) {
+            &mut modifiers.meta
+        } else {
+            return false;
+        };
+        *flag = true;
+    }
+    if modifiers != event.modifiers {
+        return false;
+    }
+
+    let mut key_chars = key.chars();
+    let key = match (key_chars.next(), key_chars.next()) {
+        (Some(c), None) => c,
+        (Some(_), Some(_)) => match special_key_from_name(key) {
+            Some(c) => c,
+            None => return false,
+        },
+        (None, _) => return false,
+    };
+
+    let mut text = event.text.chars();
+    match (text.next(), text.next()) {
+        (Some(c), None) => c == key || c.to_lowercase().eq(key.to_lowercase()),
+        _ => false,
+    }
+}
+
+#[repr(C)]
+#[derive(FieldOffsets, Default, SlintElement)]
+#[pin]
+/// The root item of the `ContextMenu` element. It only reacts to a press of the right mouse
+/// button, and leaves all other events to the items below it.
+pub struct ContextMenuArea {
+    pub x: Property<Coord>,
+    pub y: Property<Coord>,
+    pub width: Property<Coord>,
+    pub height: Property<Coord>,
+    pub enabled: Property<bool>,
+    pub show: Callback<PointArg>,
+    pub cached_rendering_data: CachedRenderingData,
+}
+
+impl Item for ContextMenuArea {
+    fn init(self: Pin<&Self>, _window_adapter: &Rc<dyn WindowAdapter>) {}
+
+    fn geometry(self: Pin<&Self>) -> Rect {
+        euclid::rect(self.x(), self.y(), self.width(), self.height())
+    }
+
+    fn layout_info(
+        self: Pin<&Self>,
+        _orientation: Orientation,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> LayoutInfo {
+        LayoutInfo::default()
+    }
+
+    fn input_event_filter_before_children(
+        self: Pin<&Self>,
+        _: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventFilterResult {
+        InputEventFilterResult::ForwardEvent
+    }
+
+    fn input_event(
+        self: Pin<&Self>,
+        event: MouseEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+        _self_rc: &ItemRc,
+    ) -> InputEventResult {
+        match event {
+            MouseEvent::Pressed { position, button: PointerEventButton::Right }
+                if self.enabled() =>
+            {
+                Self::FIELD_OFFSETS.show.apply_pin(self).call(&(position,));
+                InputEventResult::EventAccepted
+            }
+            _ => InputEventResult::EventIgnored,
+        }
+    }
+
+    fn key_event(
+        self: Pin<&Self>,
+        _: &KeyEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> KeyEventResult {
+        KeyEventResult::EventIgnored
+    }
+
+    fn focus_event(
+        self: Pin<&Self>,
+        _: &FocusEvent,
+        _window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> FocusEventResult {
+        FocusEventResult::FocusIgnored
+    }
+
+    fn render(
+        self: Pin<&Self>,
+        _backend: &mut ItemRendererRef,
+        _self_rc: &ItemRc,
+    ) -> RenderingResult {
+        RenderingResult::ContinueRenderingChildren
+    }
+}
+
+impl ItemConsts for ContextMenuArea {
+    const cached_rendering_data_offset: const_field_offset::FieldOffset<
+        ContextMenuArea,
+        CachedRenderingData,
+    > = ContextMenuArea::FIELD_OFFSETS.cached_rendering_data.as_unpinned_projection();
+}
+
+declare_item_vtable! {
+    fn slint_get_ContextMenuAreaVTable() -> ContextMenuAreaVTable for ContextMenuArea
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::KeyEventType;
+
+    fn key(text: &str, modifiers: KeyboardModifiers) -> KeyEvent {
+        KeyEvent { modifiers, text: text.into(), event_type: KeyEventType::KeyPressed }
+    }
+
+    #[test]
+    fn test_shortcut_matches() {
+        let ctrl = KeyboardModifiers { control: true, ..Default::default() };
+        let ctrl_shift = KeyboardModifiers { control: true, shift: true, ..Default::default() };
+
+        assert!(shortcut_matches("Ctrl+O", &key("o", ctrl)));
+        assert!(shortcut_matches("ctrl+o", &key("o", ctrl)));
+        assert!(!shortcut_matches("Ctrl+O", &key("o", Default::default())));
+        assert!(!shortcut_matches("Ctrl+O", &key("p", ctrl)));
+        assert!(shortcut_matches("Ctrl+Shift+S", &key("S", ctrl_shift)));
+        assert!(!shortcut_matches("Ctrl+S", &key("s", ctrl_shift)));
+        assert!(shortcut_matches("Ctrl++", &key("+", ctrl)));
+        assert!(shortcut_matches("F5", &key("\u{F708}", Default::default())));
+        assert!(shortcut_matches("Del", &key("\u{007f}", Default::default())));
+        assert!(!shortcut_matches("Hyper+X", &key("x", Default::default())));
+        assert!(!shortcut_matches("", &key("x", Default::default())));
+    }
+}
@@ -10,8 +10,8 @@
 
 use super::{
     InputType, Item, ItemConsts, ItemRc, KeyEventResult, KeyEventType, PointArg,
-    PointerEventButton, RenderingResult, TextHorizontalAlignment, TextOverflow,
-    TextVerticalAlignment, TextWrap, VoidArg,
+    PointerEventButton, RenderingResult, StringArg, TextDecoration, TextFormat,
+    TextHorizontalAlignment, TextOverflow, TextVerticalAlignment, TextWrap, VoidArg,
 };
 use crate::graphics::{Brush, Color, FontRequest, Rect};
 use crate::input::{
@@ -22,6 +22,8 @@
 use crate::layout::{LayoutInfo, Orientation};
 #[cfg(feature = "rtti")]
 use crate::rtti::*;
+use crate::textlayout::bidi::BidiInfo;
+use crate::textlayout::styled_text::StyledText;
 use crate::window::{WindowAdapter, WindowInner};
 use crate::{Callback, Coord, Property, SharedString};
 use alloc::rc::Rc;
@@ -48,11 +50,20 @@ pub struct Text {
     pub wrap: Property<TextWrap>,
     pub overflow: Property<TextOverflow>,
     pub letter_spacing: Property<Coord>,
+    pub font_italic: Property<bool>,
+    pub text_decoration: Property<TextDecoration>,
+    pub line_height: Property<Coord>,
+    pub text_format: Property<TextFormat>,
+    pub link_clicked: Callback<StringArg>,
     pub x: Property<Coord>,
     pub y: Property<Coord>,
     pub width: Property<Coord>,
     pub height: Property<Coord>,
     pub cached_rendering_data: CachedRenderingData,
+    /// true when the mouse was pressed on a link, and is grabbed until it is released
+    link_pressed: core::cell::Cell<bool>,
+    /// The byte offset of the start of the link on which the mouse was pressed
+    pressed_link_start: core::cell::Cell<usize>,
 }
 
 impl Item for Text {
@@ -68,13 +79,19 @@ fn layout_info(
         window_adapter: &Rc<dyn WindowAdapter>,
     ) -> LayoutInfo {
         let window_inner = WindowInner::from_pub(window_adapter.window());
-        let implicit_size = |max_width| {
-            window_adapter.renderer().text_size(
+        let implicit_size = |max_width| match self.text_format() {
+            TextFormat::Plain => window_adapter.renderer().text_size(
                 self.font_request(window_inner),
                 self.text().as_str(),
                 max_width,
                 window_adapter.window().scale_factor(),
-            )
+            ),
+            TextFormat::Markup => window_adapter.renderer().styled_text_size(
+                self.font_request(window_inner),
+                &self.styled_text(),
+                max_width,
+                window_adapter.window().scale_factor(),
+            ),
         };
 
         // Stretch uses `round_layout` to explicitly align the top left and bottom right of layout nodes
@@ -123,16 +140,51 @@ fn input_event_filter_before_children(
         _window_adapter: &Rc<dyn WindowAdapter>,
         _self_rc: &ItemRc,
     ) -> InputEventFilterResult {
-        InputEventFilterResult::ForwardAndIgnore
+        // Only the links of a markup react to the mouse
+        match self.text_format() {
+            TextFormat::Plain => InputEventFilterResult::ForwardAndIgnore,
+            TextFormat::Markup => InputEventFilterResult::ForwardEvent,
+        }
     }
 
     fn input_event(
         self: Pin<&Self>,
-        _: MouseEvent,
-        _window_adapter: &Rc<dyn WindowAdapter>,
+        event: MouseEvent,
+        window_adapter: &Rc<dyn WindowAdapter>,
         _self_rc: &ItemRc,
     ) -> InputEventResult {
-        InputEventResult::EventIgnored
+        if self.text_format() != TextFormat::Markup {
+            return InputEventResult::EventIgnored;
+        }
+        match event {
+            MouseEvent::Pressed { position, button: PointerEventButton::Left } => {
+                match self.link_span_at(position, window_adapter) {
+                    Some((start, _)) => {
+                        self.link_pressed.set(true);
+                        self.pressed_link_start.set(start);
+                        InputEventResult::GrabMouse
+                    }
+                    None => InputEventResult::EventIgnored,
+                }
+            }
+            MouseEvent::Released { position, button: PointerEventButton::Left }
+                if self.link_pressed.get() =>
+            {
+                self.link_pressed.set(false);
+                if let Some((start, url)) = self.link_span_at(position, window_adapter) {
+                    if start == self.pressed_link_start.get() {
+                        Self::FIELD_OFFSETS.link_clicked.apply_pin(self).call(&(url,));
+                    }
+                }
+                InputEventResult::EventAccepted
+            }
+            MouseEvent::Exit => {
+                self.link_pressed.set(false);
+                InputEventResult::EventIgnored
+            }
+            _ if self.link_pressed.get() => InputEventResult::GrabMouse,
+            _ => InputEventResult::EventIgnored,
+        }
     }
 
     fn key_event(
@@ -167,6 +219,27 @@ impl ItemConsts for Text {
 }
 
 impl Text {
+    /// Returns the text to show, with the spans of its markup if the `text-format` is markup
+    pub fn styled_text(self: Pin<&Self>) -> StyledText {
+        match self.text_format() {
+            TextFormat::Plain => StyledText::plain(self.text()),
+            TextFormat::Markup => StyledText::parse_markup(self.text().as_str()),
+        }
+    }
+
+    /// Returns the start of the link span at the given position and its url, if there is a link there
+    fn link_span_at(
+        self: Pin<&Self>,
+        position: crate::graphics::Point,
+        window_adapter: &Rc<dyn WindowAdapter>,
+    ) -> Option<(usize, SharedString)> {
+        let byte_offset =
+            window_adapter.renderer().text_byte_offset_for_position(self, position)?;
+        let styled_text = self.styled_text();
+        let span = styled_text.span_at(byte_offset)?;
+        Some((span.range.start, span.style.link.clone()?))
+    }
+
     pub fn font_request(self: Pin<&Self>, window: &WindowInner) -> FontRequest {
         let window_item = window.window_item();
 
@@ -187,6 +260,7 @@ pub fn font_request(self: Pin<&Self>, window: &WindowInner) -> FontRequest {
                     Some(weight)
                 }
             },
+            italic: self.font_italic(),
             pixel_size: {
                 let font_size = self.font_size();
                 if font_size == 0 as Coord {
@@ -196,6 +270,15 @@ pub fn font_request(self: Pin<&Self>, window: &WindowInner) -> FontRequest {
                 }
             },
             letter_spacing: Some(self.letter_spacing()),
+            line_height: {
+                let line_height = self.line_height();
+                if line_height > 0 as Coord {
+                    Some(line_height)
+                } else {
+                    None
+                }
+            },
+            decoration: self.text_decoration(),
         }
     }
 }
@@ -217,6 +300,9 @@ pub struct TextInput {
     pub wrap: Property<TextWrap>,
     pub input_type: Property<InputType>,
     pub letter_spacing: Property<Coord>,
+    pub font_italic: Property<bool>,
+    pub text_decoration: Property<TextDecoration>,
+    pub line_height: Property<Coord>,
     pub x: Property<Coord>,
     pub y: Property<Coord>,
     pub width: Property<Coord>,
@@ -356,6 +442,15 @@ fn key_event(
                 match event.text_shortcut() {
                     Some(text_shortcut) if !self.read_only() => match text_shortcut {
                         TextShortcut::Move(direction) => {
+                            // The arrow keys move the cursor visually, so left moves forward
+                            // in a right-to-left paragraph
+                            let text = self.text();
+                            let cursor = (self.cursor_position().max(0) as usize).min(text.len());
+                            let direction = if BidiInfo::new(&text).is_rtl_paragraph_at(cursor) {
+                                direction.mirrored()
+                            } else {
+                                direction
+                            };
                             TextInput::move_cursor(
                                 self,
                                 direction,
@@ -527,6 +622,19 @@ pub enum TextCursorDirection {
     EndOfText,
 }
 
+impl TextCursorDirection {
+    /// Swaps the forward and backward directions, for right-to-left text
+    fn mirrored(self) -> Self {
+        match self {
+            Self::Forward => Self::Backward,
+            Self::Backward => Self::Forward,
+            Self::ForwardByWord => Self::BackwardByWord,
+            Self::BackwardByWord => Self::ForwardByWord,
+            direction => direction,
+        }
+    }
+}
+
 impl core::convert::TryFrom<char> for TextCursorDirection {
     type Error = ();
 
@@ -839,6 +947,7 @@ pub fn font_request(self: Pin<&Self>, window_adapter: &Rc<dyn WindowAdapter>) ->
                     Some(weight)
                 }
             },
+            italic: self.font_italic(),
             pixel_size: {
                 let font_size = self.font_size();
                 if font_size == 0 as Coord {
@@ -848,6 +957,15 @@ pub fn font_request(self: Pin<&Self>, window_adapter: &Rc<dyn WindowAdapter>) ->
                 }
             },
             letter_spacing: Some(self.letter_spacing()),
+            line_height: {
+                let line_height = self.line_height();
+                if line_height > 0 as Coord {
+                    Some(line_height)
+                } else {
+                    None
+                }
+            },
+            decoration: self.text_decoration(),
         }
     }
 }
@@ -23,6 +23,8 @@
 pub mod component;
 pub mod graphics;
 pub mod input;
+#[cfg(feature = "rtti")]
+pub mod introspection;
 pub mod item_focus;
 pub mod item_rendering;
 pub mod item_tree;
@@ -511,6 +511,22 @@ fn from(model: Rc<dyn Model<Data = T> + 'static>) -> Self {
     }
 }
 
+/// Models are serialized as a sequence of their rows.
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize + 'static> serde::Serialize for ModelRc<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+/// Models are deserialized from a sequence, into a [`VecModel`].
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de> + Clone + 'static> serde::Deserialize<'de> for ModelRc<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(ModelRc::new(VecModel::from(Vec::<T>::deserialize(deserializer)?)))
+    }
+}
+
 impl<T> TryInto<Rc<dyn Model<Data = T>>> for ModelRc<T> {
     type Error = ();
 
@@ -1041,6 +1057,11 @@ pub fn components_vec(&self) -> Vec<ComponentRc<C>> {
 /// Represent an item in a StandardListView
 #[repr(C)]
 #[derive(Clone, Default, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, rename_all = "kebab-case")
+)]
 pub struct StandardListViewItem {
     /// The text content of the item
     pub text: crate::SharedString,
@@ -1058,6 +1079,39 @@ fn from(other: SharedString) -> Self {
     }
 }
 
+/// Represent a column header of a StandardTableView
+#[repr(C)]
+#[derive(Clone, Default, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, rename_all = "kebab-case")
+)]
+pub struct TableColumn {
+    /// The title of the column header
+    pub title: crate::SharedString,
+    /// The minimum column width (logical length)
+    pub min_width: crate::Coord,
+    /// The width of the column (logical length). If it is 0, the column is sized by the layout.
+    pub width: crate::Coord,
+    /// The horizontal column stretch
+    pub horizontal_stretch: f32,
+    /// Whether the column can be sorted by clicking on its header
+    pub sortable: bool,
+}
+
+impl From<&str> for TableColumn {
+    fn from(other: &str) -> Self {
+        Self { title: other.into(), ..Default::default() }
+    }
+}
+
+impl From<SharedString> for TableColumn {
+    fn from(other: SharedString) -> Self {
+        Self { title: other, ..Default::default() }
+    }
+}
+
 #[test]
 fn test_tracking_model_handle() {
     let model: Rc<VecModel<u8>> = Rc::new(Default::default());
@@ -27,6 +27,15 @@ fn run_event_loop(&self) {
         unimplemented!("The backend does not implement running an eventloop")
     }
 
+    /// Like [`Self::run_event_loop`], but returns an error instead of panicking when the event
+    /// loop cannot run, for example when the screen cannot be opened.
+    ///
+    /// The default implementation calls [`Self::run_event_loop`].
+    fn try_run_event_loop(&self) -> Result<(), PlatformError> {
+        self.run_event_loop();
+        Ok(())
+    }
+
     /// Specify if the event loop should quit quen the last window is closed.
     /// The default behavior is `true`.
     /// When this is set to `false`, the event loop must keep running until
@@ -144,6 +153,27 @@ pub enum SetPlatformError {
     AlreadySet,
 }
 
+/// This enum describes the errors that may occur when the platform runs the event loop,
+/// see [`Platform::try_run_event_loop`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PlatformError {
+    /// The platform failed to run the event loop, for example because the screen could not be opened.
+    /// The string describes the error.
+    Other(String),
+}
+
+impl core::fmt::Display for PlatformError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PlatformError::Other(message) => f.write_str(message),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PlatformError {}
+
 /// Set the Slint platform abstraction.
 ///
 /// If the platform abstraction was already set this will return `Err`.
@@ -18,6 +18,29 @@ fn text_size(
         scale_factor: f32,
     ) -> Size;
 
+    /// Returns the size of the given text with styled spans in logical pixels, like [`Self::text_size`].
+    /// The default implementation ignores the styles of the spans.
+    fn styled_text_size(
+        &self,
+        font_request: crate::graphics::FontRequest,
+        text: &crate::textlayout::styled_text::StyledText,
+        max_width: Option<Coord>,
+        scale_factor: f32,
+    ) -> Size {
+        self.text_size(font_request, text.text.as_str(), max_width, scale_factor)
+    }
+
+    /// Returns the (UTF-8) byte offset in the text shown by the `Text` element (without its markup)
+    /// of the character at the given coordinate, or None if there is no character there.
+    /// This is used to find the link that is clicked in a markup.
+    fn text_byte_offset_for_position(
+        &self,
+        _text: Pin<&crate::items::Text>,
+        _pos: Point,
+    ) -> Option<usize> {
+        None
+    }
+
     /// Returns the (UTF-8) byte offset in the text property that refers to the character that contributed to
     /// the glyph cluster that's visually nearest to the given coordinate. This is used for hit-testing,
     /// for example when receiving a mouse click into a text field. Then this function returns the "cursor"
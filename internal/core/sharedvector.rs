@@ -417,6 +417,20 @@ fn eq(&self, other: &U) -> bool {
 
 impl<T: Eq> Eq for SharedVector<T> {}
 
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for SharedVector<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.as_slice())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for SharedVector<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(alloc::vec::Vec::<T>::deserialize(deserializer)?.into_iter().collect())
+    }
+}
+
 impl<T: Clone> IntoIterator for SharedVector<T> {
     type Item = T;
     type IntoIter = IntoIter<T>;
@@ -17,7 +17,7 @@
     PhysicalPx, PhysicalRect, PhysicalSize, PointLengths, RectLengths, ScaleFactor, SizeLengths,
 };
 use crate::renderer::Renderer;
-use crate::textlayout::{FontMetrics as _, TextParagraphLayout};
+use crate::textlayout::{FontMetrics as _, TextLayout, TextParagraphLayout};
 use crate::window::{WindowAdapter, WindowInner};
 use crate::{Color, Coord, ImageInner, StaticTextures};
 use alloc::rc::{Rc, Weak};
@@ -245,6 +245,26 @@ pub fn render_by_line(&self, line_buffer: impl LineBufferProvider) {
     }
 }
 
+/// Returns the paragraph layout of the text of a `Text` element, in the given size
+fn text_paragraph_layout<'a>(
+    text: Pin<&crate::items::Text>,
+    string: &'a str,
+    layout: TextLayout<'a, fonts::Font>,
+    max_size: PhysicalSize,
+) -> TextParagraphLayout<'a, fonts::Font> {
+    TextParagraphLayout {
+        string,
+        layout,
+        max_width: max_size.width_length(),
+        max_height: max_size.height_length(),
+        horizontal_alignment: text.horizontal_alignment(),
+        vertical_alignment: text.vertical_alignment(),
+        wrap: text.wrap(),
+        overflow: text.overflow(),
+        single_line: false,
+    }
+}
+
 #[doc(hidden)]
 impl<const MAX_BUFFER_AGE: usize> Renderer for SoftwareRenderer<MAX_BUFFER_AGE> {
     fn text_size(
@@ -254,7 +274,61 @@ fn text_size(
         max_width: Option<Coord>,
         scale_factor: f32,
     ) -> crate::graphics::Size {
-        fonts::text_size(font_request, text, max_width, ScaleFactor::new(scale_factor)).to_untyped()
+        fonts::text_size(font_request, text, &[], max_width, ScaleFactor::new(scale_factor))
+            .to_untyped()
+    }
+
+    fn styled_text_size(
+        &self,
+        font_request: crate::graphics::FontRequest,
+        text: &crate::textlayout::styled_text::StyledText,
+        max_width: Option<Coord>,
+        scale_factor: f32,
+    ) -> crate::graphics::Size {
+        fonts::text_size(
+            font_request,
+            text.text.as_str(),
+            &text.spans,
+            max_width,
+            ScaleFactor::new(scale_factor),
+        )
+        .to_untyped()
+    }
+
+    fn text_byte_offset_for_position(
+        &self,
+        text: Pin<&crate::items::Text>,
+        pos: crate::graphics::Point,
+    ) -> Option<usize> {
+        let window_adapter = self.window.upgrade()?;
+        let window = WindowInner::from_pub(window_adapter.window());
+        let scale_factor = ScaleFactor::new(window.scale_factor());
+        let styled_text = text.styled_text();
+
+        let font_request = text.font_request(window);
+        let font = fonts::match_font(&font_request, scale_factor);
+        let span_fonts = fonts::span_fonts(&styled_text.spans, &font_request, scale_factor);
+        let layout = fonts::text_layout_for_font(&font, &span_fonts, &font_request, scale_factor);
+        let max_size = (text.logical_geometry().size.cast() * scale_factor).cast();
+        let paragraph = text_paragraph_layout(text, &styled_text.text, layout, max_size);
+
+        let pos: PhysicalPoint = (LogicalPoint::from_untyped(pos).cast() * scale_factor).cast();
+        let mut byte_offset = None;
+        paragraph.layout_lines(|glyphs, line_x, line_y| {
+            if byte_offset.is_some()
+                || pos.y < line_y.get()
+                || pos.y >= (line_y + font.height()).get()
+            {
+                return;
+            }
+            byte_offset = glyphs
+                .find(|glyph| {
+                    let x = line_x + glyph.x;
+                    pos.x >= x.get() && pos.x < (x + glyph.advance).get()
+                })
+                .map(|glyph| glyph.text_byte_offset);
+        });
+        byte_offset
     }
 
     fn text_input_byte_offset_for_position(
@@ -290,6 +364,22 @@ fn mark_dirty_region(&self, region: crate::item_rendering::DirtyRegion) {
     fn register_bitmap_font(&self, font_data: &'static crate::graphics::BitmapFont) {
         fonts::register_bitmap_font(font_data);
     }
+
+    #[cfg(feature = "software-renderer-systemfonts")]
+    fn register_font_from_memory(
+        &self,
+        data: &'static [u8],
+    ) -> Result<(), alloc::boxed::Box<dyn std::error::Error>> {
+        fonts::register_font_from_memory(data)
+    }
+
+    #[cfg(feature = "software-renderer-systemfonts")]
+    fn register_font_from_path(
+        &self,
+        path: &std::path::Path,
+    ) -> Result<(), alloc::boxed::Box<dyn std::error::Error>> {
+        fonts::register_font_from_path(path)
+    }
 }
 
 fn render_window_frame_by_line<const MAX_BUFFER_AGE: usize>(
@@ -572,16 +662,41 @@ enum SceneCommand {
 }
 
 struct SceneTexture<'a> {
+    /// For the uncompressed formats, the data starting at the first pixel to draw.
+    /// For the compressed formats, the data of the whole texture.
     data: &'a [u8],
     format: PixelFormat,
-    /// bytes between two lines in the source
+    /// bytes between two lines in the source.
+    /// For the compressed formats, the width of the texture in pixels.
     stride: u16,
     source_size: PhysicalSize,
+    /// The position of the first pixel to draw in the texture, for the compressed formats
+    source_origin: PhysicalPoint,
     color: Color,
 }
 
+enum SharedBufferData {
+    SharedImage(SharedImageBuffer),
+    /// The coverage of the pixels of a glyph rasterized at run-time, drawn with the colorize color
+    #[cfg(feature = "software-renderer-systemfonts")]
+    AlphaMap {
+        data: Rc<[u8]>,
+        width: u16,
+    },
+}
+
+impl SharedBufferData {
+    fn width(&self) -> usize {
+        match self {
+            SharedBufferData::SharedImage(image) => image.width() as usize,
+            #[cfg(feature = "software-renderer-systemfonts")]
+            SharedBufferData::AlphaMap { width, .. } => *width as usize,
+        }
+    }
+}
+
 struct SharedBufferCommand {
-    buffer: SharedImageBuffer,
+    buffer: SharedBufferData,
     /// The source rectangle that is mapped into this command span
     source_rect: PhysicalRect,
     colorize: Color,
@@ -589,29 +704,43 @@ struct SharedBufferCommand {
 
 impl SharedBufferCommand {
     fn as_texture(&self) -> SceneTexture<'_> {
-        let begin = self.buffer.width() as usize * self.source_rect.min_y() as usize
+        let begin = self.buffer.width() * self.source_rect.min_y() as usize
             + self.source_rect.min_x() as usize;
 
         match &self.buffer {
-            SharedImageBuffer::RGB8(b) => SceneTexture {
+            SharedBufferData::SharedImage(SharedImageBuffer::RGB8(b)) => SceneTexture {
                 data: &b.as_bytes()[begin * 3..],
                 stride: 3 * b.stride() as u16,
                 format: PixelFormat::Rgb,
                 source_size: self.source_rect.size,
+                source_origin: Default::default(),
                 color: self.colorize,
             },
-            SharedImageBuffer::RGBA8(b) => SceneTexture {
+            SharedBufferData::SharedImage(SharedImageBuffer::RGBA8(b)) => SceneTexture {
                 data: &b.as_bytes()[begin * 4..],
                 stride: 4 * b.stride() as u16,
                 format: PixelFormat::Rgba,
                 source_size: self.source_rect.size,
+                source_origin: Default::default(),
                 color: self.colorize,
             },
-            SharedImageBuffer::RGBA8Premultiplied(b) => SceneTexture {
-                data: &b.as_bytes()[begin * 4..],
-                stride: 4 * b.stride() as u16,
-                format: PixelFormat::RgbaPremultiplied,
+            SharedBufferData::SharedImage(SharedImageBuffer::RGBA8Premultiplied(b)) => {
+                SceneTexture {
+                    data: &b.as_bytes()[begin * 4..],
+                    stride: 4 * b.stride() as u16,
+                    format: PixelFormat::RgbaPremultiplied,
+                    source_size: self.source_rect.size,
+                    source_origin: Default::default(),
+                    color: self.colorize,
+                }
+            }
+            #[cfg(feature = "software-renderer-systemfonts")]
+            SharedBufferData::AlphaMap { data, width } => SceneTexture {
+                data: &data[begin..],
+                stride: *width,
+                format: PixelFormat::AlphaMap,
                 source_size: self.source_rect.size,
+                source_origin: Default::default(),
                 color: self.colorize,
             },
         }
@@ -907,15 +1036,31 @@ fn draw_image_impl(
                         let actual_y = clipped_relative_source_rect.origin.y as usize
                             + source_rect.origin.y as usize
                             - t.rect.origin.y as usize;
-                        let stride = t.rect.width() as u16 * t.format.bpp() as u16;
+                        let (data, stride, source_origin) = match t.format.bpp() {
+                            Some(bpp) => {
+                                let stride = t.rect.width() as u16 * bpp as u16;
+                                (
+                                    &data.as_slice()[(t.index
+                                        + (stride as usize) * actual_y
+                                        + bpp * actual_x)..],
+                                    stride,
+                                    Default::default(),
+                                )
+                            }
+                            // Compressed textures are decoded from the start of their lines
+                            None => (
+                                &data.as_slice()[t.index..],
+                                t.rect.width() as u16,
+                                euclid::point2(actual_x as i16, actual_y as i16),
+                            ),
+                        };
                         self.processor.process_texture(
                             target_rect.cast(),
                             SceneTexture {
-                                data: &data.as_slice()[(t.index
-                                    + (stride as usize) * actual_y
-                                    + (t.format.bpp()) * actual_x)..],
+                                data,
                                 stride,
                                 source_size: clipped_relative_source_rect.size.ceil().cast(),
+                                source_origin,
                                 format: t.format,
                                 color: if colorize.alpha() > 0 { colorize } else { t.color },
                             },
@@ -949,7 +1094,7 @@ fn draw_image_impl(
                         self.processor.process_shared_image_buffer(
                             target_rect.cast(),
                             SharedBufferCommand {
-                                buffer,
+                                buffer: SharedBufferData::SharedImage(buffer),
                                 source_rect: clipped_relative_source_rect
                                     .translate(
                                         euclid::Point2D::from_untyped(source_rect.origin.cast())
@@ -1130,8 +1275,8 @@ fn draw_clipped_image(&mut self, image: Pin<&crate::items::ClippedImage>, _: &It
     }
 
     fn draw_text(&mut self, text: Pin<&crate::items::Text>, _: &ItemRc) {
-        let string = text.text();
-        if string.trim().is_empty() {
+        let styled_text = text.styled_text();
+        if styled_text.text.trim().is_empty() {
             return;
         }
         let geom = LogicalRect::new(LogicalPoint::default(), text.logical_geometry().size_length());
@@ -1141,22 +1286,14 @@ fn draw_text(&mut self, text: Pin<&crate::items::Text>, _: &ItemRc) {
 
         let font_request = text.font_request(self.window);
         let font = fonts::match_font(&font_request, self.scale_factor);
-        let layout = fonts::text_layout_for_font(&font, &font_request, self.scale_factor);
+        let span_fonts = fonts::span_fonts(&styled_text.spans, &font_request, self.scale_factor);
+        let layout =
+            fonts::text_layout_for_font(&font, &span_fonts, &font_request, self.scale_factor);
 
-        let color = text.color().color();
+        let text_color = text.color().color();
         let max_size = (geom.size.cast() * self.scale_factor).cast();
 
-        let paragraph = TextParagraphLayout {
-            string: &string,
-            layout,
-            max_width: max_size.width_length(),
-            max_height: max_size.height_length(),
-            horizontal_alignment: text.horizontal_alignment(),
-            vertical_alignment: text.vertical_alignment(),
-            wrap: text.wrap(),
-            overflow: text.overflow(),
-            single_line: false,
-        };
+        let paragraph = text_paragraph_layout(text, &styled_text.text, layout, max_size);
 
         // Clip glyphs not only against the global clip but also against the Text's geometry to avoid drawing outside
         // of its boundaries (that breaks partial rendering and the cast to usize for the item relative coordinate below).
@@ -1168,10 +1305,25 @@ fn draw_text(&mut self, text: Pin<&crate::items::Text>, _: &ItemRc) {
             return; // This should have been caught earlier already
         };
         let offset = self.current_state.offset.to_vector().cast() * self.scale_factor;
+        let decoration = crate::textlayout::decoration_line(&font, font_request.decoration);
 
         paragraph.layout_lines(|glyphs, line_x, line_y| {
             let baseline_y = line_y + font.ascent();
+            // The horizontal extent of the glyphs of the line, for the text decoration
+            let mut line_extent: Option<(PhysicalLength, PhysicalLength)> = None;
             while let Some(positioned_glyph) = glyphs.next() {
+                let glyph_end = positioned_glyph.x + positioned_glyph.advance;
+                line_extent = Some(match line_extent {
+                    Some((start, end)) => (
+                        euclid::approxord::min(start, positioned_glyph.x),
+                        euclid::approxord::max(end, glyph_end),
+                    ),
+                    None => (positioned_glyph.x, glyph_end),
+                });
+                let color = styled_text
+                    .span_at(positioned_glyph.text_byte_offset)
+                    .and_then(|span| span.style.color)
+                    .unwrap_or(text_color);
                 let src_rect = PhysicalRect::new(
                     PhysicalPoint::from_lengths(
                         line_x + positioned_glyph.x + positioned_glyph.platform_glyph.x(),
@@ -1190,16 +1342,57 @@ fn draw_text(&mut self, text: Pin<&crate::items::Text>, _: &ItemRc) {
                     let actual_y = origin.y - src_rect.origin.y as usize;
                     let stride = positioned_glyph.platform_glyph.width().get() as u16;
                     let geometry = geometry.cast();
-                    self.processor.process_texture(
-                        geometry,
-                        SceneTexture {
-                            data: &positioned_glyph.platform_glyph.data().as_slice()
-                                [actual_x + actual_y * stride as usize..],
-                            stride,
-                            source_size: geometry.size,
-                            format: PixelFormat::AlphaMap,
-                            color,
-                        },
+                    match &positioned_glyph.platform_glyph {
+                        fonts::PlatformGlyph::Empty => {}
+                        fonts::PlatformGlyph::Bitmap(bitmap_glyph) => {
+                            self.processor.process_texture(
+                                geometry,
+                                SceneTexture {
+                                    data: &bitmap_glyph.data.as_slice()
+                                        [actual_x + actual_y * stride as usize..],
+                                    stride,
+                                    source_size: geometry.size,
+                                    source_origin: Default::default(),
+                                    format: PixelFormat::AlphaMap,
+                                    color,
+                                },
+                            );
+                        }
+                        #[cfg(feature = "software-renderer-systemfonts")]
+                        fonts::PlatformGlyph::Vector(vector_glyph) => {
+                            self.processor.process_shared_image_buffer(
+                                geometry,
+                                SharedBufferCommand {
+                                    buffer: SharedBufferData::AlphaMap {
+                                        data: vector_glyph.alpha_map(),
+                                        width: stride,
+                                    },
+                                    source_rect: PhysicalRect::new(
+                                        PhysicalPoint::new(actual_x as _, actual_y as _),
+                                        geometry.size,
+                                    ),
+                                    colorize: color,
+                                },
+                            );
+                        }
+                    }
+                }
+            }
+
+            if let (Some((decoration_y, thickness)), Some((start, end))) = (decoration, line_extent)
+            {
+                let decoration_rect = PhysicalRect::new(
+                    PhysicalPoint::from_lengths(line_x + start, line_y + decoration_y),
+                    PhysicalSize::from_lengths(
+                        end - start,
+                        euclid::approxord::max(thickness, PhysicalLength::new(1)),
+                    ),
+                )
+                .cast();
+                if let Some(clipped_rect) = decoration_rect.intersection(&physical_clip) {
+                    self.processor.process_rectangle(
+                        clipped_rect.translate(offset).round().cast(),
+                        text_color,
                     );
                 }
             }
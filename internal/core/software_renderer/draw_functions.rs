@@ -19,58 +19,121 @@ pub(super) fn draw_texture_line(
     texture: &super::SceneTexture,
     line_buffer: &mut [impl TargetPixel],
 ) {
-    let super::SceneTexture { data, format, stride, source_size, color } = *texture;
-    let source_size = source_size.cast::<usize>();
+    let color = texture.color;
+    match texture.format {
+        PixelFormat::Rgb => {
+            draw_plain_texture_line(span, line, texture, line_buffer, 3, |p, pix| {
+                *pix = TargetPixel::from_rgb(p[0], p[1], p[2])
+            })
+        }
+        PixelFormat::Rgba => {
+            draw_plain_texture_line(span, line, texture, line_buffer, 4, |p, pix| {
+                pix.blend(PremultipliedRgbaColor::premultiply(if color.alpha() == 0 {
+                    Color::from_argb_u8(p[3], p[0], p[1], p[2])
+                } else {
+                    Color::from_argb_u8(p[3], color.red(), color.green(), color.blue())
+                }))
+            })
+        }
+        PixelFormat::RgbaPremultiplied => {
+            draw_plain_texture_line(span, line, texture, line_buffer, 4, |p, pix| {
+                pix.blend(if color.alpha() == 0 {
+                    PremultipliedRgbaColor { alpha: p[3], red: p[0], green: p[1], blue: p[2] }
+                } else {
+                    PremultipliedRgbaColor::premultiply(Color::from_argb_u8(
+                        p[3],
+                        color.red(),
+                        color.green(),
+                        color.blue(),
+                    ))
+                })
+            })
+        }
+        PixelFormat::AlphaMap => {
+            draw_plain_texture_line(span, line, texture, line_buffer, 1, |p, pix| {
+                pix.blend(PremultipliedRgbaColor::premultiply(Color::from_argb_u8(
+                    p[0],
+                    color.red(),
+                    color.green(),
+                    color.blue(),
+                )))
+            })
+        }
+        PixelFormat::Indexed8 | PixelFormat::Rgb565Rle | PixelFormat::RgbaPremultipliedRle => {
+            draw_compressed_texture_line(span, line, texture, line_buffer)
+        }
+    }
+}
+
+/// Draw one line of a texture whose pixels are `bpp` bytes, with `draw_pixel` called with the
+/// bytes of the source pixel and the pixel of the line buffer
+fn draw_plain_texture_line<T: TargetPixel>(
+    span: &PhysicalRect,
+    line: PhysicalLength,
+    texture: &super::SceneTexture,
+    line_buffer: &mut [T],
+    bpp: usize,
+    draw_pixel: impl Fn(&[u8], &mut T),
+) {
+    let source_size = texture.source_size.cast::<usize>();
     let span_size = span.size.cast::<usize>();
-    let bpp = format.bpp();
     let y = (line - span.origin.y_length()).cast::<usize>();
-    let y_pos = (y.get() * source_size.height / span_size.height) * stride as usize;
+    let y_pos = (y.get() * source_size.height / span_size.height) * texture.stride as usize;
     for (x, pix) in line_buffer
         [span.origin.x as usize..(span.origin.x_length() + span.size.width_length()).get() as usize]
         .iter_mut()
         .enumerate()
     {
         let pos = y_pos + (x * source_size.width / span_size.width) * bpp;
-        let c = match format {
-            PixelFormat::Rgb => {
-                let p = &data[pos..pos + 3];
-                *pix = TargetPixel::from_rgb(p[0], p[1], p[2]);
-                continue;
-            }
-            PixelFormat::Rgba => {
-                let alpha = data[pos + 3];
-                PremultipliedRgbaColor::premultiply(if color.alpha() == 0 {
-                    Color::from_argb_u8(alpha, data[pos + 0], data[pos + 1], data[pos + 2])
-                } else {
-                    Color::from_argb_u8(alpha, color.red(), color.green(), color.blue())
-                })
-            }
-            PixelFormat::RgbaPremultiplied => {
-                let alpha = data[pos + 3];
-                if color.alpha() == 0 {
-                    PremultipliedRgbaColor {
-                        alpha,
-                        red: data[pos + 0],
-                        green: data[pos + 1],
-                        blue: data[pos + 2],
-                    }
-                } else {
-                    PremultipliedRgbaColor::premultiply(Color::from_argb_u8(
-                        alpha,
-                        color.red(),
-                        color.green(),
-                        color.blue(),
-                    ))
-                }
-            }
-            PixelFormat::AlphaMap => PremultipliedRgbaColor::premultiply(Color::from_argb_u8(
-                data[pos],
+        draw_pixel(&texture.data[pos..pos + bpp], pix);
+    }
+}
+
+/// Draw one line of a texture in one of the formats of [`crate::graphics::image::compressed`]
+fn draw_compressed_texture_line(
+    span: &PhysicalRect,
+    line: PhysicalLength,
+    texture: &super::SceneTexture,
+    line_buffer: &mut [impl TargetPixel],
+) {
+    let super::SceneTexture { data, format, stride, source_size, source_origin, color } = *texture;
+    let source_size = source_size.cast::<usize>();
+    let span_size = span.size.cast::<usize>();
+    let y = (line - span.origin.y_length()).cast::<usize>();
+    let source_origin = source_origin.cast::<usize>();
+    let mut pixels = match crate::graphics::image::compressed::line_pixels(
+        format,
+        data,
+        stride as usize,
+        source_origin.y + y.get() * source_size.height / span_size.height,
+    ) {
+        Some(pixels) => pixels,
+        None => return,
+    };
+    // The pixels are decoded in order, so keep the last one for when the texture is scaled up
+    let mut next_x = 0;
+    let mut current = Default::default();
+    for (x, pix) in line_buffer
+        [span.origin.x as usize..(span.origin.x_length() + span.size.width_length()).get() as usize]
+        .iter_mut()
+        .enumerate()
+    {
+        let source_x = source_origin.x + x * source_size.width / span_size.width;
+        if source_x >= next_x {
+            current = pixels.nth(source_x - next_x).unwrap_or_default();
+            next_x = source_x + 1;
+        }
+        let crate::graphics::Rgba8Pixel { r: red, g: green, b: blue, a: alpha } = current;
+        pix.blend(if color.alpha() == 0 {
+            PremultipliedRgbaColor { red, green, blue, alpha }
+        } else {
+            PremultipliedRgbaColor::premultiply(Color::from_argb_u8(
+                alpha,
                 color.red(),
                 color.green(),
                 color.blue(),
-            )),
-        };
-        pix.blend(c);
+            ))
+        });
     }
 }
 
@@ -3,44 +3,78 @@
 
 use alloc::vec::Vec;
 use core::cell::RefCell;
+use core::ops::Range;
 
 #[cfg(all(not(feature = "std"), feature = "unsafe-single-threaded"))]
 use crate::thread_local_ as thread_local;
 
 use crate::graphics::{BitmapFont, BitmapGlyph, BitmapGlyphs, FontRequest};
 use crate::lengths::{LogicalLength, LogicalSize, PhysicalLength, PhysicalSize, ScaleFactor};
-use crate::slice::Slice;
-use crate::textlayout::{Glyph, TextLayout, TextShaper};
+use crate::textlayout::styled_text::TextSpan;
+use crate::textlayout::{FontMetrics as _, Glyph, TextLayout, TextShaper};
 use crate::Coord;
 
+#[cfg(feature = "software-renderer-systemfonts")]
+mod vectorfont;
+
+#[cfg(feature = "software-renderer-systemfonts")]
+pub use vectorfont::{register_font_from_memory, register_font_from_path};
+
 thread_local! {
     static FONTS: RefCell<Vec<&'static BitmapFont>> = RefCell::default()
 }
 
-#[derive(Debug, Default, Clone)]
-pub struct PlatformGlyph {
-    pub(crate) bitmap_glyph: Option<&'static BitmapGlyph>,
+#[derive(Debug, Clone)]
+pub enum PlatformGlyph {
+    /// A glyph without pixels, for example for a character that the font does not have
+    Empty,
+    Bitmap(&'static BitmapGlyph),
+    #[cfg(feature = "software-renderer-systemfonts")]
+    Vector(vectorfont::VectorGlyph),
+}
+
+impl Default for PlatformGlyph {
+    fn default() -> Self {
+        Self::Empty
+    }
 }
 
 impl PlatformGlyph {
     pub fn x(&self) -> PhysicalLength {
-        self.bitmap_glyph.map(|g| PhysicalLength::new(g.x)).unwrap_or_default()
+        match self {
+            Self::Empty => PhysicalLength::default(),
+            Self::Bitmap(g) => PhysicalLength::new(g.x),
+            #[cfg(feature = "software-renderer-systemfonts")]
+            Self::Vector(g) => g.x(),
+        }
     }
     pub fn y(&self) -> PhysicalLength {
-        self.bitmap_glyph.map(|g| PhysicalLength::new(g.y)).unwrap_or_default()
+        match self {
+            Self::Empty => PhysicalLength::default(),
+            Self::Bitmap(g) => PhysicalLength::new(g.y),
+            #[cfg(feature = "software-renderer-systemfonts")]
+            Self::Vector(g) => g.y(),
+        }
     }
     pub fn width(&self) -> PhysicalLength {
-        self.bitmap_glyph.map(|g| PhysicalLength::new(g.width)).unwrap_or_default()
+        match self {
+            Self::Empty => PhysicalLength::default(),
+            Self::Bitmap(g) => PhysicalLength::new(g.width),
+            #[cfg(feature = "software-renderer-systemfonts")]
+            Self::Vector(g) => g.width(),
+        }
     }
     pub fn height(&self) -> PhysicalLength {
-        self.bitmap_glyph.map(|g| PhysicalLength::new(g.height)).unwrap_or_default()
+        match self {
+            Self::Empty => PhysicalLength::default(),
+            Self::Bitmap(g) => PhysicalLength::new(g.height),
+            #[cfg(feature = "software-renderer-systemfonts")]
+            Self::Vector(g) => g.height(),
+        }
     }
     pub fn size(&self) -> PhysicalSize {
         PhysicalSize::from_lengths(self.width(), self.height())
     }
-    pub fn data(&self) -> &Slice<'static, u8> {
-        &self.bitmap_glyph.expect("invalid error: Glyph::data called on null").data
-    }
 }
 
 trait FontMetrics {
@@ -104,7 +138,7 @@ fn shape_text<GlyphStorage: core::iter::Extend<Glyph<PhysicalLength, PlatformGly
             let x_advance = bitmap_glyph
                 .map_or_else(|| self.pixel_size(), |g| PhysicalLength::new(g.x_advance));
             Glyph {
-                platform_glyph: PlatformGlyph { bitmap_glyph },
+                platform_glyph: bitmap_glyph.map_or(PlatformGlyph::Empty, PlatformGlyph::Bitmap),
                 advance: x_advance,
                 text_byte_offset: byte_offset,
                 ..Default::default()
@@ -123,7 +157,7 @@ fn glyph_for_char(&self, ch: char) -> Option<Glyph<PhysicalLength, PlatformGlyph
                 let bitmap_glyph = &self.glyphs.glyph_data[glyph_index as usize];
                 let x_advance = PhysicalLength::new(bitmap_glyph.x_advance);
                 Glyph {
-                    platform_glyph: PlatformGlyph { bitmap_glyph: Some(bitmap_glyph) },
+                    platform_glyph: PlatformGlyph::Bitmap(bitmap_glyph),
                     advance: x_advance,
                     text_byte_offset: 0,
                     ..Default::default()
@@ -146,28 +180,78 @@ fn descent(&self) -> PhysicalLength {
     }
 }
 
-pub fn match_font(request: &FontRequest, scale_factor: ScaleFactor) -> PixelFont {
-    let font = FONTS.with(|fonts| {
-        let fonts = fonts.borrow();
-        let fallback_font = *fonts
-            .first()
-            .expect("The software renderer requires enabling the `EmbedForSoftwareRenderer` option when compiling slint files.");
+/// A font resolved to a specific pixel size: either one of the bitmap fonts embedded by the
+/// compiler, or a font rasterized at run-time
+pub enum Font {
+    PixelFont(PixelFont),
+    #[cfg(feature = "software-renderer-systemfonts")]
+    VectorFont(vectorfont::VectorFont),
+}
 
-        request.family.as_ref().map_or(fallback_font, |requested_family| {
-            fonts
-                .iter()
-                .find(|bitmap_font| {
-                    core::str::from_utf8(bitmap_font.family_name.as_slice()).unwrap()
-                        == requested_family.as_str()
-                })
-                .unwrap_or(&fallback_font)
-        })
-    });
+impl TextShaper for Font {
+    type LengthPrimitive = i16;
+    type Length = PhysicalLength;
+    type PlatformGlyphData = PlatformGlyph;
+    fn shape_text<GlyphStorage: core::iter::Extend<Glyph<PhysicalLength, PlatformGlyph>>>(
+        &self,
+        text: &str,
+        glyphs: &mut GlyphStorage,
+    ) {
+        match self {
+            Font::PixelFont(font) => font.shape_text(text, glyphs),
+            #[cfg(feature = "software-renderer-systemfonts")]
+            Font::VectorFont(font) => font.shape_text(text, glyphs),
+        }
+    }
 
-    let requested_pixel_size: PhysicalLength =
-        (LogicalLength::new(request.pixel_size.unwrap_or(DEFAULT_FONT_SIZE)).cast() * scale_factor)
-            .cast();
+    fn glyph_for_char(&self, ch: char) -> Option<Glyph<PhysicalLength, PlatformGlyph>> {
+        match self {
+            Font::PixelFont(font) => font.glyph_for_char(ch),
+            #[cfg(feature = "software-renderer-systemfonts")]
+            Font::VectorFont(font) => font.glyph_for_char(ch),
+        }
+    }
+}
+
+impl crate::textlayout::FontMetrics<PhysicalLength> for Font {
+    fn ascent(&self) -> PhysicalLength {
+        match self {
+            Font::PixelFont(font) => font.ascent(),
+            #[cfg(feature = "software-renderer-systemfonts")]
+            Font::VectorFont(font) => font.ascent(),
+        }
+    }
+
+    fn height(&self) -> PhysicalLength {
+        match self {
+            Font::PixelFont(font) => font.height(),
+            #[cfg(feature = "software-renderer-systemfonts")]
+            Font::VectorFont(font) => font.height(),
+        }
+    }
+
+    fn descent(&self) -> PhysicalLength {
+        match self {
+            Font::PixelFont(font) => font.descent(),
+            #[cfg(feature = "software-renderer-systemfonts")]
+            Font::VectorFont(font) => font.descent(),
+        }
+    }
+}
+
+fn find_bitmap_font(family: &str) -> Option<&'static BitmapFont> {
+    FONTS.with(|fonts| {
+        fonts
+            .borrow()
+            .iter()
+            .find(|bitmap_font| {
+                core::str::from_utf8(bitmap_font.family_name.as_slice()).unwrap() == family
+            })
+            .copied()
+    })
+}
 
+fn pixel_font(font: &'static BitmapFont, requested_pixel_size: PhysicalLength) -> PixelFont {
     let nearest_pixel_size = font
         .glyphs
         .partition_point(|glyphs| glyphs.pixel_size() <= requested_pixel_size)
@@ -178,16 +262,69 @@ pub fn match_font(request: &FontRequest, scale_factor: ScaleFactor) -> PixelFont
     PixelFont { bitmap_font: font, glyphs: matching_glyphs }
 }
 
+pub fn match_font(request: &FontRequest, scale_factor: ScaleFactor) -> Font {
+    let requested_pixel_size: PhysicalLength =
+        (LogicalLength::new(request.pixel_size.unwrap_or(DEFAULT_FONT_SIZE)).cast() * scale_factor)
+            .cast();
+
+    if let Some(bitmap_font) = request.family.as_ref().and_then(|family| find_bitmap_font(family)) {
+        return Font::PixelFont(pixel_font(bitmap_font, requested_pixel_size));
+    }
+
+    let fallback_bitmap_font = FONTS.with(|fonts| fonts.borrow().first().copied());
+
+    // Fonts rasterized at run-time are used for the families that the compiler did not embed,
+    // or for the default font if no font was embedded at all
+    #[cfg(feature = "software-renderer-systemfonts")]
+    if request.family.is_some() || fallback_bitmap_font.is_none() {
+        if let Some(vector_font) = vectorfont::VectorFont::new(request, requested_pixel_size) {
+            return Font::VectorFont(vector_font);
+        }
+        if fallback_bitmap_font.is_none() {
+            let default_request = FontRequest { family: None, ..request.clone() };
+            if let Some(vector_font) =
+                vectorfont::VectorFont::new(&default_request, requested_pixel_size)
+            {
+                return Font::VectorFont(vector_font);
+            }
+        }
+    }
+
+    let fallback_bitmap_font = fallback_bitmap_font
+        .expect("The software renderer requires enabling the `EmbedForSoftwareRenderer` option when compiling slint files.");
+
+    Font::PixelFont(pixel_font(fallback_bitmap_font, requested_pixel_size))
+}
+
+/// Returns the fonts of the spans of a styled text that are not shown with the font of the request
+pub fn span_fonts(
+    spans: &[TextSpan],
+    font_request: &FontRequest,
+    scale_factor: ScaleFactor,
+) -> Vec<(Range<usize>, Font)> {
+    spans
+        .iter()
+        .filter(|span| span.style.changes_font())
+        .map(|span| {
+            (span.range.clone(), match_font(&span.style.font_request(font_request), scale_factor))
+        })
+        .collect()
+}
+
 pub fn text_layout_for_font<'a>(
-    font: &'a PixelFont,
+    font: &'a Font,
+    span_fonts: &'a [(Range<usize>, Font)],
     font_request: &FontRequest,
     scale_factor: ScaleFactor,
-) -> TextLayout<'a, PixelFont> {
+) -> TextLayout<'a, Font> {
     let letter_spacing = font_request
         .letter_spacing
         .map(|spacing| (LogicalLength::new(spacing).cast() * scale_factor).cast());
+    let line_height = font_request
+        .line_height
+        .map(|line_height| (LogicalLength::new(line_height).cast() * scale_factor).cast());
 
-    TextLayout { font, letter_spacing }
+    TextLayout { font, letter_spacing, span_fonts, line_height }
 }
 
 pub fn register_bitmap_font(font_data: &'static BitmapFont) {
@@ -197,11 +334,13 @@ pub fn register_bitmap_font(font_data: &'static BitmapFont) {
 pub fn text_size(
     font_request: FontRequest,
     text: &str,
+    spans: &[TextSpan],
     max_width: Option<Coord>,
     scale_factor: ScaleFactor,
 ) -> LogicalSize {
     let font = match_font(&font_request, scale_factor);
-    let layout = text_layout_for_font(&font, &font_request, scale_factor);
+    let span_fonts = span_fonts(spans, &font_request, scale_factor);
+    let layout = text_layout_for_font(&font, &span_fonts, &font_request, scale_factor);
 
     let (longest_line_width, height) = layout.text_size(
         text,
@@ -0,0 +1,442 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! TrueType and OpenType fonts that are rasterized at run-time, for text in sizes or
+//! with characters that the compiler did not embed as bitmap fonts.
+
+// cspell:ignore Noto fontdue
+
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use std::collections::HashMap;
+
+use super::PlatformGlyph;
+use crate::graphics::FontRequest;
+use crate::lengths::PhysicalLength;
+use crate::textlayout::{Glyph, TextShaper};
+use crate::SharedString;
+
+/// Families that are tried, after the fonts registered by the application, for the characters
+/// that the requested font does not have.
+const FALLBACK_FAMILIES: &[&str] = if cfg!(target_os = "macos") {
+    &["Menlo", "Apple Symbols", "Apple Color Emoji"]
+} else if cfg!(target_family = "windows") {
+    &["Segoe UI", "Segoe UI Symbol", "Segoe UI Emoji"]
+} else {
+    &["DejaVu Sans", "Noto Sans", "Noto Sans Symbols", "Noto Sans Symbols2", "Liberation Sans"]
+};
+
+/// The maximum number of bytes of rasterized glyphs kept in the glyph cache
+const GLYPH_CACHE_SIZE: usize = 1024 * 1024;
+
+/// A font face, parsed for rasterization
+struct LoadedFont {
+    id: fontdb::ID,
+    font: fontdue::Font,
+}
+
+impl LoadedFont {
+    fn has_glyph(&self, ch: char) -> bool {
+        self.font.lookup_glyph_index(ch) != 0
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct GlyphCacheKey {
+    font: fontdb::ID,
+    glyph_index: u16,
+    pixel_size: i16,
+}
+
+struct GlyphWeightInBytes;
+
+impl clru::WeightScale<GlyphCacheKey, Rc<[u8]>> for GlyphWeightInBytes {
+    fn weight(&self, _: &GlyphCacheKey, value: &Rc<[u8]>) -> usize {
+        value.len()
+    }
+}
+
+type GlyphCache = clru::CLruCache<
+    GlyphCacheKey,
+    Rc<[u8]>,
+    std::collections::hash_map::RandomState,
+    GlyphWeightInBytes,
+>;
+
+struct FontCache {
+    database: fontdb::Database,
+    /// The fonts registered with `register_font_from_memory` or `register_font_from_path`,
+    /// in the order of registration
+    registered_fonts: Vec<fontdb::ID>,
+    loaded_fonts: HashMap<fontdb::ID, Option<Rc<LoadedFont>>>,
+    /// The font matching a family, a weight and a style. The empty family is the default font.
+    matched_fonts: HashMap<(SharedString, i32, bool), Option<Rc<LoadedFont>>>,
+    fallback_fonts: Option<Rc<Vec<Rc<LoadedFont>>>>,
+}
+
+impl Default for FontCache {
+    fn default() -> Self {
+        let mut database = fontdb::Database::new();
+        database.load_system_fonts();
+        Self {
+            database,
+            registered_fonts: Default::default(),
+            loaded_fonts: Default::default(),
+            matched_fonts: Default::default(),
+            fallback_fonts: None,
+        }
+    }
+}
+
+impl FontCache {
+    fn load_font(&mut self, id: fontdb::ID) -> Option<Rc<LoadedFont>> {
+        let database = &self.database;
+        self.loaded_fonts
+            .entry(id)
+            .or_insert_with(|| {
+                database
+                    .with_face_data(id, |data, collection_index| {
+                        fontdue::Font::from_bytes(
+                            data,
+                            fontdue::FontSettings { collection_index, ..Default::default() },
+                        )
+                        .ok()
+                    })
+                    .flatten()
+                    .map(|font| Rc::new(LoadedFont { id, font }))
+            })
+            .clone()
+    }
+
+    fn match_font(
+        &mut self,
+        family: Option<&SharedString>,
+        weight: i32,
+        italic: bool,
+    ) -> Option<Rc<LoadedFont>> {
+        let key = (family.cloned().unwrap_or_default(), weight, italic);
+        if let Some(font) = self.matched_fonts.get(&key) {
+            return font.clone();
+        }
+
+        let weight = fontdb::Weight(weight.clamp(1, u16::MAX as i32) as u16);
+        let style = if italic { fontdb::Style::Italic } else { fontdb::Style::Normal };
+        let id = match family.filter(|family| !family.is_empty()) {
+            Some(family) => self.database.query(&fontdb::Query {
+                families: &[fontdb::Family::Name(family.as_str())],
+                weight,
+                style,
+                ..Default::default()
+            }),
+            None => {
+                // Prefer the default font of the system, then the fonts of the application,
+                // then any font
+                let mut families = vec![fontdb::Family::SansSerif];
+                families
+                    .extend(FALLBACK_FAMILIES.iter().map(|family| fontdb::Family::Name(*family)));
+                self.database
+                    .query(&fontdb::Query {
+                        families: &families,
+                        weight,
+                        style,
+                        ..Default::default()
+                    })
+                    .or_else(|| self.registered_fonts.first().copied())
+                    .or_else(|| self.database.faces().first().map(|face| face.id))
+            }
+        };
+
+        let font = id.and_then(|id| self.load_font(id));
+        self.matched_fonts.insert(key, font.clone());
+        font
+    }
+
+    fn fallback_fonts(&mut self) -> Rc<Vec<Rc<LoadedFont>>> {
+        if let Some(fallback_fonts) = &self.fallback_fonts {
+            return fallback_fonts.clone();
+        }
+
+        let mut ids = self.registered_fonts.clone();
+        ids.extend(FALLBACK_FAMILIES.iter().filter_map(|family| {
+            self.database.query(&fontdb::Query {
+                families: &[fontdb::Family::Name(*family)],
+                ..Default::default()
+            })
+        }));
+        let mut fallback_fonts = Vec::new();
+        for id in ids {
+            if fallback_fonts.iter().all(|font: &Rc<LoadedFont>| font.id != id) {
+                fallback_fonts.extend(self.load_font(id));
+            }
+        }
+
+        let fallback_fonts = Rc::new(fallback_fonts);
+        self.fallback_fonts = Some(fallback_fonts.clone());
+        fallback_fonts
+    }
+
+    fn register_faces(
+        &mut self,
+        load: impl FnOnce(&mut fontdb::Database) -> std::io::Result<()>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let face_count = self.database.faces().len();
+        load(&mut self.database)?;
+        let new_faces = &self.database.faces()[face_count..];
+        if new_faces.is_empty() {
+            return Err("the data does not contain any TrueType or OpenType font".into());
+        }
+        self.registered_fonts.extend(new_faces.iter().map(|face| face.id));
+        // The new fonts may match families that were not found before, or have
+        // glyphs that the fallback fonts lack
+        self.matched_fonts.clear();
+        self.fallback_fonts = None;
+        Ok(())
+    }
+}
+
+thread_local! {
+    static FONT_CACHE: RefCell<FontCache> = RefCell::default();
+
+    static GLYPH_CACHE: RefCell<GlyphCache> = RefCell::new(clru::CLruCache::with_config(
+        clru::CLruCacheConfig::new(core::num::NonZeroUsize::new(GLYPH_CACHE_SIZE).unwrap())
+            .with_scale(GlyphWeightInBytes),
+    ));
+}
+
+pub fn register_font_from_memory(data: &'static [u8]) -> Result<(), Box<dyn std::error::Error>> {
+    FONT_CACHE.with(|cache| {
+        cache.borrow_mut().register_faces(|database| {
+            database.load_font_source(fontdb::Source::Binary(std::sync::Arc::new(data)));
+            Ok(())
+        })
+    })
+}
+
+pub fn register_font_from_path(path: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
+    let requested_path = path.canonicalize().unwrap_or_else(|_| path.to_owned());
+    FONT_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let already_registered = cache.registered_fonts.iter().any(|id| {
+            matches!(cache.database.face(*id).map(|face| &face.source),
+                Some(fontdb::Source::File(loaded_path)) if *loaded_path == requested_path)
+        });
+        if already_registered {
+            return Ok(());
+        }
+        cache.register_faces(|database| database.load_font_file(&requested_path))
+    })
+}
+
+/// A TrueType or OpenType font at a given pixel size, with its fallback fonts
+pub struct VectorFont {
+    font: Rc<LoadedFont>,
+    fallback_fonts: Rc<Vec<Rc<LoadedFont>>>,
+    pixel_size: PhysicalLength,
+}
+
+impl VectorFont {
+    /// Returns the font of the requested family, or the default font if no family is requested.
+    /// Returns None if there is no such font.
+    pub fn new(request: &FontRequest, pixel_size: PhysicalLength) -> Option<Self> {
+        FONT_CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            let font = cache.match_font(
+                request.family.as_ref(),
+                request.weight.unwrap_or(400),
+                request.italic,
+            )?;
+            Some(Self { font, fallback_fonts: cache.fallback_fonts(), pixel_size })
+        })
+    }
+
+    fn glyph(&self, font: &Rc<LoadedFont>, glyph_index: u16) -> VectorGlyph {
+        let metrics = font.font.metrics_indexed(glyph_index, self.pixel_size.get() as f32);
+        VectorGlyph { font: font.clone(), glyph_index, pixel_size: self.pixel_size, metrics }
+    }
+
+    /// The font that has a glyph for the character: the font itself or one of the fallbacks
+    fn font_for_char(&self, ch: char) -> Option<&Rc<LoadedFont>> {
+        core::iter::once(&self.font)
+            .chain(self.fallback_fonts.iter())
+            .find(|font| font.has_glyph(ch))
+    }
+
+    fn line_metrics(&self) -> fontdue::LineMetrics {
+        self.font.font.horizontal_line_metrics(self.pixel_size.get() as f32).unwrap_or(
+            fontdue::LineMetrics {
+                ascent: self.pixel_size.get() as f32,
+                descent: 0.,
+                line_gap: 0.,
+                new_line_size: self.pixel_size.get() as f32,
+            },
+        )
+    }
+}
+
+impl TextShaper for VectorFont {
+    type LengthPrimitive = i16;
+    type Length = PhysicalLength;
+    type PlatformGlyphData = PlatformGlyph;
+    fn shape_text<GlyphStorage: core::iter::Extend<Glyph<PhysicalLength, PlatformGlyph>>>(
+        &self,
+        text: &str,
+        glyphs: &mut GlyphStorage,
+    ) {
+        let glyphs_iter = text.char_indices().map(|(byte_offset, ch)| {
+            if ch.is_control() {
+                return Glyph { text_byte_offset: byte_offset, ..Default::default() };
+            }
+            // Characters that no font has are shown with the "missing glyph" of the font
+            let glyph = match self.font_for_char(ch) {
+                Some(font) => self.glyph(font, font.font.lookup_glyph_index(ch)),
+                None => self.glyph(&self.font, 0),
+            };
+            Glyph {
+                advance: glyph.advance(),
+                platform_glyph: PlatformGlyph::Vector(glyph),
+                text_byte_offset: byte_offset,
+                ..Default::default()
+            }
+        });
+        glyphs.extend(glyphs_iter);
+    }
+
+    fn glyph_for_char(&self, ch: char) -> Option<Glyph<PhysicalLength, PlatformGlyph>> {
+        self.font_for_char(ch).map(|font| {
+            let glyph = self.glyph(font, font.font.lookup_glyph_index(ch));
+            Glyph {
+                advance: glyph.advance(),
+                platform_glyph: PlatformGlyph::Vector(glyph),
+                text_byte_offset: 0,
+                ..Default::default()
+            }
+        })
+    }
+}
+
+impl crate::textlayout::FontMetrics<PhysicalLength> for VectorFont {
+    fn ascent(&self) -> PhysicalLength {
+        PhysicalLength::new(self.line_metrics().ascent.round() as i16)
+    }
+
+    fn height(&self) -> PhysicalLength {
+        // The descent is negative (relative to the baseline)
+        let metrics = self.line_metrics();
+        PhysicalLength::new((metrics.ascent - metrics.descent).round() as i16)
+    }
+
+    fn descent(&self) -> PhysicalLength {
+        PhysicalLength::new(self.line_metrics().descent.round() as i16)
+    }
+}
+
+/// A glyph of a [`VectorFont`]. It is only rasterized when drawn.
+#[derive(Clone)]
+pub struct VectorGlyph {
+    font: Rc<LoadedFont>,
+    glyph_index: u16,
+    pixel_size: PhysicalLength,
+    metrics: fontdue::Metrics,
+}
+
+impl core::fmt::Debug for VectorGlyph {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("VectorGlyph")
+            .field("glyph_index", &self.glyph_index)
+            .field("pixel_size", &self.pixel_size)
+            .field("metrics", &self.metrics)
+            .finish()
+    }
+}
+
+impl VectorGlyph {
+    fn advance(&self) -> PhysicalLength {
+        PhysicalLength::new(self.metrics.advance_width.round() as i16)
+    }
+    pub fn x(&self) -> PhysicalLength {
+        PhysicalLength::new(self.metrics.xmin as i16)
+    }
+    pub fn y(&self) -> PhysicalLength {
+        PhysicalLength::new(self.metrics.ymin as i16)
+    }
+    pub fn width(&self) -> PhysicalLength {
+        PhysicalLength::new(self.metrics.width as i16)
+    }
+    pub fn height(&self) -> PhysicalLength {
+        PhysicalLength::new(self.metrics.height as i16)
+    }
+
+    /// The coverage of the pixels of the glyph, one byte per pixel and `width()` bytes per line.
+    /// The glyph is rasterized on first use and kept in a cache.
+    pub fn alpha_map(&self) -> Rc<[u8]> {
+        let key = GlyphCacheKey {
+            font: self.font.id,
+            glyph_index: self.glyph_index,
+            pixel_size: self.pixel_size.get(),
+        };
+        GLYPH_CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            if let Some(data) = cache.get(&key) {
+                return data.clone();
+            }
+            let (_, data) =
+                self.font.font.rasterize_indexed(self.glyph_index, self.pixel_size.get() as f32);
+            let data: Rc<[u8]> = data.into();
+            // A glyph bigger than the whole cache is simply not cached
+            let _ = cache.put_with_weight(key, data.clone());
+            data
+        })
+    }
+}
+
+#[test]
+fn test_vector_font() {
+    use crate::textlayout::FontMetrics;
+
+    let dejavu_path: std::path::PathBuf = [
+        env!("CARGO_MANIFEST_DIR"),
+        "..",
+        "backends",
+        "winit",
+        "renderer",
+        "femtovg",
+        "fonts",
+        "DejaVuSans.ttf",
+    ]
+    .iter()
+    .collect();
+    register_font_from_path(&dejavu_path).unwrap();
+    // Registering the same file again is not an error
+    register_font_from_path(&dejavu_path).unwrap();
+    assert!(register_font_from_memory(b"not a font").is_err());
+
+    let request = FontRequest { family: Some("DejaVu Sans".into()), ..Default::default() };
+    let font = VectorFont::new(&request, PhysicalLength::new(20)).unwrap();
+    assert!(font.ascent() > PhysicalLength::new(0));
+    assert!(font.descent() < PhysicalLength::new(0));
+    assert_eq!(font.height(), font.ascent() - font.descent());
+
+    let mut glyphs = Vec::new();
+    font.shape_text("a b", &mut glyphs);
+    assert_eq!(glyphs.len(), 3);
+    assert_eq!(glyphs.iter().map(|g| g.text_byte_offset).collect::<Vec<_>>(), [0, 1, 2]);
+
+    let a = match &glyphs[0].platform_glyph {
+        PlatformGlyph::Vector(glyph) => glyph.clone(),
+        _ => panic!("expected a vector glyph"),
+    };
+    assert!(glyphs[0].advance > PhysicalLength::new(0));
+    let data = a.alpha_map();
+    assert_eq!(data.len(), (a.width().get() * a.height().get()) as usize);
+    assert!(data.iter().any(|coverage| *coverage > 0));
+    // The second time, the glyph comes from the cache
+    assert!(Rc::ptr_eq(&data, &a.alpha_map()));
+
+    assert!(VectorFont::new(
+        &FontRequest { family: Some("No Such Font Family".into()), ..Default::default() },
+        PhysicalLength::new(20)
+    )
+    .is_none());
+}
@@ -19,13 +19,18 @@
 //!         Emit current line as new line
 //!     If encountering a mandatory line break opportunity:
 //!         Emit current line as new line
+//! 4. Finally, the glyphs of each line are reordered visually according to the Unicode Bidirectional Algorithm, so that
+//!    right-to-left text (such as arabic or hebrew) is laid out from right to left.
 //!
 
 use alloc::vec::Vec;
+use core::ops::Range;
 
 use euclid::num::{One, Zero};
 
-use crate::items::{TextHorizontalAlignment, TextOverflow, TextVerticalAlignment, TextWrap};
+use crate::items::{
+    TextDecoration, TextHorizontalAlignment, TextOverflow, TextVerticalAlignment, TextWrap,
+};
 
 #[cfg(feature = "unicode-linebreak")]
 mod linebreak_unicode;
@@ -37,9 +42,11 @@
 #[cfg(not(feature = "unicode-linebreak"))]
 use linebreak_simple::{BreakOpportunity, LineBreakIterator};
 
+pub mod bidi;
 mod fragments;
 mod glyphclusters;
 mod shaping;
+pub mod styled_text;
 use shaping::ShapeBuffer;
 pub use shaping::{AbstractFont, FontMetrics, Glyph, TextShaper};
 
@@ -51,9 +58,34 @@
 pub struct TextLayout<'a, Font: AbstractFont> {
     pub font: &'a Font,
     pub letter_spacing: Option<<Font as TextShaper>::Length>,
+    /// Fonts that replace `font` for byte ranges of the text, for example for the bold spans
+    /// of a styled text. The ranges must be sorted and must not overlap.
+    pub span_fonts: &'a [(Range<usize>, Font)],
+    /// The distance between the baselines of two lines. If None, the height of the font is used.
+    pub line_height: Option<<Font as TextShaper>::Length>,
 }
 
 impl<'a, Font: AbstractFont> TextLayout<'a, Font> {
+    /// Returns the font to use for the text at the given byte offset
+    pub fn font_at(&self, byte_offset: usize) -> &'a Font {
+        self.span_fonts
+            .iter()
+            .find(|(range, _)| range.contains(&byte_offset))
+            .map_or(self.font, |(_, font)| font)
+    }
+
+    /// Returns the height of a line of text
+    pub fn line_height(&self) -> Font::Length {
+        self.line_height.unwrap_or_else(|| self.font.height())
+    }
+
+    /// Returns the space to add above the glyphs of a line so that they are vertically
+    /// centered in the line height
+    pub fn half_leading(&self) -> Font::Length {
+        let two = Font::LengthPrimitive::one() + Font::LengthPrimitive::one();
+        (self.line_height() - self.font.height()) / two
+    }
+
     // Measures the size of the given text when rendered with the specified font and optionally constrained
     // by the provided `max_width`.
     // Returns a tuple of the width of the longest line as well as height of all lines.
@@ -74,14 +106,34 @@ pub fn text_size(
             line_count += 1;
         }
 
-        (max_line_width, self.font.height() * line_count.into())
+        (max_line_width, self.line_height() * line_count.into())
     }
 }
 
+/// Returns the position of the line drawn for the given text decoration, relative to the top of the
+/// glyphs of a line, as well as its thickness. Returns None for [`TextDecoration::None`].
+pub fn decoration_line<Font: AbstractFont>(
+    font: &Font,
+    decoration: TextDecoration,
+) -> Option<(Font::Length, Font::Length)> {
+    let two = Font::LengthPrimitive::one() + Font::LengthPrimitive::one();
+    let thickness = font.height() / Font::LengthPrimitive::from(14);
+    let y = match decoration {
+        TextDecoration::None => return None,
+        TextDecoration::Underline => font.ascent() - font.descent() / two,
+        TextDecoration::Overline => return Some((Font::Length::zero(), thickness)),
+        TextDecoration::LineThrough => font.ascent() * two / Font::LengthPrimitive::from(3),
+    };
+    Some((y - thickness / two, thickness))
+}
+
 pub struct PositionedGlyph<'a, Length, PlatformGlyphData> {
     pub x: Length,
     pub y: Length,
+    pub advance: Length,
     pub platform_glyph: &'a PlatformGlyphData,
+    /// The byte offset in the laid out string of the character that produced this glyph
+    pub text_byte_offset: usize,
 }
 
 pub struct TextParagraphLayout<'a, Font: AbstractFont> {
@@ -130,10 +182,10 @@ pub fn layout_lines(
 
         let mut text_height = || {
             if self.single_line {
-                self.layout.font.height()
+                self.layout.line_height()
             } else {
                 text_lines = Some(new_line_break_iter().collect::<Vec<_>>());
-                self.layout.font.height() * (text_lines.as_ref().unwrap().len() as i16).into()
+                self.layout.line_height() * (text_lines.as_ref().unwrap().len() as i16).into()
             }
         };
 
@@ -146,10 +198,11 @@ pub fn layout_lines(
         };
 
         let mut y = baseline_y;
+        let half_leading = self.layout.half_leading();
 
         let mut process_line =
             |line: &TextLine<Font::Length>,
-             glyphs: &[Glyph<Font::Length, Font::PlatformGlyphData>]| {
+             shape_buffer: &ShapeBuffer<Font::Length, Font::PlatformGlyphData>| {
                 let x = match self.horizontal_alignment {
                     TextHorizontalAlignment::Left => Font::Length::zero(),
                     TextHorizontalAlignment::Center => {
@@ -163,16 +216,33 @@ pub fn layout_lines(
 
                 let mut elide_glyph = elide_glyph.as_ref().clone();
 
-                let glyph_it = glyphs[line.glyph_range.clone()].iter();
+                let glyphs = &shape_buffer.glyphs;
+                let mut logical_glyph_it;
+                let mut visual_glyph_it;
+                let glyph_it: &mut dyn Iterator<Item = usize> =
+                    match shape_buffer.visual_glyph_order(self.string, line.glyph_range.clone()) {
+                        Some(order) => {
+                            visual_glyph_it = order.into_iter();
+                            &mut visual_glyph_it
+                        }
+                        None => {
+                            logical_glyph_it = line.glyph_range.clone();
+                            &mut logical_glyph_it
+                        }
+                    };
                 let mut glyph_x = Font::Length::zero();
-                let mut positioned_glyph_it = glyph_it.map_while(|glyph| {
+                let mut positioned_glyph_it = glyph_it.map_while(|index| {
+                    let glyph = &glyphs[index];
+                    let text_byte_offset = shape_buffer.glyph_byte_offset(index);
                     // TODO: cut off at grapheme boundaries
                     if glyph_x > max_width_without_elision {
                         if let Some(elide_glyph) = elide_glyph.take() {
                             return Some(PositionedGlyph {
                                 x: glyph_x,
                                 y: Font::Length::zero(),
+                                advance: elide_glyph.advance,
                                 platform_glyph: &elide_glyph.platform_glyph,
+                                text_byte_offset,
                             });
                         } else {
                             return None;
@@ -181,23 +251,25 @@ pub fn layout_lines(
                     let positioned_glyph = PositionedGlyph {
                         x: glyph_x,
                         y: Font::Length::zero(),
+                        advance: glyph.advance,
                         platform_glyph: &glyph.platform_glyph,
+                        text_byte_offset,
                     };
                     glyph_x += glyph.advance;
                     Some(positioned_glyph)
                 });
 
-                line_callback(&mut positioned_glyph_it, x, y);
-                y += self.layout.font.height();
+                line_callback(&mut positioned_glyph_it, x, y + half_leading);
+                y += self.layout.line_height();
             };
 
         if let Some(lines_vec) = text_lines.take() {
             for line in lines_vec {
-                process_line(&line, &shape_buffer.glyphs);
+                process_line(&line, &shape_buffer);
             }
         } else {
             for line in new_line_break_iter() {
-                process_line(&line, &shape_buffer.glyphs);
+                process_line(&line, &shape_buffer);
             }
         }
 
@@ -282,7 +354,12 @@ fn test_elision() {
 
     let paragraph = TextParagraphLayout {
         string: text,
-        layout: TextLayout { font: &font, letter_spacing: None },
+        layout: TextLayout {
+            font: &font,
+            letter_spacing: None,
+            span_fonts: &[],
+            line_height: None,
+        },
         max_width: 13. * 10.,
         max_height: 10.,
         horizontal_alignment: TextHorizontalAlignment::Left,
@@ -314,7 +391,12 @@ fn test_exact_fit() {
 
     let paragraph = TextParagraphLayout {
         string: text,
-        layout: TextLayout { font: &font, letter_spacing: None },
+        layout: TextLayout {
+            font: &font,
+            letter_spacing: None,
+            span_fonts: &[],
+            line_height: None,
+        },
         max_width: 4. * 10.,
         max_height: 10.,
         horizontal_alignment: TextHorizontalAlignment::Left,
@@ -346,7 +428,12 @@ fn test_no_line_separators_characters_rendered() {
 
     let paragraph = TextParagraphLayout {
         string: text,
-        layout: TextLayout { font: &font, letter_spacing: None },
+        layout: TextLayout {
+            font: &font,
+            letter_spacing: None,
+            span_fonts: &[],
+            line_height: None,
+        },
         max_width: 13. * 10.,
         max_height: 10.,
         horizontal_alignment: TextHorizontalAlignment::Left,
@@ -375,3 +462,109 @@ fn test_no_line_separators_characters_rendered() {
         .collect::<Vec<_>>();
     debug_assert_eq!(rendered_text, vec!["Hello", "World"]);
 }
+
+#[test]
+#[cfg_attr(
+    not(feature = "unicode-bidi"),
+    ignore = "Not supported without the unicode-bidi feature"
+)]
+fn test_bidi_reordering() {
+    let font = FixedTestFont;
+    let layout_line = |text: &str| {
+        let mut lines = Vec::new();
+        let paragraph = TextParagraphLayout {
+            string: text,
+            layout: TextLayout {
+                font: &font,
+                letter_spacing: None,
+                span_fonts: &[],
+                line_height: None,
+            },
+            max_width: 20. * 10.,
+            max_height: 10.,
+            horizontal_alignment: TextHorizontalAlignment::Left,
+            vertical_alignment: TextVerticalAlignment::Top,
+            wrap: TextWrap::NoWrap,
+            overflow: TextOverflow::Clip,
+            single_line: true,
+        };
+        paragraph.layout_lines(|glyphs, _, _| {
+            lines.push(
+                glyphs
+                    .map(|positioned_glyph| positioned_glyph.platform_glyph.char.unwrap())
+                    .collect::<String>(),
+            );
+        });
+        lines
+    };
+
+    // The hebrew letters alef, bet and gimel
+    assert_eq!(layout_line("abc \u{5d0}\u{5d1}\u{5d2}"), ["abc \u{5d2}\u{5d1}\u{5d0}"]);
+    assert_eq!(layout_line("\u{5d0}\u{5d1}\u{5d2} abc"), ["abc \u{5d2}\u{5d1}\u{5d0}"]);
+    // Numbers keep their order in right-to-left text
+    assert_eq!(layout_line("\u{5d0} 12 \u{5d1}"), ["\u{5d1} 12 \u{5d0}"]);
+    assert_eq!(layout_line("Hello\n\u{5d0}\u{5d1}\n"), ["Hello", "\u{5d1}\u{5d0}"]);
+}
+
+#[test]
+fn test_span_fonts() {
+    let font = FixedTestFont;
+    let span_fonts = [(1..3, FixedTestFont)];
+    let layout = TextLayout {
+        font: &font,
+        letter_spacing: None,
+        span_fonts: &span_fonts,
+        line_height: None,
+    };
+
+    let shape_buffer = ShapeBuffer::new(&layout, "ab cd");
+    assert_eq!(
+        shape_buffer.text_runs.iter().map(|run| run.byte_range.clone()).collect::<Vec<_>>(),
+        [0..1, 1..3, 3..5]
+    );
+
+    let mut glyphs = Vec::new();
+    let paragraph = TextParagraphLayout {
+        string: "ab cd",
+        layout,
+        max_width: 100.,
+        max_height: 10.,
+        horizontal_alignment: TextHorizontalAlignment::Left,
+        vertical_alignment: TextVerticalAlignment::Top,
+        wrap: TextWrap::NoWrap,
+        overflow: TextOverflow::Clip,
+        single_line: true,
+    };
+    paragraph.layout_lines(|line_glyphs, _, _| {
+        glyphs.extend(line_glyphs.map(|glyph| (glyph.x, glyph.text_byte_offset)));
+    });
+    assert_eq!(glyphs, [(0., 0), (10., 1), (20., 2), (30., 3), (40., 4)]);
+}
+
+#[test]
+fn test_line_height() {
+    let font = FixedTestFont;
+    let layout =
+        TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: Some(20.) };
+    assert_eq!(layout.text_size("Hello\nWorld", None).1, 40.);
+
+    let mut line_positions = Vec::new();
+    let paragraph = TextParagraphLayout {
+        string: "Hello\nWorld",
+        layout,
+        max_width: 100.,
+        max_height: 100.,
+        horizontal_alignment: TextHorizontalAlignment::Left,
+        vertical_alignment: TextVerticalAlignment::Top,
+        wrap: TextWrap::NoWrap,
+        overflow: TextOverflow::Clip,
+        single_line: false,
+    };
+    paragraph.layout_lines(|_, _, y| line_positions.push(y));
+    // The glyphs are 10 pixels high, so they are centered in the 20 pixels of each line
+    assert_eq!(line_positions, [5., 25.]);
+
+    assert_eq!(decoration_line(&font, TextDecoration::None), None);
+    let (y, thickness) = decoration_line(&font, TextDecoration::Underline).unwrap();
+    assert!(y > 5. && y + thickness <= 10.);
+}
@@ -0,0 +1,221 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Support for bidirectional text, using the Unicode Bidirectional Algorithm (UAX #9).
+//!
+//! The embedding levels are resolved for the whole text, and the lines are then
+//! reordered visually one by one, once the text was broken into lines.
+
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// The resolved bidi embedding levels of a text. Odd levels are right-to-left.
+#[derive(Default, Debug, Clone)]
+pub struct BidiInfo {
+    /// The level of each byte of the text. Empty if the whole text is left-to-right.
+    levels: Vec<u8>,
+    /// The byte range and the base level of each paragraph
+    paragraphs: Vec<(Range<usize>, u8)>,
+}
+
+impl BidiInfo {
+    /// Resolves the levels of the text. The direction of every paragraph is determined by
+    /// its first strong character.
+    pub fn new(text: &str) -> Self {
+        Self::new_impl(text, None)
+    }
+
+    /// Resolves the levels of the text, forcing the direction of the paragraphs.
+    pub fn with_direction(text: &str, rtl: bool) -> Self {
+        Self::new_impl(text, Some(rtl))
+    }
+
+    #[cfg(feature = "unicode-bidi")]
+    fn new_impl(text: &str, rtl: Option<bool>) -> Self {
+        use unicode_bidi::Level;
+        let default_level = rtl.map(|rtl| if rtl { Level::rtl() } else { Level::ltr() });
+        let info = unicode_bidi::BidiInfo::new(text, default_level);
+        if !info.levels.iter().any(|level| level.is_rtl())
+            && !info.paragraphs.iter().any(|p| p.level.is_rtl())
+        {
+            return Self::default();
+        }
+        Self {
+            levels: info.levels.iter().map(|level| level.number()).collect(),
+            paragraphs: info
+                .paragraphs
+                .iter()
+                .map(|p| (p.range.clone(), p.level.number()))
+                .collect(),
+        }
+    }
+
+    #[cfg(not(feature = "unicode-bidi"))]
+    fn new_impl(_text: &str, _rtl: Option<bool>) -> Self {
+        Self::default()
+    }
+
+    /// Returns true if some of the text, or the direction of a paragraph, is right-to-left.
+    pub fn has_rtl(&self) -> bool {
+        !self.levels.is_empty()
+    }
+
+    /// The embedding level of the character at the given byte offset
+    pub fn level_at(&self, byte_offset: usize) -> u8 {
+        self.levels.get(byte_offset).copied().unwrap_or_default()
+    }
+
+    /// Returns true if the character at the given byte offset is right-to-left
+    pub fn is_rtl(&self, byte_offset: usize) -> bool {
+        self.level_at(byte_offset) % 2 == 1
+    }
+
+    /// The base level of the paragraph that contains the given byte offset
+    pub fn paragraph_level_at(&self, byte_offset: usize) -> u8 {
+        self.paragraphs
+            .iter()
+            .find(|(range, _)| range.contains(&byte_offset))
+            .or_else(|| self.paragraphs.last().filter(|(range, _)| range.end == byte_offset))
+            .map_or(0, |(_, level)| *level)
+    }
+
+    /// Returns true if the paragraph that contains the given byte offset is right-to-left
+    pub fn is_rtl_paragraph_at(&self, byte_offset: usize) -> bool {
+        self.paragraph_level_at(byte_offset) % 2 == 1
+    }
+
+    /// Returns the levels of the characters of a line, with the trailing whitespace reset to
+    /// the paragraph level (rule L1).
+    fn line_levels(
+        &self,
+        text: &str,
+        line: Range<usize>,
+    ) -> impl Iterator<Item = (usize, u8)> + '_ {
+        let paragraph_level = self.paragraph_level_at(line.start);
+        let trailing_whitespace_start = line.start + text[line.clone()].trim_end().len();
+        text[line.clone()].char_indices().map(move |(offset, _)| {
+            let offset = line.start + offset;
+            if offset >= trailing_whitespace_start {
+                (offset, paragraph_level)
+            } else {
+                (offset, self.level_at(offset))
+            }
+        })
+    }
+
+    /// Splits the line (a byte range of `text`) in runs of the same direction,
+    /// in visual order from left to right.
+    pub fn visual_runs(&self, text: &str, line: Range<usize>) -> Vec<VisualRun> {
+        let mut runs: Vec<(Range<usize>, u8)> = Vec::new();
+        for (offset, level) in self.line_levels(text, line.clone()) {
+            match runs.last_mut() {
+                Some((_, run_level)) if *run_level == level => {}
+                _ => runs.push((offset..offset, level)),
+            }
+        }
+        // Each run ends where the next one starts
+        let mut end = line.end;
+        for (range, _) in runs.iter_mut().rev() {
+            range.end = end;
+            end = range.start;
+        }
+        let levels = runs.iter().map(|(_, level)| *level).collect::<Vec<_>>();
+        reorder_visually(&levels)
+            .into_iter()
+            .map(|index| VisualRun {
+                byte_range: runs[index].0.clone(),
+                rtl: runs[index].1 % 2 == 1,
+            })
+            .collect()
+    }
+}
+
+/// A run of text of a single direction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VisualRun {
+    pub byte_range: Range<usize>,
+    pub rtl: bool,
+}
+
+/// Returns the indices of the items with the given levels, in visual order (rule L2):
+/// from the highest level to the lowest odd level, every sequence of items at that
+/// level or higher is reversed.
+pub fn reorder_visually(levels: &[u8]) -> Vec<usize> {
+    let mut order = (0..levels.len()).collect::<Vec<_>>();
+    let max_level = match levels.iter().max() {
+        Some(level) => *level,
+        None => return order,
+    };
+    let lowest_odd_level = match levels.iter().filter(|level| *level % 2 == 1).min() {
+        Some(level) => *level,
+        None => return order,
+    };
+    for level in (lowest_odd_level..=max_level).rev() {
+        let mut index = 0;
+        while index < order.len() {
+            if levels[order[index]] < level {
+                index += 1;
+                continue;
+            }
+            let start = index;
+            while index < order.len() && levels[order[index]] >= level {
+                index += 1;
+            }
+            order[start..index].reverse();
+        }
+    }
+    order
+}
+
+#[test]
+fn test_reorder_visually() {
+    assert_eq!(reorder_visually(&[]), Vec::<usize>::new());
+    assert_eq!(reorder_visually(&[0, 0, 0]), [0, 1, 2]);
+    assert_eq!(reorder_visually(&[1, 1, 1]), [2, 1, 0]);
+    assert_eq!(reorder_visually(&[0, 1, 1, 0]), [0, 2, 1, 3]);
+    // Numbers in right-to-left text keep their order
+    assert_eq!(reorder_visually(&[1, 2, 2, 1]), [3, 1, 2, 0]);
+}
+
+#[test]
+#[cfg_attr(
+    not(feature = "unicode-bidi"),
+    ignore = "Not supported without the unicode-bidi feature"
+)]
+fn test_bidi_info() {
+    let latin = BidiInfo::new("Hello World");
+    assert!(!latin.has_rtl());
+    assert_eq!(
+        latin.visual_runs("Hello World", 0..11),
+        [VisualRun { byte_range: 0..11, rtl: false }]
+    );
+
+    // "abc" followed by the hebrew letters alef, bet, gimel
+    let text = "abc \u{5d0}\u{5d1}\u{5d2}";
+    let mixed = BidiInfo::new(text);
+    assert!(mixed.has_rtl());
+    assert!(!mixed.is_rtl_paragraph_at(0));
+    assert!(!mixed.is_rtl(0));
+    assert!(mixed.is_rtl(4));
+    assert_eq!(
+        mixed.visual_runs(text, 0..text.len()),
+        [
+            VisualRun { byte_range: 0..4, rtl: false },
+            VisualRun { byte_range: 4..text.len(), rtl: true }
+        ]
+    );
+
+    // The same, in a right-to-left paragraph
+    let text = "\u{5d0}\u{5d1}\u{5d2} abc";
+    let rtl = BidiInfo::new(text);
+    assert!(rtl.is_rtl_paragraph_at(0));
+    assert_eq!(
+        rtl.visual_runs(text, 0..text.len()),
+        [
+            VisualRun { byte_range: 7..text.len(), rtl: false },
+            VisualRun { byte_range: 0..7, rtl: true }
+        ]
+    );
+
+    assert!(BidiInfo::with_direction("abc", true).is_rtl_paragraph_at(0));
+}
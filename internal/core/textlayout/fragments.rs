@@ -110,7 +110,10 @@ fn next(&mut self) -> Option<Self::Item> {
 fn fragment_iterator_simple() {
     let font = FixedTestFont;
     let text = "H WX";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let fragments = TextFragmentIterator::new(text, &shape_buffer).collect::<Vec<_>>();
     let expected = vec![
         TextFragment {
@@ -135,7 +138,10 @@ fn fragment_iterator_simple() {
 fn fragment_iterator_simple_v2() {
     let font = FixedTestFont;
     let text = "Hello World";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let fragments = TextFragmentIterator::new(text, &shape_buffer).collect::<Vec<_>>();
     let expected = vec![
         TextFragment {
@@ -160,7 +166,10 @@ fn fragment_iterator_simple_v2() {
 fn fragment_iterator_forced_break() {
     let font = FixedTestFont;
     let text = "H\nW";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let fragments = TextFragmentIterator::new(text, &shape_buffer).collect::<Vec<_>>();
     assert_eq!(
         fragments,
@@ -187,7 +196,10 @@ fn fragment_iterator_forced_break() {
 fn fragment_iterator_forced_break_multi() {
     let font = FixedTestFont;
     let text = "H\n\n\nW";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let fragments = TextFragmentIterator::new(text, &shape_buffer).collect::<Vec<_>>();
     assert_eq!(
         fragments,
@@ -228,7 +240,10 @@ fn fragment_iterator_forced_break_multi() {
 fn fragment_iterator_nbsp() {
     let font = FixedTestFont;
     let text = "X H\u{00a0}W";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let fragments = TextFragmentIterator::new(text, &shape_buffer).collect::<Vec<_>>();
     assert_eq!(
         fragments,
@@ -255,7 +270,10 @@ fn fragment_iterator_nbsp() {
 fn fragment_iterator_break_anywhere() {
     let font = FixedTestFont;
     let text = "AB\nCD\nEF";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let mut fragments = TextFragmentIterator::new(text, &shape_buffer);
     assert_eq!(
         fragments.next(),
@@ -167,7 +167,10 @@ fn next(&mut self) -> Option<Self::Item> {
 fn test_empty_line_break() {
     let font = FixedTestFont;
     let text = "";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let lines =
         TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(50.)).collect::<Vec<_>>();
     assert_eq!(lines.len(), 1);
@@ -178,7 +181,10 @@ fn test_empty_line_break() {
 fn test_basic_line_break() {
     let font = FixedTestFont;
     let text = "Hello World";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let lines =
         TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(50.)).collect::<Vec<_>>();
     assert_eq!(lines.len(), 2);
@@ -190,7 +196,10 @@ fn test_basic_line_break() {
 fn test_linebreak_trailing_space() {
     let font = FixedTestFont;
     let text = "Hello              ";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let lines =
         TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(50.)).collect::<Vec<_>>();
     assert_eq!(lines.len(), 1);
@@ -201,7 +210,10 @@ fn test_linebreak_trailing_space() {
 fn test_forced_break() {
     let font = FixedTestFont;
     let text = "Hello\nWorld";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let lines =
         TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, None).collect::<Vec<_>>();
     assert_eq!(lines.len(), 2);
@@ -213,7 +225,10 @@ fn test_forced_break() {
 fn test_forced_break_multi() {
     let font = FixedTestFont;
     let text = "Hello\n\n\nWorld";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let lines =
         TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, None).collect::<Vec<_>>();
     assert_eq!(lines.len(), 4);
@@ -227,7 +242,10 @@ fn test_forced_break_multi() {
 fn test_nbsp_break() {
     let font = FixedTestFont;
     let text = "Ok Hello\u{00a0}World";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let lines =
         TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(110.)).collect::<Vec<_>>();
     assert_eq!(lines.len(), 2);
@@ -239,7 +257,10 @@ fn test_nbsp_break() {
 fn test_single_line_multi_break_opportunity() {
     let font = FixedTestFont;
     let text = "a b c";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let lines =
         TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, None).collect::<Vec<_>>();
     assert_eq!(lines.len(), 1);
@@ -250,7 +271,10 @@ fn test_single_line_multi_break_opportunity() {
 fn test_basic_line_break_anywhere_fallback() {
     let font = FixedTestFont;
     let text = "HelloWorld";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let lines =
         TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(50.)).collect::<Vec<_>>();
     assert_eq!(lines.len(), 2);
@@ -262,7 +286,10 @@ fn test_basic_line_break_anywhere_fallback() {
 fn test_basic_line_break_anywhere_fallback_multi_line() {
     let font = FixedTestFont;
     let text = "HelloWorld\nHelloWorld";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let lines =
         TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(50.)).collect::<Vec<_>>();
     assert_eq!(lines.len(), 4);
@@ -276,7 +303,10 @@ fn test_basic_line_break_anywhere_fallback_multi_line() {
 fn test_basic_line_break_anywhere_fallback_multi_line_v2() {
     let font = FixedTestFont;
     let text = "HelloW orldHellow";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let lines =
         TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(50.)).collect::<Vec<_>>();
     assert_eq!(lines.len(), 4);
@@ -291,7 +321,10 @@ fn test_basic_line_break_space() {
     // The available width is half-way into the trailing "W"
     let font = FixedTestFont;
     let text = "H W";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let lines =
         TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(25.)).collect::<Vec<_>>();
     assert_eq!(lines.len(), 2);
@@ -304,7 +337,10 @@ fn test_basic_line_break_space_v2() {
     // The available width is half-way into the trailing "W"
     let font = FixedTestFont;
     let text = "B B W";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let lines =
         TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(45.)).collect::<Vec<_>>();
     assert_eq!(lines.len(), 2);
@@ -317,7 +353,10 @@ fn test_basic_line_break_space_v3() {
     // The available width is half-way into the trailing "W"
     let font = FixedTestFont;
     let text = "H   W";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let lines =
         TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(15.)).collect::<Vec<_>>();
     assert_eq!(lines.len(), 2);
@@ -330,7 +369,10 @@ fn test_basic_line_break_space_v4() {
     // The available width is half-way into the trailing space
     let font = FixedTestFont;
     let text = "H W  H  ";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let lines =
         TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(65.)).collect::<Vec<_>>();
     assert_eq!(lines.len(), 1);
@@ -341,7 +383,10 @@ fn test_basic_line_break_space_v4() {
 fn test_line_width_with_whitespace() {
     let font = FixedTestFont;
     let text = "Hello World";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let lines =
         TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(200.)).collect::<Vec<_>>();
     assert_eq!(lines.len(), 1);
@@ -352,7 +397,10 @@ fn test_line_width_with_whitespace() {
 fn zero_width() {
     let font = FixedTestFont;
     let text = "He\nHe o";
-    let shape_buffer = ShapeBuffer::new(&TextLayout { font: &font, letter_spacing: None }, text);
+    let shape_buffer = ShapeBuffer::new(
+        &TextLayout { font: &font, letter_spacing: None, span_fonts: &[], line_height: None },
+        text,
+    );
     let lines = TextLineBreaker::<FixedTestFont>::new(text, &shape_buffer, Some(0.0001))
         .map(|t| t.line_text(&text))
         .collect::<Vec<_>>();
@@ -4,6 +4,7 @@
 use alloc::vec::Vec;
 use core::ops::Range;
 
+use super::bidi::BidiInfo;
 use super::TextLayout;
 
 /// This struct describes a glyph from shaping to rendering. This includes the relative shaping
@@ -75,28 +76,29 @@ pub trait AbstractFont: TextShaper + FontMetrics<<Self as TextShaper>::Length> {
 
 impl<T> AbstractFont for T where T: TextShaper + FontMetrics<<Self as TextShaper>::Length> {}
 
+/// Iterates over the end of the ranges of the text that can be shaped at once: a boundary
+/// is emitted whenever the script (with the unicode-script feature) or the bidi level changes.
 pub struct ShapeBoundaries<'a> {
     text: &'a str,
-    #[cfg(feature = "unicode-script")]
-    // TODO: We should do a better analysis to find boundaries for text shaping; including
-    // boundaries when the bidi level changes or an explicit separator like
-    // paragraph/lineseparator/space is encountered.
+    bidi: &'a BidiInfo,
     chars: core::str::CharIndices<'a>,
     next_boundary_start: Option<usize>,
     #[cfg(feature = "unicode-script")]
     last_script: Option<unicode_script::Script>,
+    last_level: u8,
 }
 
 impl<'a> ShapeBoundaries<'a> {
-    pub fn new(text: &'a str) -> Self {
+    pub fn new(text: &'a str, bidi: &'a BidiInfo) -> Self {
         let next_boundary_start = if !text.is_empty() { Some(0) } else { None };
         Self {
             text,
-            #[cfg(feature = "unicode-script")]
+            bidi,
             chars: text.char_indices(),
             next_boundary_start,
             #[cfg(feature = "unicode-script")]
             last_script: None,
+            last_level: bidi.level_at(0),
         }
     }
 }
@@ -104,66 +106,62 @@ pub fn new(text: &'a str) -> Self {
 impl<'a> Iterator for ShapeBoundaries<'a> {
     type Item = usize;
 
-    #[cfg(feature = "unicode-script")]
     fn next(&mut self) -> Option<Self::Item> {
-        if self.next_boundary_start.is_none() {
-            return None;
-        }
+        self.next_boundary_start?;
 
-        let (next_offset, script) = loop {
+        let next_offset = loop {
             match self.chars.next() {
-                Some((byte_offset, ch)) => {
-                    use unicode_script::UnicodeScript;
-                    let next_script = ch.script();
-                    let previous_script = *self.last_script.get_or_insert(next_script);
+                Some((byte_offset, _ch)) => {
+                    let level = self.bidi.level_at(byte_offset);
+                    let level_changed = level != self.last_level;
+                    self.last_level = level;
+
+                    #[cfg(feature = "unicode-script")]
+                    let script_changed = {
+                        use unicode_script::UnicodeScript;
+                        let next_script = _ch.script();
+                        let previous_script = *self.last_script.get_or_insert(next_script);
+                        let changed = next_script != previous_script
+                            && !matches!(
+                                next_script,
+                                unicode_script::Script::Unknown
+                                    | unicode_script::Script::Common
+                                    | unicode_script::Script::Inherited,
+                            );
+                        if changed {
+                            self.last_script = Some(next_script);
+                        }
+                        changed
+                    };
+                    #[cfg(not(feature = "unicode-script"))]
+                    let script_changed = false;
 
-                    if next_script == previous_script {
-                        continue;
-                    }
-                    if matches!(
-                        next_script,
-                        unicode_script::Script::Unknown
-                            | unicode_script::Script::Common
-                            | unicode_script::Script::Inherited,
-                    ) {
-                        continue;
+                    if level_changed || script_changed {
+                        break Some(byte_offset);
                     }
-
-                    break (Some(byte_offset), Some(next_script));
-                }
-                None => {
-                    break (None, None);
                 }
+                None => break None,
             }
         };
 
-        self.last_script = script;
         self.next_boundary_start = next_offset;
 
-        Some(self.next_boundary_start.unwrap_or(self.text.len()))
-    }
-
-    #[cfg(not(feature = "unicode-script"))]
-    fn next(&mut self) -> Option<Self::Item> {
-        match self.next_boundary_start {
-            Some(_) => {
-                self.next_boundary_start = None;
-                Some(self.text.len())
-            }
-            None => None,
-        }
+        Some(next_offset.unwrap_or(self.text.len()))
     }
 }
 
 pub struct TextRun {
     pub byte_range: Range<usize>,
-    //pub glyph_range: Range<usize>,
-    // TODO: direction, etc.
+    pub glyph_range: Range<usize>,
+    /// The bidi embedding level of the run. Odd levels are right-to-left.
+    pub level: u8,
 }
 
 pub struct ShapeBuffer<Length, PlatformGlyphData> {
+    /// The glyphs of all the runs, in logical order
     pub glyphs: Vec<Glyph<Length, PlatformGlyphData>>,
     pub text_runs: Vec<TextRun>,
+    pub bidi: BidiInfo,
 }
 
 impl<Length, PlatformGlyphData> ShapeBuffer<Length, PlatformGlyphData> {
@@ -172,12 +170,40 @@ pub fn new<Font>(layout: &TextLayout<Font>, text: &str) -> Self
         Font: AbstractFont<Length = Length, PlatformGlyphData = PlatformGlyphData>,
         Length: Copy + core::ops::AddAssign,
     {
+        let bidi = BidiInfo::new(text);
         let mut glyphs = Vec::new();
-        let text_runs = ShapeBoundaries::new(text)
+
+        // A run is shaped with a single font, so the runs also end where a span font starts or ends
+        let mut run_ends = ShapeBoundaries::new(text, &bidi).collect::<Vec<_>>();
+        if !layout.span_fonts.is_empty() {
+            run_ends.extend(
+                layout
+                    .span_fonts
+                    .iter()
+                    .flat_map(|(range, _)| [range.start, range.end])
+                    .filter(|offset| *offset > 0 && *offset < text.len()),
+            );
+            run_ends.sort_unstable();
+            run_ends.dedup();
+        }
+
+        let text_runs = run_ends
+            .into_iter()
             .scan(0, |run_start, run_end| {
                 let glyphs_start = glyphs.len();
 
-                layout.font.shape_text(&text[*run_start..run_end], &mut glyphs);
+                layout.font_at(*run_start).shape_text(&text[*run_start..run_end], &mut glyphs);
+
+                // Shapers emit the glyphs of right-to-left text in visual order, but the
+                // line breaking works in logical order. The lines are reordered when laid out.
+                let level = bidi.level_at(*run_start);
+                if level % 2 == 1
+                    && glyphs.len() > glyphs_start + 1
+                    && glyphs[glyphs_start].text_byte_offset
+                        > glyphs.last().unwrap().text_byte_offset
+                {
+                    glyphs[glyphs_start..].reverse();
+                }
 
                 if let Some(letter_spacing) = layout.letter_spacing {
                     if glyphs.len() > glyphs_start {
@@ -197,10 +223,8 @@ pub fn new<Font>(layout: &TextLayout<Font>, text: &str) -> Self
 
                 let run = TextRun {
                     byte_range: Range { start: *run_start, end: run_end },
-                    //glyph_range: Range {
-                    //     start: glyphs_start,
-                    //     end: glyph_buffer.borrow().as_ref().len(),
-                    // },
+                    glyph_range: Range { start: glyphs_start, end: glyphs.len() },
+                    level,
                 };
                 *run_start = run_end;
 
@@ -208,7 +232,52 @@ pub fn new<Font>(layout: &TextLayout<Font>, text: &str) -> Self
             })
             .collect();
 
-        Self { glyphs, text_runs }
+        Self { glyphs, text_runs, bidi }
+    }
+
+    /// Returns the byte offset in the text of the character that produced the glyph at the given index
+    pub fn glyph_byte_offset(&self, index: usize) -> usize {
+        let run = self
+            .text_runs
+            .iter()
+            .find(|run| run.glyph_range.contains(&index))
+            .expect("glyph index must be in a run");
+        run.byte_range.start + self.glyphs[index].text_byte_offset
+    }
+
+    /// Returns the indices of the glyphs in the given range, in visual order from left to right,
+    /// or None if the glyphs are all left-to-right and their logical order is also the visual order.
+    pub fn visual_glyph_order(&self, text: &str, glyph_range: Range<usize>) -> Option<Vec<usize>> {
+        if !self.bidi.has_rtl() || glyph_range.is_empty() {
+            return None;
+        }
+
+        // Trailing whitespace is reset to the paragraph level (rule L1)
+        let paragraph_level =
+            self.bidi.paragraph_level_at(self.glyph_byte_offset(glyph_range.start));
+        let mut trailing_whitespace = true;
+        let mut levels = glyph_range
+            .clone()
+            .rev()
+            .map(|index| {
+                let byte_offset = self.glyph_byte_offset(index);
+                trailing_whitespace = trailing_whitespace
+                    && text[byte_offset..].chars().next().map_or(true, char::is_whitespace);
+                if trailing_whitespace {
+                    paragraph_level
+                } else {
+                    self.bidi.level_at(byte_offset)
+                }
+            })
+            .collect::<Vec<_>>();
+        levels.reverse();
+
+        Some(
+            super::bidi::reorder_visually(&levels)
+                .into_iter()
+                .map(|index| glyph_range.start + index)
+                .collect(),
+        )
     }
 }
 
@@ -216,7 +285,8 @@ pub fn new<Font>(layout: &TextLayout<Font>, text: &str) -> Self
 fn test_shape_boundaries_simple() {
     {
         let simple_text = "Hello World";
-        let mut itemizer = ShapeBoundaries::new(simple_text);
+        let bidi = BidiInfo::new(simple_text);
+        let mut itemizer = ShapeBoundaries::new(simple_text, &bidi);
         assert_eq!(itemizer.next(), Some(simple_text.len()));
         assert_eq!(itemizer.next(), None);
     }
@@ -225,7 +295,8 @@ fn test_shape_boundaries_simple() {
 #[test]
 fn test_shape_boundaries_empty() {
     {
-        let mut itemizer = ShapeBoundaries::new("");
+        let bidi = BidiInfo::default();
+        let mut itemizer = ShapeBoundaries::new("", &bidi);
         assert_eq!(itemizer.next(), None);
     }
 }
@@ -238,7 +309,8 @@ fn test_shape_boundaries_empty() {
 fn test_shape_boundaries_script_change() {
     {
         let text = "abc🍌🐒defதோசை.";
-        let mut itemizer = ShapeBoundaries::new(text).scan(0, |start, end| {
+        let bidi = BidiInfo::new(text);
+        let mut itemizer = ShapeBoundaries::new(text, &bidi).scan(0, |start, end| {
             let str = &text[*start..end];
             *start = end;
             Some(str)
@@ -249,6 +321,29 @@ fn test_shape_boundaries_script_change() {
     }
 }
 
+#[test]
+#[cfg_attr(
+    not(feature = "unicode-bidi"),
+    ignore = "Not supported without the unicode-bidi feature"
+)]
+fn test_shape_boundaries_bidi_level_change() {
+    {
+        // The hebrew letters alef and bet, between latin words
+        let text = "abc \u{5d0}\u{5d1} def";
+        let bidi = BidiInfo::new(text);
+        let mut itemizer = ShapeBoundaries::new(text, &bidi).scan(0, |start, end| {
+            let str = &text[*start..end];
+            *start = end;
+            Some(str)
+        });
+        assert_eq!(itemizer.next(), Some("abc "));
+        assert_eq!(itemizer.next(), Some("\u{5d0}\u{5d1}"));
+        assert_eq!(itemizer.next(), Some(" "));
+        assert_eq!(itemizer.next(), Some("def"));
+        assert_eq!(itemizer.next(), None);
+    }
+}
+
 #[cfg(test)]
 #[derive(Clone, Debug, Default)]
 pub struct TestGlyphData {
@@ -405,7 +500,12 @@ fn test_letter_spacing() {
             shaped_glyphs.iter().map(|g| g.advance).collect::<Vec<_>>()
         };
 
-        let layout = TextLayout { font: &face, letter_spacing: Some(20.) };
+        let layout = TextLayout {
+            font: &face,
+            letter_spacing: Some(20.),
+            span_fonts: &[],
+            line_height: None,
+        };
         let buffer = ShapeBuffer::new(&layout, text);
 
         assert_eq!(buffer.glyphs.len(), advances.len());
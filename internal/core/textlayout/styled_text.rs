@@ -0,0 +1,278 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Text with spans of different styles, as parsed from the markup of a `Text` element
+//! with `text-format: markup`.
+//!
+//! The markup is a small subset of HTML:
+//!  * `<b>bold</b>`
+//!  * `<i>italic</i>`
+//!  * `<font color="#ff0000">red</font>`, with a color in the `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa` form
+//!  * `<a href="https://slint-ui.com">link</a>`
+//!  * the entities `&lt;`, `&gt;`, `&amp;`, `&quot;` and `&apos;`
+//!
+//! Tags can be nested. Anything that is not a known tag is shown as is.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::graphics::{Color, FontRequest};
+use crate::SharedString;
+
+/// The font weight of the text in a `<b>` tag
+const BOLD_FONT_WEIGHT: i32 = 700;
+
+/// The style of a span of text. The properties that are not set are the ones of the `Text` element.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextStyle {
+    pub weight: Option<i32>,
+    pub italic: bool,
+    pub color: Option<Color>,
+    /// The url of the link, if the span is a link
+    pub link: Option<SharedString>,
+}
+
+impl TextStyle {
+    /// Returns true if the text of this style needs another font than the one of the `Text` element
+    pub fn changes_font(&self) -> bool {
+        self.weight.is_some() || self.italic
+    }
+
+    /// Returns the font request for the text of this style, based on the one of the `Text` element
+    pub fn font_request(&self, base: &FontRequest) -> FontRequest {
+        FontRequest {
+            weight: self.weight.or(base.weight),
+            italic: self.italic || base.italic,
+            ..base.clone()
+        }
+    }
+}
+
+/// A span of text with a style
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextSpan {
+    /// The byte range of the span in the text
+    pub range: Range<usize>,
+    pub style: TextStyle,
+}
+
+/// A text and the styles of its spans
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StyledText {
+    /// The text, without the markup
+    pub text: SharedString,
+    /// The spans with a style, sorted and without overlap. The text that is in no span
+    /// has the style of the `Text` element.
+    pub spans: Vec<TextSpan>,
+}
+
+impl StyledText {
+    /// A text without any span
+    pub fn plain(text: SharedString) -> Self {
+        Self { text, spans: Vec::new() }
+    }
+
+    /// Parses the markup described in the [module documentation](self)
+    pub fn parse_markup(markup: &str) -> Self {
+        let mut text = String::with_capacity(markup.len());
+        let mut spans: Vec<TextSpan> = Vec::new();
+        // The open tags, with the style of the text inside them
+        let mut stack: Vec<(&str, TextStyle)> = Vec::new();
+        let mut rest = markup;
+
+        while let Some(special) = rest.find(|c| c == '<' || c == '&') {
+            push_text(
+                &mut text,
+                &mut spans,
+                stack.last().map(|(_, style)| style),
+                &rest[..special],
+            );
+            rest = &rest[special..];
+
+            if rest.starts_with('&') {
+                let (replacement, len) = ENTITIES
+                    .iter()
+                    .find(|(entity, _)| rest.starts_with(entity))
+                    .map_or(("&", 1), |(entity, replacement)| (*replacement, entity.len()));
+                push_text(&mut text, &mut spans, stack.last().map(|(_, style)| style), replacement);
+                rest = &rest[len..];
+                continue;
+            }
+
+            let tag = match rest.find('>') {
+                Some(end) => &rest[1..end],
+                None => "",
+            };
+            let current_style = stack.last().map(|(_, style)| style.clone()).unwrap_or_default();
+            if let Some(name) = tag.strip_prefix('/') {
+                if let Some(position) = stack.iter().rposition(|(open, _)| *open == name.trim()) {
+                    stack.truncate(position);
+                    rest = &rest[tag.len() + 2..];
+                    continue;
+                }
+            } else if let Some((name, style)) = parse_open_tag(tag, current_style) {
+                stack.push((name, style));
+                rest = &rest[tag.len() + 2..];
+                continue;
+            }
+
+            // Not a tag that we know: show the `<` as is
+            push_text(&mut text, &mut spans, stack.last().map(|(_, style)| style), "<");
+            rest = &rest[1..];
+        }
+        push_text(&mut text, &mut spans, stack.last().map(|(_, style)| style), rest);
+
+        Self { text: text.into(), spans }
+    }
+
+    /// The span that contains the given byte offset, if any
+    pub fn span_at(&self, byte_offset: usize) -> Option<&TextSpan> {
+        self.spans.iter().find(|span| span.range.contains(&byte_offset))
+    }
+
+    /// The url of the link at the given byte offset, if any
+    pub fn link_at(&self, byte_offset: usize) -> Option<&SharedString> {
+        self.span_at(byte_offset).and_then(|span| span.style.link.as_ref())
+    }
+}
+
+const ENTITIES: &[(&str, &str)] =
+    &[("&lt;", "<"), ("&gt;", ">"), ("&amp;", "&"), ("&quot;", "\""), ("&apos;", "'")];
+
+/// Appends a piece of text, extending the last span if it has the same style
+fn push_text(text: &mut String, spans: &mut Vec<TextSpan>, style: Option<&TextStyle>, piece: &str) {
+    if piece.is_empty() {
+        return;
+    }
+    let range = text.len()..text.len() + piece.len();
+    text.push_str(piece);
+    let style = match style {
+        Some(style) if *style != TextStyle::default() => style,
+        _ => return,
+    };
+    match spans.last_mut() {
+        Some(last) if last.range.end == range.start && last.style == *style => {
+            last.range.end = range.end
+        }
+        _ => spans.push(TextSpan { range, style: style.clone() }),
+    }
+}
+
+/// Parses the content of an opening tag (between `<` and `>`), and returns its name
+/// and the style of the text inside it
+fn parse_open_tag(tag: &str, mut style: TextStyle) -> Option<(&str, TextStyle)> {
+    let (name, mut attributes) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+    let mut attribute = |attribute_name: &str| -> Option<SharedString> {
+        loop {
+            let (name, rest) = attributes.trim_start().split_once('=')?;
+            let rest = rest.trim_start();
+            let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+            let (value, rest) = rest[1..].split_once(quote)?;
+            attributes = rest;
+            if name.trim() == attribute_name {
+                return Some(unescape(value).into());
+            }
+        }
+    };
+    match name {
+        "b" => style.weight = Some(BOLD_FONT_WEIGHT),
+        "i" => style.italic = true,
+        "font" => style.color = Some(parse_color(&attribute("color")?)?),
+        "a" => style.link = Some(attribute("href")?),
+        _ => return None,
+    }
+    Some((name, style))
+}
+
+fn unescape(value: &str) -> String {
+    ENTITIES.iter().fold(value.into(), |value: String, (entity, replacement)| {
+        value.replace(entity, replacement)
+    })
+}
+
+/// Parses a color in the `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa` form
+fn parse_color(color: &str) -> Option<Color> {
+    let hex = color.trim().strip_prefix('#').filter(|hex| hex.is_ascii())?;
+    let component = |index: usize, len: usize| {
+        u8::from_str_radix(&hex[index * len..(index + 1) * len], 16).ok().map(|value| {
+            if len == 1 {
+                value * 0x11
+            } else {
+                value
+            }
+        })
+    };
+    let (len, has_alpha) = match hex.len() {
+        3 => (1, false),
+        4 => (1, true),
+        6 => (2, false),
+        8 => (2, true),
+        _ => return None,
+    };
+    let alpha = if has_alpha { component(3, len)? } else { 255 };
+    Some(Color::from_argb_u8(alpha, component(0, len)?, component(1, len)?, component(2, len)?))
+}
+
+#[test]
+fn test_parse_markup() {
+    let styled = StyledText::parse_markup("Hello World");
+    assert_eq!(styled, StyledText::plain("Hello World".into()));
+
+    let styled = StyledText::parse_markup("<b>Warning:</b> file <i>foo</i> not found");
+    assert_eq!(styled.text, "Warning: file foo not found");
+    let bold = TextStyle { weight: Some(BOLD_FONT_WEIGHT), ..Default::default() };
+    let italic = TextStyle { italic: true, ..Default::default() };
+    assert_eq!(
+        styled.spans,
+        [
+            TextSpan { range: 0..8, style: bold.clone() },
+            TextSpan { range: 14..17, style: italic.clone() }
+        ]
+    );
+
+    // Nested tags, colors and links
+    let styled = StyledText::parse_markup(
+        "<font color=\"#f00\">red <b>bold</b></font> <a href='https://slint-ui.com?a=1&amp;b=2'>link</a>",
+    );
+    assert_eq!(styled.text, "red bold link");
+    let red = TextStyle { color: Some(Color::from_rgb_u8(255, 0, 0)), ..Default::default() };
+    assert_eq!(
+        styled.spans,
+        [
+            TextSpan { range: 0..4, style: red.clone() },
+            TextSpan { range: 4..8, style: TextStyle { weight: bold.weight, ..red } },
+            TextSpan {
+                range: 9..13,
+                style: TextStyle {
+                    link: Some("https://slint-ui.com?a=1&b=2".into()),
+                    ..Default::default()
+                }
+            },
+        ]
+    );
+    assert_eq!(styled.link_at(10).unwrap(), "https://slint-ui.com?a=1&b=2");
+    assert_eq!(styled.link_at(3), None);
+
+    // Entities, unknown tags and unterminated tags are kept as text
+    let styled = StyledText::parse_markup("a &lt; b && <u>c</u> <b d");
+    assert_eq!(styled.text, "a < b && <u>c</u> <b d");
+    assert!(styled.spans.is_empty());
+
+    // A closing tag closes the tags that are opened inside it
+    let styled = StyledText::parse_markup("<b><i>x</b>y</i>");
+    assert_eq!(styled.text, "xy</i>");
+    assert_eq!(
+        styled.spans,
+        [TextSpan { range: 0..1, style: TextStyle { italic: true, ..bold.clone() } }]
+    );
+}
+
+#[test]
+fn test_parse_color() {
+    assert_eq!(parse_color("#fff"), Some(Color::from_rgb_u8(255, 255, 255)));
+    assert_eq!(parse_color("#12345678"), Some(Color::from_argb_u8(0x78, 0x12, 0x34, 0x56)));
+    assert_eq!(parse_color("#0f08"), Some(Color::from_argb_u8(0x88, 0, 0xff, 0)));
+    assert_eq!(parse_color("red"), None);
+    assert_eq!(parse_color("#12345"), None);
+}
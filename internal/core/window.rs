@@ -7,7 +7,8 @@
 //! Exposed Window API
 
 use crate::api::{
-    CloseRequestResponse, PhysicalPosition, PhysicalSize, Window, WindowPosition, WindowSize,
+    CloseRequestResponse, LogicalSize, PhysicalPosition, PhysicalSize, Window, WindowPosition,
+    WindowSize,
 };
 use crate::component::{ComponentRc, ComponentRef, ComponentVTable, ComponentWeak};
 use crate::graphics::{Point, Rect, Size};
@@ -21,6 +22,7 @@
 use crate::{Callback, Coord};
 use alloc::boxed::Box;
 use alloc::rc::{Rc, Weak};
+use alloc::vec::Vec;
 use core::cell::{Cell, RefCell};
 use core::pin::Pin;
 use vtable::VRcMapped;
@@ -83,6 +85,18 @@ fn create_popup(&self, _geometry: Rect) -> Option<Rc<dyn WindowAdapter>> {
         None
     }
 
+    /// Show the menu bar as a native menu bar of the window.
+    ///
+    /// `menu_bar` is the [`MenuBarItem`](crate::items::MenuBarItem), its menus are the
+    /// [`MenuEntryItem`](crate::items::MenuEntryItem) children. Returns the height the native menu
+    /// bar occupies at the top of the window.
+    ///
+    /// If this function return None (the default implementation), then the
+    /// menu bar will be rendered within the window itself.
+    fn setup_menu_bar(&self, _menu_bar: &ItemRc) -> Option<Coord> {
+        None
+    }
+
     /// Request for the event loop to wake up and call [`WindowInner::update_window_properties()`].
     fn request_window_properties_update(&self) {}
     /// Request for the given title string to be set to the windowing system for use as window title.
@@ -100,6 +114,15 @@ fn apply_geometry_constraint(
     /// Set the mouse cursor
     fn set_mouse_cursor(&self, _cursor: MouseCursor) {}
 
+    /// Called when the transient parent or the modality of the window changed, so the
+    /// backend can tell the windowing system. The input to the parent of a modal window is
+    /// blocked by the [`WindowInner`] regardless.
+    fn apply_transient_parent(&self, _parent: Option<&Rc<dyn WindowAdapter>>, _modal: bool) {}
+
+    /// Bring the window to the front and give it the keyboard focus. This is called when the
+    /// user tries to interact with a window that is blocked by this modal window.
+    fn raise(&self) {}
+
     /// This is called when the virtual keyboard should be shown because a widget that
     /// uses input has the focus.
     fn show_virtual_keyboard(&self, _: crate::items::InputType) {}
@@ -174,6 +197,8 @@ pub enum PopupWindowLocation {
 pub struct PopupWindow {
     /// The location defines where the pop up is rendered.
     pub location: PopupWindowLocation,
+    /// The position of the popup, relative to the window.
+    pub position: Point,
     /// The component that is responsible for providing the popup content.
     pub component: ComponentRc,
 }
@@ -192,7 +217,27 @@ pub struct WindowInner {
 
     scale_factor: Pin<Box<Property<f32>>>,
     active: Pin<Box<Property<bool>>>,
-    active_popup: RefCell<Option<PopupWindow>>,
+    /// The minimum size set with [`Window::set_min_size`], combined with the constraints of the layout
+    min_size: Pin<Box<Property<Option<LogicalSize>>>>,
+    /// The maximum size set with [`Window::set_max_size`], combined with the constraints of the layout
+    max_size: Pin<Box<Property<Option<LogicalSize>>>>,
+    /// The stack of popups that are currently shown. A popup shown from within another popup
+    /// (for example a sub-menu) is pushed on top of it.
+    active_popups: RefCell<Vec<PopupWindow>>,
+    /// Set when the mouse is pressed while a popup is shown, and reset if that press opens a new
+    /// popup: the popups are then closed when the mouse is released.
+    close_popups_on_release: Cell<bool>,
+    /// The component that received the last mouse event (the window's component or a popup)
+    mouse_input_target: RefCell<Option<ComponentWeak>>,
+    menu_bar: RefCell<crate::item_tree::ItemWeak>,
+    /// The window set with [`Window::set_transient_parent`]
+    transient_parent: RefCell<Option<Weak<dyn WindowAdapter>>>,
+    /// The windows that have this window as transient parent
+    transient_children: RefCell<Vec<Weak<dyn WindowAdapter>>>,
+    /// When set, the input to the transient parent is blocked while this window is shown
+    modal: Cell<bool>,
+    /// Whether the window is currently shown
+    shown: Cell<bool>,
     close_requested: Callback<(), CloseRequestResponse>,
     /// This is a cache of the size set by the set_inner_size setter.
     /// It should be mapping with the WindowItem::width and height (only in physical)
@@ -238,7 +283,16 @@ pub fn new(window_adapter_weak: Weak<dyn WindowAdapter>) -> Self {
             cursor_blinker: Default::default(),
             scale_factor: Box::pin(Property::new_named(1., "i_slint_core::Window::scale_factor")),
             active: Box::pin(Property::new_named(false, "i_slint_core::Window::active")),
-            active_popup: Default::default(),
+            min_size: Box::pin(Property::new_named(None, "i_slint_core::Window::min_size")),
+            max_size: Box::pin(Property::new_named(None, "i_slint_core::Window::max_size")),
+            active_popups: Default::default(),
+            close_popups_on_release: Default::default(),
+            mouse_input_target: Default::default(),
+            menu_bar: Default::default(),
+            transient_parent: Default::default(),
+            transient_children: Default::default(),
+            modal: Default::default(),
+            shown: Default::default(),
             close_requested: Default::default(),
             inner_size: Default::default(),
         };
@@ -281,56 +335,114 @@ pub fn try_component(&self) -> Option<ComponentRc> {
     pub fn process_mouse_input(&self, mut event: MouseEvent) {
         crate::animations::update_animations();
 
-        let embedded_popup_component =
-            self.active_popup.borrow().as_ref().and_then(|popup| match popup.location {
-                PopupWindowLocation::TopLevel(_) => None,
-                PopupWindowLocation::ChildWindow(coordinates) => {
-                    Some((popup.component.clone(), coordinates))
-                }
-            });
+        if let Some(modal_window) = self.modal_window() {
+            // The input is blocked by a modal window. Let the items know that the mouse left,
+            // and bring the modal window to the user's attention when they click.
+            if matches!(event, MouseEvent::Pressed { .. }) {
+                modal_window.raise();
+            }
+            event = MouseEvent::Exit;
+        }
 
-        let component = embedded_popup_component
-            .as_ref()
-            .and_then(|(popup_component, coordinates)| {
-                event.translate(-coordinates.to_vector());
-
-                if let MouseEvent::Pressed { position, .. } = &event {
-                    // close the popup if one press outside the popup
-                    let geom = ComponentRc::borrow_pin(popup_component)
-                        .as_ref()
-                        .get_item_ref(0)
-                        .as_ref()
-                        .geometry();
-                    if !geom.contains(*position) {
-                        self.close_popup();
-                        return None;
-                    }
-                }
-                Some(popup_component.clone())
-            })
-            .or_else(|| self.component.borrow().upgrade());
+        let embedded_popup_components = self.embedded_popup_components();
+        let popup_geometry = |(popup_component, coordinates): &(ComponentRc, Point)| {
+            ComponentRc::borrow_pin(popup_component)
+                .as_ref()
+                .get_item_ref(0)
+                .as_ref()
+                .geometry()
+                .translate(coordinates.to_vector())
+        };
 
-        let component = if let Some(component) = component {
-            component
+        let mut mouse_input_state = self.mouse_input_state.take();
+        let last_target = self.mouse_input_target.borrow().as_ref().and_then(|c| c.upgrade());
+
+        let popup_target = if mouse_input_state.is_grabbed() {
+            // Keep sending the events to the component that has the grab
+            last_target.as_ref().and_then(|last_target| {
+                embedded_popup_components
+                    .iter()
+                    .find(|(popup_component, _)| vtable::VRc::ptr_eq(popup_component, last_target))
+                    .cloned()
+            })
+        } else if let MouseEvent::Pressed { position, .. } = event {
+            let popup = embedded_popup_components
+                .iter()
+                .rev()
+                .find(|popup| popup_geometry(popup).contains(position))
+                .cloned();
+            if popup.is_none() && !embedded_popup_components.is_empty() {
+                // close the popups if one press outside the popups
+                self.close_popup();
+            }
+            popup
         } else {
-            return;
+            event
+                .position()
+                .and_then(|position| {
+                    embedded_popup_components
+                        .iter()
+                        .rev()
+                        .find(|popup| popup_geometry(popup).contains(position))
+                })
+                .or_else(|| embedded_popup_components.last())
+                .cloned()
+        };
+
+        let (component, coordinates) = match popup_target {
+            Some(popup) => popup,
+            None => match self.component.borrow().upgrade() {
+                Some(component) => (component, Point::default()),
+                None => return,
+            },
         };
 
+        if let Some(last_target) = last_target {
+            if !vtable::VRc::ptr_eq(&last_target, &component) {
+                // The mouse moved to another component: send the exit events to the previous one
+                mouse_input_state = crate::input::process_mouse_input(
+                    last_target,
+                    MouseEvent::Exit,
+                    &self.window_adapter(),
+                    mouse_input_state,
+                );
+            }
+        }
+
+        if matches!(event, MouseEvent::Pressed { .. }) {
+            self.close_popups_on_release.set(!self.active_popups.borrow().is_empty());
+        }
+
+        event.translate(-coordinates.to_vector());
+        self.mouse_input_target.replace(Some(ComponentRc::downgrade(&component)));
         self.mouse_input_state.set(crate::input::process_mouse_input(
             component,
             event,
             &self.window_adapter(),
-            self.mouse_input_state.take(),
+            mouse_input_state,
         ));
 
-        if embedded_popup_component.is_some() {
-            //FIXME: currently the ComboBox is the only thing that uses the popup, and it should close automatically
-            // on release.  But ideally, there would be API to close the popup rather than always closing it on release
-            if matches!(event, MouseEvent::Released { .. }) {
-                self.close_popup();
-            }
+        // A click on an item of a popup closes the popups, unless that click opened another popup
+        // (such as a sub-menu)
+        if matches!(event, MouseEvent::Released { .. }) && self.close_popups_on_release.take() {
+            self.close_popup();
         }
     }
+
+    /// Returns the popups that are rendered within the window, with their position.
+    fn embedded_popup_components(&self) -> Vec<(ComponentRc, Point)> {
+        self.active_popups
+            .borrow()
+            .iter()
+            .filter_map(|popup| match popup.location {
+                PopupWindowLocation::TopLevel(_) => None,
+                PopupWindowLocation::ChildWindow(coordinates) => {
+                    Some((popup.component.clone(), coordinates))
+                }
+            })
+            .collect()
+    }
+
     /// Receive a key event and pass it to the items of the component to
     /// change their state.
     ///
@@ -338,6 +450,9 @@ pub fn process_mouse_input(&self, mut event: MouseEvent) {
     /// * `event`: The key event received by the windowing system.
     /// * `component`: The Slint compiled component that provides the tree of items.
     pub fn process_key_input(&self, event: &KeyEvent) {
+        if self.modal_window().is_some() {
+            return;
+        }
         let mut item = self.focus_item.borrow().clone().upgrade();
         while let Some(focus_item) = item {
             if !focus_item.is_visible() {
@@ -353,6 +468,15 @@ pub fn process_key_input(&self, event: &KeyEvent) {
             item = focus_item.parent_item();
         }
 
+        if event.event_type == KeyEventType::KeyPressed {
+            let menu_bar = self.menu_bar.borrow().upgrade();
+            if let Some(menu_bar) = menu_bar {
+                if crate::items::activate_menu_shortcut(&menu_bar, event) {
+                    return;
+                }
+            }
+        }
+
         // Make Tab/Backtab handle keyboard focus
         if event.text.starts_with(key_codes::Tab) && event.event_type == KeyEventType::KeyPressed {
             self.focus_next_item();
@@ -496,10 +620,22 @@ pub fn update_window_properties(&self) {
         self.window_properties_tracker.as_ref().evaluate_as_dependency_root(|| {
             let component = self.component();
             let component = ComponentRc::borrow_pin(&component);
-            self.window_adapter().apply_geometry_constraint(
-                component.as_ref().layout_info(crate::layout::Orientation::Horizontal),
-                component.as_ref().layout_info(crate::layout::Orientation::Vertical),
-            );
+            let mut constraints_horizontal =
+                component.as_ref().layout_info(crate::layout::Orientation::Horizontal);
+            let mut constraints_vertical =
+                component.as_ref().layout_info(crate::layout::Orientation::Vertical);
+            if let Some(min_size) = self.min_size.as_ref().get() {
+                constraints_horizontal.min =
+                    constraints_horizontal.min.max(min_size.width as Coord);
+                constraints_vertical.min = constraints_vertical.min.max(min_size.height as Coord);
+            }
+            if let Some(max_size) = self.max_size.as_ref().get() {
+                constraints_horizontal.max =
+                    constraints_horizontal.max.min(max_size.width as Coord);
+                constraints_vertical.max = constraints_vertical.max.min(max_size.height as Coord);
+            }
+            self.window_adapter()
+                .apply_geometry_constraint(constraints_horizontal, constraints_vertical);
             if let Some(window_item) = self.window_item() {
                 self.window_adapter().apply_window_properties(window_item.as_pin_ref());
             }
@@ -511,23 +647,16 @@ pub fn update_window_properties(&self) {
     pub fn draw_contents(&self, render_components: impl FnOnce(&[(&ComponentRc, Point)])) {
         let draw_fn = || {
             let component_rc = self.component();
-
-            let popup_component =
-                self.active_popup.borrow().as_ref().and_then(|popup| match popup.location {
-                    PopupWindowLocation::TopLevel(_) => None,
-                    PopupWindowLocation::ChildWindow(coordinates) => {
-                        Some((popup.component.clone(), coordinates))
-                    }
-                });
-
-            if let Some((popup_component, popup_coordinates)) = popup_component {
-                render_components(&[
-                    (&component_rc, Point::default()),
-                    (&popup_component, popup_coordinates),
-                ])
-            } else {
-                render_components(&[(&component_rc, Point::default())]);
-            }
+            let popup_components = self.embedded_popup_components();
+
+            let components = core::iter::once((&component_rc, Point::default()))
+                .chain(
+                    popup_components
+                        .iter()
+                        .map(|(component, coordinates)| (component, *coordinates)),
+                )
+                .collect::<Vec<_>>();
+            render_components(&components);
         };
 
         self.redraw_tracker.as_ref().evaluate_as_dependency_root(draw_fn)
@@ -536,16 +665,77 @@ pub fn draw_contents(&self, render_components: impl FnOnce(&[(&ComponentRc, Poin
     /// Registers the window with the windowing system, in order to render the component's items and react
     /// to input events once the event loop spins.
     pub fn show(&self) {
+        self.shown.set(true);
         self.window_adapter().show();
         self.update_window_properties();
     }
 
     /// De-registers the window with the windowing system.
     pub fn hide(&self) {
+        self.shown.set(false);
         self.window_adapter().hide();
     }
 
+    /// Sets the window that this window is a transient child of.
+    ///
+    /// The call is ignored if `parent` is this window or one of its transient children, as that
+    /// would make a cycle.
+    pub fn set_transient_parent(&self, parent: Option<Rc<dyn WindowAdapter>>) {
+        let self_ptr = self.window_adapter_weak.as_ptr() as *const u8;
+        let mut ancestor = parent.clone();
+        while let Some(window_adapter) = ancestor {
+            if Rc::as_ptr(&window_adapter) as *const u8 == self_ptr {
+                return;
+            }
+            ancestor = window_adapter.window().0.transient_parent();
+        }
+
+        let previous = self.transient_parent.replace(parent.as_ref().map(Rc::downgrade));
+        if let Some(previous) = previous.and_then(|p| p.upgrade()) {
+            previous.window().0.transient_children.borrow_mut().retain(|child| {
+                child.strong_count() > 0 && child.as_ptr() as *const u8 != self_ptr
+            });
+        }
+        if let Some(parent) = &parent {
+            let mut children = parent.window().0.transient_children.borrow_mut();
+            children.retain(|child| child.strong_count() > 0);
+            children.push(self.window_adapter_weak.clone());
+        }
+        self.window_adapter().apply_transient_parent(parent.as_ref(), self.modal.get());
+    }
+
+    /// Returns the window set with [`Self::set_transient_parent`], if it still exists.
+    pub fn transient_parent(&self) -> Option<Rc<dyn WindowAdapter>> {
+        self.transient_parent.borrow().as_ref().and_then(|parent| parent.upgrade())
+    }
+
+    /// Sets whether this window blocks the input to its transient parent while it is shown.
+    pub fn set_modal(&self, modal: bool) {
+        self.modal.set(modal);
+        self.window_adapter().apply_transient_parent(self.transient_parent().as_ref(), modal);
+    }
+
+    /// Returns true if this window is modal. See [`Self::set_modal`]
+    pub fn is_modal(&self) -> bool {
+        self.modal.get()
+    }
+
+    /// Returns the shown modal window that blocks the input to this window, if any.
+    /// If that modal window is itself blocked by another modal window, the latter is returned.
+    pub fn modal_window(&self) -> Option<Rc<dyn WindowAdapter>> {
+        let modal_child = self.transient_children.borrow().iter().find_map(|child| {
+            let child = child.upgrade()?;
+            let child_inner = &child.window().0;
+            (child_inner.modal.get() && child_inner.shown.get()).then(|| child)
+        })?;
+        // There is no cycle since set_transient_parent() rejects them
+        modal_child.window().0.modal_window().or(Some(modal_child))
+    }
+
     /// Show a popup at the given position relative to the item
+    ///
+    /// If the item is itself in a popup, the new popup is shown on top of it (any popup that
+    /// was shown from that popup is closed first). Otherwise, all the popups are closed first.
     pub fn show_popup(
         &self,
         popup_componentrc: &ComponentRc,
@@ -561,6 +751,20 @@ pub fn show_popup(
             }
         }
 
+        // parent_item is now the root item of the component containing the item
+        let parent_popup_index = self
+            .active_popups
+            .borrow()
+            .iter()
+            .position(|popup| vtable::VRc::ptr_eq(&popup.component, &parent_item.component()));
+        match parent_popup_index {
+            Some(index) => {
+                self.close_popups_above(index + 1);
+                position += self.active_popups.borrow()[index].position.to_vector();
+            }
+            None => self.close_popup(),
+        }
+
         let popup_component = ComponentRc::borrow_pin(&popup_componentrc);
         let popup_root = popup_component.as_ref().get_item_ref(0);
 
@@ -609,13 +813,42 @@ pub fn show_popup(
             }
         };
 
-        self.active_popup
-            .replace(Some(PopupWindow { location, component: popup_componentrc.clone() }));
+        self.active_popups.borrow_mut().push(PopupWindow {
+            location,
+            position,
+            component: popup_componentrc.clone(),
+        });
+        self.close_popups_on_release.set(false);
+    }
+
+    /// Registers the menu bar of the window, so that the shortcuts of its entries are handled.
+    /// This is called when the component containing the [`MenuBarItem`](crate::items::MenuBarItem)
+    /// is instantiated. The `native` and `native-height` properties of the item are set
+    /// depending on whether the backend shows a native menu bar.
+    pub fn register_menu_bar(&self, menu_bar: &ItemRc) {
+        self.menu_bar.replace(menu_bar.downgrade());
+        let native_height = self.window_adapter().setup_menu_bar(menu_bar);
+        if let Some(item) = menu_bar.downcast::<crate::items::MenuBarItem>() {
+            item.as_pin_ref().set_native_height(native_height);
+        }
     }
 
     /// Removes any active popup.
     pub fn close_popup(&self) {
-        if let Some(current_popup) = self.active_popup.replace(None) {
+        self.close_popups_above(0);
+    }
+
+    /// Close the popups that are above the given depth in the stack of popups.
+    fn close_popups_above(&self, depth: usize) {
+        loop {
+            // Don't keep the borrow while the popup is dropped
+            let current_popup = {
+                let mut active_popups = self.active_popups.borrow_mut();
+                if active_popups.len() <= depth {
+                    break;
+                }
+                active_popups.pop().unwrap()
+            };
             if let PopupWindowLocation::ChildWindow(offset) = current_popup.location {
                 // Refresh the area that was previously covered by the popup.
                 let popup_region = crate::properties::evaluate_no_tracking(|| {
@@ -670,6 +903,44 @@ pub fn set_window_item_geometry(&self, width: Coord, height: Coord) {
         }
     }
 
+    /// Sets the minimum size of the window, in addition to the constraints of the layout.
+    pub fn set_min_size(&self, size: Option<LogicalSize>) {
+        self.min_size.as_ref().set(size);
+    }
+
+    /// Sets the maximum size of the window, in addition to the constraints of the layout.
+    pub fn set_max_size(&self, size: Option<LogicalSize>) {
+        self.max_size.as_ref().set(size);
+    }
+
+    /// Called by the backends when the user or the windowing system changed the state of the window,
+    /// to update the properties of the Window element.
+    ///
+    /// Only the properties whose value differs are set, so that their bindings are kept as long as
+    /// the window follows them. Setting a property with a two-way binding updates the other side.
+    pub fn set_window_item_state(&self, minimized: bool, maximized: bool, fullscreen: bool) {
+        if let Some(window_item) = self.window_item() {
+            let window_item = window_item.as_pin_ref();
+            let update = |property: Pin<&Property<bool>>, value: bool| {
+                if property.get_untracked() != value {
+                    property.set(value);
+                }
+            };
+            update(
+                crate::items::WindowItem::FIELD_OFFSETS.minimized.apply_pin(window_item),
+                minimized,
+            );
+            update(
+                crate::items::WindowItem::FIELD_OFFSETS.maximized.apply_pin(window_item),
+                maximized,
+            );
+            update(
+                crate::items::WindowItem::FIELD_OFFSETS.fullscreen.apply_pin(window_item),
+                fullscreen,
+            );
+        }
+    }
+
     /// Sets the close_requested callback. The callback will be run when the user tries to close a window.
     pub fn on_close_requested(&self, mut callback: impl FnMut() -> CloseRequestResponse + 'static) {
         self.close_requested.set_handler(move |()| callback());
@@ -678,7 +949,13 @@ pub fn on_close_requested(&self, mut callback: impl FnMut() -> CloseRequestRespo
     /// Runs the close_requested callback.
     /// If the callback returns KeepWindowShown, this function returns false. That should prevent the Window from closing.
     /// Otherwise it returns true, which allows the Window to hide.
+    ///
+    /// The request is always rejected while a modal window blocks this window.
     pub fn request_close(&self) -> bool {
+        if let Some(modal_window) = self.modal_window() {
+            modal_window.raise();
+            return false;
+        }
         match self.close_requested.call(&()) {
             CloseRequestResponse::HideWindow => true,
             CloseRequestResponse::KeepWindowShown => false,
@@ -753,14 +1030,14 @@ pub enum GraphicsAPI {
     #[no_mangle]
     pub unsafe extern "C" fn slint_windowrc_show(handle: *const WindowAdapterRcOpaque) {
         let window_adapter = &*(handle as *const Rc<dyn WindowAdapter>);
-        window_adapter.show();
+        WindowInner::from_pub(window_adapter.window()).show();
     }
 
     /// Spins an event loop and renders the items of the provided component in this window.
     #[no_mangle]
     pub unsafe extern "C" fn slint_windowrc_hide(handle: *const WindowAdapterRcOpaque) {
-        let window = &*(handle as *const Rc<dyn WindowAdapter>);
-        window.hide();
+        let window_adapter = &*(handle as *const Rc<dyn WindowAdapter>);
+        WindowInner::from_pub(window_adapter.window()).hide();
     }
 
     /// Returns the window scale factor.
@@ -796,6 +1073,16 @@ pub enum GraphicsAPI {
         WindowInner::from_pub(window_adapter.window()).set_focus_item(focus_item)
     }
 
+    /// Registers the menu bar of the window.
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_windowrc_register_menu_bar(
+        handle: *const WindowAdapterRcOpaque,
+        menu_bar: &ItemRc,
+    ) {
+        let window_adapter = &*(handle as *const Rc<dyn WindowAdapter>);
+        WindowInner::from_pub(window_adapter.window()).register_menu_bar(menu_bar)
+    }
+
     /// Associates the window with the given component.
     #[no_mangle]
     pub unsafe extern "C" fn slint_windowrc_set_component(
@@ -915,6 +1202,34 @@ fn call(&self) -> CloseRequestResponse {
         window_adapter.request_redraw();
     }
 
+    /// Sets the transient parent of the window. `parent` may be null.
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_windowrc_set_transient_parent(
+        handle: *const WindowAdapterRcOpaque,
+        parent: *const WindowAdapterRcOpaque,
+    ) {
+        let window_adapter = &*(handle as *const Rc<dyn WindowAdapter>);
+        let parent = (parent as *const Rc<dyn WindowAdapter>).as_ref();
+        WindowInner::from_pub(window_adapter.window()).set_transient_parent(parent.cloned());
+    }
+
+    /// Sets whether the window blocks the input to its transient parent while it is shown.
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_windowrc_set_modal(
+        handle: *const WindowAdapterRcOpaque,
+        modal: bool,
+    ) {
+        let window_adapter = &*(handle as *const Rc<dyn WindowAdapter>);
+        WindowInner::from_pub(window_adapter.window()).set_modal(modal);
+    }
+
+    /// Returns true if the window is modal.
+    #[no_mangle]
+    pub unsafe extern "C" fn slint_windowrc_is_modal(handle: *const WindowAdapterRcOpaque) -> bool {
+        let window_adapter = &*(handle as *const Rc<dyn WindowAdapter>);
+        WindowInner::from_pub(window_adapter.window()).is_modal()
+    }
+
     /// Returns the position of the window on the screen, in physical screen coordinates and including
     /// a window frame (if present).
     #[no_mangle]
@@ -14,9 +14,16 @@
 use std::rc::Rc;
 
 #[doc(inline)]
-pub use i_slint_compiler::diagnostics::{Diagnostic, DiagnosticLevel};
+pub use crate::precompiled::{
+    LoadError as LoadPrecompiledError, SaveError as SavePrecompiledError,
+};
+#[cfg(feature = "display-diagnostics")]
+pub use i_slint_compiler::diagnostics::DiagnosticsFormat;
+#[doc(inline)]
+pub use i_slint_compiler::diagnostics::{Diagnostic, DiagnosticCode, DiagnosticLevel};
 
 pub use i_slint_core::api::*;
+pub use i_slint_core::platform::PlatformError;
 
 use crate::dynamic_component::ErasedComponentBox;
 
@@ -256,6 +263,7 @@ fn try_from(v: Value) -> Result<$name, Self::Error> {
 }
 
 declare_value_struct_conversion!(struct i_slint_core::model::StandardListViewItem { text });
+declare_value_struct_conversion!(struct i_slint_core::model::TableColumn { title, min_width, width, horizontal_stretch, sortable });
 declare_value_struct_conversion!(struct i_slint_core::properties::StateInfo { current_state, previous_state, change_time });
 declare_value_struct_conversion!(struct i_slint_core::input::KeyboardModifiers { control, alt, shift, meta });
 declare_value_struct_conversion!(struct i_slint_core::input::KeyEvent { text, modifiers, ..Default::default() });
@@ -446,6 +454,20 @@ pub fn include_paths(&self) -> &Vec<std::path::PathBuf> {
         &self.config.include_paths
     }
 
+    /// Sets the library paths used for looking up `.slint` imports that start with `@name`.
+    /// The import `"@name/file.slint"` loads `file.slint` from the path mapped to `name`.
+    pub fn set_library_paths(
+        &mut self,
+        library_paths: std::collections::HashMap<String, std::path::PathBuf>,
+    ) {
+        self.config.library_paths = library_paths;
+    }
+
+    /// Returns the library paths the component compiler is currently configured with.
+    pub fn library_paths(&self) -> &std::collections::HashMap<String, std::path::PathBuf> {
+        &self.config.library_paths
+    }
+
     /// Sets the style to be used for widgets.
     pub fn set_style(&mut self, style: String) {
         self.config.style = Some(style);
@@ -456,6 +478,18 @@ pub fn style(&self) -> Option<&String> {
         self.config.style.as_ref()
     }
 
+    /// Sets the name of the exported component that [`Self::build_from_path`] and
+    /// [`Self::build_from_source`] compile, for example to create another window of the same
+    /// file. By default, the last component of the file is compiled.
+    pub fn set_main_component(&mut self, name: Option<String>) {
+        self.config.main_component = name;
+    }
+
+    /// Returns the name of the component that is compiled, if set with [`Self::set_main_component`].
+    pub fn main_component(&self) -> Option<&String> {
+        self.config.main_component.as_ref()
+    }
+
     /// Sets the callback that will be invoked when loading imported .slint files. The specified
     /// `file_loader_callback` parameter will be called with a canonical file path as argument
     /// and is expected to return a future that, when resolved, provides the source code of the
@@ -661,6 +695,20 @@ pub fn global_properties(
         })
     }
 
+    /// List of publicly declared properties or callback in the exported global singleton specified by its name.
+    ///
+    /// This is internal because it exposes the `Type` from compilerlib.
+    #[doc(hidden)]
+    pub fn global_properties_and_callbacks(
+        &self,
+        global_name: &str,
+    ) -> Option<impl Iterator<Item = (String, i_slint_compiler::langtype::Type)> + '_> {
+        // We create here a 'static guard, because unfortunately the returned type would be restricted to the guard lifetime
+        // which is not required, but this is safe because there is only one instance of the unerased type
+        let guard = unsafe { generativity::Guard::new(generativity::Id::new()) };
+        self.inner.unerase(guard).global_properties(global_name)
+    }
+
     /// List of publicly declared callbacks in the exported global singleton specified by its name.
     pub fn global_callbacks(&self, global_name: &str) -> Option<impl Iterator<Item = String> + '_> {
         // We create here a 'static guard, because unfortunately the returned type would be restricted to the guard lifetime
@@ -684,6 +732,28 @@ pub fn name(&self) -> &str {
         let guard = unsafe { generativity::Guard::new(generativity::Id::new()) };
         self.inner.unerase(guard).id()
     }
+
+    /// Save this component definition in a versioned binary format.
+    ///
+    /// [`Self::load_precompiled`] creates the definition again from that data without parsing
+    /// nor compiling the .slint files, so an application can ship the saved data instead of
+    /// its .slint files and start faster.
+    ///
+    /// An error is returned if the definition contains an expression that can't be saved.
+    pub fn save_precompiled(&self) -> Result<Vec<u8>, SavePrecompiledError> {
+        generativity::make_guard!(guard);
+        crate::precompiled::save(&self.inner.unerase(guard).original)
+    }
+
+    /// Load a component definition saved with [`Self::save_precompiled`].
+    ///
+    /// Data saved with another version of the format is rejected with
+    /// [`LoadPrecompiledError::UnsupportedVersion`].
+    pub fn load_precompiled(data: &[u8]) -> Result<Self, LoadPrecompiledError> {
+        let component = crate::precompiled::load(data)?;
+        generativity::make_guard!(guard);
+        Ok(Self { inner: crate::dynamic_component::generate_component(&component, guard).into() })
+    }
 }
 
 /// Print the diagnostics to stderr
@@ -700,6 +770,26 @@ pub fn print_diagnostics(diagnostics: &[Diagnostic]) {
     build_diagnostics.print();
 }
 
+/// Print the diagnostics in the given format
+///
+/// The [`DiagnosticsFormat::Human`] format is printed on stderr. The [`DiagnosticsFormat::Json`]
+/// and [`DiagnosticsFormat::Sarif`] formats are meant to be processed by other tools, and are
+/// written to `output`. They identify each kind of diagnostic with its [`DiagnosticCode`].
+///
+/// This function is available when the `display-diagnostics` is enabled.
+#[cfg(feature = "display-diagnostics")]
+pub fn print_diagnostics_as(
+    diagnostics: &[Diagnostic],
+    format: DiagnosticsFormat,
+    output: &mut dyn std::io::Write,
+) -> std::io::Result<()> {
+    let mut build_diagnostics = i_slint_compiler::diagnostics::BuildDiagnostics::default();
+    for d in diagnostics {
+        build_diagnostics.push_compiler_error(d.clone())
+    }
+    build_diagnostics.print_as(format, output)
+}
+
 /// This represent an instance of a dynamic component
 ///
 /// You can create an instance with the [`ComponentDefinition::create`] function.
@@ -755,6 +845,50 @@ pub fn set_property(&self, name: &str, value: Value) -> Result<(), SetPropertyEr
         comp.description().set_property(comp.borrow(), &normalize_identifier(name), value)
     }
 
+    /// Deserialize the value of a public property of this component and set it.
+    ///
+    /// The declared type of the property guides the deserialization: colors and brushes are
+    /// read from color strings such as `"#ff0000"`, images from paths, enumeration values from
+    /// their name, and the missing fields of structs get their default value.
+    ///
+    /// ```
+    /// # i_slint_backend_testing::init();
+    /// use slint_interpreter::{ComponentCompiler, ComponentHandle, Value};
+    /// let code = r#"
+    ///     export Demo := Rectangle {
+    ///         property <color> accent;
+    ///     }
+    /// "#;
+    /// let definition = spin_on::spin_on(
+    ///     ComponentCompiler::default().build_from_source(code.into(), Default::default()));
+    /// let instance = definition.unwrap().create();
+    /// let mut json = serde_json::Deserializer::from_str("\"#336699\"");
+    /// instance.deserialize_property("accent", &mut json).unwrap();
+    /// assert_eq!(
+    ///     instance.get_property("accent").unwrap(),
+    ///     Value::from(slint_interpreter::Color::from_rgb_u8(0x33, 0x66, 0x99))
+    /// );
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn deserialize_property<'de, D: serde::Deserializer<'de>>(
+        &self,
+        name: &str,
+        deserializer: D,
+    ) -> Result<(), D::Error> {
+        use serde::de::{DeserializeSeed, Error};
+        generativity::make_guard!(guard);
+        let comp = self.inner.unerase(guard);
+        let name = normalize_identifier(name);
+        let ty = comp
+            .description()
+            .properties()
+            .find(|(n, ty)| normalize_identifier(n) == name && ty.is_property_type())
+            .map(|(_, ty)| ty)
+            .ok_or_else(|| D::Error::custom(SetPropertyError::NoSuchProperty))?;
+        let value = crate::value_serde::TypedValue(&ty).deserialize(deserializer)?;
+        comp.description().set_property(comp.borrow(), &name, value).map_err(D::Error::custom)
+    }
+
     /// Set a handler for the callback with the given name. A callback with that
     /// name must be defined in the document otherwise an error will be returned.
     ///
@@ -871,6 +1005,39 @@ pub fn set_global_property(
             .set_property(&normalize_identifier(property), value)
     }
 
+    /// Deserialize the value of a property in the exported global singleton and set it.
+    ///
+    /// The declared type of the property guides the deserialization, like with
+    /// [`Self::deserialize_property`].
+    #[cfg(feature = "serde")]
+    pub fn deserialize_global_property<'de, D: serde::Deserializer<'de>>(
+        &self,
+        global: &str,
+        property: &str,
+        deserializer: D,
+    ) -> Result<(), D::Error> {
+        use serde::de::{DeserializeSeed, Error};
+        generativity::make_guard!(guard);
+        let comp = self.inner.unerase(guard);
+        let property = normalize_identifier(property);
+        let ty = comp
+            .description()
+            .global_properties(global)
+            .and_then(|mut properties| {
+                properties
+                    .find(|(n, ty)| normalize_identifier(n) == property && ty.is_property_type())
+            })
+            .map(|(_, ty)| ty)
+            .ok_or_else(|| D::Error::custom(SetPropertyError::NoSuchProperty))?;
+        let value = crate::value_serde::TypedValue(&ty).deserialize(deserializer)?;
+        comp.description()
+            .get_global(comp.borrow(), &normalize_identifier(global))
+            .map_err(|()| D::Error::custom(SetPropertyError::NoSuchProperty))?
+            .as_ref()
+            .set_property(&property, value)
+            .map_err(D::Error::custom)
+    }
+
     /// Set a handler for the callback in the exported global singleton. A callback with that
     /// name must be defined in the specified global and the global must be exported from the
     /// main document otherwise an error will be returned.
@@ -939,6 +1106,208 @@ pub fn invoke_global_callback(
             .invoke_callback(&normalize_identifier(callback_name), args)
             .map_err(|()| InvokeCallbackError::NoSuchCallback)
     }
+
+    /// Create an instance of `definition` in the window of this instance, and carry the state of
+    /// this instance over to it. This is meant to reload a component whose source changed, without
+    /// losing what was typed or selected in it.
+    ///
+    /// The value of a public property of the component, or of a property of an exported global,
+    /// is carried over if it was changed since this instance was created and if `definition`
+    /// still has that property, with the same type and without a binding that computes it.
+    /// The same goes for the state of the elements that have an id: the properties declared in
+    /// them, and the properties that their items change, such as the text of a `TextInput` or
+    /// the position of a `Flickable`. These elements are matched by the ids of the element and
+    /// of its parents, which also name the property in the report, as in `edit.inner.text`.
+    /// Models are carried over as is, so that the changes done to their rows are kept.
+    /// The other properties get the value from the new definition.
+    ///
+    /// Returns the new instance, as well as a report of the properties that were carried over
+    /// and of the ones that could not be.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slint_interpreter::{ComponentDefinition, ComponentCompiler, Value};
+    /// let build = |code: &str| spin_on::spin_on(
+    ///     ComponentCompiler::default().build_from_source(code.into(), Default::default())).unwrap();
+    /// let definition = build("export Demo := Window { property <int> counter; }");
+    /// let instance = definition.create();
+    /// instance.set_property("counter", Value::from(42)).unwrap();
+    ///
+    /// let new_definition = build(r#"
+    ///     export Demo := Window { property <int> counter; property <string> label: "new"; }"#);
+    /// let (new_instance, report) = instance.reload(&new_definition);
+    /// assert_eq!(new_instance.get_property("counter").unwrap(), Value::from(42));
+    /// assert_eq!(report.migrated, ["counter"]);
+    /// ```
+    pub fn reload(&self, definition: &ComponentDefinition) -> (ComponentInstance, ReloadReport) {
+        let new_instance = definition.create_with_existing_window(self.window());
+        let mut report = ReloadReport::default();
+        {
+            generativity::make_guard!(guard);
+            let comp = self.inner.unerase(guard);
+            generativity::make_guard!(new_guard);
+            let new_comp = new_instance.inner.unerase(new_guard);
+            let old_definition = self.definition();
+
+            for (name, value_type) in old_definition.properties() {
+                let normalized_name = normalize_identifier(&name);
+                migrate_property(
+                    &mut report,
+                    name.clone(),
+                    value_type,
+                    self.get_property(&name),
+                    comp.description().property_initial_value(comp.borrow(), &normalized_name),
+                    definition
+                        .properties()
+                        .find(|(n, _)| normalize_identifier(n) == normalized_name),
+                    || {
+                        new_comp
+                            .description()
+                            .property_initial_value(new_comp.borrow(), &normalized_name)
+                    },
+                    |value| new_instance.set_property(&name, value),
+                );
+            }
+
+            let new_state_properties = new_comp.description().element_state_properties();
+            for (name, property) in comp.description().element_state_properties() {
+                let new_property = new_state_properties.get(&name);
+                migrate_property(
+                    &mut report,
+                    name,
+                    property.property_type.clone().into(),
+                    crate::eval::load_property(
+                        comp.borrow_instance(),
+                        &property.element,
+                        &property.name,
+                    )
+                    .map_err(|()| GetPropertyError::NoSuchProperty),
+                    comp.description().element_state_initial_value(comp.borrow(), &property),
+                    new_property.map(|p| (p.name.clone(), p.property_type.clone().into())),
+                    || {
+                        new_comp
+                            .description()
+                            .element_state_initial_value(new_comp.borrow(), new_property?)
+                    },
+                    |value| {
+                        let new_property = new_property.unwrap();
+                        crate::eval::store_property(
+                            new_comp.borrow_instance(),
+                            &new_property.element,
+                            &new_property.name,
+                            value,
+                        )
+                    },
+                );
+            }
+
+            for global_name in old_definition.globals() {
+                let normalized_global_name = normalize_identifier(&global_name);
+                let global =
+                    match comp.description().get_global(comp.borrow(), &normalized_global_name) {
+                        Ok(global) => global,
+                        Err(()) => continue,
+                    };
+                let new_global = new_comp
+                    .description()
+                    .get_global(new_comp.borrow(), &normalized_global_name)
+                    .ok();
+                for (name, value_type) in old_definition.global_properties(&global_name).unwrap() {
+                    let normalized_name = normalize_identifier(&name);
+                    migrate_property(
+                        &mut report,
+                        format!("{}.{}", global_name, name),
+                        value_type,
+                        global
+                            .as_ref()
+                            .get_property(&normalized_name)
+                            .map_err(|()| GetPropertyError::NoSuchProperty),
+                        global.as_ref().property_initial_value(&normalized_name),
+                        definition.global_properties(&global_name).and_then(|mut properties| {
+                            properties.find(|(n, _)| normalize_identifier(n) == normalized_name)
+                        }),
+                        || new_global.as_ref()?.as_ref().property_initial_value(&normalized_name),
+                        |value| {
+                            new_global
+                                .as_ref()
+                                .unwrap()
+                                .as_ref()
+                                .set_property(&normalized_name, value)
+                        },
+                    );
+                }
+            }
+        }
+
+        (new_instance, report)
+    }
+}
+
+/// Carry the value of a property over to a reloaded instance. See [`ComponentInstance::reload`]
+#[allow(clippy::too_many_arguments)]
+fn migrate_property(
+    report: &mut ReloadReport,
+    name: String,
+    value_type: ValueType,
+    value: Result<Value, GetPropertyError>,
+    initial_value: Option<Value>,
+    new_property: Option<(String, ValueType)>,
+    new_initial_value: impl FnOnce() -> Option<Value>,
+    set_new_value: impl FnOnce(Value) -> Result<(), SetPropertyError>,
+) {
+    let (value, initial_value) = match (value, initial_value) {
+        (Ok(value), Some(initial_value)) => (value, initial_value),
+        // The value is computed by a binding: it will be computed again in the new instance
+        _ => return,
+    };
+    if same_content(&value, &initial_value) {
+        return;
+    }
+    let error = match new_property {
+        None => PropertyMigrationError::Removed,
+        Some((_, new_value_type)) if new_value_type != value_type => {
+            PropertyMigrationError::TypeChanged
+        }
+        Some(_) if new_initial_value().is_none() => PropertyMigrationError::Bound,
+        Some(_) => match set_new_value(value) {
+            Ok(()) => {
+                report.migrated.push(name);
+                return;
+            }
+            Err(error) => PropertyMigrationError::SetProperty(error),
+        },
+    };
+    report.not_migrated.push((name, error));
+}
+
+/// Compare two values, comparing the rows of models instead of their identity
+fn same_content(lhs: &Value, rhs: &Value) -> bool {
+    match (lhs, rhs) {
+        (Value::Model(lhs), Value::Model(rhs)) => {
+            lhs == rhs
+                || (lhs.row_count() == rhs.row_count()
+                    && (0..lhs.row_count()).all(|row| {
+                        match (lhs.row_data(row), rhs.row_data(row)) {
+                            (Some(lhs), Some(rhs)) => same_content(&lhs, &rhs),
+                            (lhs, rhs) => lhs.is_none() && rhs.is_none(),
+                        }
+                    }))
+        }
+        _ => lhs == rhs,
+    }
+}
+
+/// The result of [`ComponentInstance::reload`]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ReloadReport {
+    /// The names of the properties whose value was carried over to the new instance.
+    /// The properties of exported globals are named `Global.property`.
+    pub migrated: Vec<String>,
+    /// The names of the properties whose value was changed but could not be carried over,
+    /// with the reason why
+    pub not_migrated: Vec<(String, PropertyMigrationError)>,
 }
 
 impl ComponentHandle for ComponentInstance {
@@ -1038,13 +1407,42 @@ pub enum InvokeCallbackError {
     NoSuchCallback,
 }
 
+/// The reason why the value of a property could not be carried over by [`ComponentInstance::reload`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum PropertyMigrationError {
+    /// The property does not exist in the new definition
+    #[error("the property was removed")]
+    Removed,
+    /// The property has another type in the new definition
+    #[error("the type of the property changed")]
+    TypeChanged,
+    /// The property is computed by a binding in the new definition
+    #[error("the property is bound to an expression")]
+    Bound,
+    /// Setting the value in the new instance failed
+    #[error("{0}")]
+    SetProperty(SetPropertyError),
+}
+
 /// Enters the main event loop. This is necessary in order to receive
 /// events from the windowing system in order to render to the screen
 /// and react to user input.
+///
+/// # Panics
+///
+/// Panics if the backend cannot run the event loop, for example when the `linuxfb` backend
+/// cannot open the screen. Use [`try_run_event_loop()`] to handle that error.
 pub fn run_event_loop() {
     i_slint_backend_selector::with_platform(|b| b.run_event_loop());
 }
 
+/// Like [`run_event_loop()`], but returns an error if the backend cannot run the event loop,
+/// for example when the `linuxfb` backend cannot open the screen.
+pub fn try_run_event_loop() -> Result<(), PlatformError> {
+    i_slint_backend_selector::with_platform(|b| b.try_run_event_loop())
+}
+
 /// This module contains a few function use by tests
 pub mod testing {
     use super::ComponentHandle;
@@ -1376,6 +1774,473 @@ fn check_model(val: Value, r: &[f64]) {
     check_model(instance.get_property("prop").unwrap(), &[]);
 }
 
+#[test]
+fn reload_component_instance() {
+    i_slint_backend_testing::init();
+    let build = |code: &str| {
+        let mut compiler = ComponentCompiler::default();
+        compiler.set_style("fluent".into());
+        let definition = spin_on::spin_on(compiler.build_from_source(code.into(), "".into()));
+        assert!(compiler.diagnostics().is_empty(), "{:?}", compiler.diagnostics());
+        definition.unwrap()
+    };
+
+    let definition = build(
+        r#"
+    export global Settings := {
+        property <bool> dark;
+        property <int> level: 3;
+    }
+    export Demo := Rectangle {
+        property <string> name: "old default";
+        property <int> count;
+        property <int> doubled: count * 2;
+        property <[int]> items: [1, 2];
+        property <int> removed;
+        property <int> retyped;
+        property <int> now-bound;
+        property <string> untouched: "old";
+        callback edit();
+        edit => {
+            input.text = "typed";
+            panel.clicks = 3;
+            field.text = "typed in panel";
+            moved.text = "typed";
+            other.text = "typed";
+        }
+        input := TextInput { }
+        bound-input := TextInput { text: untouched; }
+        panel := Rectangle {
+            background: blue;
+            property <int> clicks;
+            field := TextInput { }
+        }
+        moved := TextInput { }
+        other := TextInput { }
+    }"#,
+    );
+    let instance = definition.create();
+    instance.set_property("name", Value::String("typed".into())).unwrap();
+    instance.set_property("count", Value::Number(5.)).unwrap();
+    if let Value::Model(items) = instance.get_property("items").unwrap() {
+        items.set_row_data(0, Value::Number(10.));
+    }
+    instance.set_property("removed", Value::Number(1.)).unwrap();
+    instance.set_property("retyped", Value::Number(1.)).unwrap();
+    instance.set_property("now-bound", Value::Number(1.)).unwrap();
+    instance.set_global_property("Settings", "dark", Value::Bool(true)).unwrap();
+    instance.invoke_callback("edit", &[]).unwrap();
+
+    let new_definition = build(
+        r#"
+    export global Settings := {
+        property <bool> dark;
+        property <int> level: 4;
+    }
+    export Demo := Rectangle {
+        property <string> name: "new default";
+        property <int> count;
+        property <int> doubled: count * 2;
+        property <[int]> items: [1, 2];
+        property <string> retyped;
+        property <int> now-bound: count + 1;
+        property <string> untouched: "new";
+        callback edit();
+        edit => {
+            input.text = "typed";
+            panel.clicks = 3;
+            field.text = "typed in panel";
+            moved.text = "typed";
+            other.text = "typed";
+        }
+        property <string> input-text: input.text;
+        property <string> field-text: field.text;
+        property <int> clicks: panel.clicks;
+        input := TextInput { }
+        bound-input := TextInput { text: untouched; }
+        panel := Rectangle {
+            background: blue;
+            property <int> clicks;
+            field := TextInput { }
+            moved := TextInput { }
+        }
+        other := TextInput { text: name; }
+    }"#,
+    );
+    let (new_instance, report) = instance.reload(&new_definition);
+
+    assert_eq!(
+        report.migrated,
+        [
+            "count",
+            "items",
+            "name",
+            "input.text",
+            "panel.clicks",
+            "panel.field.text",
+            "Settings.dark"
+        ]
+    );
+    assert_eq!(
+        report.not_migrated,
+        [
+            ("now-bound".into(), PropertyMigrationError::Bound),
+            ("removed".into(), PropertyMigrationError::Removed),
+            ("retyped".into(), PropertyMigrationError::TypeChanged),
+            ("moved.text".into(), PropertyMigrationError::Removed),
+            ("other.text".into(), PropertyMigrationError::Bound),
+        ]
+    );
+    assert_eq!(new_instance.get_property("input-text").unwrap(), Value::String("typed".into()));
+    assert_eq!(
+        new_instance.get_property("field-text").unwrap(),
+        Value::String("typed in panel".into())
+    );
+    assert_eq!(new_instance.get_property("clicks").unwrap(), Value::Number(3.));
+    assert_eq!(new_instance.get_property("name").unwrap(), Value::String("typed".into()));
+    assert_eq!(new_instance.get_property("doubled").unwrap(), Value::Number(10.));
+    assert_eq!(new_instance.get_property("now-bound").unwrap(), Value::Number(6.));
+    assert_eq!(new_instance.get_property("untouched").unwrap(), Value::String("new".into()));
+    match new_instance.get_property("items").unwrap() {
+        Value::Model(items) => assert_eq!(items.row_data(0), Some(Value::Number(10.))),
+        _ => panic!("items should be a model"),
+    }
+    assert_eq!(new_instance.get_global_property("Settings", "dark").unwrap(), Value::Bool(true));
+    assert_eq!(new_instance.get_global_property("Settings", "level").unwrap(), Value::Number(4.));
+}
+
+#[test]
+fn introspect_element_tree() {
+    use i_slint_core::introspection::ElementHandle;
+
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let definition = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    Item := Rectangle {
+        property <string> label;
+        t := Text { text: label; }
+    }
+    export Demo := Window {
+        width: 100px;
+        height: 100px;
+        Rectangle {
+            x: 10px;
+            y: 20px;
+            panel := Rectangle {
+                x: 5px;
+                y: 5px;
+                width: 30px;
+                height: 40px;
+                background: red;
+                accessible-label: "the panel";
+            }
+        }
+        for name in ["a", "b"] : Item { label: name; }
+    }"#
+            .into(),
+            "".into(),
+        ),
+    );
+    assert!(compiler.diagnostics().is_empty(), "{:?}", compiler.diagnostics());
+    let instance = definition.unwrap().create();
+
+    let root = ElementHandle::root_of(instance.window()).unwrap();
+    assert_eq!(root.type_name(), Some("WindowItem"));
+
+    let panel = root.find_by_id("panel").unwrap();
+    assert_eq!(panel.type_name(), Some("Rectangle"));
+    assert_eq!(
+        panel.geometry(),
+        i_slint_core::graphics::Rect::new((15., 25.).into(), (30., 40.).into())
+    );
+    assert_eq!(
+        panel.accessible_string_property(
+            i_slint_core::accessibility::AccessibleStringProperty::Label
+        ),
+        "the panel"
+    );
+    assert_eq!(
+        panel.get_property::<Value>("background"),
+        Some(Value::Brush(i_slint_core::Color::from_rgb_u8(255, 0, 0).into()))
+    );
+    panel
+        .set_property(
+            "background",
+            Value::Brush(i_slint_core::Color::from_rgb_u8(0, 0, 255).into()),
+        )
+        .unwrap();
+    assert_eq!(
+        panel.get_property::<Value>("background"),
+        Some(Value::Brush(i_slint_core::Color::from_rgb_u8(0, 0, 255).into()))
+    );
+    assert!(panel.get_property::<Value>("no-such-property").is_none());
+
+    let texts = root.descendants().into_iter().filter(|e| e.id() == "t").collect::<Vec<_>>();
+    assert_eq!(texts.len(), 2);
+    assert_eq!(texts[1].get_property::<Value>("text"), Some(Value::String("b".into())));
+    let item = texts[1].parent().unwrap();
+    assert_eq!(item.type_name(), Some("Rectangle"));
+    // Only the properties of the builtin items are accessible, not the declared ones
+    assert!(item.get_property::<Value>("background").is_some());
+    assert!(item.get_property::<Value>("label").is_none());
+    assert!(item.set_property("label", Value::String("c".into())).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_property_values() {
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let definition = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    export struct Entry := { title: string, done: bool, priority: int }
+    export global Theme := { property <color> accent; }
+    export Demo := Rectangle {
+        property <[Entry]> entries;
+        property <brush> fill;
+        property <TextHorizontalAlignment> align;
+        property <length> size;
+    }"#
+            .into(),
+            "".into(),
+        ),
+    );
+    assert!(compiler.diagnostics().is_empty(), "{:?}", compiler.diagnostics());
+    let instance = definition.unwrap().create();
+
+    let data = serde_json::json!({
+        "entries": [{ "title": "first", "done": true }, { "title": "second", "priority": 2 }],
+        "fill": { "linear-gradient": { "angle": 90.0, "stops": [
+            { "color": "#ff0000ff", "position": 0.0 },
+            { "color": "#0000ffff", "position": 1.0 },
+        ] } },
+        "align": "center",
+        "size": 12.5,
+    });
+    for (name, value) in data.as_object().unwrap() {
+        instance.deserialize_property(name, value).unwrap();
+    }
+    instance.deserialize_global_property("Theme", "accent", &serde_json::json!("#123")).unwrap();
+
+    let entries = instance.get_property("entries").unwrap();
+    if let Value::Model(model) = &entries {
+        let second: Struct = model.row_data(1).unwrap().try_into().unwrap();
+        assert_eq!(second.get_field("done"), Some(&Value::Bool(false)));
+    } else {
+        panic!("not a model");
+    }
+    assert_eq!(
+        instance.get_global_property("Theme", "accent").unwrap(),
+        Value::Brush(i_slint_core::Color::from_rgb_u8(0x11, 0x22, 0x33).into())
+    );
+
+    for (name, value) in data.as_object().unwrap() {
+        let mut saved = serde_json::to_value(instance.get_property(name).unwrap()).unwrap();
+        if name == "entries" {
+            // missing fields were filled with their default value
+            saved[0].as_object_mut().unwrap().remove("priority");
+            saved[1].as_object_mut().unwrap().remove("done");
+        }
+        assert_eq!(&saved, value, "{}", name);
+    }
+
+    assert!(instance.deserialize_property("align", &serde_json::json!("middle")).is_err());
+    assert!(instance.deserialize_property("fill", &serde_json::json!(42)).is_err());
+    assert!(instance.deserialize_property("nope", &serde_json::json!(42)).is_err());
+}
+
+#[test]
+fn other_window_of_the_same_file() {
+    i_slint_backend_testing::init();
+    let source = r#"
+    export Confirm := Dialog {
+        property <string> question: "Quit?";
+        property <string> received;
+        forward-focus: scope;
+        scope := FocusScope {
+            key-pressed(event) => {
+                received += event.text;
+                return accept;
+            }
+        }
+    }
+    export Main := Window {
+        property <string> received;
+        forward-focus: scope;
+        scope := FocusScope {
+            key-pressed(event) => {
+                received += event.text;
+                return accept;
+            }
+        }
+    }"#;
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let main =
+        spin_on::spin_on(compiler.build_from_source(source.into(), "".into())).unwrap().create();
+    assert_eq!(main.definition().name(), "Main");
+
+    compiler.set_main_component(Some("Confirm".into()));
+    let dialog =
+        spin_on::spin_on(compiler.build_from_source(source.into(), "".into())).unwrap().create();
+    assert_eq!(dialog.definition().name(), "Confirm");
+    assert_eq!(dialog.get_property("question"), Ok(Value::String("Quit?".into())));
+
+    main.show();
+    dialog.set_transient_parent(Some(main.window()));
+    dialog.set_modal(true);
+    dialog.show();
+    testing::send_keyboard_string_sequence(&main, "a".into());
+    testing::send_keyboard_string_sequence(&dialog, "b".into());
+    assert_eq!(main.get_property("received"), Ok(Value::String("".into())));
+    assert_eq!(dialog.get_property("received"), Ok(Value::String("b".into())));
+    dialog.hide();
+    testing::send_keyboard_string_sequence(&main, "c".into());
+    assert_eq!(main.get_property("received"), Ok(Value::String("c".into())));
+
+    compiler.set_main_component(Some("Unknown".into()));
+    assert!(spin_on::spin_on(compiler.build_from_source(source.into(), "".into())).is_none());
+    assert_eq!(compiler.diagnostics()[0].message(), "No exported component named 'Unknown'");
+}
+
+#[test]
+fn precompiled_round_trip() {
+    use crate::precompiled::{FORMAT_VERSION, MAGIC};
+    i_slint_backend_testing::init();
+    let mut compiler = ComponentCompiler::default();
+    compiler.set_style("fluent".into());
+    let definition = spin_on::spin_on(
+        compiler.build_from_source(
+            r#"
+    import { Button } from "std-widgets.slint";
+    export global Settings := {
+        property <string> user_name: "Jane";
+    }
+    export Demo := Window {
+        property <int> counter: 3;
+        property <int> doubled: counter * 2;
+        property <string> greeting: "Hello " + Settings.user_name;
+        property <[int]> entries: [1, 2, 3];
+        property <length> content-height: layout.preferred-height;
+        callback add(int) -> int;
+        add(x) => { counter += x; return counter; }
+        layout := VerticalLayout {
+            padding: 0;
+            spacing: 0;
+            for entry in entries: Rectangle { height: 10px; }
+        }
+        Button { text: "OK"; }
+    }"#
+            .into(),
+            "".into(),
+        ),
+    )
+    .unwrap();
+
+    let data = definition.save_precompiled().unwrap();
+    assert!(data.starts_with(MAGIC));
+    let loaded = ComponentDefinition::load_precompiled(&data).unwrap();
+    assert_eq!(loaded.name(), "Demo");
+    assert_eq!(
+        loaded.properties().collect::<Vec<_>>(),
+        definition.properties().collect::<Vec<_>>()
+    );
+    assert_eq!(loaded.callbacks().collect::<Vec<_>>(), vec!["add"]);
+    assert_eq!(loaded.globals().collect::<Vec<_>>(), vec!["Settings"]);
+    assert_eq!(
+        loaded.global_properties("Settings").unwrap().collect::<Vec<_>>(),
+        vec![("user_name".to_string(), ValueType::String)]
+    );
+    // Nothing is lost: saving the loaded definition gives the same data
+    assert_eq!(loaded.save_precompiled().unwrap(), data);
+
+    let instance = loaded.create();
+    assert_eq!(instance.get_property("doubled"), Ok(Value::Number(6.)));
+    assert_eq!(instance.get_property("content-height"), Ok(Value::Number(30.)));
+    assert_eq!(instance.get_property("greeting"), Ok(Value::String("Hello Jane".into())));
+    instance.set_global_property("Settings", "user-name", Value::String("John".into())).unwrap();
+    assert_eq!(instance.get_property("greeting"), Ok(Value::String("Hello John".into())));
+    assert_eq!(instance.invoke_callback("add", &[Value::Number(4.)]), Ok(Value::Number(7.)));
+    assert_eq!(instance.get_property("doubled"), Ok(Value::Number(14.)));
+    let entries =
+        Value::Model(i_slint_core::model::VecModel::from_slice(&vec![Value::Number(1.); 5]));
+    instance.set_property("entries", entries).unwrap();
+    assert_eq!(instance.get_property("content-height"), Ok(Value::Number(50.)));
+
+    let mut other_version = data.clone();
+    other_version[MAGIC.len()..MAGIC.len() + 4]
+        .copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+    assert_eq!(
+        ComponentDefinition::load_precompiled(&other_version).err(),
+        Some(LoadPrecompiledError::UnsupportedVersion(FORMAT_VERSION + 1))
+    );
+    assert_eq!(
+        ComponentDefinition::load_precompiled(b"Demo := Window {}").err(),
+        Some(LoadPrecompiledError::NotPrecompiled)
+    );
+    assert_eq!(
+        ComponentDefinition::load_precompiled(&data[..data.len() / 2]).err(),
+        Some(LoadPrecompiledError::Corrupted)
+    );
+}
+
+/// Save and load each of the `tests/cases`, and check that the loaded component passes its test
+#[test]
+fn precompiled_test_cases() {
+    i_slint_backend_testing::init();
+    let mut loaded_count = 0;
+    for testcase in test_driver_lib::collect_test_cases().unwrap() {
+        let source = std::fs::read_to_string(&testcase.absolute_path).unwrap();
+        let mut compiler = ComponentCompiler::default();
+        compiler.set_include_paths(
+            test_driver_lib::extract_include_paths(&source).map(PathBuf::from).collect(),
+        );
+        compiler.set_style("fluent".into());
+        let definition = match spin_on::spin_on(
+            compiler.build_from_source(source, testcase.absolute_path.clone()),
+        ) {
+            Some(definition) => definition,
+            // Errors are tested by the interpreter test driver
+            None => continue,
+        };
+
+        let data = definition
+            .save_precompiled()
+            .unwrap_or_else(|e| panic!("{}: {}", testcase.relative_path.display(), e));
+        let loaded = ComponentDefinition::load_precompiled(&data)
+            .unwrap_or_else(|e| panic!("{}: {}", testcase.relative_path.display(), e));
+        assert_eq!(
+            loaded.save_precompiled().unwrap(),
+            data,
+            "{}",
+            testcase.relative_path.display()
+        );
+        assert_eq!(
+            loaded.properties().collect::<Vec<_>>(),
+            definition.properties().collect::<Vec<_>>(),
+            "{}",
+            testcase.relative_path.display()
+        );
+
+        let instance = loaded.create();
+        if loaded.properties().any(|(name, ty)| name == "test" && ty == ValueType::Bool) {
+            assert_eq!(
+                instance.get_property("test"),
+                Ok(Value::Bool(true)),
+                "{}",
+                testcase.relative_path.display()
+            );
+        }
+        loaded_count += 1;
+    }
+    assert!(loaded_count > 100, "only {} test cases were loaded", loaded_count);
+}
+
 #[cfg(feature = "ffi")]
 #[allow(missing_docs)]
 #[path = "ffi.rs"]
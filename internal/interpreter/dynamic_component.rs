@@ -8,11 +8,12 @@
 use core::convert::TryInto;
 use core::ptr::NonNull;
 use dynamic_type::{Instance, InstanceBox};
+use i_slint_compiler::diagnostics::{BuildDiagnostics, DiagnosticCode, SourceLocation};
 use i_slint_compiler::expression_tree::{Expression, NamedReference};
 use i_slint_compiler::langtype::Type;
 use i_slint_compiler::object_tree::ElementRc;
+use i_slint_compiler::object_tree::PropertyDeclaration;
 use i_slint_compiler::*;
-use i_slint_compiler::{diagnostics::BuildDiagnostics, object_tree::PropertyDeclaration};
 use i_slint_core::accessibility::AccessibleStringProperty;
 use i_slint_core::component::{
     Component, ComponentRef, ComponentRefPin, ComponentVTable, ComponentWeak, IndexRange,
@@ -199,6 +200,10 @@ fn accessible_string_property(
     ) {
         self.borrow().as_ref().accessible_string_property(index, what, result)
     }
+
+    fn element_id(self: Pin<&Self>, index: usize, result: &mut SharedString) {
+        self.borrow().as_ref().element_id(index, result)
+    }
 }
 
 i_slint_core::ComponentVTable_static!(static COMPONENT_BOX_VT for ErasedComponentBox);
@@ -335,6 +340,45 @@ pub struct ComponentDescription<'id> {
     exported_globals_by_name: BTreeMap<String, usize>,
 }
 
+/// The properties of the items that are changed by the items themselves and that are part of the
+/// state of the user interface, rather than of the state of the pointer or of the focus
+const ITEM_STATE_PROPERTIES: &[&str] =
+    &["text", "cursor-position", "anchor-position", "viewport-x", "viewport-y", "checked", "value"];
+
+/// A property returned by [`ComponentDescription::element_state_properties`]
+pub(crate) struct ElementStateProperty {
+    /// The element that holds the property after the passes, which is the root element for the
+    /// declared properties
+    pub element: ElementRc,
+    /// The name of the property in `element`
+    pub name: String,
+    pub property_type: Type,
+}
+
+/// Return the value that the property of the element has when the component is created, or None
+/// if that value is computed from other properties, through a binding or a two way binding.
+fn initial_value(
+    c: InstanceRef,
+    element: &ElementRc,
+    name: &str,
+    property_type: &Type,
+) -> Option<Value> {
+    let element = element.borrow();
+    match element.bindings.get(name) {
+        None => Some(eval::default_value_for_type(property_type)),
+        Some(binding) => {
+            let binding = binding.borrow();
+            if !binding.two_way_bindings.is_empty() || !binding.expression.is_constant() {
+                return None;
+            }
+            Some(eval::eval_expression(
+                &binding.expression,
+                &mut eval::EvalLocalContext::from_component_instance(c),
+            ))
+        }
+    }
+}
+
 fn internal_properties_to_public<'a>(
     prop_iter: impl Iterator<Item = (&'a String, &'a PropertyDeclaration)> + 'a,
 ) -> impl Iterator<Item = (String, i_slint_compiler::langtype::Type)> + 'a {
@@ -495,6 +539,137 @@ pub fn get_property(&self, component: ComponentRefPin, name: &str) -> Result<Val
         }
     }
 
+    /// Return the value that a public property has when the component is created, or None if
+    /// that value is computed from other properties, through a binding or a two way binding.
+    ///
+    /// Panics if the component is not an instance corresponding to this ComponentDescription,
+    pub(crate) fn property_initial_value(
+        &self,
+        component: ComponentRefPin,
+        name: &str,
+    ) -> Option<Value> {
+        if !core::ptr::eq((&self.ct) as *const _, component.get_vtable() as *const _) {
+            panic!("mismatch instance and vtable");
+        }
+        generativity::make_guard!(guard);
+        let c = unsafe { InstanceRef::from_pin_ref(component, guard) };
+        let (element, name, property_type) = {
+            let root = self.original.root_element.borrow();
+            let declaration = root.property_declarations.get(name)?;
+            match &declaration.is_alias {
+                Some(alias) => {
+                    (alias.element(), alias.name().to_owned(), declaration.property_type.clone())
+                }
+                None => (
+                    self.original.root_element.clone(),
+                    name.to_owned(),
+                    declaration.property_type.clone(),
+                ),
+            }
+        };
+        initial_value(c, &element, &name, &property_type)
+    }
+
+    /// Return the properties that hold the state of the elements that have an id: the
+    /// properties declared in them, and the properties that the items change themselves, such
+    /// as the text of a TextInput.
+    /// The key is the path of ids from the root to the element, followed by the name of the
+    /// property, for example `edit.inner.text`. The elements of repeaters and popups are not
+    /// included, nor are the elements whose path is not unique or the rectangles removed by the
+    /// optimizations.
+    pub(crate) fn element_state_properties(&self) -> BTreeMap<String, ElementStateProperty> {
+        let mut elements = BTreeMap::<String, Option<ElementRc>>::new();
+        object_tree::recurse_elem(
+            &self.original.root_element,
+            &String::new(),
+            &mut |elem, path| {
+                let id = elem.borrow().original_id();
+                if id.is_empty() || elem.borrow().repeated.is_some() {
+                    return path.clone();
+                }
+                let path = if path.is_empty() { id } else { format!("{}.{}", path, id) };
+                elements
+                    .entry(path.clone())
+                    .and_modify(|e| *e = None)
+                    .or_insert_with(|| Some(elem.clone()));
+                path
+            },
+        );
+
+        let root = self.original.root_element.borrow();
+        let mut properties = BTreeMap::new();
+        for (path, element) in elements {
+            let element = match element {
+                Some(element) => element,
+                None => continue,
+            };
+            // The declarations were moved to the root by the move_declarations pass
+            let prefix = format!("{}-", element.borrow().id);
+            for (name, declaration) in &root.property_declarations {
+                let property_name = match name.strip_prefix(&prefix) {
+                    Some(property_name) => property_name,
+                    None => continue,
+                };
+                if declaration.expose_in_public_api
+                    || declaration.is_alias.is_some()
+                    || !declaration.property_type.is_property_type()
+                {
+                    continue;
+                }
+                properties.insert(
+                    format!("{}.{}", path, property_name),
+                    ElementStateProperty {
+                        element: self.original.root_element.clone(),
+                        name: name.clone(),
+                        property_type: declaration.property_type.clone(),
+                    },
+                );
+            }
+
+            let item_properties = match self.items.get(element.borrow().id.as_str()) {
+                Some(item) => &item.rtti.properties,
+                None => continue,
+            };
+            let mut native_class = element.borrow().native_class();
+            while let Some(class) = native_class {
+                for (name, info) in &class.properties {
+                    if info.is_native_output
+                        && ITEM_STATE_PROPERTIES.contains(&name.as_str())
+                        && item_properties.contains_key(name.as_str())
+                    {
+                        properties.insert(
+                            format!("{}.{}", path, name),
+                            ElementStateProperty {
+                                element: element.clone(),
+                                name: name.clone(),
+                                property_type: info.ty.clone(),
+                            },
+                        );
+                    }
+                }
+                native_class = class.parent.clone();
+            }
+        }
+        properties
+    }
+
+    /// Return the value that a property returned by [`Self::element_state_properties`] has when
+    /// the component is created, or None if that value is computed from other properties.
+    ///
+    /// Panics if the component is not an instance corresponding to this ComponentDescription,
+    pub(crate) fn element_state_initial_value(
+        &self,
+        component: ComponentRefPin,
+        property: &ElementStateProperty,
+    ) -> Option<Value> {
+        if !core::ptr::eq((&self.ct) as *const _, component.get_vtable() as *const _) {
+            panic!("mismatch instance and vtable");
+        }
+        generativity::make_guard!(guard);
+        let c = unsafe { InstanceRef::from_pin_ref(component, guard) };
+        initial_value(c, &property.element, &property.name, &property.property_type)
+    }
+
     /// Sets an handler for a callback
     ///
     /// Returns an error if the component is not an instance corresponding to this ComponentDescription,
@@ -748,7 +923,11 @@ pub async fn load(
         return (Err(()), diag);
     }
     if matches!(doc.root_component.root_element.borrow().base_type, Type::Invalid | Type::Void) {
-        diag.push_error_with_span("No component found".into(), Default::default());
+        diag.push_error_with_code(
+            DiagnosticCode::NoComponent,
+            "No component found".into(),
+            &SourceLocation::default(),
+        );
         return (Err(()), diag);
     }
     (Ok(generate_component(&doc.root_component, guard)), diag)
@@ -780,6 +959,9 @@ pub(crate) fn generate_component<'id>(
                 rtti_for::<Rotate>(),
                 rtti_for::<Opacity>(),
                 rtti_for::<Layer>(),
+                rtti_for::<MenuEntryItem>(),
+                rtti_for::<MenuBarItem>(),
+                rtti_for::<ContextMenuArea>(),
             ]
             .iter()
             .cloned(),
@@ -1074,6 +1256,7 @@ macro_rules! match_enum_type {
         accessible_string_property,
         drop_in_place,
         dealloc,
+        element_id,
     };
     let t = ComponentDescription {
         ct: t,
@@ -1628,6 +1811,13 @@ extern "C" fn accessible_string_property(
     }
 }
 
+extern "C" fn element_id(component: ComponentRefPin, item_index: usize, result: &mut SharedString) {
+    generativity::make_guard!(guard);
+    let instance_ref = unsafe { InstanceRef::from_pin_ref(component, guard) };
+    *result =
+        instance_ref.component_type.original_elements[item_index].borrow().original_id().into();
+}
+
 unsafe extern "C" fn drop_in_place(component: vtable::VRefMut<ComponentVTable>) -> vtable::Layout {
     let instance_ptr = component.as_ptr() as *mut Instance<'static>;
     let layout = (*instance_ptr).type_info().layout();
@@ -313,6 +313,32 @@ pub fn eval_expression(expression: &Expression, local_context: &mut EvalLocalCon
                     panic!("internal error: argument to SetFocusItem must be an element")
                 }
             }
+            Expression::BuiltinFunctionReference(BuiltinFunction::RegisterMenuBar, _) => {
+                if arguments.len() != 1 {
+                    panic!("internal error: incorrect argument count to RegisterMenuBar")
+                }
+                let component = match  local_context.component_instance  {
+                    ComponentInstance::InstanceRef(c) => c,
+                    ComponentInstance::GlobalComponent(_) => panic!("Cannot register a menu bar from a global component")
+                };
+                if let Expression::ElementReference(menu_bar) = &arguments[0] {
+                    generativity::make_guard!(guard);
+
+                    let menu_bar = menu_bar.upgrade().unwrap();
+                    let enclosing_component =
+                        enclosing_component_for_element(&menu_bar, component, guard);
+                    let component_type = enclosing_component.component_type;
+
+                    let item_info = &component_type.items[menu_bar.borrow().id.as_str()];
+
+                    let menu_bar_comp = enclosing_component.self_weak().get().unwrap().upgrade().unwrap();
+
+                    window_ref(component).unwrap().register_menu_bar(&corelib::items::ItemRc::new(vtable::VRc::into_dyn(menu_bar_comp), item_info.item_index()));
+                    Value::Void
+                } else {
+                    panic!("internal error: argument to RegisterMenuBar must be an element")
+                }
+            }
             Expression::BuiltinFunctionReference(BuiltinFunction::ShowPopupWindow, _) => {
                 if arguments.len() != 1 {
                     panic!("internal error: incorrect argument count to ShowPopupWindow")
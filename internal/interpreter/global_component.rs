@@ -88,6 +88,10 @@ fn set_property(
     ) -> Result<(), SetPropertyError>;
     fn get_property(self: Pin<&Self>, prop_name: &str) -> Result<Value, ()>;
 
+    /// The value of the property when the global is created, or None if it is computed from
+    /// other properties
+    fn property_initial_value(self: Pin<&Self>, prop_name: &str) -> Option<Value>;
+
     fn get_property_ptr(self: Pin<&Self>, prop_name: &str) -> *const ();
 }
 
@@ -154,6 +158,12 @@ fn get_property(self: Pin<&Self>, prop_name: &str) -> Result<Value, ()> {
         comp.description().get_property(comp.borrow(), prop_name)
     }
 
+    fn property_initial_value(self: Pin<&Self>, prop_name: &str) -> Option<Value> {
+        generativity::make_guard!(guard);
+        let comp = self.0.unerase(guard);
+        comp.description().property_initial_value(comp.borrow(), prop_name)
+    }
+
     fn get_property_ptr(self: Pin<&Self>, prop_name: &str) -> *const () {
         generativity::make_guard!(guard);
         let comp = self.0.unerase(guard);
@@ -199,6 +209,11 @@ fn get_property(self: Pin<&Self>, prop_name: &str) -> Result<Value, ()> {
         prop.get(self)
     }
 
+    fn property_initial_value(self: Pin<&Self>, _prop_name: &str) -> Option<Value> {
+        // The properties of the native globals are not part of the public API
+        None
+    }
+
     fn get_property_ptr(self: Pin<&Self>, prop_name: &str) -> *const () {
         let prop: &dyn rtti::PropertyInfo<Self, Value> =
             Self::properties().into_iter().find(|(k, _)| *k == prop_name).unwrap().1;
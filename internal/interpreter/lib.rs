@@ -78,7 +78,10 @@
 mod eval;
 mod eval_layout;
 mod global_component;
+mod precompiled;
 mod value_model;
+#[cfg(feature = "serde")]
+mod value_serde;
 
 #[doc(inline)]
 pub use api::*;
@@ -87,6 +90,14 @@
 #[doc(inline)]
 pub use i_slint_core::{Brush, Color, SharedString, SharedVector};
 
+/// API to inspect the tree of elements of a running component.
+/// Properties of the elements are read and written as [`Value`].
+/// (Re-export from corelib.)
+pub mod introspection {
+    pub use i_slint_core::accessibility::AccessibleStringProperty;
+    pub use i_slint_core::introspection::*;
+}
+
 /// One need to use at least one function in each module in order to get them
 /// exported in the final binary.
 /// This only use functions from modules which are not otherwise used.
@@ -0,0 +1,1958 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Save a compiled component to a versioned binary format, and load it back.
+//!
+//! The data contains the object tree as it is after the passes, so [`load`] doesn't parse any
+//! .slint code nor run the passes. Only what the interpreter reads is saved: the syntax nodes and
+//! the source locations are not, except for the identifiers that are shown in the public API with
+//! their original spelling.
+//!
+//! The format starts with [`MAGIC`] followed by [`FORMAT_VERSION`]. Then come the number of
+//! elements and components, the header of each component, and the body of the components and
+//! elements. Elements and components refer to each other by their index, native classes, builtin
+//! elements and enumerations are written the first time they are used and then referred to by
+//! their index.
+
+use by_address::ByAddress;
+use i_slint_compiler::embedded_resources::{EmbeddedResources, EmbeddedResourcesKind};
+use i_slint_compiler::expression_tree::{
+    BindingAnalysis, BindingExpression, BuiltinFunction, EasingCurve, Expression, ImageReference,
+    NamedReference, Path, PathElement, Unit,
+};
+use i_slint_compiler::langtype::{
+    BuiltinElement, BuiltinPropertyInfo, DefaultSizeBinding, Enumeration, EnumerationValue,
+    NativeClass, Type,
+};
+use i_slint_compiler::layout::{
+    BoxLayout, GridLayout, GridLayoutElement, Layout, LayoutConstraints, LayoutGeometry,
+    LayoutItem, LayoutRect, Orientation, Padding, PathLayout,
+};
+use i_slint_compiler::object_tree::*;
+use i_slint_compiler::parser::{syntax_nodes, SyntaxKind, SyntaxNode};
+use itertools::Either;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+
+/// The bytes every precompiled file starts with
+pub const MAGIC: &[u8; 8] = b"SLINTOBJ";
+
+/// The version of the format. It must be incremented with each change of the encoding, so that
+/// data written by another version is rejected instead of misread.
+pub const FORMAT_VERSION: u32 = 2;
+
+/// Error returned by [`load`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum LoadError {
+    /// The data doesn't start with [`MAGIC`]
+    #[error("not a precompiled Slint component")]
+    NotPrecompiled,
+    /// The data was saved with another version of the format
+    #[error("unsupported format version {0}, expected version {}", FORMAT_VERSION)]
+    UnsupportedVersion(u32),
+    /// The data is truncated or invalid
+    #[error("the precompiled data is corrupted")]
+    Corrupted,
+}
+
+/// Error returned by [`save`]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum SaveError {
+    /// The component contains an expression that the passes should have removed
+    #[error("cannot save the expression {0}, which should have been removed by the passes")]
+    UnsupportedExpression(String),
+}
+
+type Result<T> = std::result::Result<T, LoadError>;
+type SaveResult = std::result::Result<(), SaveError>;
+
+/// Save the component, which must have gone through all the passes, and everything it uses.
+pub fn save(component: &Rc<Component>) -> std::result::Result<Vec<u8>, SaveError> {
+    let mut writer = Writer::default();
+    writer.component_index(component);
+
+    // Writing a body registers the components and elements it refers to, so this loops
+    // until everything that is reachable from the root component has been written.
+    let (mut next_component, mut next_element) = (0, 0);
+    loop {
+        if let Some(component) = writer.components.get(next_component).cloned() {
+            next_component += 1;
+            writer.u8(0);
+            writer.component_body(&component)?;
+        } else if let Some(element) = writer.elements.get(next_element).cloned() {
+            next_element += 1;
+            writer.u8(1);
+            writer.element_body(&element.borrow())?;
+        } else {
+            break;
+        }
+    }
+
+    let bodies = std::mem::take(&mut writer.out);
+    writer.out.extend_from_slice(MAGIC);
+    writer.out.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    writer.usize(writer.elements.len());
+    writer.usize(writer.components.len());
+    for component in writer.components.clone() {
+        writer.string(&component.id);
+        writer.element(&component.root_element);
+        writer.weak_element(&component.parent_element);
+        writer.bool(component.is_root_component.get());
+    }
+    writer.out.extend_from_slice(&bodies);
+    Ok(writer.out)
+}
+
+/// Load a component saved with [`save`]
+pub fn load(data: &[u8]) -> Result<Rc<Component>> {
+    let data = data.strip_prefix(MAGIC).ok_or(LoadError::NotPrecompiled)?;
+    let (version, data) = data.split_at(data.len().min(4));
+    let version = u32::from_le_bytes(version.try_into().map_err(|_| LoadError::Corrupted)?);
+    if version != FORMAT_VERSION {
+        return Err(LoadError::UnsupportedVersion(version));
+    }
+
+    let mut reader = Reader { data, ..Default::default() };
+    let element_count = reader.usize()?;
+    let component_count = reader.usize()?;
+    if element_count > reader.data.len() || component_count > reader.data.len() {
+        return Err(LoadError::Corrupted);
+    }
+    reader.elements = (0..element_count).map(|_| ElementRc::default()).collect();
+    for _ in 0..component_count {
+        let component = Component {
+            id: reader.string()?,
+            root_element: reader.element()?,
+            parent_element: reader.weak_element()?,
+            ..Default::default()
+        };
+        component.is_root_component.set(reader.bool()?);
+        reader.components.push(Rc::new(component));
+    }
+
+    let (mut next_component, mut next_element) = (0, 0);
+    while !reader.data.is_empty() {
+        match reader.u8()? {
+            0 => {
+                let component =
+                    reader.components.get(next_component).cloned().ok_or(LoadError::Corrupted)?;
+                next_component += 1;
+                reader.component_body(&component)?;
+            }
+            1 => {
+                let element =
+                    reader.elements.get(next_element).cloned().ok_or(LoadError::Corrupted)?;
+                next_element += 1;
+                reader.element_body(&element)?;
+            }
+            _ => return Err(LoadError::Corrupted),
+        }
+    }
+    if next_component != component_count || next_element != element_count {
+        return Err(LoadError::Corrupted);
+    }
+    reader.components.first().cloned().ok_or(LoadError::Corrupted)
+}
+
+/// Return the entries of the map sorted by key, so that saving the same component twice gives
+/// the same bytes
+fn sorted<K: Ord, V>(map: &HashMap<K, V>) -> Vec<(&K, &V)> {
+    let mut entries = map.iter().collect::<Vec<_>>();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+/// Build a syntax node with only the identifiers that are read back from the syntax tree
+fn synthetic_node(build: impl FnOnce(&mut rowan::GreenNodeBuilder<'static>)) -> SyntaxNode {
+    let mut builder = rowan::GreenNodeBuilder::new();
+    build(&mut builder);
+    SyntaxNode {
+        node: rowan::SyntaxNode::new_root(builder.finish()),
+        source_file: Default::default(),
+    }
+}
+
+#[derive(Default)]
+struct Writer {
+    out: Vec<u8>,
+    components: Vec<Rc<Component>>,
+    component_indices: HashMap<ByAddress<Rc<Component>>, usize>,
+    elements: Vec<ElementRc>,
+    element_indices: HashMap<ByAddress<ElementRc>, usize>,
+    native_classes: HashMap<ByAddress<Rc<NativeClass>>, usize>,
+    builtin_elements: HashMap<ByAddress<Rc<BuiltinElement>>, usize>,
+    enumerations: HashMap<ByAddress<Rc<Enumeration>>, usize>,
+}
+
+impl Writer {
+    fn u8(&mut self, value: u8) {
+        self.out.push(value);
+    }
+
+    /// LEB128 encoding
+    fn usize(&mut self, mut value: usize) {
+        while value >= 0x80 {
+            self.out.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.out.push(value as u8);
+    }
+
+    /// Zigzag encoding, so that small negative numbers stay small
+    fn i32(&mut self, value: i32) {
+        self.usize(((value << 1) ^ (value >> 31)) as u32 as usize);
+    }
+
+    fn f32(&mut self, value: f32) {
+        self.out.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn f64(&mut self, value: f64) {
+        self.out.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.u8(value as u8);
+    }
+
+    fn char(&mut self, value: char) {
+        self.usize(value as usize);
+    }
+
+    fn string(&mut self, value: &str) {
+        self.usize(value.len());
+        self.out.extend_from_slice(value.as_bytes());
+    }
+
+    fn option<T>(&mut self, value: &Option<T>, f: impl FnOnce(&mut Self, &T)) {
+        match value {
+            None => self.u8(0),
+            Some(value) => {
+                self.u8(1);
+                f(self, value);
+            }
+        }
+    }
+
+    fn seq<I: ExactSizeIterator>(&mut self, items: I, mut f: impl FnMut(&mut Self, I::Item)) {
+        self.usize(items.len());
+        for item in items {
+            f(self, item);
+        }
+    }
+
+    fn try_option<T>(
+        &mut self,
+        value: &Option<T>,
+        f: impl FnOnce(&mut Self, &T) -> SaveResult,
+    ) -> SaveResult {
+        match value {
+            None => {
+                self.u8(0);
+                Ok(())
+            }
+            Some(value) => {
+                self.u8(1);
+                f(self, value)
+            }
+        }
+    }
+
+    fn try_seq<I: ExactSizeIterator>(
+        &mut self,
+        items: I,
+        mut f: impl FnMut(&mut Self, I::Item) -> SaveResult,
+    ) -> SaveResult {
+        self.usize(items.len());
+        for item in items {
+            f(self, item)?;
+        }
+        Ok(())
+    }
+
+    fn component_index(&mut self, component: &Rc<Component>) -> usize {
+        if let Some(index) = self.component_indices.get(&ByAddress(component.clone())) {
+            return *index;
+        }
+        let index = self.components.len();
+        self.components.push(component.clone());
+        self.component_indices.insert(ByAddress(component.clone()), index);
+        // The component header refers to these
+        self.element_index(&component.root_element);
+        if let Some(parent) = component.parent_element.upgrade() {
+            self.element_index(&parent);
+        }
+        index
+    }
+
+    fn component(&mut self, component: &Rc<Component>) {
+        let index = self.component_index(component);
+        self.usize(index);
+    }
+
+    fn element_index(&mut self, element: &ElementRc) -> usize {
+        if let Some(index) = self.element_indices.get(&ByAddress(element.clone())) {
+            return *index;
+        }
+        let index = self.elements.len();
+        self.elements.push(element.clone());
+        self.element_indices.insert(ByAddress(element.clone()), index);
+        index
+    }
+
+    fn element(&mut self, element: &ElementRc) {
+        let index = self.element_index(element);
+        self.usize(index);
+    }
+
+    fn weak_element(&mut self, element: &Weak<RefCell<Element>>) {
+        self.option(&element.upgrade(), |w, e| w.element(e));
+    }
+
+    fn named_reference(&mut self, nr: &NamedReference) {
+        self.element(&nr.element());
+        self.string(nr.name());
+    }
+
+    fn optional_named_reference(&mut self, nr: &Option<NamedReference>) {
+        self.option(nr, |w, nr| w.named_reference(nr));
+    }
+
+    fn component_body(&mut self, component: &Component) -> SaveResult {
+        self.seq(component.optimized_elements.borrow().iter(), |w, e| w.element(e));
+        // The interpreter only looks up the path of the resources from their id
+        self.seq(
+            sorted(&component.embedded_file_resources.borrow()).into_iter(),
+            |w, (path, r)| {
+                w.string(path);
+                w.usize(r.id);
+            },
+        );
+        self.layout_constraints(&component.root_constraints.borrow());
+        self.try_seq(component.setup_code.borrow().iter(), |w, e| w.expression(e))?;
+        let used_types = component.used_types.borrow();
+        self.seq(used_types.globals.iter(), |w, c| w.component(c));
+        self.try_seq(used_types.structs.iter(), |w, ty| w.ty(ty))?;
+        self.seq(used_types.sub_components.iter(), |w, c| w.component(c));
+        self.seq(component.popup_windows.borrow().iter(), |w, popup| {
+            w.component(&popup.component);
+            w.named_reference(&popup.x);
+            w.named_reference(&popup.y);
+            w.element(&popup.parent_element);
+        });
+        self.seq(component.exported_global_names.borrow().iter(), |w, name| {
+            w.string(&name.name);
+            w.string(&name.original_name());
+        });
+        Ok(())
+    }
+
+    fn element_body(&mut self, element: &Element) -> SaveResult {
+        self.string(&element.id);
+        self.ty(&element.base_type)?;
+        self.bindings(&element.bindings)?;
+        self.seq(sorted(&element.property_analysis.borrow()).into_iter(), |w, (name, a)| {
+            w.string(name);
+            w.bool(a.is_set);
+            w.bool(a.is_set_externally);
+            w.bool(a.is_read);
+            w.bool(a.is_read_externally);
+        });
+        self.seq(element.children.iter(), |w, e| w.element(e));
+        self.option(&element.enclosing_component.upgrade(), |w, c| w.component(c));
+        self.try_seq(element.property_declarations.iter(), |w, (name, decl)| {
+            w.string(name);
+            w.ty(&decl.property_type)?;
+            let spelling = decl.node.as_ref().and_then(|node| {
+                node.as_ref()
+                    .either(|n| n.DeclaredIdentifier(), |n| n.DeclaredIdentifier())
+                    .child_text(SyntaxKind::Identifier)
+                    .map(|text| (node.is_right(), text))
+            });
+            w.option(&spelling, |w, (is_callback, text)| {
+                w.bool(*is_callback);
+                w.string(text);
+            });
+            w.bool(decl.expose_in_public_api);
+            w.optional_named_reference(&decl.is_alias);
+            Ok(())
+        })?;
+        self.try_option(&element.repeated, |w, repeated| {
+            w.expression(&repeated.model)?;
+            w.string(&repeated.model_data_id);
+            w.string(&repeated.index_id);
+            w.bool(repeated.is_conditional_element);
+            w.option(&repeated.is_listview, |w, listview| {
+                w.named_reference(&listview.viewport_y);
+                w.named_reference(&listview.viewport_height);
+                w.named_reference(&listview.viewport_width);
+                w.named_reference(&listview.listview_height);
+                w.named_reference(&listview.listview_width);
+            });
+            Ok(())
+        })?;
+        // The states and transitions were lowered to bindings by the passes
+        self.bool(element.child_of_layout);
+        self.option(&element.layout_info_prop, |w, (horizontal, vertical)| {
+            w.named_reference(horizontal);
+            w.named_reference(vertical);
+        });
+        self.seq(element.accessibility_props.0.iter(), |w, (name, nr)| {
+            w.string(name);
+            w.named_reference(nr);
+        });
+        self.bool(element.is_flickable_viewport);
+        self.bool(element.has_popup_child);
+        self.option(&element.item_index.get(), |w, index| w.usize(**index));
+        self.option(&element.item_index_of_first_children.get(), |w, index| w.usize(**index));
+        self.i32(element.inline_depth);
+        self.usize(element.inlined_components);
+        let original_name =
+            element.node.as_ref().and_then(|n| n.child_text(SyntaxKind::Identifier));
+        self.option(&original_name, |w, name| w.string(name));
+        self.string(&element.original_id());
+        Ok(())
+    }
+
+    fn bindings(&mut self, bindings: &BindingsMap) -> SaveResult {
+        self.try_seq(bindings.iter(), |w, (name, binding)| {
+            w.string(name);
+            w.binding(&binding.borrow())
+        })
+    }
+
+    fn binding(&mut self, binding: &BindingExpression) -> SaveResult {
+        self.expression(&binding.expression)?;
+        self.i32(binding.priority);
+        self.try_option(&binding.animation, |w, animation| {
+            match animation {
+                PropertyAnimation::Static(element) => {
+                    w.u8(0);
+                    w.element(element);
+                }
+                PropertyAnimation::Transition { state_ref, animations } => {
+                    w.u8(1);
+                    w.expression(state_ref)?;
+                    w.seq(animations.iter(), |w, animation| {
+                        w.i32(animation.state_id);
+                        w.bool(animation.is_out);
+                        w.element(&animation.animation);
+                    });
+                }
+            }
+            Ok(())
+        })?;
+        self.option(&binding.analysis, |w, analysis| {
+            w.bool(analysis.is_in_binding_loop.get());
+            w.bool(analysis.is_const);
+            w.bool(analysis.no_external_dependencies);
+        });
+        self.seq(binding.two_way_bindings.iter(), |w, nr| w.named_reference(nr));
+        self.bool(binding.is_explicit);
+        Ok(())
+    }
+
+    fn ty(&mut self, ty: &Type) -> SaveResult {
+        match ty {
+            Type::Invalid => self.u8(0),
+            Type::Void => self.u8(1),
+            Type::InferredProperty => self.u8(2),
+            Type::InferredCallback => self.u8(3),
+            Type::Component(component) => {
+                self.u8(4);
+                self.component(component);
+            }
+            Type::Builtin(builtin) => {
+                self.u8(5);
+                self.builtin_element(builtin)?;
+            }
+            Type::Native(native) => {
+                self.u8(6);
+                self.native_class(native)?;
+            }
+            Type::Callback { return_type, args } => {
+                self.u8(7);
+                self.try_option(return_type, |w, ty| w.ty(ty))?;
+                self.try_seq(args.iter(), |w, ty| w.ty(ty))?;
+            }
+            Type::Function { return_type, args } => {
+                self.u8(8);
+                self.ty(return_type)?;
+                self.try_seq(args.iter(), |w, ty| w.ty(ty))?;
+            }
+            Type::Float32 => self.u8(9),
+            Type::Int32 => self.u8(10),
+            Type::String => self.u8(11),
+            Type::Color => self.u8(12),
+            Type::Duration => self.u8(13),
+            Type::PhysicalLength => self.u8(14),
+            Type::LogicalLength => self.u8(15),
+            Type::Angle => self.u8(16),
+            Type::Percent => self.u8(17),
+            Type::Image => self.u8(18),
+            Type::Bool => self.u8(19),
+            Type::Model => self.u8(20),
+            Type::PathData => self.u8(21),
+            Type::Easing => self.u8(22),
+            Type::Brush => self.u8(23),
+            Type::Array(ty) => {
+                self.u8(24);
+                self.ty(ty)?;
+            }
+            Type::Struct { fields, name, node: _ } => {
+                self.u8(25);
+                self.try_seq(fields.iter(), |w, (name, ty)| {
+                    w.string(name);
+                    w.ty(ty)
+                })?;
+                self.option(name, |w, name| w.string(name));
+            }
+            Type::Enumeration(enumeration) => {
+                self.u8(26);
+                self.enumeration(enumeration);
+            }
+            Type::UnitProduct(units) => {
+                self.u8(27);
+                self.seq(units.iter(), |w, (unit, power)| {
+                    w.string(&unit.to_string());
+                    w.i32(*power as i32);
+                });
+            }
+            Type::ElementReference => self.u8(28),
+            Type::LayoutCache => self.u8(29),
+        }
+        Ok(())
+    }
+
+    fn native_class(&mut self, native: &Rc<NativeClass>) -> SaveResult {
+        if let Some(index) = self.native_classes.get(&ByAddress(native.clone())) {
+            let index = *index;
+            self.usize(index + 1);
+            return Ok(());
+        }
+        self.usize(0);
+        self.try_option(&native.parent, |w, parent| w.native_class(parent))?;
+        self.string(&native.class_name);
+        self.string(&native.cpp_vtable_getter);
+        self.builtin_properties(&native.properties)?;
+        self.seq(sorted(&native.deprecated_aliases).into_iter(), |w, (alias, name)| {
+            w.string(alias);
+            w.string(name);
+        });
+        self.option(&native.cpp_type, |w, t| w.string(t));
+        self.option(&native.rust_type_constructor, |w, t| w.string(t));
+        let index = self.native_classes.len();
+        self.native_classes.insert(ByAddress(native.clone()), index);
+        Ok(())
+    }
+
+    /// The child types and the member functions are only used to resolve the .slint code, so
+    /// they are not saved
+    fn builtin_element(&mut self, builtin: &Rc<BuiltinElement>) -> SaveResult {
+        if let Some(index) = self.builtin_elements.get(&ByAddress(builtin.clone())) {
+            let index = *index;
+            self.usize(index + 1);
+            return Ok(());
+        }
+        self.usize(0);
+        self.string(&builtin.name);
+        self.native_class(&builtin.native_class)?;
+        self.builtin_properties(&builtin.properties)?;
+        self.bool(builtin.disallow_global_types_as_child_elements);
+        self.bool(builtin.is_non_item_type);
+        self.bool(builtin.accepts_focus);
+        self.bool(builtin.is_global);
+        self.u8(match builtin.default_size_binding {
+            DefaultSizeBinding::None => 0,
+            DefaultSizeBinding::ExpandsToParentGeometry => 1,
+            DefaultSizeBinding::ImplicitSize => 2,
+        });
+        self.bool(builtin.is_internal);
+        let index = self.builtin_elements.len();
+        self.builtin_elements.insert(ByAddress(builtin.clone()), index);
+        Ok(())
+    }
+
+    fn builtin_properties(
+        &mut self,
+        properties: &HashMap<String, BuiltinPropertyInfo>,
+    ) -> SaveResult {
+        self.try_seq(sorted(properties).into_iter(), |w, (name, info)| {
+            w.string(name);
+            w.ty(&info.ty)?;
+            w.try_option(&info.default_value, |w, e| w.expression(e))?;
+            w.bool(info.is_native_output);
+            Ok(())
+        })
+    }
+
+    fn enumeration(&mut self, enumeration: &Rc<Enumeration>) {
+        if let Some(index) = self.enumerations.get(&ByAddress(enumeration.clone())) {
+            let index = *index;
+            self.usize(index + 1);
+            return;
+        }
+        self.usize(0);
+        self.string(&enumeration.name);
+        self.seq(enumeration.values.iter(), |w, value| w.string(value));
+        self.usize(enumeration.default_value);
+        let index = self.enumerations.len();
+        self.enumerations.insert(ByAddress(enumeration.clone()), index);
+    }
+
+    fn expression(&mut self, expression: &Expression) -> SaveResult {
+        match expression {
+            Expression::Invalid => self.u8(0),
+            Expression::Uncompiled(_)
+            | Expression::MemberFunction { .. }
+            | Expression::BuiltinMacroReference(..) => {
+                return Err(SaveError::UnsupportedExpression(format!("{:?}", expression)));
+            }
+            Expression::StringLiteral(s) => {
+                self.u8(1);
+                self.string(s);
+            }
+            Expression::NumberLiteral(value, unit) => {
+                self.u8(2);
+                self.f64(*value);
+                self.string(&unit.to_string());
+            }
+            Expression::BoolLiteral(value) => {
+                self.u8(3);
+                self.bool(*value);
+            }
+            Expression::CallbackReference(nr) => {
+                self.u8(4);
+                self.named_reference(nr);
+            }
+            Expression::PropertyReference(nr) => {
+                self.u8(5);
+                self.named_reference(nr);
+            }
+            Expression::BuiltinFunctionReference(function, _) => {
+                self.u8(6);
+                self.builtin_function(function);
+            }
+            Expression::ElementReference(element) => {
+                self.u8(7);
+                self.weak_element(element);
+            }
+            Expression::RepeaterIndexReference { element } => {
+                self.u8(8);
+                self.weak_element(element);
+            }
+            Expression::RepeaterModelReference { element } => {
+                self.u8(9);
+                self.weak_element(element);
+            }
+            Expression::FunctionParameterReference { index, ty } => {
+                self.u8(10);
+                self.usize(*index);
+                self.ty(ty)?;
+            }
+            Expression::StoreLocalVariable { name, value } => {
+                self.u8(11);
+                self.string(name);
+                self.expression(value)?;
+            }
+            Expression::ReadLocalVariable { name, ty } => {
+                self.u8(12);
+                self.string(name);
+                self.ty(ty)?;
+            }
+            Expression::StructFieldAccess { base, name } => {
+                self.u8(13);
+                self.expression(base)?;
+                self.string(name);
+            }
+            Expression::ArrayIndex { array, index } => {
+                self.u8(14);
+                self.expression(array)?;
+                self.expression(index)?;
+            }
+            Expression::Cast { from, to } => {
+                self.u8(15);
+                self.expression(from)?;
+                self.ty(to)?;
+            }
+            Expression::CodeBlock(sub) => {
+                self.u8(16);
+                self.try_seq(sub.iter(), |w, e| w.expression(e))?;
+            }
+            Expression::FunctionCall { function, arguments, source_location: _ } => {
+                self.u8(17);
+                self.expression(function)?;
+                self.try_seq(arguments.iter(), |w, e| w.expression(e))?;
+            }
+            Expression::SelfAssignment { lhs, rhs, op } => {
+                self.u8(18);
+                self.expression(lhs)?;
+                self.expression(rhs)?;
+                self.char(*op);
+            }
+            Expression::BinaryExpression { lhs, rhs, op } => {
+                self.u8(19);
+                self.expression(lhs)?;
+                self.expression(rhs)?;
+                self.char(*op);
+            }
+            Expression::UnaryOp { sub, op } => {
+                self.u8(20);
+                self.expression(sub)?;
+                self.char(*op);
+            }
+            Expression::ImageReference { resource_ref, source_location: _ } => {
+                self.u8(21);
+                match resource_ref {
+                    ImageReference::None => self.u8(0),
+                    ImageReference::AbsolutePath(path) => {
+                        self.u8(1);
+                        self.string(path);
+                    }
+                    ImageReference::EmbeddedData { resource_id, extension } => {
+                        self.u8(2);
+                        self.usize(*resource_id);
+                        self.string(extension);
+                    }
+                    ImageReference::EmbeddedTexture { resource_id } => {
+                        self.u8(3);
+                        self.usize(*resource_id);
+                    }
+                }
+            }
+            Expression::Condition { condition, true_expr, false_expr } => {
+                self.u8(22);
+                self.expression(condition)?;
+                self.expression(true_expr)?;
+                self.expression(false_expr)?;
+            }
+            Expression::Array { element_ty, values } => {
+                self.u8(23);
+                self.ty(element_ty)?;
+                self.try_seq(values.iter(), |w, e| w.expression(e))?;
+            }
+            Expression::Struct { ty, values } => {
+                self.u8(24);
+                self.ty(ty)?;
+                self.try_seq(sorted(values).into_iter(), |w, (name, e)| {
+                    w.string(name);
+                    w.expression(e)
+                })?;
+            }
+            Expression::PathData(path) => {
+                self.u8(25);
+                self.path(path)?;
+            }
+            Expression::EasingCurve(curve) => {
+                self.u8(26);
+                match curve {
+                    EasingCurve::Linear => self.u8(0),
+                    EasingCurve::CubicBezier(a, b, c, d) => {
+                        self.u8(1);
+                        for x in [a, b, c, d] {
+                            self.f32(*x);
+                        }
+                    }
+                }
+            }
+            Expression::LinearGradient { angle, stops } => {
+                self.u8(27);
+                self.expression(angle)?;
+                self.gradient_stops(stops)?;
+            }
+            Expression::RadialGradient { stops } => {
+                self.u8(28);
+                self.gradient_stops(stops)?;
+            }
+            Expression::EnumerationValue(value) => {
+                self.u8(29);
+                self.enumeration(&value.enumeration);
+                self.usize(value.value);
+            }
+            Expression::ReturnStatement(value) => {
+                self.u8(30);
+                self.try_option(value, |w, e| w.expression(e))?;
+            }
+            Expression::LayoutCacheAccess { layout_cache_prop, index, repeater_index } => {
+                self.u8(31);
+                self.named_reference(layout_cache_prop);
+                self.usize(*index);
+                self.try_option(repeater_index, |w, e| w.expression(e))?;
+            }
+            Expression::ComputeLayoutInfo(layout, orientation) => {
+                self.u8(32);
+                self.layout(layout)?;
+                self.orientation(*orientation);
+            }
+            Expression::SolveLayout(layout, orientation) => {
+                self.u8(33);
+                self.layout(layout)?;
+                self.orientation(*orientation);
+            }
+        }
+        Ok(())
+    }
+
+    fn gradient_stops(&mut self, stops: &[(Expression, Expression)]) -> SaveResult {
+        self.try_seq(stops.iter(), |w, (color, position)| {
+            w.expression(color)?;
+            w.expression(position)
+        })
+    }
+
+    fn path(&mut self, path: &Path) -> SaveResult {
+        match path {
+            Path::Elements(elements) => {
+                self.u8(0);
+                self.try_seq(elements.iter(), |w, element| {
+                    w.builtin_element(&element.element_type)?;
+                    w.bindings(&element.bindings)
+                })?;
+            }
+            Path::Events(events, points) => {
+                self.u8(1);
+                self.try_seq(events.iter(), |w, e| w.expression(e))?;
+                self.try_seq(points.iter(), |w, e| w.expression(e))?;
+            }
+            Path::Commands(commands) => {
+                self.u8(2);
+                self.expression(commands)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn orientation(&mut self, orientation: Orientation) {
+        self.u8(match orientation {
+            Orientation::Horizontal => 0,
+            Orientation::Vertical => 1,
+        });
+    }
+
+    fn layout(&mut self, layout: &Layout) -> SaveResult {
+        match layout {
+            Layout::GridLayout(grid) => {
+                self.u8(0);
+                self.seq(grid.elems.iter(), |w, cell| {
+                    w.usize(cell.col as usize);
+                    w.usize(cell.row as usize);
+                    w.usize(cell.colspan as usize);
+                    w.usize(cell.rowspan as usize);
+                    w.layout_item(&cell.item);
+                });
+                self.layout_geometry(&grid.geometry);
+                self.option(&grid.dialog_button_roles, |w, roles| {
+                    w.seq(roles.iter(), |w, role| w.string(role));
+                });
+            }
+            Layout::PathLayout(path) => {
+                self.u8(1);
+                self.path(&path.path)?;
+                self.seq(path.elements.iter(), |w, e| w.element(e));
+                self.layout_rect(&path.rect);
+                self.optional_named_reference(&path.offset_reference);
+            }
+            Layout::BoxLayout(layout) => {
+                self.u8(2);
+                self.orientation(layout.orientation);
+                self.seq(layout.elems.iter(), |w, item| w.layout_item(item));
+                self.layout_geometry(&layout.geometry);
+            }
+        }
+        Ok(())
+    }
+
+    fn layout_item(&mut self, item: &LayoutItem) {
+        self.element(&item.element);
+        self.layout_constraints(&item.constraints);
+    }
+
+    fn layout_constraints(&mut self, constraints: &LayoutConstraints) {
+        self.optional_named_reference(&constraints.min_width);
+        self.optional_named_reference(&constraints.max_width);
+        self.optional_named_reference(&constraints.min_height);
+        self.optional_named_reference(&constraints.max_height);
+        self.optional_named_reference(&constraints.preferred_width);
+        self.optional_named_reference(&constraints.preferred_height);
+        self.optional_named_reference(&constraints.horizontal_stretch);
+        self.optional_named_reference(&constraints.vertical_stretch);
+        self.bool(constraints.fixed_width);
+        self.bool(constraints.fixed_height);
+    }
+
+    fn layout_rect(&mut self, rect: &LayoutRect) {
+        self.optional_named_reference(&rect.width_reference);
+        self.optional_named_reference(&rect.height_reference);
+        self.optional_named_reference(&rect.x_reference);
+        self.optional_named_reference(&rect.y_reference);
+    }
+
+    fn layout_geometry(&mut self, geometry: &LayoutGeometry) {
+        self.layout_rect(&geometry.rect);
+        self.optional_named_reference(&geometry.spacing);
+        self.optional_named_reference(&geometry.alignment);
+        self.optional_named_reference(&geometry.padding.left);
+        self.optional_named_reference(&geometry.padding.right);
+        self.optional_named_reference(&geometry.padding.top);
+        self.optional_named_reference(&geometry.padding.bottom);
+    }
+}
+
+#[derive(Default)]
+struct Reader<'a> {
+    data: &'a [u8],
+    components: Vec<Rc<Component>>,
+    elements: Vec<ElementRc>,
+    native_classes: Vec<Rc<NativeClass>>,
+    builtin_elements: Vec<Rc<BuiltinElement>>,
+    enumerations: Vec<Rc<Enumeration>>,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if len > self.data.len() {
+            return Err(LoadError::Corrupted);
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn usize(&mut self) -> Result<usize> {
+        let mut value = 0usize;
+        let mut shift = 0;
+        loop {
+            let byte = self.u8()?;
+            if shift >= usize::BITS {
+                return Err(LoadError::Corrupted);
+            }
+            value |= ((byte & 0x7f) as usize) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+    }
+
+    fn i32(&mut self) -> Result<i32> {
+        let value = u32::try_from(self.usize()?).map_err(|_| LoadError::Corrupted)?;
+        Ok(((value >> 1) as i32) ^ -((value & 1) as i32))
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        u16::try_from(self.usize()?).map_err(|_| LoadError::Corrupted)
+    }
+
+    fn f32(&mut self) -> Result<f32> {
+        Ok(f32::from_le_bytes(self.bytes(4)?.try_into().unwrap()))
+    }
+
+    fn f64(&mut self) -> Result<f64> {
+        Ok(f64::from_le_bytes(self.bytes(8)?.try_into().unwrap()))
+    }
+
+    fn bool(&mut self) -> Result<bool> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(LoadError::Corrupted),
+        }
+    }
+
+    fn char(&mut self) -> Result<char> {
+        u32::try_from(self.usize()?).ok().and_then(char::from_u32).ok_or(LoadError::Corrupted)
+    }
+
+    fn string(&mut self) -> Result<String> {
+        let len = self.usize()?;
+        let bytes = self.bytes(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| LoadError::Corrupted)
+    }
+
+    fn unit(&mut self) -> Result<Unit> {
+        self.string()?.parse().map_err(|_| LoadError::Corrupted)
+    }
+
+    fn option<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<Option<T>> {
+        Ok(if self.bool()? { Some(f(self)?) } else { None })
+    }
+
+    fn seq<T>(&mut self, mut f: impl FnMut(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+        let len = self.usize()?;
+        let mut result = Vec::new();
+        for _ in 0..len {
+            result.push(f(self)?);
+        }
+        Ok(result)
+    }
+
+    fn component(&mut self) -> Result<Rc<Component>> {
+        let index = self.usize()?;
+        self.components.get(index).cloned().ok_or(LoadError::Corrupted)
+    }
+
+    fn element(&mut self) -> Result<ElementRc> {
+        let index = self.usize()?;
+        self.elements.get(index).cloned().ok_or(LoadError::Corrupted)
+    }
+
+    fn weak_element(&mut self) -> Result<Weak<RefCell<Element>>> {
+        Ok(self.option(Self::element)?.as_ref().map_or_else(Weak::new, Rc::downgrade))
+    }
+
+    fn named_reference(&mut self) -> Result<NamedReference> {
+        let element = self.element()?;
+        Ok(NamedReference::new(&element, &self.string()?))
+    }
+
+    fn optional_named_reference(&mut self) -> Result<Option<NamedReference>> {
+        self.option(Self::named_reference)
+    }
+
+    fn component_body(&mut self, component: &Component) -> Result<()> {
+        *component.optimized_elements.borrow_mut() = self.seq(Self::element)?;
+        *component.embedded_file_resources.borrow_mut() = self
+            .seq(|r| {
+                let path = r.string()?;
+                let id = r.usize()?;
+                Ok((path, EmbeddedResources { id, kind: EmbeddedResourcesKind::RawData }))
+            })?
+            .into_iter()
+            .collect();
+        *component.root_constraints.borrow_mut() = self.layout_constraints()?;
+        *component.setup_code.borrow_mut() = self.seq(Self::expression)?;
+        *component.used_types.borrow_mut() = UsedSubTypes {
+            globals: self.seq(Self::component)?,
+            structs: self.seq(Self::ty)?,
+            sub_components: self.seq(Self::component)?,
+        };
+        *component.popup_windows.borrow_mut() = self.seq(|r| {
+            Ok(PopupWindow {
+                component: r.component()?,
+                x: r.named_reference()?,
+                y: r.named_reference()?,
+                parent_element: r.element()?,
+            })
+        })?;
+        *component.exported_global_names.borrow_mut() = self.seq(|r| {
+            let name = r.string()?;
+            let original_name = r.string()?;
+            let name_ident = synthetic_node(|b| {
+                b.start_node(SyntaxKind::ExportIdentifier.into());
+                b.token(SyntaxKind::Identifier.into(), &original_name);
+                b.finish_node();
+            });
+            Ok(ExportedName { name, name_ident })
+        })?;
+        Ok(())
+    }
+
+    /// Everything is read before the element is borrowed mutably, because creating the named
+    /// references borrows the element they refer to
+    fn element_body(&mut self, element: &ElementRc) -> Result<()> {
+        let id = self.string()?;
+        let base_type = self.ty()?;
+        let bindings = self.bindings()?;
+        let property_analysis = self
+            .seq(|r| {
+                let name = r.string()?;
+                let analysis = PropertyAnalysis {
+                    is_set: r.bool()?,
+                    is_set_externally: r.bool()?,
+                    is_read: r.bool()?,
+                    is_read_externally: r.bool()?,
+                };
+                Ok((name, analysis))
+            })?
+            .into_iter()
+            .collect();
+        let children = self.seq(Self::element)?;
+        let enclosing_component =
+            self.option(Self::component)?.as_ref().map_or_else(Weak::new, Rc::downgrade);
+        let property_declarations = self
+            .seq(|r| {
+                let name = r.string()?;
+                let property_type = r.ty()?;
+                let node = r.option(|r| {
+                    let is_callback = r.bool()?;
+                    let text = r.string()?;
+                    let node = synthetic_node(|b| {
+                        b.start_node(if is_callback {
+                            SyntaxKind::CallbackDeclaration.into()
+                        } else {
+                            SyntaxKind::PropertyDeclaration.into()
+                        });
+                        b.start_node(SyntaxKind::DeclaredIdentifier.into());
+                        b.token(SyntaxKind::Identifier.into(), &text);
+                        b.finish_node();
+                        b.finish_node();
+                    });
+                    Ok(if is_callback {
+                        Either::Right(syntax_nodes::CallbackDeclaration::from(node))
+                    } else {
+                        Either::Left(syntax_nodes::PropertyDeclaration::from(node))
+                    })
+                })?;
+                let declaration = PropertyDeclaration {
+                    property_type,
+                    node,
+                    expose_in_public_api: r.bool()?,
+                    is_alias: r.optional_named_reference()?,
+                };
+                Ok((name, declaration))
+            })?
+            .into_iter()
+            .collect();
+        let repeated = self.option(|r| {
+            Ok(RepeatedElementInfo {
+                model: r.expression()?,
+                model_data_id: r.string()?,
+                index_id: r.string()?,
+                is_conditional_element: r.bool()?,
+                is_listview: r.option(|r| {
+                    Ok(ListViewInfo {
+                        viewport_y: r.named_reference()?,
+                        viewport_height: r.named_reference()?,
+                        viewport_width: r.named_reference()?,
+                        listview_height: r.named_reference()?,
+                        listview_width: r.named_reference()?,
+                    })
+                })?,
+            })
+        })?;
+        let child_of_layout = self.bool()?;
+        let layout_info_prop = self.option(|r| Ok((r.named_reference()?, r.named_reference()?)))?;
+        let accessibility_props = AccessibilityProps(
+            self.seq(|r| Ok((r.string()?, r.named_reference()?)))?.into_iter().collect(),
+        );
+        let is_flickable_viewport = self.bool()?;
+        let has_popup_child = self.bool()?;
+        let item_index = self.option(Self::usize)?;
+        let item_index_of_first_children = self.option(Self::usize)?;
+        let inline_depth = self.i32()?;
+        let inlined_components = self.usize()?;
+        let original_name = self.option(Self::string)?;
+        let original_id = self.string()?;
+
+        let node = (original_name.is_some() || !original_id.is_empty()).then(|| {
+            let node = synthetic_node(|b| {
+                if !original_id.is_empty() {
+                    b.start_node(SyntaxKind::SubElement.into());
+                    b.token(SyntaxKind::Identifier.into(), &original_id);
+                }
+                b.start_node(SyntaxKind::Element.into());
+                if let Some(name) = &original_name {
+                    b.token(SyntaxKind::Identifier.into(), name);
+                }
+                b.finish_node();
+                if !original_id.is_empty() {
+                    b.finish_node();
+                }
+            });
+            let node = if original_id.is_empty() {
+                node
+            } else {
+                node.child_node(SyntaxKind::Element).unwrap()
+            };
+            syntax_nodes::Element::from(node)
+        });
+
+        let mut e = element.borrow_mut();
+        e.id = id;
+        e.base_type = base_type;
+        e.bindings = bindings;
+        e.property_analysis = RefCell::new(property_analysis);
+        e.children = children;
+        e.enclosing_component = enclosing_component;
+        e.property_declarations = property_declarations;
+        e.repeated = repeated;
+        e.child_of_layout = child_of_layout;
+        e.layout_info_prop = layout_info_prop;
+        e.accessibility_props = accessibility_props;
+        e.is_flickable_viewport = is_flickable_viewport;
+        e.has_popup_child = has_popup_child;
+        if let Some(index) = item_index {
+            e.item_index.set(index).map_err(|_| LoadError::Corrupted)?;
+        }
+        if let Some(index) = item_index_of_first_children {
+            e.item_index_of_first_children.set(index).map_err(|_| LoadError::Corrupted)?;
+        }
+        e.inline_depth = inline_depth;
+        e.inlined_components = inlined_components;
+        e.node = node;
+        Ok(())
+    }
+
+    fn bindings(&mut self) -> Result<BindingsMap> {
+        Ok(self.seq(|r| Ok((r.string()?, RefCell::new(r.binding()?))))?.into_iter().collect())
+    }
+
+    fn binding(&mut self) -> Result<BindingExpression> {
+        Ok(BindingExpression {
+            expression: self.expression()?,
+            span: None,
+            priority: self.i32()?,
+            animation: self.option(|r| {
+                Ok(match r.u8()? {
+                    0 => PropertyAnimation::Static(r.element()?),
+                    1 => PropertyAnimation::Transition {
+                        state_ref: r.expression()?,
+                        animations: r.seq(|r| {
+                            Ok(TransitionPropertyAnimation {
+                                state_id: r.i32()?,
+                                is_out: r.bool()?,
+                                animation: r.element()?,
+                            })
+                        })?,
+                    },
+                    _ => return Err(LoadError::Corrupted),
+                })
+            })?,
+            analysis: self.option(|r| {
+                Ok(BindingAnalysis {
+                    is_in_binding_loop: r.bool()?.into(),
+                    is_const: r.bool()?,
+                    no_external_dependencies: r.bool()?,
+                })
+            })?,
+            two_way_bindings: self.seq(Self::named_reference)?,
+            is_explicit: self.bool()?,
+        })
+    }
+
+    fn ty(&mut self) -> Result<Type> {
+        Ok(match self.u8()? {
+            0 => Type::Invalid,
+            1 => Type::Void,
+            2 => Type::InferredProperty,
+            3 => Type::InferredCallback,
+            4 => Type::Component(self.component()?),
+            5 => Type::Builtin(self.builtin_element()?),
+            6 => Type::Native(self.native_class()?),
+            7 => Type::Callback {
+                return_type: self.option(|r| Ok(Box::new(r.ty()?)))?,
+                args: self.seq(Self::ty)?,
+            },
+            8 => Type::Function { return_type: Box::new(self.ty()?), args: self.seq(Self::ty)? },
+            9 => Type::Float32,
+            10 => Type::Int32,
+            11 => Type::String,
+            12 => Type::Color,
+            13 => Type::Duration,
+            14 => Type::PhysicalLength,
+            15 => Type::LogicalLength,
+            16 => Type::Angle,
+            17 => Type::Percent,
+            18 => Type::Image,
+            19 => Type::Bool,
+            20 => Type::Model,
+            21 => Type::PathData,
+            22 => Type::Easing,
+            23 => Type::Brush,
+            24 => Type::Array(Box::new(self.ty()?)),
+            25 => Type::Struct {
+                fields: self.seq(|r| Ok((r.string()?, r.ty()?)))?.into_iter().collect(),
+                name: self.option(Self::string)?,
+                node: None,
+            },
+            26 => Type::Enumeration(self.enumeration()?),
+            27 => Type::UnitProduct(self.seq(|r| {
+                let unit = r.unit()?;
+                let power = i8::try_from(r.i32()?).map_err(|_| LoadError::Corrupted)?;
+                Ok((unit, power))
+            })?),
+            28 => Type::ElementReference,
+            29 => Type::LayoutCache,
+            _ => return Err(LoadError::Corrupted),
+        })
+    }
+
+    fn native_class(&mut self) -> Result<Rc<NativeClass>> {
+        let index = self.usize()?;
+        if index > 0 {
+            return self.native_classes.get(index - 1).cloned().ok_or(LoadError::Corrupted);
+        }
+        let native = Rc::new(NativeClass {
+            parent: self.option(Self::native_class)?,
+            class_name: self.string()?,
+            cpp_vtable_getter: self.string()?,
+            properties: self.builtin_properties()?,
+            deprecated_aliases: self.seq(|r| Ok((r.string()?, r.string()?)))?.into_iter().collect(),
+            cpp_type: self.option(Self::string)?,
+            rust_type_constructor: self.option(Self::string)?,
+        });
+        self.native_classes.push(native.clone());
+        Ok(native)
+    }
+
+    fn builtin_element(&mut self) -> Result<Rc<BuiltinElement>> {
+        let index = self.usize()?;
+        if index > 0 {
+            return self.builtin_elements.get(index - 1).cloned().ok_or(LoadError::Corrupted);
+        }
+        let builtin = Rc::new(BuiltinElement {
+            name: self.string()?,
+            native_class: self.native_class()?,
+            properties: self.builtin_properties()?,
+            disallow_global_types_as_child_elements: self.bool()?,
+            is_non_item_type: self.bool()?,
+            accepts_focus: self.bool()?,
+            is_global: self.bool()?,
+            default_size_binding: match self.u8()? {
+                0 => DefaultSizeBinding::None,
+                1 => DefaultSizeBinding::ExpandsToParentGeometry,
+                2 => DefaultSizeBinding::ImplicitSize,
+                _ => return Err(LoadError::Corrupted),
+            },
+            is_internal: self.bool()?,
+            ..Default::default()
+        });
+        self.builtin_elements.push(builtin.clone());
+        Ok(builtin)
+    }
+
+    fn builtin_properties(&mut self) -> Result<HashMap<String, BuiltinPropertyInfo>> {
+        Ok(self
+            .seq(|r| {
+                let name = r.string()?;
+                let info = BuiltinPropertyInfo {
+                    ty: r.ty()?,
+                    default_value: r.option(Self::expression)?,
+                    is_native_output: r.bool()?,
+                };
+                Ok((name, info))
+            })?
+            .into_iter()
+            .collect())
+    }
+
+    fn enumeration(&mut self) -> Result<Rc<Enumeration>> {
+        let index = self.usize()?;
+        if index > 0 {
+            return self.enumerations.get(index - 1).cloned().ok_or(LoadError::Corrupted);
+        }
+        let enumeration = Rc::new(Enumeration {
+            name: self.string()?,
+            values: self.seq(Self::string)?,
+            default_value: self.usize()?,
+        });
+        self.enumerations.push(enumeration.clone());
+        Ok(enumeration)
+    }
+
+    fn expression(&mut self) -> Result<Expression> {
+        let boxed = |r: &mut Self| -> Result<Box<Expression>> { Ok(Box::new(r.expression()?)) };
+        Ok(match self.u8()? {
+            0 => Expression::Invalid,
+            1 => Expression::StringLiteral(self.string()?),
+            2 => Expression::NumberLiteral(self.f64()?, self.unit()?),
+            3 => Expression::BoolLiteral(self.bool()?),
+            4 => Expression::CallbackReference(self.named_reference()?),
+            5 => Expression::PropertyReference(self.named_reference()?),
+            6 => Expression::BuiltinFunctionReference(self.builtin_function()?, None),
+            7 => Expression::ElementReference(self.weak_element()?),
+            8 => Expression::RepeaterIndexReference { element: self.weak_element()? },
+            9 => Expression::RepeaterModelReference { element: self.weak_element()? },
+            10 => Expression::FunctionParameterReference { index: self.usize()?, ty: self.ty()? },
+            11 => Expression::StoreLocalVariable { name: self.string()?, value: boxed(self)? },
+            12 => Expression::ReadLocalVariable { name: self.string()?, ty: self.ty()? },
+            13 => Expression::StructFieldAccess { base: boxed(self)?, name: self.string()? },
+            14 => Expression::ArrayIndex { array: boxed(self)?, index: boxed(self)? },
+            15 => Expression::Cast { from: boxed(self)?, to: self.ty()? },
+            16 => Expression::CodeBlock(self.seq(Self::expression)?),
+            17 => Expression::FunctionCall {
+                function: boxed(self)?,
+                arguments: self.seq(Self::expression)?,
+                source_location: None,
+            },
+            18 => Expression::SelfAssignment {
+                lhs: boxed(self)?,
+                rhs: boxed(self)?,
+                op: self.char()?,
+            },
+            19 => Expression::BinaryExpression {
+                lhs: boxed(self)?,
+                rhs: boxed(self)?,
+                op: self.char()?,
+            },
+            20 => Expression::UnaryOp { sub: boxed(self)?, op: self.char()? },
+            21 => Expression::ImageReference {
+                resource_ref: match self.u8()? {
+                    0 => ImageReference::None,
+                    1 => ImageReference::AbsolutePath(self.string()?),
+                    2 => ImageReference::EmbeddedData {
+                        resource_id: self.usize()?,
+                        extension: self.string()?,
+                    },
+                    3 => ImageReference::EmbeddedTexture { resource_id: self.usize()? },
+                    _ => return Err(LoadError::Corrupted),
+                },
+                source_location: None,
+            },
+            22 => Expression::Condition {
+                condition: boxed(self)?,
+                true_expr: boxed(self)?,
+                false_expr: boxed(self)?,
+            },
+            23 => Expression::Array { element_ty: self.ty()?, values: self.seq(Self::expression)? },
+            24 => Expression::Struct {
+                ty: self.ty()?,
+                values: self.seq(|r| Ok((r.string()?, r.expression()?)))?.into_iter().collect(),
+            },
+            25 => Expression::PathData(self.path()?),
+            26 => Expression::EasingCurve(match self.u8()? {
+                0 => EasingCurve::Linear,
+                1 => EasingCurve::CubicBezier(self.f32()?, self.f32()?, self.f32()?, self.f32()?),
+                _ => return Err(LoadError::Corrupted),
+            }),
+            27 => Expression::LinearGradient { angle: boxed(self)?, stops: self.gradient_stops()? },
+            28 => Expression::RadialGradient { stops: self.gradient_stops()? },
+            29 => {
+                let enumeration = self.enumeration()?;
+                let value = self.usize()?;
+                if value >= enumeration.values.len() {
+                    return Err(LoadError::Corrupted);
+                }
+                Expression::EnumerationValue(EnumerationValue { value, enumeration })
+            }
+            30 => Expression::ReturnStatement(self.option(boxed)?),
+            31 => Expression::LayoutCacheAccess {
+                layout_cache_prop: self.named_reference()?,
+                index: self.usize()?,
+                repeater_index: self.option(boxed)?,
+            },
+            32 => Expression::ComputeLayoutInfo(self.layout()?, self.orientation()?),
+            33 => Expression::SolveLayout(self.layout()?, self.orientation()?),
+            _ => return Err(LoadError::Corrupted),
+        })
+    }
+
+    fn gradient_stops(&mut self) -> Result<Vec<(Expression, Expression)>> {
+        self.seq(|r| Ok((r.expression()?, r.expression()?)))
+    }
+
+    fn path(&mut self) -> Result<Path> {
+        Ok(match self.u8()? {
+            0 => Path::Elements(self.seq(|r| {
+                Ok(PathElement { element_type: r.builtin_element()?, bindings: r.bindings()? })
+            })?),
+            1 => Path::Events(self.seq(Self::expression)?, self.seq(Self::expression)?),
+            2 => Path::Commands(Box::new(self.expression()?)),
+            _ => return Err(LoadError::Corrupted),
+        })
+    }
+
+    fn orientation(&mut self) -> Result<Orientation> {
+        match self.u8()? {
+            0 => Ok(Orientation::Horizontal),
+            1 => Ok(Orientation::Vertical),
+            _ => Err(LoadError::Corrupted),
+        }
+    }
+
+    fn layout(&mut self) -> Result<Layout> {
+        Ok(match self.u8()? {
+            0 => Layout::GridLayout(GridLayout {
+                elems: self.seq(|r| {
+                    Ok(GridLayoutElement {
+                        col: r.u16()?,
+                        row: r.u16()?,
+                        colspan: r.u16()?,
+                        rowspan: r.u16()?,
+                        item: r.layout_item()?,
+                    })
+                })?,
+                geometry: self.layout_geometry()?,
+                dialog_button_roles: self.option(|r| r.seq(Self::string))?,
+            }),
+            1 => Layout::PathLayout(PathLayout {
+                path: self.path()?,
+                elements: self.seq(Self::element)?,
+                rect: self.layout_rect()?,
+                offset_reference: self.optional_named_reference()?,
+            }),
+            2 => Layout::BoxLayout(BoxLayout {
+                orientation: self.orientation()?,
+                elems: self.seq(Self::layout_item)?,
+                geometry: self.layout_geometry()?,
+            }),
+            _ => return Err(LoadError::Corrupted),
+        })
+    }
+
+    fn layout_item(&mut self) -> Result<LayoutItem> {
+        Ok(LayoutItem { element: self.element()?, constraints: self.layout_constraints()? })
+    }
+
+    fn layout_constraints(&mut self) -> Result<LayoutConstraints> {
+        Ok(LayoutConstraints {
+            min_width: self.optional_named_reference()?,
+            max_width: self.optional_named_reference()?,
+            min_height: self.optional_named_reference()?,
+            max_height: self.optional_named_reference()?,
+            preferred_width: self.optional_named_reference()?,
+            preferred_height: self.optional_named_reference()?,
+            horizontal_stretch: self.optional_named_reference()?,
+            vertical_stretch: self.optional_named_reference()?,
+            fixed_width: self.bool()?,
+            fixed_height: self.bool()?,
+        })
+    }
+
+    fn layout_rect(&mut self) -> Result<LayoutRect> {
+        Ok(LayoutRect {
+            width_reference: self.optional_named_reference()?,
+            height_reference: self.optional_named_reference()?,
+            x_reference: self.optional_named_reference()?,
+            y_reference: self.optional_named_reference()?,
+        })
+    }
+
+    fn layout_geometry(&mut self) -> Result<LayoutGeometry> {
+        Ok(LayoutGeometry {
+            rect: self.layout_rect()?,
+            spacing: self.optional_named_reference()?,
+            alignment: self.optional_named_reference()?,
+            padding: Padding {
+                left: self.optional_named_reference()?,
+                right: self.optional_named_reference()?,
+                top: self.optional_named_reference()?,
+                bottom: self.optional_named_reference()?,
+            },
+        })
+    }
+}
+
+/// Declares the tag of the builtin functions that have no argument
+macro_rules! builtin_function_tags {
+    ($($tag:literal => $name:ident,)*) => {
+        impl Writer {
+            fn builtin_function(&mut self, function: &BuiltinFunction) {
+                match function {
+                    $(BuiltinFunction::$name => self.u8($tag),)*
+                    BuiltinFunction::ImplicitLayoutInfo(orientation) => {
+                        self.u8(255);
+                        self.orientation(*orientation);
+                    }
+                }
+            }
+        }
+
+        impl Reader<'_> {
+            fn builtin_function(&mut self) -> Result<BuiltinFunction> {
+                Ok(match self.u8()? {
+                    $($tag => BuiltinFunction::$name,)*
+                    255 => BuiltinFunction::ImplicitLayoutInfo(self.orientation()?),
+                    _ => return Err(LoadError::Corrupted),
+                })
+            }
+        }
+    };
+}
+
+builtin_function_tags! {
+    0 => GetWindowScaleFactor,
+    1 => AnimationTick,
+    2 => Debug,
+    3 => Mod,
+    4 => Round,
+    5 => Ceil,
+    6 => Floor,
+    7 => Abs,
+    8 => Sqrt,
+    9 => Cos,
+    10 => Sin,
+    11 => Tan,
+    12 => ACos,
+    13 => ASin,
+    14 => ATan,
+    15 => Log,
+    16 => Pow,
+    17 => SetFocusItem,
+    18 => ShowPopupWindow,
+    19 => RegisterMenuBar,
+    20 => StringToFloat,
+    21 => StringIsFloat,
+    22 => ColorBrighter,
+    23 => ColorDarker,
+    24 => ImageSize,
+    25 => ArrayLength,
+    26 => Rgb,
+    27 => RegisterCustomFontByPath,
+    28 => RegisterCustomFontByMemory,
+    29 => RegisterBitmapFont,
+}
+
+/// The types whose fields are saved, with the source of the file that declares them
+#[cfg(test)]
+const SAVED_TYPES: &[(&str, &[&str])] = &[
+    (
+        include_str!("../compiler/object_tree.rs"),
+        &[
+            "struct PopupWindow",
+            "struct UsedSubTypes",
+            "struct Component",
+            "struct PropertyDeclaration",
+            "struct TransitionPropertyAnimation",
+            "enum PropertyAnimation",
+            "struct AccessibilityProps",
+            "struct Element",
+            "struct PropertyAnalysis",
+            "struct ListViewInfo",
+            "struct RepeatedElementInfo",
+            "struct ExportedName",
+        ],
+    ),
+    (
+        include_str!("../compiler/expression_tree.rs"),
+        &[
+            "enum BuiltinFunction",
+            "enum Expression",
+            "struct BindingExpression",
+            "struct BindingAnalysis",
+            "enum Path",
+            "struct PathElement",
+            "enum EasingCurve",
+            "enum ImageReference",
+        ],
+    ),
+    (
+        include_str!("../compiler/langtype.rs"),
+        &[
+            "enum Type",
+            "struct BuiltinPropertyInfo",
+            "struct NativeClass",
+            "enum DefaultSizeBinding",
+            "struct BuiltinElement",
+            "struct Enumeration",
+            "struct EnumerationValue",
+        ],
+    ),
+    (
+        include_str!("../compiler/layout.rs"),
+        &[
+            "enum Orientation",
+            "enum Layout",
+            "struct LayoutItem",
+            "struct LayoutRect",
+            "struct LayoutConstraints",
+            "struct GridLayoutElement",
+            "struct Padding",
+            "struct LayoutGeometry",
+            "struct GridLayout",
+            "struct BoxLayout",
+            "struct PathLayout",
+        ],
+    ),
+    (include_str!("../compiler/embedded_resources.rs"), &["struct EmbeddedResources"]),
+];
+
+/// The fingerprint of the declarations of [`SAVED_TYPES`] for each [`FORMAT_VERSION`]
+#[cfg(test)]
+const FINGERPRINTS: &[(u32, u64)] = &[(1, 0x22936654b930ef92), (2, 0x7ff10b0139c521cc)];
+
+/// Return the declaration of `pub <name>` in the source, without the comments and the whitespace
+#[cfg(test)]
+fn declaration(source: &str, name: &str) -> String {
+    let source =
+        source.lines().map(|l| l.split("//").next().unwrap()).collect::<Vec<_>>().join("\n");
+    let pattern = format!("pub {}", name);
+    let start = source
+        .match_indices(&pattern)
+        .map(|(i, _)| i)
+        .find(|i| {
+            !source[i + pattern.len()..].starts_with(|c: char| c.is_alphanumeric() || c == '_')
+        })
+        .unwrap_or_else(|| panic!("Cannot find the declaration of {}", name));
+    let mut depth = 0;
+    let mut declaration = String::new();
+    for c in source[start..].chars() {
+        declaration.push(c);
+        match c {
+            '{' | '(' => depth += 1,
+            ')' => depth -= 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            ';' if depth == 0 => break,
+            _ => (),
+        }
+    }
+    declaration.retain(|c| !c.is_whitespace());
+    declaration
+}
+
+#[test]
+fn format_version_follows_the_saved_types() {
+    // FNV-1a, which is stable across Rust versions, unlike the DefaultHasher
+    let mut fingerprint: u64 = 0xcbf29ce484222325;
+    for (source, names) in SAVED_TYPES {
+        for name in names.iter() {
+            for byte in declaration(source, name).bytes() {
+                fingerprint = (fingerprint ^ byte as u64).wrapping_mul(0x100000001b3);
+            }
+        }
+    }
+    assert!(FINGERPRINTS.windows(2).all(|w| w[0].0 < w[1].0));
+    assert_eq!(
+        FINGERPRINTS.last(),
+        Some(&(FORMAT_VERSION, fingerprint)),
+        "The saved types changed (fingerprint {:#x}): update the encoding, increment FORMAT_VERSION \
+         and add its fingerprint to FINGERPRINTS",
+        fingerprint
+    );
+}
+
+/// The expressions of the round trip test, one or more per variant
+#[cfg(test)]
+fn expressions_of_every_kind(root: &ElementRc, child: &ElementRc) -> Vec<Expression> {
+    let nr = |name: &str| NamedReference::new(root, name);
+    let number = |value: f64| Expression::NumberLiteral(value, Unit::Px);
+    let enumeration = Rc::new(Enumeration {
+        name: "TestEnum".into(),
+        values: vec!["first".into(), "second".into()],
+        default_value: 0,
+    });
+    let struct_ty = Type::Struct {
+        fields: [("a".to_string(), Type::Int32)].into_iter().collect(),
+        name: Some("TestStruct".into()),
+        node: None,
+    };
+    let geometry = LayoutGeometry {
+        rect: LayoutRect { width_reference: Some(nr("width")), ..Default::default() },
+        spacing: Some(nr("spacing")),
+        alignment: None,
+        padding: Padding { left: Some(nr("padding-left")), right: None, top: None, bottom: None },
+    };
+    let item = LayoutItem {
+        element: child.clone(),
+        constraints: LayoutConstraints { min_width: Some(nr("min")), ..Default::default() },
+    };
+    vec![
+        Expression::Invalid,
+        Expression::StringLiteral("hello".into()),
+        number(4.5),
+        Expression::BoolLiteral(true),
+        Expression::CallbackReference(nr("clicked")),
+        Expression::PropertyReference(nr("width")),
+        Expression::BuiltinFunctionReference(BuiltinFunction::Round, None),
+        Expression::BuiltinFunctionReference(
+            BuiltinFunction::ImplicitLayoutInfo(Orientation::Vertical),
+            None,
+        ),
+        Expression::ElementReference(Rc::downgrade(child)),
+        Expression::RepeaterIndexReference { element: Rc::downgrade(child) },
+        Expression::RepeaterModelReference { element: Rc::downgrade(child) },
+        Expression::FunctionParameterReference { index: 1, ty: Type::Float32 },
+        Expression::StoreLocalVariable { name: "local".into(), value: Box::new(number(1.)) },
+        Expression::ReadLocalVariable { name: "local".into(), ty: struct_ty.clone() },
+        Expression::StructFieldAccess {
+            base: Box::new(Expression::ReadLocalVariable {
+                name: "s".into(),
+                ty: struct_ty.clone(),
+            }),
+            name: "a".into(),
+        },
+        Expression::ArrayIndex {
+            array: Box::new(Expression::Invalid),
+            index: Box::new(number(0.)),
+        },
+        Expression::Cast { from: Box::new(number(2.)), to: Type::String },
+        Expression::CodeBlock(vec![number(1.), Expression::BoolLiteral(false)]),
+        Expression::FunctionCall {
+            function: Box::new(Expression::BuiltinFunctionReference(BuiltinFunction::Abs, None)),
+            arguments: vec![number(-1.)],
+            source_location: None,
+        },
+        Expression::SelfAssignment {
+            lhs: Box::new(Expression::PropertyReference(nr("width"))),
+            rhs: Box::new(number(1.)),
+            op: '+',
+        },
+        Expression::BinaryExpression {
+            lhs: Box::new(number(1.)),
+            rhs: Box::new(number(2.)),
+            op: '≤',
+        },
+        Expression::UnaryOp { sub: Box::new(Expression::BoolLiteral(true)), op: '!' },
+        Expression::ImageReference { resource_ref: ImageReference::None, source_location: None },
+        Expression::ImageReference {
+            resource_ref: ImageReference::AbsolutePath("/image.png".into()),
+            source_location: None,
+        },
+        Expression::ImageReference {
+            resource_ref: ImageReference::EmbeddedData { resource_id: 1, extension: "png".into() },
+            source_location: None,
+        },
+        Expression::ImageReference {
+            resource_ref: ImageReference::EmbeddedTexture { resource_id: 2 },
+            source_location: None,
+        },
+        Expression::Condition {
+            condition: Box::new(Expression::BoolLiteral(true)),
+            true_expr: Box::new(number(1.)),
+            false_expr: Box::new(number(2.)),
+        },
+        Expression::Array { element_ty: Type::LogicalLength, values: vec![number(1.), number(2.)] },
+        Expression::Struct {
+            ty: struct_ty,
+            values: [("a".to_string(), number(3.))].into_iter().collect(),
+        },
+        Expression::PathData(Path::Events(vec![Expression::Invalid], vec![number(1.)])),
+        Expression::PathData(Path::Commands(Box::new(Expression::StringLiteral("M 0 0".into())))),
+        Expression::EasingCurve(EasingCurve::Linear),
+        Expression::EasingCurve(EasingCurve::CubicBezier(0.1, 0.2, 0.3, 0.4)),
+        Expression::LinearGradient {
+            angle: Box::new(number(90.)),
+            stops: vec![(Expression::Invalid, number(0.5))],
+        },
+        Expression::RadialGradient { stops: vec![(Expression::Invalid, number(1.))] },
+        Expression::EnumerationValue(EnumerationValue { value: 1, enumeration }),
+        Expression::ReturnStatement(None),
+        Expression::ReturnStatement(Some(Box::new(number(1.)))),
+        Expression::LayoutCacheAccess {
+            layout_cache_prop: nr("layout-cache"),
+            index: 3,
+            repeater_index: Some(Box::new(number(0.))),
+        },
+        Expression::ComputeLayoutInfo(
+            Layout::BoxLayout(BoxLayout {
+                orientation: Orientation::Horizontal,
+                elems: vec![item.clone()],
+                geometry: geometry.clone(),
+            }),
+            Orientation::Vertical,
+        ),
+        Expression::SolveLayout(
+            Layout::GridLayout(GridLayout {
+                elems: vec![GridLayoutElement { col: 1, row: 2, colspan: 1, rowspan: 3, item }],
+                geometry,
+                dialog_button_roles: Some(vec!["accept".into()]),
+            }),
+            Orientation::Horizontal,
+        ),
+    ]
+}
+
+/// The number of variants of [`Expression`]
+#[cfg(test)]
+const EXPRESSION_VARIANT_COUNT: usize = 37;
+
+/// The index of the variant, in the order of the declaration. There is no wildcard arm, so that
+/// a new variant doesn't compile until it is added to the round trip test.
+#[cfg(test)]
+fn expression_variant_index(expression: &Expression) -> usize {
+    match expression {
+        Expression::Invalid => 0,
+        Expression::Uncompiled(_) => 1,
+        Expression::StringLiteral(_) => 2,
+        Expression::NumberLiteral(..) => 3,
+        Expression::BoolLiteral(_) => 4,
+        Expression::CallbackReference(_) => 5,
+        Expression::PropertyReference(_) => 6,
+        Expression::BuiltinFunctionReference(..) => 7,
+        Expression::MemberFunction { .. } => 8,
+        Expression::BuiltinMacroReference(..) => 9,
+        Expression::ElementReference(_) => 10,
+        Expression::RepeaterIndexReference { .. } => 11,
+        Expression::RepeaterModelReference { .. } => 12,
+        Expression::FunctionParameterReference { .. } => 13,
+        Expression::StoreLocalVariable { .. } => 14,
+        Expression::ReadLocalVariable { .. } => 15,
+        Expression::StructFieldAccess { .. } => 16,
+        Expression::ArrayIndex { .. } => 17,
+        Expression::Cast { .. } => 18,
+        Expression::CodeBlock(_) => 19,
+        Expression::FunctionCall { .. } => 20,
+        Expression::SelfAssignment { .. } => 21,
+        Expression::BinaryExpression { .. } => 22,
+        Expression::UnaryOp { .. } => 23,
+        Expression::ImageReference { .. } => 24,
+        Expression::Condition { .. } => 25,
+        Expression::Array { .. } => 26,
+        Expression::Struct { .. } => 27,
+        Expression::PathData(_) => 28,
+        Expression::EasingCurve(_) => 29,
+        Expression::LinearGradient { .. } => 30,
+        Expression::RadialGradient { .. } => 31,
+        Expression::EnumerationValue(_) => 32,
+        Expression::ReturnStatement(_) => 33,
+        Expression::LayoutCacheAccess { .. } => 34,
+        Expression::ComputeLayoutInfo(..) => 35,
+        Expression::SolveLayout(..) => 36,
+    }
+}
+
+#[test]
+fn round_trip_every_expression() {
+    let component = Rc::new(Component { id: "Test".into(), ..Default::default() });
+    let root = component.root_element.clone();
+    let child = Rc::new(RefCell::new(Element {
+        id: "child".into(),
+        enclosing_component: Rc::downgrade(&component),
+        ..Default::default()
+    }));
+    {
+        let mut root = root.borrow_mut();
+        root.id = "root".into();
+        root.enclosing_component = Rc::downgrade(&component);
+        root.children.push(child.clone());
+    }
+
+    let unsupported = vec![
+        Expression::Uncompiled(synthetic_node(|b| {
+            b.start_node(SyntaxKind::Expression.into());
+            b.finish_node();
+        })),
+        Expression::MemberFunction {
+            base: Box::new(Expression::ElementReference(Rc::downgrade(&child))),
+            base_node: None,
+            member: Box::new(Expression::BuiltinFunctionReference(
+                BuiltinFunction::SetFocusItem,
+                None,
+            )),
+        },
+        Expression::BuiltinMacroReference(
+            i_slint_compiler::expression_tree::BuiltinMacroFunction::Min,
+            None,
+        ),
+    ];
+    let expressions = expressions_of_every_kind(&root, &child);
+    let covered = expressions
+        .iter()
+        .chain(unsupported.iter())
+        .map(expression_variant_index)
+        .collect::<std::collections::BTreeSet<_>>();
+    assert_eq!(covered, (0..EXPRESSION_VARIANT_COUNT).collect());
+
+    for (index, expression) in expressions.into_iter().enumerate() {
+        root.borrow_mut()
+            .bindings
+            .insert(format!("binding-{}", index), RefCell::new(expression.into()));
+    }
+    let data = save(&component).unwrap();
+    let loaded = load(&data).unwrap();
+    assert_eq!(save(&loaded).unwrap(), data);
+    assert_eq!(loaded.root_element.borrow().bindings.len(), root.borrow().bindings.len());
+
+    // An expression that the passes should have removed fails the whole save
+    for expression in unsupported {
+        let variant = expression_variant_index(&expression);
+        root.borrow_mut().bindings.insert("unsupported".into(), RefCell::new(expression.into()));
+        assert!(
+            matches!(save(&component), Err(SaveError::UnsupportedExpression(_))),
+            "variant {} was saved",
+            variant
+        );
+    }
+}
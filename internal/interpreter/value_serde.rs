@@ -0,0 +1,202 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Implementation of the serde traits for [`Value`] and [`Struct`]
+
+use crate::api::{Struct, Value};
+use i_slint_compiler::langtype::Type as LangType;
+use i_slint_core::graphics::{Brush, Color, Image};
+use i_slint_core::model::{ModelRc, VecModel};
+use i_slint_core::SharedString;
+use serde::de::{DeserializeSeed, Error as _, MapAccess, SeqAccess, Visitor};
+use serde::ser::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+
+/// Numbers without fractional part are serialized as integers, colors and brushes like
+/// [`Brush`], images as the path they were loaded from, and enumeration values as their name.
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Void => serializer.serialize_unit(),
+            Value::Number(n)
+                if n.fract() == 0. && n.abs() < (1u64 << f64::MANTISSA_DIGITS) as f64 =>
+            {
+                serializer.serialize_i64(*n as i64)
+            }
+            Value::Number(n) => serializer.serialize_f64(*n),
+            Value::String(s) => serializer.serialize_str(s),
+            Value::Bool(b) => serializer.serialize_bool(*b),
+            Value::Image(image) => image.serialize(serializer),
+            Value::Model(model) => model.serialize(serializer),
+            Value::Struct(s) => s.serialize(serializer),
+            Value::Brush(brush) => brush.serialize(serializer),
+            Value::EnumerationValue(_, value) => serializer.serialize_str(value),
+            Value::PathData(_) | Value::EasingCurve(_) | Value::LayoutCache(_) => {
+                Err(S::Error::custom("this value cannot be serialized"))
+            }
+        }
+    }
+}
+
+/// Structs are serialized as a map with the fields sorted by name.
+impl Serialize for Struct {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter().collect::<BTreeMap<_, _>>())
+    }
+}
+
+/// Deserialize a value without knowing its type: sequences become models and maps become
+/// structs. Strings stay strings, even if they look like colors.
+///
+/// Use [`ComponentInstance::deserialize_property()`](crate::ComponentInstance::deserialize_property)
+/// to deserialize the value of a property following its declared type.
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(UntypedVisitor)
+    }
+}
+
+impl<'de> Deserialize<'de> for Struct {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(BTreeMap::<String, Value>::deserialize(deserializer)?.into_iter().collect())
+    }
+}
+
+struct UntypedVisitor;
+
+impl<'de> Visitor<'de> for UntypedVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a value")
+    }
+
+    fn visit_unit<E: serde::de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Void)
+    }
+    fn visit_none<E: serde::de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Void)
+    }
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        Value::deserialize(deserializer)
+    }
+    fn visit_bool<E: serde::de::Error>(self, v: bool) -> Result<Value, E> {
+        Ok(Value::Bool(v))
+    }
+    fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Value, E> {
+        Ok(Value::Number(v as f64))
+    }
+    fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Value, E> {
+        Ok(Value::Number(v as f64))
+    }
+    fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<Value, E> {
+        Ok(Value::Number(v))
+    }
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Value, E> {
+        Ok(Value::String(v.into()))
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut rows = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(row) = seq.next_element::<Value>()? {
+            rows.push(row);
+        }
+        Ok(Value::Model(ModelRc::new(VecModel::from(rows))))
+    }
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut s = Struct::default();
+        while let Some((name, value)) = map.next_entry::<String, Value>()? {
+            s.set_field(name, value);
+        }
+        Ok(Value::Struct(s))
+    }
+}
+
+/// Deserialize a value of the given type of the .slint language.
+pub(crate) struct TypedValue<'a>(pub &'a LangType);
+
+impl<'a, 'de> DeserializeSeed<'de> for TypedValue<'a> {
+    type Value = Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        Ok(match self.0 {
+            LangType::Float32
+            | LangType::Int32
+            | LangType::Duration
+            | LangType::Angle
+            | LangType::PhysicalLength
+            | LangType::LogicalLength
+            | LangType::Percent
+            | LangType::UnitProduct(_) => Value::Number(f64::deserialize(deserializer)?),
+            LangType::String => Value::String(SharedString::deserialize(deserializer)?),
+            LangType::Bool => Value::Bool(bool::deserialize(deserializer)?),
+            LangType::Color => Value::Brush(Brush::SolidColor(Color::deserialize(deserializer)?)),
+            LangType::Brush => Value::Brush(Brush::deserialize(deserializer)?),
+            LangType::Image => Value::Image(Image::deserialize(deserializer)?),
+            LangType::Enumeration(e) => {
+                let value = String::deserialize(deserializer)?;
+                if !e.values.contains(&value) {
+                    return Err(D::Error::custom(format_args!(
+                        "unknown value `{}` for enum {}, expected one of: {}",
+                        value,
+                        e.name,
+                        e.values.join(", ")
+                    )));
+                }
+                Value::EnumerationValue(e.name.clone(), value)
+            }
+            LangType::Array(row_type) => deserializer.deserialize_seq(ArrayVisitor(row_type))?,
+            LangType::Struct { .. } => deserializer.deserialize_map(StructVisitor(self.0))?,
+            _ => Value::deserialize(deserializer)?,
+        })
+    }
+}
+
+struct ArrayVisitor<'a>(&'a LangType);
+
+impl<'a, 'de> Visitor<'de> for ArrayVisitor<'a> {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "an array of {}", self.0)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut rows = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        while let Some(row) = seq.next_element_seed(TypedValue(self.0))? {
+            rows.push(row);
+        }
+        Ok(Value::Model(ModelRc::new(VecModel::from(rows))))
+    }
+}
+
+/// Missing fields keep their default value
+struct StructVisitor<'a>(&'a LangType);
+
+impl<'a, 'de> Visitor<'de> for StructVisitor<'a> {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "a {}", self.0)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let fields = match self.0 {
+            LangType::Struct { fields, .. } => fields,
+            _ => unreachable!(),
+        };
+        let mut s = match crate::eval::default_value_for_type(self.0) {
+            Value::Struct(s) => s,
+            _ => unreachable!(),
+        };
+        while let Some(name) = map.next_key::<String>()? {
+            let name = crate::api::normalize_identifier(&name).into_owned();
+            let ty = fields
+                .get(&name)
+                .ok_or_else(|| A::Error::custom(format_args!("unknown field `{}`", name)))?;
+            let value = map.next_value_seed(TypedValue(ty))?;
+            s.set_field(name, value);
+        }
+        Ok(Value::Struct(s))
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Tests that the textures compressed by the compiler are decoded to the same pixels by the
+//! run-time library.
+
+#[cfg(test)]
+use i_slint_compiler::embedded_resources::{EmbeddedResourcesKind, PixelFormat};
+
+/// Embeds the image as a compressed texture, and returns the format chosen by the compiler with
+/// the premultiplied pixels decoded by the run-time library
+#[cfg(test)]
+fn round_trip(name: &str, image: &image::RgbaImage) -> (PixelFormat, Vec<[u8; 4]>) {
+    use i_slint_core::graphics::{
+        euclid, ImageInner, IntSize, SharedImageBuffer, StaticTexture, StaticTextures,
+    };
+    use i_slint_core::slice::Slice;
+
+    let path = std::env::temp_dir().join(format!(
+        "slint-compressed-textures-{}-{}.png",
+        std::process::id(),
+        name
+    ));
+    image.save(&path).unwrap();
+    let source = format!(
+        "export Test := Window {{ Image {{ source: @image-url(\"{}\"); }} }}",
+        path.to_string_lossy().replace('\\', "/")
+    );
+
+    let mut config = i_slint_compiler::CompilerConfiguration::new(
+        i_slint_compiler::generator::OutputFormat::Llr,
+    );
+    config.embed_resources = i_slint_compiler::EmbedResourcesKind::EmbedTextures;
+    config.compress_textures = true;
+    let mut diag = i_slint_compiler::diagnostics::BuildDiagnostics::default();
+    let syntax_node = i_slint_compiler::parser::parse(
+        source,
+        Some(path.with_extension("slint").as_path()),
+        &mut diag,
+    );
+    let (doc, diag) =
+        spin_on::spin_on(i_slint_compiler::compile_syntax_node(syntax_node, diag, config));
+    std::fs::remove_file(&path).unwrap();
+    assert!(!diag.has_error(), "{:?}", diag.to_string_vec());
+
+    let resources = doc.root_component.embedded_file_resources.borrow();
+    // The default font is also embedded
+    let texture = resources
+        .values()
+        .find_map(|r| match &r.kind {
+            EmbeddedResourcesKind::TextureData(texture) => Some(texture.clone()),
+            _ => None,
+        })
+        .expect("the image wasn't embedded as a texture");
+    let format = match texture.format {
+        PixelFormat::Rgb => i_slint_core::graphics::PixelFormat::Rgb,
+        PixelFormat::Rgba => i_slint_core::graphics::PixelFormat::Rgba,
+        PixelFormat::RgbaPremultiplied => i_slint_core::graphics::PixelFormat::RgbaPremultiplied,
+        PixelFormat::AlphaMap(_) => panic!("the test images have more than one color"),
+        PixelFormat::Indexed8 => i_slint_core::graphics::PixelFormat::Indexed8,
+        PixelFormat::Rgb565Rle => i_slint_core::graphics::PixelFormat::Rgb565Rle,
+        PixelFormat::RgbaPremultipliedRle => {
+            i_slint_core::graphics::PixelFormat::RgbaPremultipliedRle
+        }
+    };
+    let rect = texture.rect;
+    let textures: &'static StaticTextures = Box::leak(Box::new(StaticTextures {
+        size: IntSize::new(texture.total_size.width, texture.total_size.height),
+        original_size: IntSize::new(texture.original_size.width, texture.original_size.height),
+        data: Slice::from_slice(Box::leak(texture.data.into_boxed_slice())),
+        textures: Slice::from_slice(Box::leak(
+            vec![StaticTexture {
+                rect: euclid::rect(rect.x(), rect.y(), rect.width() as _, rect.height() as _),
+                format,
+                color: i_slint_core::Color::from_argb_encoded(0),
+                index: 0,
+            }]
+            .into_boxed_slice(),
+        )),
+    }));
+    match ImageInner::StaticTextures(textures).render_to_buffer(None) {
+        Some(SharedImageBuffer::RGBA8Premultiplied(buffer)) => {
+            (texture.format, buffer.as_slice().iter().map(|p| [p.r, p.g, p.b, p.a]).collect())
+        }
+        _ => panic!("the texture wasn't decoded"),
+    }
+}
+
+/// The pixels of the image with the colors premultiplied by the alpha, like the compiler does
+#[cfg(test)]
+fn premultiplied(image: &image::RgbaImage) -> Vec<[u8; 4]> {
+    image
+        .pixels()
+        .map(|p| {
+            let [r, g, b, a] = p.0;
+            let m = |c: u8| (c as u32 * a as u32 / 255) as u8;
+            [m(r), m(g), m(b), a]
+        })
+        .collect()
+}
+
+#[test]
+fn indexed() {
+    let colors = [[255, 0, 0, 255], [0, 0, 255, 128], [10, 20, 30, 0]];
+    let image =
+        image::RgbaImage::from_fn(16, 16, |x, y| image::Rgba(colors[(x + 2 * y) as usize % 3]));
+    let (format, pixels) = round_trip("indexed", &image);
+    assert!(matches!(format, PixelFormat::Indexed8));
+    assert_eq!(pixels, premultiplied(&image));
+}
+
+#[test]
+fn rgb565_rle() {
+    // More than 256 colors, that don't lose precision in 16 bits
+    let expand = |v: u32, bits: u32| (v << (8 - bits) | v >> (2 * bits - 8)) as u8;
+    let image = image::RgbaImage::from_fn(300, 2, |x, y| {
+        image::Rgba([expand(x % 32, 5), expand(x / 32 + y * 10, 6), 0, 255])
+    });
+    let (format, pixels) = round_trip("rgb565", &image);
+    assert!(matches!(format, PixelFormat::Rgb565Rle));
+    assert_eq!(pixels, premultiplied(&image));
+}
+
+#[test]
+fn rgba_premultiplied_rle() {
+    let image = image::RgbaImage::from_fn(400, 4, |x, y| {
+        image::Rgba(match y {
+            // A transparent line, which the compiler removes from the texture
+            0 => [0, 0, 0, 0],
+            // More than 256 colors
+            1 => [(x % 256) as u8, (x / 256) as u8, 0, 255],
+            // Short runs
+            2 => [0, 0, 255, (x / 3) as u8],
+            // Runs longer than the maximum run length
+            _ if x % 130 == 0 => [64, 128, 191, 4],
+            _ => [0, 0, 0, 255],
+        })
+    });
+    let (format, pixels) = round_trip("rgba", &image);
+    assert!(matches!(format, PixelFormat::RgbaPremultipliedRle));
+    assert_eq!(pixels, premultiplied(&image));
+}
+
+fn main() {
+    println!("Nothing to see here, please run me through cargo test :)");
+}
@@ -2,14 +2,14 @@
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
 use clap::Parser;
-use i_slint_compiler::diagnostics::BuildDiagnostics;
+use i_slint_compiler::diagnostics::{BuildDiagnostics, DiagnosticCode, DiagnosticsFormat};
 use i_slint_compiler::*;
 use std::io::Write;
 
 #[derive(Parser)]
 #[clap(author, version, about, long_about = None)]
 struct Cli {
-    /// Set output format
+    /// Set output format (cpp, rust, wasm-bundle, typescript or llr)
     #[clap(short = 'f', long = "format", default_value = "cpp", action)]
     format: generator::OutputFormat,
 
@@ -17,6 +17,16 @@ struct Cli {
     #[clap(short = 'I', name = "include path", number_of_values = 1, action)]
     include_paths: Vec<std::path::PathBuf>,
 
+    /// Library path for the imports starting with '@name', as 'name=path'
+    #[clap(
+        short = 'L',
+        name = "library path",
+        number_of_values = 1,
+        value_parser = parse_library_path,
+        action
+    )]
+    library_paths: Vec<(String, std::path::PathBuf)>,
+
     /// Path to .slint file ('-' for stdin)
     #[clap(name = "file", action)]
     path: std::path::PathBuf,
@@ -29,38 +39,151 @@ struct Cli {
     #[clap(name = "dependency file", long = "depfile", number_of_values = 1, action)]
     depfile: Option<std::path::PathBuf>,
 
-    /// Sets the output file ('-' for stdout)
+    /// Sets the output file ('-' for stdout). With the wasm-bundle format, a path without the '.rs'
+    /// extension is a directory where the crate is generated, to be built with 'wasm-pack'
     #[clap(name = "file to generate", short = 'o', default_value = "-", action)]
     output: std::path::PathBuf,
+
+    /// With the wasm-bundle format, the directory of the 'slint' crate that the generated crate
+    /// depends on, such as 'api/rs/slint' in a checkout of the Slint repository
+    #[clap(long, name = "slint crate path", conflicts_with = "slint git url", action)]
+    slint_path: Option<std::path::PathBuf>,
+
+    /// With the wasm-bundle format, the git repository of the 'slint' crate that the generated
+    /// crate depends on
+    #[clap(long, name = "slint git url", action)]
+    slint_git: Option<String>,
+
+    /// With the wasm-bundle format, build the generated crate with 'wasm-pack build --target web'
+    #[clap(long, action)]
+    build: bool,
+
+    /// Set the format of the diagnostics: human (printed on stderr), json or sarif
+    #[clap(long, default_value = "human", action)]
+    diagnostics_format: DiagnosticsFormat,
+
+    /// Write the json or sarif diagnostics to this file instead of stderr ('-' for stdout, which
+    /// can't be used when the generated code is also written to stdout)
+    #[clap(long, name = "diagnostics file", action)]
+    diagnostics_file: Option<std::path::PathBuf>,
+
+    /// Print a report of the components and of the embedded resources (human or json).
+    /// Unless '--report-file' is given, the report is printed on stdout, and the code is then
+    /// not generated if it would also go to stdout
+    #[clap(
+        long,
+        name = "report format",
+        min_values = 0,
+        require_equals = true,
+        default_missing_value = "human",
+        action
+    )]
+    report: Option<report::ReportFormat>,
+
+    /// Write the report to this file instead of stdout
+    #[clap(long, name = "report file", requires = "report format", action)]
+    report_file: Option<std::path::PathBuf>,
+
+    /// Do not report the warnings with this code (such as 'deprecated-property')
+    #[clap(long = "allow-warning", name = "warning code", number_of_values = 1, action)]
+    allowed_warnings: Vec<DiagnosticCode>,
+}
+
+/// Builds the crate generated in `directory` with `wasm-pack`, which writes the WebAssembly module
+/// and the JavaScript glue in the `pkg` sub-directory
+fn build_wasm_bundle(directory: &std::path::Path) {
+    let status = std::process::Command::new("wasm-pack")
+        .args(["build", "--target", "web", "--release"])
+        .arg(directory)
+        .status();
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(_) => std::process::exit(-1),
+        Err(err) => {
+            eprintln!(
+                "Could not run wasm-pack: {}. The crate generated in {} can be built with 'wasm-pack build --target web'",
+                err,
+                directory.display()
+            );
+            std::process::exit(-1);
+        }
+    }
 }
 
 fn main() -> std::io::Result<()> {
     proc_macro2::fallback::force(); // avoid a abort if panic=abort is set
     let args = Cli::parse();
+    if args.diagnostics_format != DiagnosticsFormat::Human
+        && args.diagnostics_file.as_deref() == Some(std::path::Path::new("-"))
+        && args.output == std::path::Path::new("-")
+    {
+        eprintln!("The diagnostics and the generated code can't both be written to stdout. Use --output or --diagnostics-file to write one of them to a file");
+        std::process::exit(-1);
+    }
     let mut diag = BuildDiagnostics::default();
     let syntax_node = parser::parse_file(&args.path, &mut diag);
     //println!("{:#?}", syntax_node);
     if diag.has_error() {
-        diag.print();
+        print_diagnostics(diag, &args)?;
         std::process::exit(-1);
     }
     let mut compiler_config = CompilerConfiguration::new(args.format);
-    compiler_config.include_paths = args.include_paths;
-    if let Some(style) = args.style {
-        compiler_config.style = Some(style);
+    compiler_config.include_paths = args.include_paths.clone();
+    compiler_config.library_paths = args.library_paths.iter().cloned().collect();
+    if let Some(style) = &args.style {
+        compiler_config.style = Some(style.clone());
     }
     let syntax_node = syntax_node.expect("diags contained no compilation errors");
-    let (doc, diag) = spin_on::spin_on(compile_syntax_node(syntax_node, diag, compiler_config));
+    let (doc, mut diag) = spin_on::spin_on(compile_syntax_node(syntax_node, diag, compiler_config));
 
-    let diag = diag.check_and_exit_on_error();
+    diag.silence_warnings(&args.allowed_warnings);
+    if diag.has_error() {
+        print_diagnostics(diag, &args)?;
+        std::process::exit(-1);
+    }
+
+    if let Some(report_format) = args.report {
+        let report = report::size_report(&doc);
+        match &args.report_file {
+            Some(path) => report.write_as(report_format, &mut std::fs::File::create(path)?)?,
+            None => report.write_as(report_format, &mut std::io::stdout())?,
+        }
+    }
 
-    if args.output == std::path::Path::new("-") {
-        generator::generate(args.format, &mut std::io::stdout(), &doc)?;
+    if args.format == generator::OutputFormat::WasmBundle
+        && args.output != std::path::Path::new("-")
+        && args.output.extension().map_or(true, |ext| ext != "rs")
+    {
+        let slint_dependency = match (&args.slint_path, &args.slint_git) {
+            (Some(path), _) => match std::fs::canonicalize(path) {
+                Ok(path) => generator::SlintDependency::Path(path),
+                Err(err) => {
+                    eprintln!("Could not find the 'slint' crate in {}: {}", path.display(), err);
+                    std::process::exit(-1);
+                }
+            },
+            (None, Some(url)) => generator::SlintDependency::Git(url.clone()),
+            (None, None) => {
+                eprintln!(
+                    "The crate generated with the wasm-bundle format needs the 'serde' feature of the 'slint' crate, which is not released yet. Use '--slint-path' or '--slint-git' to choose the 'slint' crate it depends on"
+                );
+                std::process::exit(-1);
+            }
+        };
+        generator::generate_wasm_bundle_crate(&doc, &args.output, &slint_dependency)?;
+        if args.build {
+            build_wasm_bundle(&args.output);
+        }
+    } else if args.output == std::path::Path::new("-") {
+        // Unless the report was already printed on stdout
+        if args.report.is_none() || args.report_file.is_some() {
+            generator::generate(args.format, &mut std::io::stdout(), &doc)?;
+        }
     } else {
         generator::generate(args.format, &mut std::fs::File::create(&args.output)?, &doc)?;
     }
 
-    if let Some(depfile) = args.depfile {
+    if let Some(depfile) = &args.depfile {
         let mut f = std::fs::File::create(depfile)?;
         write!(f, "{}:", args.output.display())?;
         for x in &diag.all_loaded_files {
@@ -74,6 +197,15 @@ fn main() -> std::io::Result<()> {
 
         writeln!(f)?;
     }
-    diag.print_warnings_and_exit_on_error();
-    Ok(())
+    print_diagnostics(diag, &args)
+}
+
+fn print_diagnostics(diag: BuildDiagnostics, args: &Cli) -> std::io::Result<()> {
+    match &args.diagnostics_file {
+        None => diag.print_as(args.diagnostics_format, &mut std::io::stderr()),
+        Some(path) if path == std::path::Path::new("-") => {
+            diag.print_as(args.diagnostics_format, &mut std::io::stdout())
+        }
+        Some(path) => diag.print_as(args.diagnostics_format, &mut std::fs::File::create(path)?),
+    }
 }
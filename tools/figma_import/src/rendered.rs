@@ -204,6 +204,17 @@ fn render_text(
     writeln!(rc, "horizontal-alignment: {};", font.textAlignHorizontal.to_ascii_lowercase())?;
     writeln!(rc, "vertical-alignment: {};", font.textAlignVertical.to_ascii_lowercase())?;
     writeln!(rc, "letter-spacing: {}px;", font.letterSpacing)?;
+    if font.italic {
+        writeln!(rc, "font-italic: true;")?;
+    }
+    match font.textDecoration.as_str() {
+        "UNDERLINE" => writeln!(rc, "text-decoration: underline;")?,
+        "STRIKETHROUGH" => writeln!(rc, "text-decoration: line-through;")?,
+        _ => {}
+    }
+    if font.lineHeightPx > 0. {
+        writeln!(rc, "line-height: {}px;", font.lineHeightPx)?;
+    }
     for p in vector.fills.iter() {
         handle_paint(p, rc, "color")?;
     }
@@ -37,6 +37,16 @@ struct Cli {
     )]
     include_paths: Vec<std::path::PathBuf>,
 
+    /// Library path for the imports starting with '@name', as 'name=path'
+    #[clap(
+        short = 'L',
+        name = "library path",
+        number_of_values = 1,
+        value_parser = i_slint_compiler::parse_library_path,
+        action
+    )]
+    library_paths: Vec<(String, std::path::PathBuf)>,
+
     /// The style name for the preview ('native' or 'fluent')
     #[clap(long, name = "style name", default_value_t, action)]
     style: String,
@@ -161,6 +171,7 @@ fn main_loop(connection: &Connection, params: serde_json::Value) -> Result<(), E
     compiler_config.style =
         Some(if cli_args.style.is_empty() { "fluent".into() } else { cli_args.style });
     compiler_config.include_paths = cli_args.include_paths;
+    compiler_config.library_paths = cli_args.library_paths.into_iter().collect();
 
     let mut document_cache = DocumentCache::new(compiler_config);
     for msg in &connection.receiver {
@@ -209,6 +209,7 @@ async fn reload_preview(
             builder.set_style(cli_args.style)
         };
         builder.set_include_paths(cli_args.include_paths);
+        builder.set_library_paths(cli_args.library_paths.into_iter().collect());
     }
 
     builder.set_file_loader(|path| {
@@ -237,8 +238,7 @@ struct PreviewState {
         PREVIEW_STATE.with(|preview_state| {
             let mut preview_state = preview_state.borrow_mut();
             if let Some(handle) = preview_state.handle.take() {
-                let window = handle.window();
-                let handle = compiled.create_with_existing_window(window);
+                let (handle, _) = handle.reload(&compiled);
                 match post_load_behavior {
                     PostLoadBehavior::ShowAfterLoad => handle.show(),
                     PostLoadBehavior::DoNothing => {}
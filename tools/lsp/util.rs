@@ -1,13 +1,15 @@
 // Copyright © SixtyFPS GmbH <info@slint-ui.com>
 // SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
 
-use i_slint_compiler::diagnostics::{DiagnosticLevel, Spanned};
+use i_slint_compiler::diagnostics::{DiagnosticCode, DiagnosticLevel, Spanned};
 use i_slint_compiler::langtype::Type;
 use i_slint_compiler::lookup::LookupCtx;
 use i_slint_compiler::object_tree;
 use i_slint_compiler::parser::{syntax_nodes, SyntaxKind, SyntaxNode};
 use i_slint_compiler::typeregister::TypeRegister;
 
+#[cfg(target_arch = "wasm32")]
+use crate::wasm_prelude::*;
 use crate::DocumentCache;
 
 /// Given a node within an element, return the Type for the Element under that node.
@@ -126,15 +128,41 @@ fn lookup_expression_context(mut n: SyntaxNode) -> Option<(syntax_nodes::Element
 }
 
 pub fn to_lsp_diag(d: &i_slint_compiler::diagnostics::Diagnostic) -> lsp_types::Diagnostic {
-    lsp_types::Diagnostic::new(
+    let mut diag = lsp_types::Diagnostic::new(
         to_range(d.line_column()),
         Some(to_lsp_diag_level(d.level())),
-        None,
+        Some(lsp_types::NumberOrString::String(d.code().as_str().into())),
         None,
         d.message().to_owned(),
+        Some(
+            d.notes()
+                .iter()
+                .filter_map(|n| {
+                    Some(lsp_types::DiagnosticRelatedInformation {
+                        location: lsp_types::Location::new(
+                            lsp_types::Url::from_file_path(n.source_file()?).ok()?,
+                            to_range(n.line_column()),
+                        ),
+                        message: n.message().to_owned(),
+                    })
+                })
+                .collect(),
+        )
+        .filter(|notes: &Vec<_>| !notes.is_empty()),
         None,
-        None,
-    )
+    );
+    // Lets the editors render the unused code faded out
+    if matches!(
+        d.code(),
+        DiagnosticCode::UnusedProperty
+            | DiagnosticCode::UnusedCallback
+            | DiagnosticCode::UnusedId
+            | DiagnosticCode::UnusedImport
+            | DiagnosticCode::UnusedComponent
+    ) {
+        diag.tags = Some(vec![lsp_types::DiagnosticTag::UNNECESSARY]);
+    }
+    diag
 }
 
 fn to_range(span: (usize, usize)) -> lsp_types::Range {
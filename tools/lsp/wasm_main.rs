@@ -143,10 +143,25 @@ pub fn create(
 ) -> Result<SlintServer, JsError> {
     console_error_panic_hook::set_once();
 
-    let init_param = init_param.into_serde()?;
+    let init_param: InitializeParams = init_param.into_serde()?;
 
     let mut compiler_config =
         CompilerConfiguration::new(i_slint_compiler::generator::OutputFormat::Interpreter);
+    // There is no command line in the browser: the library paths are passed by the client
+    // in the `libraryPaths` initialization option, and are loaded with `load_file` like the other imports.
+    if let Some(library_paths) = init_param
+        .initialization_options
+        .as_ref()
+        .and_then(|options| options.get("libraryPaths"))
+        .and_then(|paths| paths.as_object())
+    {
+        compiler_config.library_paths = library_paths
+            .iter()
+            .filter_map(|(name, path)| {
+                Some((name.trim_start_matches('@').to_string(), path.as_str()?.into()))
+            })
+            .collect();
+    }
     compiler_config.open_import_fallback = Some(Rc::new(move |path| {
         let load_file = load_file.clone();
         Box::pin(async move { Some(self::load_file(path, &load_file).await) })
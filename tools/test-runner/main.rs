@@ -0,0 +1,342 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+#![doc = include_str!("README.md")]
+
+use clap::Parser;
+use slint_interpreter::introspection::ElementHandle;
+use slint_interpreter::{ComponentHandle, ComponentInstance, Value};
+use std::path::{Path, PathBuf};
+
+#[derive(clap::Parser)]
+#[clap(author, version, about, long_about = None)]
+struct Cli {
+    #[clap(
+        short = 'I',
+        name = "include path for other .slint files",
+        number_of_values = 1,
+        action
+    )]
+    include_paths: Vec<PathBuf>,
+
+    /// The style name ('native' or 'fluent')
+    #[clap(long, name = "style name", action)]
+    style: Option<String>,
+
+    /// The .slint file to test
+    #[clap(name = "path to .slint file", action)]
+    path: PathBuf,
+
+    /// The test scripts to run
+    #[clap(name = "path to test script", required = true, action)]
+    scripts: Vec<PathBuf>,
+}
+
+fn main() {
+    let args = Cli::parse();
+    i_slint_backend_testing::init();
+
+    let mut compiler = slint_interpreter::ComponentCompiler::default();
+    compiler.set_include_paths(args.include_paths.clone());
+    compiler.set_style(args.style.clone().unwrap_or_else(|| "fluent".into()));
+    let definition = spin_on::spin_on(compiler.build_from_path(&args.path));
+    slint_interpreter::print_diagnostics(compiler.diagnostics());
+    let definition = match definition {
+        Some(definition) => definition,
+        None => std::process::exit(-1),
+    };
+
+    let mut failures = 0;
+    for script_path in &args.scripts {
+        let script = match std::fs::read_to_string(script_path) {
+            Ok(script) => script,
+            Err(e) => {
+                eprintln!("Error: cannot read {}: {}", script_path.display(), e);
+                failures += 1;
+                continue;
+            }
+        };
+        // Each script starts with a fresh instance
+        let instance = definition.create();
+        let base_dir = script_path.parent().unwrap_or_else(|| Path::new("."));
+        for (line_number, line) in script.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Err(e) = run_step(&instance, line, base_dir) {
+                eprintln!("{}:{}: {}", script_path.display(), line_number + 1, e);
+                failures += 1;
+            }
+        }
+    }
+
+    if failures > 0 {
+        eprintln!("{} step(s) failed", failures);
+        std::process::exit(1);
+    }
+}
+
+/// Run one line of a test script
+fn run_step(instance: &ComponentInstance, line: &str, base_dir: &Path) -> Result<(), String> {
+    let (command, argument) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let argument = argument.trim();
+    match command {
+        "click" => {
+            let element = find_element(instance, argument)?;
+            let geometry = element.geometry();
+            let center = geometry.center();
+            slint_interpreter::testing::send_mouse_click(instance, center.x, center.y);
+            Ok(())
+        }
+        "type" => {
+            // The text can be given as a JSON string, to type special keys such as "\n"
+            let text = if argument.starts_with('"') {
+                serde_json::from_str::<String>(argument).map_err(|e| e.to_string())?
+            } else {
+                argument.to_string()
+            };
+            slint_interpreter::testing::send_keyboard_string_sequence(instance, text.into());
+            Ok(())
+        }
+        "wait" => {
+            i_slint_backend_testing::mock_elapsed_time(parse_duration(argument)?);
+            Ok(())
+        }
+        "set" => {
+            let (property, json) = split_property_and_value(argument)?;
+            let json: serde_json::Value = serde_json::from_str(json).map_err(|e| e.to_string())?;
+            match property.split_once('.') {
+                Some((global, property)) => {
+                    instance.deserialize_global_property(global, property, &json)
+                }
+                None => instance.deserialize_property(property, &json),
+            }
+            .map_err(|e| format!("cannot set {}: {}", property, e))
+        }
+        "assert" => {
+            let (property, json) = split_property_and_value(argument)?;
+            let expected: serde_json::Value =
+                serde_json::from_str(json).map_err(|e| e.to_string())?;
+            let actual = get_value(instance, property)?;
+            let actual = serde_json::to_value(&actual).map_err(|e| e.to_string())?;
+            if json_matches(&actual, &expected) {
+                Ok(())
+            } else {
+                Err(format!("assertion failed: {} is {}, expected {}", property, actual, expected))
+            }
+        }
+        "screenshot" => {
+            let buffer = i_slint_backend_testing::render_window(instance.window());
+            let path = base_dir.join(argument);
+            image::save_buffer(
+                &path,
+                buffer.as_bytes(),
+                buffer.width(),
+                buffer.height(),
+                image::ColorType::Rgb8,
+            )
+            .map_err(|e| format!("cannot save {}: {}", path.display(), e))
+        }
+        _ => Err(format!("unknown command '{}'", command)),
+    }
+}
+
+fn find_element(instance: &ComponentInstance, id: &str) -> Result<ElementHandle, String> {
+    ElementHandle::root_of(instance.window())
+        .and_then(|root| root.find_by_id(id))
+        .ok_or_else(|| format!("no element with id '{}'", id))
+}
+
+/// Returns the value of `property` (a public property), `Global.property` (a property of an
+/// exported global) or `element-id.property` (a property of the builtin element with that id)
+fn get_value(instance: &ComponentInstance, property: &str) -> Result<Value, String> {
+    match property.split_once('.') {
+        None => instance.get_property(property).map_err(|e| format!("{}: {}", property, e)),
+        Some((global, name)) if instance.definition().globals().any(|g| g == global) => {
+            instance.get_global_property(global, name).map_err(|e| format!("{}: {}", property, e))
+        }
+        Some((id, name)) => find_element(instance, id)?
+            .get_property::<Value>(name)
+            .ok_or_else(|| format!("element '{}' has no property '{}'", id, name)),
+    }
+}
+
+fn split_property_and_value(argument: &str) -> Result<(&str, &str), String> {
+    argument
+        .split_once(char::is_whitespace)
+        .map(|(property, value)| (property, value.trim()))
+        .ok_or_else(|| "expected a property name followed by a JSON value".to_string())
+}
+
+/// Parses `300ms`, `2s`, or a number of milliseconds
+fn parse_duration(argument: &str) -> Result<u64, String> {
+    let (number, factor) = if let Some(ms) = argument.strip_suffix("ms") {
+        (ms, 1.)
+    } else if let Some(s) = argument.strip_suffix('s') {
+        (s, 1000.)
+    } else {
+        (argument, 1.)
+    };
+    number
+        .trim()
+        .parse::<f64>()
+        .map(|n| (n * factor) as u64)
+        .map_err(|_| format!("invalid duration '{}'", argument))
+}
+
+/// Compares JSON values, with numbers compared with a tolerance, and colors compared by value
+/// so that `"#f00"` matches `"#ff0000ff"`.
+fn json_matches(actual: &serde_json::Value, expected: &serde_json::Value) -> bool {
+    use serde_json::Value as Json;
+    match (actual, expected) {
+        (Json::Number(a), Json::Number(b)) => {
+            (a.as_f64().unwrap_or(f64::NAN) - b.as_f64().unwrap_or(f64::NAN)).abs() < 0.001
+        }
+        (Json::String(a), Json::String(b)) if a.starts_with('#') && b.starts_with('#') => {
+            let color = |s: &Json| serde_json::from_value::<i_slint_core::Color>(s.clone()).ok();
+            color(actual).zip(color(expected)).map_or(a == b, |(a, b)| a == b)
+        }
+        (Json::Array(a), Json::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| json_matches(a, b))
+        }
+        (Json::Object(a), Json::Object(b)) => {
+            // Fields that are not in the expected value are not checked
+            b.iter().all(|(k, b)| a.get(k).map_or(false, |a| json_matches(a, b)))
+        }
+        _ => actual == expected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("300ms"), Ok(300));
+        assert_eq!(parse_duration("2s"), Ok(2000));
+        assert_eq!(parse_duration("1.5s"), Ok(1500));
+        assert_eq!(parse_duration("42"), Ok(42));
+        assert_eq!(parse_duration(" 10 ms"), Ok(10));
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("ms").is_err());
+        assert!(parse_duration("fast").is_err());
+        assert!(parse_duration("3min").is_err());
+    }
+
+    #[test]
+    fn test_split_property_and_value() {
+        assert_eq!(split_property_and_value("foo  \"bar baz\""), Ok(("foo", "\"bar baz\"")));
+        assert_eq!(split_property_and_value("Global.prop 42"), Ok(("Global.prop", "42")));
+        assert!(split_property_and_value("foo").is_err());
+    }
+
+    #[test]
+    fn test_json_matches() {
+        // Numbers are compared with a tolerance
+        assert!(json_matches(&json!(1.0001), &json!(1)));
+        assert!(!json_matches(&json!(1.1), &json!(1)));
+        // Colors are compared by value
+        assert!(json_matches(&json!("#ff0000ff"), &json!("#f00")));
+        assert!(!json_matches(&json!("#ff0000ff"), &json!("#0f0")));
+        assert!(json_matches(&json!("#hello"), &json!("#hello")));
+        // Mismatched types
+        assert!(!json_matches(&json!(1), &json!("1")));
+        assert!(!json_matches(&json!("true"), &json!(true)));
+        assert!(!json_matches(&json!([1]), &json!(1)));
+        assert!(!json_matches(&json!({ "a": 1 }), &json!([1])));
+        assert!(!json_matches(&json!(null), &json!(0)));
+        // Arrays must have the same length
+        assert!(json_matches(&json!([1, 2]), &json!([1, 2])));
+        assert!(!json_matches(&json!([1, 2]), &json!([1])));
+        // Only the expected fields of structs are checked
+        assert!(json_matches(&json!({ "a": 1, "b": "x" }), &json!({ "a": 1 })));
+        assert!(!json_matches(&json!({ "a": 1 }), &json!({ "a": 1, "b": "x" })));
+        assert!(!json_matches(&json!({ "a": 2 }), &json!({ "a": 1 })));
+        assert!(json_matches(&json!({ "a": { "b": [1.00001] } }), &json!({ "a": { "b": [1] } })));
+    }
+
+    fn create_instance(source: &str) -> ComponentInstance {
+        i_slint_backend_testing::init();
+        let mut compiler = slint_interpreter::ComponentCompiler::default();
+        compiler.set_style("fluent".into());
+        let definition =
+            spin_on::spin_on(compiler.build_from_source(source.into(), Default::default()));
+        assert!(compiler.diagnostics().is_empty(), "{:?}", compiler.diagnostics());
+        definition.unwrap().create()
+    }
+
+    #[test]
+    fn test_run_step() {
+        let instance = create_instance(
+            r#"
+    export global Settings := {
+        property <int> level: 1;
+    }
+    export Demo := Window {
+        width: 100px;
+        height: 100px;
+        property <string> name: "foo";
+        property <int> clicks;
+        property <{ a: int, b: string }> pair: { a: 1, b: "x" };
+        label := Text { text: "Hello"; }
+        area := TouchArea {
+            x: 10px;
+            y: 10px;
+            width: 20px;
+            height: 20px;
+            clicked => { clicks += 1; }
+        }
+    }"#,
+        );
+        let run = |line| run_step(&instance, line, Path::new("."));
+
+        assert_eq!(run("assert name \"foo\""), Ok(()));
+        assert!(run("assert name \"bar\"").is_err());
+        assert!(run("assert name 42").is_err());
+        assert_eq!(run("set name \"bar\""), Ok(()));
+        assert_eq!(run("assert name \"bar\""), Ok(()));
+        assert!(run("set name").is_err());
+        assert!(run("set name not-json").is_err());
+        assert!(run("set no-such-property 1").is_err());
+
+        assert_eq!(run("assert pair {\"a\": 1}"), Ok(()));
+        assert!(run("assert pair {\"c\": 1}").is_err());
+
+        assert_eq!(run("assert Settings.level 1"), Ok(()));
+        assert_eq!(run("set Settings.level 3"), Ok(()));
+        assert_eq!(run("assert Settings.level 3"), Ok(()));
+        assert!(run("set Settings.no-such-property 3").is_err());
+
+        assert_eq!(run("assert label.text \"Hello\""), Ok(()));
+        assert!(run("assert label.no-such-property 1").is_err());
+        assert!(run("assert no-such-id.text \"Hello\"").is_err());
+
+        assert_eq!(run("click area"), Ok(()));
+        assert_eq!(run("assert clicks 1"), Ok(()));
+        assert!(run("click no-such-id").is_err());
+
+        assert_eq!(run("wait 100ms"), Ok(()));
+        assert!(run("wait soon").is_err());
+        assert!(run("jump area").is_err());
+    }
+
+    #[test]
+    fn test_example_script() {
+        let base_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples");
+        i_slint_backend_testing::init();
+        let mut compiler = slint_interpreter::ComponentCompiler::default();
+        compiler.set_style("fluent".into());
+        let definition = spin_on::spin_on(compiler.build_from_path(base_dir.join("login.slint")));
+        assert!(compiler.diagnostics().is_empty(), "{:?}", compiler.diagnostics());
+        let instance = definition.unwrap().create();
+        let script = std::fs::read_to_string(base_dir.join("login.txt")).unwrap();
+        for line in script.lines().map(str::trim) {
+            if !line.is_empty() && !line.starts_with('#') {
+                assert_eq!(run_step(&instance, line, &base_dir), Ok(()), "{}", line);
+            }
+        }
+    }
+}
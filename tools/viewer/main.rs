@@ -3,9 +3,7 @@
 
 #![doc = include_str!("README.md")]
 
-use i_slint_core::model::{Model, ModelRc};
-use i_slint_core::SharedVector;
-use slint_interpreter::{ComponentHandle, ComponentInstance, SharedString, Value};
+use slint_interpreter::{ComponentHandle, ComponentInstance, Value};
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::atomic::{AtomicU32, Ordering};
@@ -14,6 +12,8 @@
 
 use clap::Parser;
 
+mod screenshot;
+
 type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
 #[derive(Clone, clap::Parser)]
@@ -27,6 +27,16 @@ struct Cli {
     )]
     include_paths: Vec<std::path::PathBuf>,
 
+    /// Library path for the imports starting with '@name', as 'name=path'
+    #[clap(
+        short = 'L',
+        name = "library path",
+        number_of_values = 1,
+        value_parser = i_slint_compiler::parse_library_path,
+        action
+    )]
+    library_paths: Vec<(String, std::path::PathBuf)>,
+
     /// The .slint file to load ('-' for stdin)
     #[clap(name = "path to .slint file", action)]
     path: std::path::PathBuf,
@@ -57,6 +67,33 @@ struct Cli {
     /// and so on.
     #[clap(long, value_names(&["callback", "handler"]), number_of_values = 2, action)]
     on: Vec<String>,
+
+    /// Render the component into a PNG file with the software renderer, instead of showing
+    /// it in a window
+    #[clap(long, name = "png file", action)]
+    screenshot: Option<std::path::PathBuf>,
+
+    /// The size of the screenshot in logical pixels, such as 800x600.
+    /// Defaults to the preferred size of the component
+    #[clap(long, name = "size", value_parser = screenshot::parse_size)]
+    size: Option<i_slint_core::api::LogicalSize>,
+
+    /// The scale factor of the screenshot
+    #[clap(long, name = "scale factor", action)]
+    scale_factor: Option<f32>,
+
+    /// Advance the time by this many milliseconds before taking the screenshot,
+    /// so that animations and timers have run
+    #[clap(long, name = "milliseconds", default_value = "0", action)]
+    time: u64,
+
+    /// Set the format of the diagnostics: human (printed on stderr), json or sarif (printed on stdout)
+    #[clap(long, default_value = "human", action)]
+    diagnostics_format: slint_interpreter::DiagnosticsFormat,
+
+    /// Do not report the warnings with this code (such as 'deprecated-property')
+    #[clap(long = "allow-warning", name = "warning code", number_of_values = 1, action)]
+    allowed_warnings: Vec<slint_interpreter::DiagnosticCode>,
 }
 
 thread_local! {static CURRENT_INSTANCE: std::cell::RefCell<Option<ComponentInstance>> = Default::default();}
@@ -71,15 +108,28 @@ fn main() -> Result<()> {
         std::process::exit(-1);
     }
 
+    if args.auto_reload && args.screenshot.is_some() {
+        eprintln!("Cannot pass both --auto-reload and --screenshot");
+        std::process::exit(-1);
+    }
+
     if let Some(backend) = &args.backend {
         std::env::set_var("SLINT_BACKEND", backend);
     }
 
+    // The headless platform must be set before anything initializes the backend
+    if args.screenshot.is_some() {
+        if let Err(err) = screenshot::init() {
+            eprintln!("{}", err);
+            std::process::exit(-1);
+        }
+    }
+
     let fswatcher = if args.auto_reload { Some(start_fswatch_thread(args.clone())?) } else { None };
     let mut compiler = init_compiler(&args, fswatcher);
 
-    let c = spin_on::spin_on(compiler.build_from_path(args.path));
-    slint_interpreter::print_diagnostics(compiler.diagnostics());
+    let c = spin_on::spin_on(compiler.build_from_path(&args.path));
+    print_diagnostics(&args, compiler.diagnostics());
 
     let c = match c {
         Some(c) => c,
@@ -87,7 +137,9 @@ fn main() -> Result<()> {
     };
 
     let component = c.create();
-    init_dialog(&component);
+    if args.screenshot.is_none() {
+        init_dialog(&component);
+    }
 
     if let Some(data_path) = args.load_data {
         load_data(&component, &data_path)?;
@@ -98,35 +150,25 @@ fn main() -> Result<()> {
         CURRENT_INSTANCE.with(|current| current.replace(Some(component.clone_strong())));
     }
 
-    component.run();
+    match &args.screenshot {
+        Some(path) => screenshot::save(
+            &component,
+            path,
+            args.size,
+            args.scale_factor,
+            std::time::Duration::from_millis(args.time),
+        )?,
+        None => component.run(),
+    }
 
     if let Some(data_path) = args.save_data {
         let mut obj = serde_json::Map::new();
         for (name, _) in c.properties() {
-            fn to_json(val: slint_interpreter::Value) -> Option<serde_json::Value> {
-                match val {
-                    slint_interpreter::Value::Number(x) => Some(x.into()),
-                    slint_interpreter::Value::String(x) => Some(x.as_str().into()),
-                    slint_interpreter::Value::Bool(x) => Some(x.into()),
-                    slint_interpreter::Value::Model(model) => {
-                        let mut res = Vec::with_capacity(model.row_count());
-                        for i in 0..model.row_count() {
-                            res.push(to_json(model.row_data(i).unwrap())?);
-                        }
-                        Some(serde_json::Value::Array(res))
-                    }
-                    slint_interpreter::Value::Struct(st) => {
-                        let mut obj = serde_json::Map::new();
-                        for (k, v) in st.iter() {
-                            obj.insert(k.into(), to_json(v.clone())?);
-                        }
-                        Some(obj.into())
-                    }
-                    _ => None,
+            match serde_json::to_value(component.get_property(&name).unwrap()) {
+                Ok(v) => {
+                    obj.insert(name, v);
                 }
-            }
-            if let Some(v) = to_json(component.get_property(&name).unwrap()) {
-                obj.insert(name, v);
+                Err(e) => eprintln!("Warning: cannot save property '{}': {}", name, e),
             }
         }
         if data_path == std::path::Path::new("-") {
@@ -139,14 +181,37 @@ fn to_json(val: slint_interpreter::Value) -> Option<serde_json::Value> {
     std::process::exit(EXIT_CODE.load(std::sync::atomic::Ordering::Relaxed))
 }
 
+/// Print the diagnostics that are not silenced with `--allow-warning`
+fn print_diagnostics(args: &Cli, diagnostics: &[slint_interpreter::Diagnostic]) {
+    let diagnostics = diagnostics
+        .iter()
+        .filter(|d| {
+            d.level() != slint_interpreter::DiagnosticLevel::Warning
+                || !args.allowed_warnings.contains(&d.code())
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    if let Err(err) = slint_interpreter::print_diagnostics_as(
+        &diagnostics,
+        args.diagnostics_format,
+        &mut std::io::stdout(),
+    ) {
+        eprintln!("Error: cannot print the diagnostics: {}", err);
+    }
+}
+
 fn init_compiler(
     args: &Cli,
     fswatcher: Option<Arc<Mutex<notify::RecommendedWatcher>>>,
 ) -> slint_interpreter::ComponentCompiler {
     let mut compiler = slint_interpreter::ComponentCompiler::default();
     compiler.set_include_paths(args.include_paths.clone());
+    compiler.set_library_paths(args.library_paths.iter().cloned().collect());
     if let Some(style) = &args.style {
         compiler.set_style(style.clone());
+    } else if args.screenshot.is_some() {
+        // The native style needs the Qt backend to be drawn
+        compiler.set_style("fluent".into());
     }
     if let Some(watcher) = fswatcher {
         notify::Watcher::watch(
@@ -226,25 +291,28 @@ fn start_fswatch_thread(args: Cli) -> Result<Arc<Mutex<notify::RecommendedWatche
 async fn reload(args: Cli, fswatcher: Arc<Mutex<notify::RecommendedWatcher>>) {
     let mut compiler = init_compiler(&args, Some(fswatcher));
     let c = compiler.build_from_path(&args.path).await;
-    slint_interpreter::print_diagnostics(compiler.diagnostics());
+    print_diagnostics(&args, compiler.diagnostics());
 
     if let Some(c) = c {
         CURRENT_INSTANCE.with(|current| {
             let mut current = current.borrow_mut();
             if let Some(handle) = current.take() {
-                let window = handle.window();
-                let new_handle = c.create_with_existing_window(window);
+                // The data that was loaded is part of the state that is carried over
+                let (new_handle, report) = handle.reload(&c);
+                for (property, error) in report.not_migrated {
+                    eprintln!("Could not keep the value of {}: {}", property, error);
+                }
                 init_dialog(&new_handle);
                 current.replace(new_handle);
             } else {
                 let handle = c.create();
                 init_dialog(&handle);
+                if let Some(data_path) = args.load_data {
+                    let _ = load_data(&handle, &data_path);
+                }
                 handle.show();
                 current.replace(handle);
             }
-            if let Some(data_path) = args.load_data {
-                let _ = load_data(current.as_ref().unwrap(), &data_path);
-            }
             eprintln!("Successful reload of {}", args.path.display());
         });
     }
@@ -261,31 +329,9 @@ fn load_data(instance: &ComponentInstance, data_path: &std::path::Path) -> Resul
 
     let obj = json.as_object().ok_or("The data is not a JSON object")?;
     for (name, v) in obj {
-        fn from_json(v: &serde_json::Value) -> slint_interpreter::Value {
-            match v {
-                serde_json::Value::Null => slint_interpreter::Value::Void,
-                serde_json::Value::Bool(b) => (*b).into(),
-                serde_json::Value::Number(n) => {
-                    slint_interpreter::Value::Number(n.as_f64().unwrap_or(f64::NAN))
-                }
-                serde_json::Value::String(s) => SharedString::from(s.as_str()).into(),
-                serde_json::Value::Array(array) => slint_interpreter::Value::Model(ModelRc::new(
-                    i_slint_core::model::SharedVectorModel::from(
-                        array.iter().map(from_json).collect::<SharedVector<Value>>(),
-                    ),
-                )),
-                serde_json::Value::Object(obj) => obj
-                    .iter()
-                    .map(|(k, v)| (k.clone(), from_json(v)))
-                    .collect::<slint_interpreter::Struct>()
-                    .into(),
-            }
+        if let Err(e) = instance.deserialize_property(name, v) {
+            eprintln!("Warning: cannot set property '{}' from data file: {}", name, e);
         }
-
-        match instance.set_property(name, from_json(v)) {
-            Ok(()) => (),
-            Err(e) => eprintln!("Warning: cannot set property '{}' from data file: {:?}", name, e),
-        };
     }
     Ok(())
 }
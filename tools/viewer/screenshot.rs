@@ -0,0 +1,75 @@
+// Copyright © SixtyFPS GmbH <info@slint-ui.com>
+// SPDX-License-Identifier: GPL-3.0-only OR LicenseRef-Slint-commercial
+
+//! Rendering of a component into a PNG file without opening a window (`--screenshot`), with
+//! the headless rendering of the testing backend.
+
+use i_slint_core::api::{LogicalSize, WindowSize};
+use i_slint_core::window::WindowInner;
+use slint_interpreter::{ComponentHandle, ComponentInstance};
+use std::time::Duration;
+
+/// The time between two frames when advancing the time, so that the animations and timers
+/// go through the same steps as with a real event loop.
+const FRAME_DURATION: Duration = Duration::from_millis(16);
+
+/// Installs the headless platform. Must be called before the component is created.
+pub fn init() -> Result<(), String> {
+    i_slint_backend_testing::init_headless().map_err(|_| {
+        "Cannot take a screenshot: a rendering backend was already initialized".to_string()
+    })
+}
+
+/// Renders the component into a PNG file, after advancing the time by `time`.
+/// Without `size`, the window gets the preferred size of the component.
+pub fn save(
+    instance: &ComponentInstance,
+    path: &std::path::Path,
+    size: Option<LogicalSize>,
+    scale_factor: Option<f32>,
+    time: Duration,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let window = instance.window();
+    if let Some(scale_factor) = scale_factor {
+        WindowInner::from_pub(window).set_scale_factor(scale_factor);
+    }
+    if let Some(size) = size {
+        window.set_size(WindowSize::Logical(size));
+    }
+
+    i_slint_core::platform::update_timers_and_animations();
+    let mut elapsed = Duration::ZERO;
+    while elapsed < time {
+        let frame = FRAME_DURATION.min(time - elapsed);
+        i_slint_backend_testing::mock_elapsed_time(frame.as_millis() as u64);
+        elapsed += frame;
+        i_slint_core::platform::update_timers_and_animations();
+    }
+
+    let buffer = i_slint_backend_testing::render_window(window);
+    if buffer.width() == 0 || buffer.height() == 0 {
+        return Err("The component has an empty size, use --size to set one".into());
+    }
+    image::save_buffer(
+        path,
+        buffer.as_bytes(),
+        buffer.width(),
+        buffer.height(),
+        image::ColorType::Rgb8,
+    )?;
+    Ok(())
+}
+
+/// Parses a size such as `800x600`, in logical pixels
+pub fn parse_size(size: &str) -> Result<LogicalSize, String> {
+    let (width, height) =
+        size.split_once('x').ok_or_else(|| format!("expected WIDTHxHEIGHT, got '{}'", size))?;
+    let parse = |s: &str| {
+        s.trim()
+            .parse::<f32>()
+            .ok()
+            .filter(|x| *x > 0.)
+            .ok_or_else(|| format!("invalid size '{}'", size))
+    };
+    Ok(LogicalSize::new(parse(width)?, parse(height)?))
+}